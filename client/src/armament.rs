@@ -4,6 +4,7 @@
 use crate::game::Mk48Game;
 use crate::interpolated_contact::InterpolatedContact;
 use crate::particle::{Mk48Particle, Mk48ParticleLayer};
+use client_util::audio::AudioListener;
 use client_util::context::CoreState;
 use client_util::rate_limiter::RateLimiter;
 use common::angle::Angle;
@@ -12,7 +13,6 @@ use common::entity::{Armament, EntityData, EntityId, EntityKind, EntitySubKind,
 use common_util::range::gen_radius;
 use glam::Vec2;
 use rand::{thread_rng, Rng};
-use renderer2d::Particle;
 use std::collections::HashMap;
 
 impl Mk48Game {
@@ -111,7 +111,7 @@ impl Mk48Game {
         boat: &Contact,
         contacts: &HashMap<EntityId, InterpolatedContact>,
         core_state: &CoreState,
-        player_position: Vec2,
+        listener: AudioListener,
         airborne_particles: &mut Mk48ParticleLayer,
     ) -> f32 {
         let mut volume = 0.0;
@@ -166,17 +166,22 @@ impl Mk48Game {
             let normalized = vector / distance;
             let offset = 5.0 + data.width * 0.4 + rng.gen::<f32>() * 10.0;
             for i in 0..3 {
-                airborne_particles.add(Mk48Particle {
-                    position: aa_gun + normalized * (offset + i as f32),
-                    velocity: normalized * (distance.max(30.0) * (1.0 / time_of_flight))
-                        + gen_radius(&mut rng, 1.0),
-                    color: -1.0,
-                    radius: 0.5,
-                    smoothness: 0.25,
-                });
+                airborne_particles.add(
+                    Mk48Particle {
+                        position: aa_gun + normalized * (offset + i as f32),
+                        velocity: normalized * (distance.max(30.0) * (1.0 / time_of_flight))
+                            + gen_radius(&mut rng, 1.0),
+                        color: -1.0,
+                        radius: 0.5,
+                        smoothness: 0.25,
+                        lifespan: 1.0,
+                        ..Default::default()
+                    }
+                    .into(),
+                );
             }
 
-            volume += Self::volume_at(player_position.distance(aa_gun))
+            volume += listener.volume_at(aa_gun)
         }
 
         volume
@@ -2,15 +2,18 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
 use crate::game::Mk48Game;
-use client_util::audio::AudioPlayer;
+use client_util::audio::{AudioListener, AudioPlayer};
+use renderer2d::Camera2d;
 
 engine_macros::include_audio!("/sprites_audio.mp3" "./sprites_audio.json");
 
 impl Mk48Game {
-    /// Gets the volume at a distance from the center of the screen.
-    pub fn volume_at(distance: f32) -> f32 {
-        debug_assert!(distance >= 0.0);
-        1.0 / (1.0 + 0.05 * distance)
+    /// Gets the listener that sounds are heard relative to, the center of the screen.
+    pub fn audio_listener(camera: &Camera2d) -> AudioListener {
+        AudioListener {
+            center: camera.center,
+            zoom: camera.zoom,
+        }
     }
 
     /// Plays music if it is not already playing, automatically preempting lower priority music.
@@ -35,6 +38,6 @@ impl Mk48Game {
             }
         }
 
-        audio_player.play(audio);
+        audio_player.play_music(audio);
     }
 }
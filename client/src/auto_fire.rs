@@ -0,0 +1,148 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::interpolated_contact::InterpolatedContact;
+use client_util::context::CoreState;
+use common::contact::ContactTrait;
+use common::entity::{EntityId, EntityKind};
+use core_protocol::id::PlayerId;
+use glam::Vec2;
+use std::collections::HashMap;
+
+/// Returns if a contact is a valid target for auto-fire. Only boats belonging to players that
+/// aren't `friendly` (e.g. teammates) are valid, so neutrals (obstacles, collectibles, etc.) are
+/// never fired at.
+pub fn is_valid_target(kind: EntityKind, player_id: Option<PlayerId>, friendly: bool) -> bool {
+    kind == EntityKind::Boat && player_id.is_some() && !friendly
+}
+
+/// Finds the nearest of `targets` within `range` of `position`.
+pub fn nearest_target(
+    position: Vec2,
+    range: f32,
+    targets: impl IntoIterator<Item = Vec2>,
+) -> Option<Vec2> {
+    targets
+        .into_iter()
+        .map(|target| (target.distance_squared(position), target))
+        .filter(|&(distance_squared, _)| distance_squared <= range.powi(2))
+        .min_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap())
+        .map(|(_, target)| target)
+}
+
+/// Aims an auto-fire shot at the nearest of `targets` within `range` of `position`, using
+/// `find_armament` to pick an armament that can hit it. Returns the armament index and the aim
+/// target, which replaces the mouse position for that shot.
+pub fn aim_auto_fire(
+    position: Vec2,
+    range: f32,
+    targets: impl IntoIterator<Item = Vec2>,
+    find_armament: impl FnOnce(Vec2) -> Option<usize>,
+) -> Option<(usize, Vec2)> {
+    let target = nearest_target(position, range, targets)?;
+    find_armament(target).map(|armament_index| (armament_index, target))
+}
+
+/// Positions of valid (see [`is_valid_target`]) contacts, excluding `own_id`.
+pub fn auto_fire_targets<'a>(
+    own_id: EntityId,
+    contacts: &'a HashMap<EntityId, InterpolatedContact>,
+    core: &'a CoreState,
+) -> impl Iterator<Item = Vec2> + 'a {
+    contacts
+        .values()
+        .map(|InterpolatedContact { view, .. }| view)
+        .filter(move |contact| contact.id() != own_id)
+        .filter(move |contact| {
+            contact.entity_type().map_or(false, |entity_type| {
+                is_valid_target(
+                    entity_type.data().kind,
+                    contact.player_id(),
+                    core.is_friendly(contact.player_id()),
+                )
+            })
+        })
+        .map(|contact| contact.transform().position)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::armament::FireRateLimiter;
+    use crate::auto_fire::{aim_auto_fire, is_valid_target, nearest_target};
+    use common::entity::EntityKind;
+    use core_protocol::id::PlayerId;
+    use glam::Vec2;
+    use std::num::NonZeroU32;
+
+    #[test]
+    fn valid_targets() {
+        let player_id = Some(PlayerId(NonZeroU32::new(5).unwrap()));
+        assert!(is_valid_target(EntityKind::Boat, player_id, false));
+
+        // Teammates.
+        assert!(!is_valid_target(EntityKind::Boat, player_id, true));
+
+        // Neutrals.
+        assert!(!is_valid_target(EntityKind::Boat, None, false));
+        assert!(!is_valid_target(EntityKind::Obstacle, None, false));
+        assert!(!is_valid_target(EntityKind::Collectible, None, false));
+        assert!(!is_valid_target(EntityKind::Weapon, player_id, false));
+    }
+
+    #[test]
+    fn nearest_in_range() {
+        let position = Vec2::new(10.0, 0.0);
+        let targets = [
+            Vec2::new(10.0, 300.0),
+            Vec2::new(60.0, 0.0),
+            Vec2::new(-20.0, 0.0),
+        ];
+        assert_eq!(
+            nearest_target(position, 100.0, targets),
+            Some(Vec2::new(-20.0, 0.0))
+        );
+        assert_eq!(nearest_target(position, 10.0, targets), None);
+        assert_eq!(nearest_target(position, 100.0, []), None);
+    }
+
+    #[test]
+    fn aims_at_nearest() {
+        let position = Vec2::ZERO;
+        let targets = [Vec2::new(80.0, 0.0), Vec2::new(0.0, -30.0)];
+
+        let mut aimed_at = None;
+        let shot = aim_auto_fire(position, 100.0, targets, |target| {
+            aimed_at = Some(target);
+            Some(1)
+        });
+        // The armament is picked for, and the shot aimed at, the nearest enemy.
+        assert_eq!(aimed_at, Some(Vec2::new(0.0, -30.0)));
+        assert_eq!(shot, Some((1, Vec2::new(0.0, -30.0))));
+
+        // No armament can hit it.
+        assert_eq!(aim_auto_fire(position, 100.0, targets, |_| None), None);
+        // Out of range.
+        assert_eq!(
+            aim_auto_fire(position, 10.0, targets, |_| unreachable!()),
+            None
+        );
+    }
+
+    #[test]
+    fn respects_cooldown() {
+        let mut limiter = FireRateLimiter::new();
+        assert!(limiter.is_ready(2));
+        limiter.fired(2);
+        assert!(!limiter.is_ready(2));
+        assert!(limiter.is_ready(1));
+
+        // Still cooling down shortly after firing.
+        limiter.update(0.15);
+        assert!(!limiter.is_ready(2));
+
+        for _ in 0..5 {
+            limiter.update(0.1);
+        }
+        assert!(limiter.is_ready(2));
+    }
+}
@@ -63,6 +63,8 @@ impl Mk48BackgroundContext {
     const GRASS_COLOR: [u8; 3] = [71, 85, 45];
     const SAND_COLOR: [u8; 3] = [213, 176, 107];
     const SNOW_COLOR: [u8; 3] = [233, 235, 237];
+    /// How many times the grass, sand, and snow textures repeat per meter.
+    pub const TILES_PER_METER: f32 = 0.005;
 
     pub fn new(renderer: &Renderer2d, animations: bool, wave_quality: u8) -> Self {
         let terrain_texture = Texture::new_empty(renderer, TextureFormat::Alpha, true);
@@ -0,0 +1,244 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::interpolated_contact::InterpolatedContact;
+use client_util::context::CoreState;
+use common::contact::ContactTrait;
+use common::entity::{EntityId, EntityKind};
+use glam::{Vec2, Vec3, Vec4};
+use renderer2d::GraphicLayer;
+use std::collections::HashMap;
+use std::f32::consts::PI;
+
+/// An edge of the screen.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ScreenEdge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl ScreenEdge {
+    pub const ALL: [Self; 4] = [Self::Top, Self::Bottom, Self::Left, Self::Right];
+
+    /// Gets the edge of the screen that `direction` (from the center of the screen) points
+    /// towards, given the screen's `aspect_ratio` (width / height).
+    pub fn from_direction(direction: Vec2, aspect_ratio: f32) -> Self {
+        // Scale to a square screen, so the diagonals are at 45 degrees.
+        let scaled = Vec2::new(direction.x, direction.y * aspect_ratio);
+        if scaled.x.abs() > scaled.y.abs() {
+            if scaled.x > 0.0 {
+                Self::Right
+            } else {
+                Self::Left
+            }
+        } else if scaled.y >= 0.0 {
+            Self::Top
+        } else {
+            Self::Bottom
+        }
+    }
+
+    /// Angle to rotate a rectangle along the top edge by to get it along this edge.
+    fn angle(self) -> f32 {
+        match self {
+            Self::Top => 0.0,
+            Self::Left => PI * 0.5,
+            Self::Bottom => PI,
+            Self::Right => PI * -0.5,
+        }
+    }
+}
+
+/// Red flashes at the edges of the screen, pointing towards recent sources of damage.
+#[derive(Default)]
+pub struct CombatAlerts {
+    /// When each [`ScreenEdge`] was last flashed.
+    start_times: [Option<f32>; 4],
+}
+
+impl CombatAlerts {
+    /// How long, in seconds, each alert takes to fade out.
+    const DURATION: f32 = 1.5;
+    /// Fraction of the smaller screen dimension covered by an alert.
+    const THICKNESS: f32 = 0.15;
+
+    /// Flash the edge of the screen that `direction` (from the camera) points towards.
+    pub fn alert(&mut self, direction: Vec2, aspect_ratio: f32, time: f32) {
+        let edge = ScreenEdge::from_direction(direction, aspect_ratio);
+        self.start_times[edge as usize] = Some(time);
+    }
+
+    /// Gets the opacity of an alert that started at `start_time`.
+    fn alpha(start_time: f32, time: f32) -> f32 {
+        (1.0 - (time - start_time) / Self::DURATION).clamp(0.0, 1.0)
+    }
+
+    /// Draws all active alerts in `color` given the camera's `center`, `zoom` (half width) and
+    /// `aspect_ratio`.
+    pub fn draw(
+        &mut self,
+        layer: &mut GraphicLayer,
+        color: Vec3,
+        center: Vec2,
+        zoom: f32,
+        aspect_ratio: f32,
+        time: f32,
+    ) {
+        let half_size = Vec2::new(zoom, zoom / aspect_ratio);
+        let thickness = half_size.min_element() * Self::THICKNESS;
+
+        for edge in ScreenEdge::ALL {
+            let start_time = &mut self.start_times[edge as usize];
+            let alpha = if let Some(start_time) = *start_time {
+                Self::alpha(start_time, time)
+            } else {
+                continue;
+            };
+            if alpha == 0.0 {
+                *start_time = None;
+                continue;
+            }
+
+            let (length, offset) = match edge {
+                ScreenEdge::Top | ScreenEdge::Bottom => (half_size.x, half_size.y),
+                ScreenEdge::Left | ScreenEdge::Right => (half_size.y, half_size.x),
+            };
+            let angle = edge.angle();
+            let outward = Vec2::new(-angle.sin(), angle.cos());
+
+            let color = color.extend(alpha * (150.0 / 255.0));
+            let transparent = Vec4::ZERO;
+
+            layer.draw_rectangle_gradient(
+                center + outward * (offset - thickness * 0.5),
+                Vec2::new(length * 2.0, thickness),
+                angle,
+                [color, color, transparent, transparent],
+            );
+        }
+    }
+}
+
+/// Hostile contacts within this many meters of the player may have dealt damage.
+const DAMAGE_SOURCE_RANGE: f32 = 200.0;
+
+/// Estimates the directions from `position` that damage came from, one per possible source.
+/// Hostile weapons point back towards whoever fired them, and hostile boats may have rammed.
+pub fn damage_directions(
+    position: Vec2,
+    contacts: &HashMap<EntityId, InterpolatedContact>,
+    core: &CoreState,
+) -> Vec<Vec2> {
+    let sources = contacts
+        .values()
+        .map(|InterpolatedContact { view, .. }| view)
+        .filter(|contact| !core.is_friendly(contact.player_id()))
+        .filter_map(|contact| {
+            let kind = contact.entity_type()?.data().kind;
+            let transform = contact.transform();
+            let direction = match kind {
+                EntityKind::Weapon => -transform.direction.to_vec(),
+                EntityKind::Boat => transform.position - position,
+                _ => return None,
+            };
+            Some((transform.position.distance_squared(position), direction))
+        })
+        .collect();
+    nearby_sources(sources)
+}
+
+/// Takes the directions of `sources` (squared distance and direction) within
+/// [`DAMAGE_SOURCE_RANGE`], or of the nearest source if none are.
+fn nearby_sources(mut sources: Vec<(f32, Vec2)>) -> Vec<Vec2> {
+    sources.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap());
+    let nearby = sources
+        .iter()
+        .take_while(|(distance_squared, _)| *distance_squared <= DAMAGE_SOURCE_RANGE.powi(2))
+        .count()
+        .max(1);
+    sources.truncate(nearby);
+    sources
+        .into_iter()
+        .map(|(_, direction)| direction)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::combat_alert::{nearby_sources, CombatAlerts, ScreenEdge, DAMAGE_SOURCE_RANGE};
+    use glam::Vec2;
+
+    #[test]
+    fn test_screen_edge_from_direction() {
+        assert_eq!(
+            ScreenEdge::from_direction(Vec2::new(0.0, 1.0), 1.0),
+            ScreenEdge::Top
+        );
+        assert_eq!(
+            ScreenEdge::from_direction(Vec2::new(0.1, -1.0), 1.0),
+            ScreenEdge::Bottom
+        );
+        assert_eq!(
+            ScreenEdge::from_direction(Vec2::new(-1.0, 0.3), 1.0),
+            ScreenEdge::Left
+        );
+        assert_eq!(
+            ScreenEdge::from_direction(Vec2::new(1.0, -0.3), 1.0),
+            ScreenEdge::Right
+        );
+
+        // The same direction points at the side of a tall screen, but the top of a wide screen.
+        assert_eq!(
+            ScreenEdge::from_direction(Vec2::new(1.0, 1.2), 0.5),
+            ScreenEdge::Right
+        );
+        assert_eq!(
+            ScreenEdge::from_direction(Vec2::new(1.0, 1.2), 2.0),
+            ScreenEdge::Top
+        );
+    }
+
+    #[test]
+    fn test_nearby_sources() {
+        let near = DAMAGE_SOURCE_RANGE.powi(2) * 0.5;
+        let far = DAMAGE_SOURCE_RANGE.powi(2) * 2.0;
+        assert_eq!(nearby_sources(vec![]), vec![]);
+        assert_eq!(
+            nearby_sources(vec![
+                (far, Vec2::X),
+                (near, Vec2::Y),
+                (near * 0.5, -Vec2::Y)
+            ]),
+            vec![-Vec2::Y, Vec2::Y]
+        );
+        // Falls back to the nearest source.
+        assert_eq!(
+            nearby_sources(vec![(far * 2.0, Vec2::X), (far, -Vec2::X)]),
+            vec![-Vec2::X]
+        );
+    }
+
+    #[test]
+    fn test_alert_fade() {
+        assert_eq!(CombatAlerts::alpha(10.0, 10.0), 1.0);
+        let half = CombatAlerts::alpha(10.0, 10.0 + CombatAlerts::DURATION * 0.5);
+        assert!((half - 0.5).abs() < 0.001);
+        assert_eq!(
+            CombatAlerts::alpha(10.0, 10.0 + CombatAlerts::DURATION),
+            0.0
+        );
+        assert_eq!(CombatAlerts::alpha(10.0, 100.0), 0.0);
+    }
+
+    #[test]
+    fn test_multiple_alerts() {
+        let mut alerts = CombatAlerts::default();
+        alerts.alert(Vec2::new(0.0, 1.0), 1.0, 0.0);
+        alerts.alert(Vec2::new(-1.0, 0.0), 1.0, 0.5);
+        assert!(alerts.start_times[ScreenEdge::Top as usize].is_some());
+        assert!(alerts.start_times[ScreenEdge::Left as usize].is_some());
+        assert!(alerts.start_times[ScreenEdge::Right as usize].is_none());
+    }
+}
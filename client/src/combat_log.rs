@@ -0,0 +1,315 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::settings::CombatLogFormat;
+use common::entity::EntityId;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Something notable that happened in combat.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CombatEvent {
+    /// Damaged another player's boat, by a fraction of its max health.
+    DamageDealt { to: String, damage: f32 },
+    /// Own boat was damaged, by a fraction of its max health.
+    DamageReceived { damage: f32 },
+    /// Another player's boat sank, according to the server. The victim is empty if unknown.
+    Kill { victim: String },
+    /// Own boat sank, for the given reason.
+    Death { reason: String },
+}
+
+impl CombatEvent {
+    /// Name of the event, in the exported log.
+    fn name(&self) -> &'static str {
+        match self {
+            Self::DamageDealt { .. } => "damage_dealt",
+            Self::DamageReceived { .. } => "damage_received",
+            Self::Kill { .. } => "kill",
+            Self::Death { .. } => "death",
+        }
+    }
+
+    /// Who or what else was involved, if known.
+    fn other(&self) -> &str {
+        match self {
+            Self::DamageDealt { to, .. } => to,
+            Self::DamageReceived { .. } => "",
+            Self::Kill { victim } => victim,
+            Self::Death { reason } => reason,
+        }
+    }
+
+    /// Damage as a percentage of max health, if applicable.
+    fn damage_percent(&self) -> Option<f32> {
+        match *self {
+            Self::DamageDealt { damage, .. } | Self::DamageReceived { damage } => {
+                Some(damage * 100.0)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A [`CombatEvent`] and when it happened.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CombatLogEntry {
+    /// Seconds since spawning.
+    pub seconds: f32,
+    pub event: CombatEvent,
+}
+
+/// Accumulates [`CombatEvent`]s over the course of a life, for exporting after death.
+#[derive(Default)]
+pub struct CombatLog {
+    /// When the player spawned.
+    spawn_seconds: f32,
+    entries: Vec<CombatLogEntry>,
+    /// Kills since spawning, according to the server.
+    kills: u32,
+    /// Boats recently damaged by the player, and when, to name the victims of kills.
+    damaged: HashMap<EntityId, (f32, String)>,
+}
+
+impl CombatLog {
+    /// How long, in seconds, after damaging a boat it may be named as the victim of a kill.
+    const KILL_WINDOW: f32 = 3.0;
+
+    /// Clears the log upon spawning at `time_seconds`.
+    pub fn reset(&mut self, time_seconds: f32) {
+        *self = Self {
+            spawn_seconds: time_seconds,
+            ..Self::default()
+        };
+    }
+
+    /// Records an `event` that happened at `time_seconds`.
+    pub fn push(&mut self, time_seconds: f32, event: CombatEvent) {
+        self.entries.push(CombatLogEntry {
+            seconds: time_seconds - self.spawn_seconds,
+            event,
+        });
+    }
+
+    /// Records damage dealt to the boat with `entity_id`.
+    pub fn damage_dealt(
+        &mut self,
+        time_seconds: f32,
+        entity_id: EntityId,
+        to: String,
+        damage: f32,
+    ) {
+        self.damaged.insert(entity_id, (time_seconds, to.clone()));
+        self.push(time_seconds, CombatEvent::DamageDealt { to, damage });
+    }
+
+    /// Records a kill for each one the server counted since the last update, given the server's
+    /// total `kills` since spawning. The victim is presumed to be the boat most recently damaged by
+    /// the player, if any.
+    pub fn update_kills(&mut self, time_seconds: f32, kills: u32) {
+        for _ in 0..kills.saturating_sub(self.kills) {
+            let victim = self
+                .damaged
+                .iter()
+                .filter(|(_, &(damaged_seconds, _))| {
+                    time_seconds - damaged_seconds <= Self::KILL_WINDOW
+                })
+                .max_by(|(_, (a, _)), (_, (b, _))| a.total_cmp(b))
+                .map(|(&entity_id, _)| entity_id)
+                .and_then(|entity_id| self.damaged.remove(&entity_id))
+                .map(|(_, victim)| victim)
+                .unwrap_or_default();
+            self.push(time_seconds, CombatEvent::Kill { victim });
+        }
+        self.kills = kills;
+    }
+
+    /// Exports the log in the given `format`.
+    pub fn export(&self, format: CombatLogFormat) -> String {
+        match format {
+            CombatLogFormat::Csv => to_csv(&self.entries),
+            CombatLogFormat::Text => to_text(&self.entries),
+        }
+    }
+}
+
+/// Serializes `entries` as comma separated values, with a header row.
+pub fn to_csv(entries: &[CombatLogEntry]) -> String {
+    let mut csv = String::from("seconds,event,other,damage_percent\r\n");
+    for entry in entries {
+        let _ = write!(
+            csv,
+            "{:.1},{},{},{}\r\n",
+            entry.seconds,
+            entry.event.name(),
+            csv_field(entry.event.other()),
+            entry
+                .event
+                .damage_percent()
+                .map(|d| format!("{:.1}", d))
+                .unwrap_or_default()
+        );
+    }
+    csv
+}
+
+/// Quotes `field` if it contains a delimiter, quote, or line break, escaping any quotes. Fields
+/// that a spreadsheet would evaluate as a formula (e.g. a player named `=HYPERLINK(...)`) are
+/// prefixed with an apostrophe, so they are shown as text.
+fn csv_field(field: &str) -> Cow<str> {
+    let field = if field.starts_with(&['=', '+', '-', '@'][..]) {
+        Cow::Owned(format!("'{}", field))
+    } else {
+        Cow::Borrowed(field)
+    };
+    if field.contains(&[',', '"', '\r', '\n'][..]) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        field
+    }
+}
+
+/// Serializes `entries` as human readable lines.
+pub fn to_text(entries: &[CombatLogEntry]) -> String {
+    let mut text = String::new();
+    for entry in entries {
+        let _ = write!(text, "{:>7.1}s  {:<15}", entry.seconds, entry.event.name());
+        if let Some(damage_percent) = entry.event.damage_percent() {
+            let _ = write!(text, "  {:>5.1}%", damage_percent);
+        }
+        let other = entry.event.other();
+        if !other.is_empty() {
+            // Keep one entry per line.
+            let _ = write!(text, "  {}", other.replace(&['\r', '\n'][..], " "));
+        }
+        text.push('\n');
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::combat_log::{to_csv, to_text, CombatEvent, CombatLog, CombatLogEntry};
+    use crate::settings::CombatLogFormat;
+    use common::entity::EntityId;
+
+    fn sample() -> Vec<CombatLogEntry> {
+        vec![
+            CombatLogEntry {
+                seconds: 1.5,
+                event: CombatEvent::DamageDealt {
+                    to: String::from("Plain"),
+                    damage: 0.5,
+                },
+            },
+            CombatLogEntry {
+                seconds: 2.0,
+                event: CombatEvent::DamageReceived { damage: 0.125 },
+            },
+            CombatLogEntry {
+                seconds: 3.0,
+                event: CombatEvent::Kill {
+                    victim: String::from("Smith, \"Bo\""),
+                },
+            },
+            CombatLogEntry {
+                seconds: 10.0,
+                event: CombatEvent::Death {
+                    reason: String::from("Sunk by\nsomeone"),
+                },
+            },
+        ]
+    }
+
+    #[test]
+    fn csv_format() {
+        assert_eq!(to_csv(&[]), "seconds,event,other,damage_percent\r\n");
+        assert_eq!(
+            to_csv(&sample()),
+            "seconds,event,other,damage_percent\r\n\
+             1.5,damage_dealt,Plain,50.0\r\n\
+             2.0,damage_received,,12.5\r\n\
+             3.0,kill,\"Smith, \"\"Bo\"\"\",\r\n\
+             10.0,death,\"Sunk by\nsomeone\",\r\n"
+        );
+    }
+
+    #[test]
+    fn text_format() {
+        let text = to_text(&sample());
+        assert_eq!(text.lines().count(), 4, "{}", text);
+        assert!(
+            text.starts_with("    1.5s  damage_dealt      50.0%  Plain\n"),
+            "{}",
+            text
+        );
+        assert!(
+            text.ends_with("   10.0s  death            Sunk by someone\n"),
+            "{}",
+            text
+        );
+    }
+
+    #[test]
+    fn csv_formula_injection() {
+        let entries = ["=1+1", "+1", "-1", "@SUM(A1)", "=A1,B1"].map(|victim| CombatLogEntry {
+            seconds: 1.0,
+            event: CombatEvent::Kill {
+                victim: String::from(victim),
+            },
+        });
+        assert_eq!(
+            to_csv(&entries),
+            "seconds,event,other,damage_percent\r\n\
+             1.0,kill,'=1+1,\r\n\
+             1.0,kill,'+1,\r\n\
+             1.0,kill,'-1,\r\n\
+             1.0,kill,'@SUM(A1),\r\n\
+             1.0,kill,\"'=A1,B1\",\r\n"
+        );
+    }
+
+    #[test]
+    fn server_kills() {
+        let mut log = CombatLog::default();
+        log.reset(100.0);
+        let (a, b) = (EntityId::new(1).unwrap(), EntityId::new(2).unwrap());
+        log.damage_dealt(101.0, a, String::from("A"), 0.9);
+        log.damage_dealt(101.5, b, String::from("B"), 0.1);
+
+        // Boats that were merely damaged, or disappeared, aren't kills.
+        log.update_kills(102.0, 0);
+        assert_eq!(log.entries.len(), 2);
+
+        // The most recently damaged boat is named, then the next.
+        log.update_kills(102.0, 2);
+        let victims = log.entries[2..]
+            .iter()
+            .map(|entry| match &entry.event {
+                CombatEvent::Kill { victim } => victim.as_str(),
+                event => panic!("{:?}", event),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(victims, ["B", "A"]);
+        assert_eq!(log.entries[2].seconds, 2.0);
+
+        // Unknown victim.
+        log.update_kills(110.0, 3);
+        assert_eq!(
+            log.entries.last().unwrap().event,
+            CombatEvent::Kill {
+                victim: String::new()
+            }
+        );
+
+        // Reset per game.
+        log.reset(200.0);
+        log.update_kills(201.0, 0);
+        assert_eq!(
+            log.export(CombatLogFormat::Csv).lines().count(),
+            1,
+            "only the header"
+        );
+    }
+}
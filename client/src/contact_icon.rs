@@ -0,0 +1,127 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::settings::ContactIcons;
+use client_util::palette::{ColorVision, SemanticColor};
+use common::entity::EntityKind;
+use common::transform::Transform;
+use glam::{Vec2, Vec3};
+use renderer2d::GraphicLayer;
+use std::f32::consts::PI;
+
+/// How a single contact is drawn.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ContactIcon {
+    /// Silhouette from the sprite sheet (including turrets and armaments).
+    Sprite,
+    /// Colored dot, sized by level.
+    Dot,
+    /// Colored triangle, pointing in the direction of travel.
+    Triangle,
+}
+
+impl ContactIcon {
+    /// Selects how to draw a contact of `kind`, given the chosen set of `icons`. `own` boat is
+    /// always a silhouette, to show its turrets and armaments, as is the scenery (obstacles,
+    /// collectibles, etc.).
+    pub fn select(icons: ContactIcons, kind: EntityKind, own: bool) -> Self {
+        match icons {
+            ContactIcons::Silhouettes => Self::Sprite,
+            ContactIcons::Dots if own => Self::Sprite,
+            ContactIcons::Dots => match kind {
+                EntityKind::Boat => Self::Dot,
+                EntityKind::Aircraft | EntityKind::Decoy | EntityKind::Weapon => Self::Triangle,
+                EntityKind::Collectible | EntityKind::Obstacle | EntityKind::Turret => Self::Sprite,
+            },
+        }
+    }
+}
+
+/// Gets the radius of a dot for a boat of `level`, given the camera's `zoom`, such that it is a
+/// constant size on screen.
+pub fn dot_radius(level: u8, zoom: f32) -> f32 {
+    (0.006 + 0.002 * level as f32) * zoom
+}
+
+/// Gets the color of an icon, given its color if it is friendly (see
+/// [`friendly_color`][`crate::team_color::friendly_color`]).
+pub fn icon_color(friendly_color: Option<Vec3>, color_vision: ColorVision) -> Vec3 {
+    friendly_color.unwrap_or_else(|| color_vision.color(SemanticColor::Enemy))
+}
+
+/// Draws a contact as a simplified `icon`. Does nothing for [`ContactIcon::Sprite`], which is drawn
+/// on the sprite layer instead.
+pub fn draw_contact_icon(
+    layer: &mut GraphicLayer,
+    icon: ContactIcon,
+    transform: &Transform,
+    level: u8,
+    friendly_color: Option<Vec3>,
+    color_vision: ColorVision,
+    zoom: f32,
+    alpha: f32,
+) {
+    let color = icon_color(friendly_color, color_vision).extend(alpha);
+    match icon {
+        ContactIcon::Sprite => {}
+        ContactIcon::Dot => {
+            layer.draw_filled_circle(transform.position, dot_radius(level, zoom), color);
+        }
+        ContactIcon::Triangle => {
+            let size = 0.008 * zoom;
+            layer.draw_triangle(
+                transform.position,
+                Vec2::new(size, size * 1.5),
+                // Triangles point up (+y) when not rotated.
+                transform.direction.to_radians() - PI * 0.5,
+                color,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::contact_icon::{dot_radius, icon_color, ContactIcon};
+    use crate::settings::ContactIcons;
+    use crate::team_color::team_color;
+    use client_util::palette::ColorVision;
+    use common::entity::EntityKind;
+
+    #[test]
+    fn select_draw_path() {
+        for kind in [
+            EntityKind::Boat,
+            EntityKind::Weapon,
+            EntityKind::Obstacle,
+            EntityKind::Collectible,
+        ] {
+            for own in [false, true] {
+                assert_eq!(
+                    ContactIcon::select(ContactIcons::Silhouettes, kind, own),
+                    ContactIcon::Sprite
+                );
+            }
+        }
+
+        let dots = |kind, own| ContactIcon::select(ContactIcons::Dots, kind, own);
+        assert_eq!(dots(EntityKind::Boat, false), ContactIcon::Dot);
+        assert_eq!(dots(EntityKind::Boat, true), ContactIcon::Sprite);
+        assert_eq!(dots(EntityKind::Weapon, false), ContactIcon::Triangle);
+        assert_eq!(dots(EntityKind::Aircraft, false), ContactIcon::Triangle);
+        assert_eq!(dots(EntityKind::Obstacle, false), ContactIcon::Sprite);
+        assert_eq!(dots(EntityKind::Collectible, false), ContactIcon::Sprite);
+    }
+
+    #[test]
+    fn dots_encode_level_and_relationship() {
+        assert!(dot_radius(5, 100.0) > dot_radius(1, 100.0));
+        assert_eq!(dot_radius(3, 200.0), dot_radius(3, 100.0) * 2.0);
+        for color_vision in ColorVision::ALL {
+            assert_ne!(
+                icon_color(Some(team_color(color_vision)), color_vision),
+                icon_color(None, color_vision)
+            );
+        }
+    }
+}
@@ -0,0 +1,93 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use client_util::keyboard::{Key, KeyboardEvent};
+
+/// Developer debug toggles. Only compiled in debug builds.
+#[derive(Copy, Clone, Default, PartialEq)]
+pub struct DevState {
+    /// Whether the developer overlay is open.
+    pub shown: bool,
+    /// Log the player's prediction error (see [`InterpolatedContact::update_error_bound`]).
+    ///
+    /// [`InterpolatedContact::update_error_bound`]: crate::interpolated_contact::InterpolatedContact::update_error_bound
+    pub latency: bool,
+    /// Draw collision shapes of contacts (see [`Hitbox`][`crate::hitbox::Hitbox`]) and the view
+    /// bounds of the camera.
+    pub hitboxes: bool,
+    /// Show frame and contact statistics in the overlay.
+    pub stats: bool,
+    /// Hide the HUD graphics and text, e.g. names, for clean screenshots.
+    pub screenshot: bool,
+}
+
+impl DevState {
+    /// Key that, combined with Ctrl and Shift, toggles the overlay.
+    pub const TOGGLE_KEY: Key = Key::D;
+    /// Key that, combined with Ctrl and Shift, toggles [`Self::hitboxes`] without opening the
+    /// overlay.
+    pub const HITBOXES_KEY: Key = Key::H;
+
+    /// Toggles the overlay or hitboxes if `event` is their combo. Returns whether the event was
+    /// consumed.
+    pub fn peek_keyboard(&mut self, event: &KeyboardEvent) -> bool {
+        if !(event.down && event.ctrl && event.shift) {
+            return false;
+        }
+        match event.key {
+            Self::TOGGLE_KEY => self.shown = !self.shown,
+            Self::HITBOXES_KEY => self.hitboxes = !self.hitboxes,
+            _ => return false,
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dev::DevState;
+    use client_util::keyboard::{Key, KeyboardEvent};
+
+    fn event(key: Key, ctrl: bool, shift: bool, down: bool) -> KeyboardEvent {
+        KeyboardEvent {
+            key,
+            ctrl,
+            down,
+            shift,
+            time: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_toggle_combo() {
+        let mut dev = DevState::default();
+        assert!(!dev.shown);
+
+        assert!(dev.peek_keyboard(&event(Key::D, true, true, true)));
+        assert!(dev.shown);
+
+        // Key up and incomplete combos don't toggle.
+        assert!(!dev.peek_keyboard(&event(Key::D, true, true, false)));
+        assert!(!dev.peek_keyboard(&event(Key::D, true, false, true)));
+        assert!(!dev.peek_keyboard(&event(Key::D, false, true, true)));
+        assert!(!dev.peek_keyboard(&event(Key::E, true, true, true)));
+        assert!(dev.shown);
+
+        assert!(dev.peek_keyboard(&event(Key::D, true, true, true)));
+        assert!(!dev.shown);
+    }
+
+    #[test]
+    fn test_hitboxes_combo() {
+        let mut dev = DevState::default();
+        assert!(dev.peek_keyboard(&event(Key::H, true, true, true)));
+        assert!(dev.hitboxes);
+        assert!(!dev.shown);
+
+        assert!(!dev.peek_keyboard(&event(Key::H, false, true, true)));
+        assert!(dev.hitboxes);
+
+        assert!(dev.peek_keyboard(&event(Key::H, true, true, true)));
+        assert!(!dev.hitboxes);
+    }
+}
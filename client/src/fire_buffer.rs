@@ -0,0 +1,104 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+/// Remembers a fire input for a short time, so that one that is too brief to be seen by a
+/// control tick (or that arrives while no armament is ready) is still sent at a later tick.
+#[derive(Default)]
+pub struct FireBuffer {
+    /// Seconds until the buffered input expires, if any.
+    remaining: Option<f32>,
+}
+
+impl FireBuffer {
+    /// Buffers a fire input for `duration` seconds. If `duration` is zero, the input only
+    /// lasts until the end of the current frame.
+    pub fn press(&mut self, duration: f32) {
+        self.remaining = Some(duration.max(0.0));
+    }
+
+    /// Call at each control tick, with whether a fire input is `held` down. If there is a fire
+    /// input (held or buffered), `find` gets what to fire, if anything is ready, and a buffered
+    /// input is forgotten once fired. Returns `None` if there is no fire input at all.
+    pub fn tick<T>(&mut self, held: bool, find: impl FnOnce() -> Option<T>) -> Option<Option<T>> {
+        (held || self.remaining.is_some()).then(|| {
+            let fired = find();
+            if fired.is_some() {
+                self.remaining = None;
+            }
+            fired
+        })
+    }
+
+    /// Call once per frame, after the control tick (if any).
+    pub fn update(&mut self, elapsed_seconds: f32) {
+        if let Some(remaining) = &mut self.remaining {
+            *remaining -= elapsed_seconds;
+            if *remaining <= 0.0 {
+                self.remaining = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fire_buffer::FireBuffer;
+
+    #[test]
+    fn press_before_tick_is_sent_once() {
+        let mut buffer = FireBuffer::default();
+
+        // Pressed (and released) a frame before the tick.
+        buffer.press(0.1);
+        buffer.update(1.0 / 60.0);
+
+        assert_eq!(buffer.tick(false, || Some(0)), Some(Some(0)));
+        buffer.update(1.0 / 60.0);
+
+        // Not sent again at the next tick.
+        assert_eq!(buffer.tick(false, || Some(0)), None);
+    }
+
+    #[test]
+    fn press_waits_for_ready() {
+        let mut buffer = FireBuffer::default();
+        buffer.press(0.1);
+
+        // Nothing ready yet.
+        assert_eq!(buffer.tick(false, || None::<u8>), Some(None));
+        buffer.update(0.05);
+
+        assert_eq!(buffer.tick(false, || Some(1)), Some(Some(1)));
+    }
+
+    #[test]
+    fn press_expires() {
+        let mut buffer = FireBuffer::default();
+        buffer.press(0.1);
+        buffer.update(0.05);
+        buffer.update(0.06);
+        assert_eq!(buffer.tick(false, || Some(0)), None);
+    }
+
+    #[test]
+    fn held() {
+        let mut buffer = FireBuffer::default();
+        assert_eq!(buffer.tick(true, || Some(0)), Some(Some(0)));
+        assert_eq!(buffer.tick(true, || Some(0)), Some(Some(0)));
+        assert_eq!(buffer.tick(false, || Some(0)), None);
+    }
+
+    #[test]
+    fn disabled_only_lasts_one_frame() {
+        let mut buffer = FireBuffer::default();
+
+        // Same frame as tick.
+        buffer.press(0.0);
+        assert_eq!(buffer.tick(false, || Some(0)), Some(Some(0)));
+
+        // Missed the tick.
+        buffer.press(0.0);
+        buffer.update(1.0 / 60.0);
+        assert_eq!(buffer.tick(false, || Some(0)), None);
+    }
+}
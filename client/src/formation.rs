@@ -0,0 +1,151 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use core_protocol::dto::{FormationDto, FormationKind};
+use glam::{Vec2, Vec4};
+use renderer2d::GraphicLayer;
+
+/// Distance between adjacent formation slots, in meters.
+pub const FORMATION_SPACING: f32 = 100.0;
+/// Formations are placed on a grid of this size, in meters, to make them easier to line up on.
+pub const FORMATION_GRID: f32 = 50.0;
+
+/// Cycles through placement modes: off, then each kind of formation.
+pub fn next_formation_mode(mode: Option<FormationKind>) -> Option<FormationKind> {
+    match mode {
+        None => Some(FormationKind::Line),
+        Some(FormationKind::Line) => Some(FormationKind::Wedge),
+        Some(FormationKind::Wedge) => None,
+    }
+}
+
+/// Creates a formation of `kind` at `position` (snapped to the grid), facing `angle`.
+pub fn place_formation(kind: FormationKind, position: Vec2, angle: f32) -> FormationDto {
+    let anchor = snap_to_grid(position, FORMATION_GRID);
+    FormationDto {
+        kind,
+        x: anchor.x,
+        y: anchor.y,
+        angle,
+    }
+}
+
+/// Rounds `position` to the nearest multiple of `grid`.
+pub fn snap_to_grid(position: Vec2, grid: f32) -> Vec2 {
+    (position / grid).round() * grid
+}
+
+/// Gets the positions of `count` slots of `formation`, in order of team members (first is
+/// captain, at the anchor).
+pub fn formation_slots(
+    formation: &FormationDto,
+    count: usize,
+    spacing: f32,
+) -> impl Iterator<Item = Vec2> {
+    let anchor = Vec2::new(formation.x, formation.y);
+    let forward = Vec2::new(formation.angle.cos(), formation.angle.sin());
+    let right = Vec2::new(forward.y, -forward.x);
+    let kind = formation.kind;
+
+    (0..count).map(move |i| {
+        // Alternate sides, moving outwards: 0, 1, -1, 2, -2, etc.
+        let rank = ((i + 1) / 2) as f32;
+        let side = if i % 2 == 1 { 1.0 } else { -1.0 };
+        let lateral = right * (side * rank * spacing);
+
+        match kind {
+            FormationKind::Line => anchor + lateral,
+            FormationKind::Wedge => anchor + lateral - forward * (rank * spacing),
+        }
+    })
+}
+
+/// Draws a marker for each of `count` slots of `formation`, highlighting `own_slot`.
+pub fn draw_formation(
+    layer: &mut GraphicLayer,
+    formation: &FormationDto,
+    count: usize,
+    own_slot: Option<usize>,
+    zoom: f32,
+    color: Vec4,
+) {
+    let thickness = 0.0025 * zoom;
+    for (i, slot) in formation_slots(formation, count, FORMATION_SPACING).enumerate() {
+        if Some(i) == own_slot {
+            layer.draw_circle(slot, 0.02 * zoom, thickness * 2.0, color);
+        } else {
+            layer.draw_circle(
+                slot,
+                0.01 * zoom,
+                thickness,
+                color * Vec4::new(1.0, 1.0, 1.0, 0.5),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::formation::{formation_slots, place_formation, snap_to_grid};
+    use core_protocol::dto::{FormationDto, FormationKind};
+    use glam::Vec2;
+    use std::f32::consts::PI;
+
+    fn assert_near(a: Vec2, b: Vec2) {
+        assert!(a.distance(b) < 0.001, "{} != {}", a, b);
+    }
+
+    fn slots(kind: FormationKind, anchor: Vec2, angle: f32, count: usize) -> Vec<Vec2> {
+        let formation = FormationDto {
+            kind,
+            x: anchor.x,
+            y: anchor.y,
+            angle,
+        };
+        formation_slots(&formation, count, 10.0).collect()
+    }
+
+    #[test]
+    fn line_slots() {
+        let anchor = Vec2::new(100.0, 50.0);
+        let slots = slots(FormationKind::Line, anchor, 0.0, 5);
+        assert_eq!(slots.len(), 5);
+
+        // Facing +x, so the line extends along y.
+        assert_near(slots[0], anchor);
+        assert_near(slots[1], anchor + Vec2::new(0.0, -10.0));
+        assert_near(slots[2], anchor + Vec2::new(0.0, 10.0));
+        assert_near(slots[3], anchor + Vec2::new(0.0, -20.0));
+        assert_near(slots[4], anchor + Vec2::new(0.0, 20.0));
+    }
+
+    #[test]
+    fn wedge_slots() {
+        let anchor = Vec2::new(-30.0, 0.0);
+        let slots = slots(FormationKind::Wedge, anchor, PI * 0.5, 3);
+        assert_eq!(slots.len(), 3);
+
+        // Facing +y, so the wedge trails behind in -y.
+        assert_near(slots[0], anchor);
+        assert_near(slots[1], anchor + Vec2::new(10.0, -10.0));
+        assert_near(slots[2], anchor + Vec2::new(-10.0, -10.0));
+    }
+
+    #[test]
+    fn single_member() {
+        for kind in [FormationKind::Line, FormationKind::Wedge] {
+            assert_eq!(slots(kind, Vec2::ONE, 1.0, 1), vec![Vec2::ONE]);
+            assert!(slots(kind, Vec2::ONE, 1.0, 0).is_empty());
+        }
+    }
+
+    #[test]
+    fn grid() {
+        assert_eq!(
+            snap_to_grid(Vec2::new(74.0, -26.0), 50.0),
+            Vec2::new(50.0, -50.0)
+        );
+        let formation = place_formation(FormationKind::Line, Vec2::new(130.0, 10.0), 0.5);
+        assert_eq!((formation.x, formation.y), (150.0, 0.0));
+    }
+}
@@ -3,38 +3,70 @@
 
 use crate::armament::{group_armaments, FireRateLimiter, Group};
 use crate::audio::Audio;
+use crate::auto_fire::{aim_auto_fire, auto_fire_targets};
 use crate::background::{Mk48BackgroundContext, Mk48OverlayContext};
+use crate::combat_alert::{damage_directions, CombatAlerts};
+use crate::combat_log::{CombatEvent, CombatLog};
+use crate::contact_icon::{draw_contact_icon, ContactIcon};
+#[cfg(debug_assertions)]
+use crate::dev::DevState;
+use crate::fire_buffer::FireBuffer;
+use crate::formation::{draw_formation, next_formation_mode, place_formation};
+#[cfg(debug_assertions)]
+use crate::hitbox::Hitbox;
 use crate::interpolated::Interpolated;
 use crate::interpolated_contact::InterpolatedContact;
-use crate::particle::{Mk48Particle, Mk48ParticleContext, Mk48ParticleLayer};
+use crate::low_fps::LowFpsWarning;
+use crate::motion_blur::{motion_blur, CameraVelocity};
+use crate::particle::{
+    add_jittered, particle_amount, Mk48Particle, Mk48ParticleContext, Mk48ParticleLayer,
+    ParticleJitter,
+};
 use crate::settings::Mk48Settings;
+use crate::spectate::Spectator;
 use crate::sprite::SortableSprite;
 use crate::state::Mk48State;
+use crate::team_color::{friendly_color, team_color};
+use crate::tracer::draw_tracer;
+use crate::translation::Mk48Translation;
 use crate::ui::{
     InstructionsProps, UiEvent, UiProps, UiState, UiStatus, UiStatusPlaying, UiStatusRespawning,
+    UiStatusSpectating,
 };
+use crate::velocity_vector::draw_velocity_vector;
+use crate::you_are_here::YouAreHere;
+use crate::zoom::{CameraCentering, OverviewZoom};
 use client_util::context::Context;
 use client_util::fps_monitor::FpsMonitor;
 use client_util::game_client::GameClient;
 use client_util::joystick::Joystick;
+use client_util::js_util::{download, is_spectating};
+use client_util::key_bindings::GameAction;
 use client_util::keyboard::{Key, KeyboardEvent};
 use client_util::mouse::{MouseButton, MouseEvent};
+use client_util::palette::SemanticColor;
 use client_util::rate_limiter::RateLimiter;
+use client_util::setting::CommonSettings;
 use common::altitude::Altitude;
 use common::angle::Angle;
 use common::contact::{Contact, ContactTrait};
 use common::entity::{EntityData, EntityId, EntityKind, EntitySubKind, EntityType};
 use common::guidance::Guidance;
-use common::protocol::{Command, Control, Fire, Hint, Pay, Spawn, Update, Upgrade};
+use common::protocol::{Command, Control, Fire, Hint, Pay, Spawn, Spectate, Update, Upgrade};
 use common::ticks::Ticks;
 use common::transform::Transform;
 use common::velocity::Velocity;
 use common::world::strict_area_border;
 use common_util::range::{gen_radius, lerp, map_ranges};
+use core_protocol::dto::{FormationDto, FormationKind};
 use core_protocol::id::{GameId, TeamId};
+use core_protocol::rpc::{Request, TeamRequest};
 use glam::{Mat2, Vec2, Vec4, Vec4Swizzles};
+use js_hooks::console_error;
 use rand::{thread_rng, Rng};
-use renderer::{gray, rgb, rgba, Layer, Texture, TextureFormat};
+use renderer::{
+    gray, gray_a, rgba, Layer, MotionBlurLayer, RenderStats, Texture, TextureFormat, Toggle,
+};
 use renderer2d::{
     BackgroundContext, BackgroundLayer, Camera2d, GraphicLayer, ParticleLayer, Renderer2d,
     SpriteLayer, TextLayer,
@@ -53,6 +85,10 @@ pub struct Mk48Game {
     pub reversing: bool,
     /// Camera on death.
     pub saved_camera: Option<(Vec2, f32)>,
+    /// Camera center last frame, and the player's ship it was following, for the deadzone.
+    pub camera_center: Option<(EntityId, Vec2)>,
+    /// Eases the camera back onto the player's ship, after the center camera key is pressed.
+    pub camera_centering: CameraCentering,
     /// Override respawning with regular spawning.
     respawn_overridden: bool,
     /// Interpolate altitude for smooth animation of visual range and restriction.
@@ -61,6 +97,10 @@ pub struct Mk48Game {
     pub interpolated_zoom: f32,
     /// 1 = normal.
     pub zoom_input: f32,
+    /// Zooms out while the overview key is held.
+    pub overview_zoom: OverviewZoom,
+    /// For motion blur.
+    pub camera_velocity: CameraVelocity,
     /// Last control, for diffing.
     pub last_control: Option<Control>,
     /// Rate limit control websocket messages.
@@ -76,8 +116,29 @@ pub struct Mk48Game {
     /// If a given index is present and non-zero, should avoid firing weapon (was fired recently,
     /// and is probably consumed).
     pub fire_rate_limiter: FireRateLimiter,
+    /// Fire inputs that haven't been sent yet.
+    pub fire_buffer: FireBuffer,
+    /// Fire the primary (first) armament instead of the selected one, after a double-tap.
+    pub fire_primary: bool,
     /// FPS counter
     pub fps_counter: FpsMonitor,
+    /// Of the last rendered frame, for [`Mk48Settings::render_stats_shown`].
+    pub render_stats: RenderStats,
+    /// Suggests lowering graphics settings if the framerate stays low.
+    pub low_fps_warning: LowFpsWarning,
+    /// Flashes at the edge of the screen towards sources of damage.
+    pub combat_alerts: CombatAlerts,
+    /// Damage dealt and received, kills, etc. over the current life, for exporting.
+    pub combat_log: CombatLog,
+    /// Intensity of camera shake due to damage received since the last frame.
+    pub damage_shake: f32,
+    /// Kind of team formation that the next click places, if any.
+    pub formation_mode: Option<FormationKind>,
+    /// Developer debug toggles.
+    #[cfg(debug_assertions)]
+    pub dev: DevState,
+    /// Chooses whose boat to watch, if spectating instead of playing.
+    pub spectator: Option<Spectator>,
     ui_state: UiState,
 }
 
@@ -91,8 +152,8 @@ pub struct RendererLayer {
     pub airborne_particles: Mk48ParticleLayer,
     airborne_graphics: GraphicLayer,
     overlay: BackgroundLayer<Mk48OverlayContext>,
-    graphics: GraphicLayer,
-    text: TextLayer,
+    graphics: Toggle<GraphicLayer>,
+    text: Toggle<TextLayer>,
 }
 
 pub fn wind() -> Vec2 {
@@ -115,14 +176,61 @@ impl Mk48Game {
     fn has_reverse(&self, player_contact: &Contact) -> bool {
         player_contact.entity_type().unwrap().data().level > 1
     }
+
+    /// Gets the boat the camera follows, which is the player's own, or the one being spectated.
+    fn camera_contact<'a>(&self, context: &'a Context<Self>) -> Option<&'a Contact> {
+        context.state.game.player_contact().or_else(|| {
+            let target = self.spectator.as_ref()?.target()?;
+            context
+                .state
+                .game
+                .contacts
+                .values()
+                .map(|contact| &contact.view)
+                .find(|contact| {
+                    contact.player_id() == Some(target)
+                        && contact
+                            .entity_type()
+                            .map_or(false, |t| t.data().kind == EntityKind::Boat)
+                })
+        })
+    }
+
+    /// Gets the alias of the player controlling `contact`, for the combat log.
+    fn contact_alias(contact: &Contact, context: &Context<Self>) -> String {
+        contact
+            .player_id()
+            .and_then(|player_id| context.state.core.player_or_bot(player_id))
+            .map(|player| player.alias.as_str().to_owned())
+            .unwrap_or_default()
+    }
+
+    /// Gets the aim target from `mouse_position`, adjusted by the player's settings relative to own
+    /// boat at `position` (see [`CommonSettings::adjust_aim`]). Touches aren't adjusted, so they
+    /// stay under the finger.
+    fn aim_target(
+        mouse_position: Vec2,
+        position: Vec2,
+        touch_screen: bool,
+        common_settings: &CommonSettings,
+        camera: &Camera2d,
+    ) -> Vec2 {
+        if touch_screen {
+            return mouse_position;
+        }
+        let (min, max) = camera.view_bounds();
+        let reach = (max - min).length() * 0.5;
+        position + common_settings.adjust_aim(mouse_position - position, reach)
+    }
 }
 
 impl GameClient for Mk48Game {
     const GAME_ID: GameId = GameId::Mk48;
+    const VERSION: &'static str = env!("CARGO_PKG_VERSION");
 
     type Audio = Audio;
     type GameRequest = Command;
-    type RendererLayer = RendererLayer;
+    type RendererLayer = MotionBlurLayer<RendererLayer>;
     type Camera = Camera2d;
     type GameState = Mk48State;
     type UiEvent = UiEvent;
@@ -144,7 +252,11 @@ impl GameClient for Mk48Game {
             interpolated_altitude: Interpolated::new(0.2),
             interpolated_zoom: Self::DEFAULT_ZOOM_INPUT * Self::MENU_VISUAL_RANGE,
             zoom_input: Self::DEFAULT_ZOOM_INPUT,
+            overview_zoom: OverviewZoom::default(),
+            camera_velocity: CameraVelocity::default(),
             saved_camera: None,
+            camera_center: None,
+            camera_centering: CameraCentering::default(),
             respawn_overridden: false,
             last_control: None,
             control_rate_limiter: RateLimiter::new(0.1),
@@ -152,7 +264,18 @@ impl GameClient for Mk48Game {
             alarm_fast_rate_limiter: RateLimiter::new(10.0),
             peek_update_sound_counter: 0,
             fire_rate_limiter: FireRateLimiter::new(),
+            fire_buffer: FireBuffer::default(),
+            fire_primary: false,
             fps_counter: FpsMonitor::new(1.0),
+            render_stats: RenderStats::default(),
+            low_fps_warning: LowFpsWarning::default(),
+            combat_alerts: CombatAlerts::default(),
+            combat_log: CombatLog::default(),
+            damage_shake: 0.0,
+            formation_mode: None,
+            #[cfg(debug_assertions)]
+            dev: DevState::default(),
+            spectator: is_spectating().then(Spectator::default),
             ui_state: UiState::default(),
         }
     }
@@ -198,19 +321,26 @@ impl GameClient for Mk48Game {
             self.ui_props_rate_limiter.set_period(0.1);
         }
 
-        RendererLayer {
-            background: BackgroundLayer::new(renderer, background_context),
+        let mut background = BackgroundLayer::new(renderer, background_context);
+        background.set_tiling(Mk48BackgroundContext::TILES_PER_METER);
+
+        let layer = RendererLayer {
+            background,
             sea_level_particles: ParticleLayer::new(
                 renderer,
-                Mk48ParticleContext { wind: Vec2::ZERO },
+                Mk48ParticleContext::new(Vec2::ZERO, thread_rng().gen()),
             ),
             sprites: SpriteLayer::new(renderer, sprite_texture, sprite_sheet),
-            airborne_particles: ParticleLayer::new(renderer, Mk48ParticleContext { wind: wind() }),
+            airborne_particles: ParticleLayer::new(
+                renderer,
+                Mk48ParticleContext::new(wind(), thread_rng().gen()),
+            ),
             airborne_graphics: GraphicLayer::new(renderer),
             overlay: BackgroundLayer::new(renderer, overlay_context),
-            graphics: GraphicLayer::new(renderer),
-            text: TextLayer::new(renderer),
-        }
+            graphics: Toggle::new(GraphicLayer::new(renderer)),
+            text: Toggle::new(TextLayer::new(renderer)),
+        };
+        MotionBlurLayer::new(renderer, layer)
     }
 
     /// This violates the normal "peek" contract by doing the work of apply, when it comes to contacts.
@@ -225,6 +355,22 @@ impl GameClient for Mk48Game {
         // Only play sounds for 10 peeked updates between frames.
         let play_sounds = self.peek_update_sound_counter < 10;
 
+        if let Some(death_reason) = update
+            .death_reason
+            .as_ref()
+            .filter(|_| context.state.game.death_reason.is_none())
+        {
+            self.combat_log.push(
+                context.client.update_seconds,
+                CombatEvent::Death {
+                    reason: context.common_settings.language.death_reason(death_reason),
+                },
+            );
+        }
+
+        self.combat_log
+            .update_kills(context.client.update_seconds, update.kills);
+
         let updated: HashMap<EntityId, &Contact> =
             update.contacts.iter().map(|c| (c.id(), c)).collect();
 
@@ -237,6 +383,32 @@ impl GameClient for Mk48Game {
                             context.audio.play(Audio::Damage);
                         }
 
+                        let damage = recent_damage.to_secs() / model.data().max_health().to_secs();
+                        self.combat_log.push(
+                            context.client.update_seconds,
+                            CombatEvent::DamageReceived { damage },
+                        );
+
+                        if context.settings.animations && !context.settings.reduce_motion {
+                            // Big hits shake more.
+                            self.damage_shake += 0.005 + damage * 0.05;
+                        }
+
+                        if context.settings.combat_alert {
+                            let position = model.transform().position;
+                            for direction in damage_directions(
+                                position,
+                                &context.state.game.contacts,
+                                &context.state.core,
+                            ) {
+                                self.combat_alerts.alert(
+                                    direction,
+                                    renderer.aspect_ratio(),
+                                    context.client.update_seconds,
+                                );
+                            }
+                        }
+
                         // Considered "intense" 250% of the damage would have been fatal.
                         if play_sounds
                             && recent_damage * 2.5
@@ -245,6 +417,31 @@ impl GameClient for Mk48Game {
                             Self::play_music(Audio::Intense, &context.audio);
                         }
                     }
+                } else if model.is_boat() && !context.state.core.is_friendly(model.player_id()) {
+                    let recent_damage = contact.damage().saturating_sub(model.damage());
+                    if recent_damage > Ticks::ZERO {
+                        // Presume the player dealt the damage if one of their contacts (e.g. a
+                        // weapon or their boat) was touching.
+                        let position = model.transform().position;
+                        let radius = model.data().radius;
+                        let player_id = context.state.core.player_id;
+                        let by_player = player_id.is_some()
+                            && context.state.game.contacts.values().any(
+                                |InterpolatedContact { model: other, .. }| {
+                                    other.player_id() == player_id
+                                        && other.transform().position.distance(position)
+                                            <= radius + other.data().radius
+                                },
+                            );
+                        if by_player {
+                            self.combat_log.damage_dealt(
+                                context.client.update_seconds,
+                                *id,
+                                Self::contact_alias(model, context),
+                                recent_damage.to_secs() / model.data().max_health().to_secs(),
+                            );
+                        }
+                    }
                 }
 
                 // Mutable borrow after immutable borrows.
@@ -258,7 +455,7 @@ impl GameClient for Mk48Game {
                 if play_sounds {
                     self.play_new_contact_audio(
                         contact,
-                        renderer.camera.center,
+                        Self::audio_listener(&renderer.camera),
                         &*context,
                         &context.audio,
                     );
@@ -269,6 +466,7 @@ impl GameClient for Mk48Game {
                     self.first_control = true;
                     self.first_zoom = true;
                     self.interpolated_altitude.reset();
+                    self.combat_log.reset(context.client.update_seconds);
                 }
                 context
                     .state
@@ -312,7 +510,7 @@ impl GameClient for Mk48Game {
             if play_sounds {
                 let time_seconds = context.client.update_seconds;
                 self.play_lost_contact_audio_and_animations(
-                    renderer.camera.center,
+                    Self::audio_listener(&renderer.camera),
                     &contact,
                     &context.audio,
                     &mut context.state.game.animations,
@@ -321,7 +519,7 @@ impl GameClient for Mk48Game {
             }
         }
 
-        let player_position = renderer.camera.center;
+        let listener = Self::audio_listener(&renderer.camera);
         let player_altitude = context
             .state
             .game
@@ -336,14 +534,14 @@ impl GameClient for Mk48Game {
         for (_, InterpolatedContact { view: contact, .. }) in context.state.game.contacts.iter() {
             if let Some(entity_type) = contact.entity_type() {
                 let data: &'static EntityData = entity_type.data();
-                let position_diff = contact.transform().position - player_position;
+                let position_diff = contact.transform().position - listener.center;
                 let direction = Angle::from(position_diff);
                 let distance = position_diff.length();
                 let inbound =
                     (contact.transform().direction - direction + Angle::PI).abs() < Angle::PI_2;
 
                 let friendly = context.state.core.is_friendly(contact.player_id());
-                let volume = Self::volume_at(distance);
+                let volume = listener.volume_at(contact.transform().position);
 
                 if data.kind == EntityKind::Aircraft {
                     if matches!(entity_type, EntityType::SuperEtendard) {
@@ -409,7 +607,28 @@ impl GameClient for Mk48Game {
     }
 
     fn peek_keyboard(&mut self, event: &KeyboardEvent, context: &mut Context<Self>) {
+        #[cfg(debug_assertions)]
+        if self.dev.peek_keyboard(event) {
+            self.ui_props_rate_limiter.fast_track();
+            return;
+        }
+
         if event.down {
+            if let Some(zoom_input) = Self::preset_zoom_input(&context.settings, event.key) {
+                self.zoom_input = zoom_input;
+                self.first_zoom = false;
+            }
+
+            if event.key == GameAction::CenterCamera.default_key() {
+                self.camera_centering.start();
+            }
+
+            if event.key == Key::Space && context.state.game.player_contact().is_none() {
+                if let Some(spectator) = self.spectator.as_mut() {
+                    spectator.next();
+                }
+            }
+
             if let Some(contact) = context.state.game.player_contact() {
                 let entity_type = contact.entity_type().unwrap();
                 let consumptions: Vec<bool> = contact.reloads().iter().map(|b| *b).collect();
@@ -421,6 +640,24 @@ impl GameClient for Mk48Game {
                     Key::Z => {
                         self.ui_state.active = !self.ui_state.active;
                     }
+                    Key::Space | Key::E => {
+                        self.fire_buffer
+                            .press(context.settings.fire_buffer_millis as f32 * 0.001);
+                    }
+                    Key::F => {
+                        let core = &context.state.core;
+                        if core.player_id.is_some()
+                            && core.members.first() == core.player_id.as_ref()
+                        {
+                            if event.shift {
+                                // Clear the formation.
+                                self.formation_mode = None;
+                                context.send_to_server(Request::Team(TeamRequest::Formation(None)));
+                            } else {
+                                self.formation_mode = next_formation_mode(self.formation_mode);
+                            }
+                        }
+                    }
                     Key::Tab => {
                         self.ui_state.armament = groups
                             .get(
@@ -457,9 +694,13 @@ impl GameClient for Mk48Game {
         _context: &mut Context<Self>,
         _renderer: &Renderer2d,
     ) {
-        if let MouseEvent::Wheel(delta) = event {
-            self.zoom(*delta);
-            self.first_zoom = false;
+        match event {
+            MouseEvent::Wheel(delta) => {
+                self.zoom(*delta);
+                self.first_zoom = false;
+            }
+            MouseEvent::DoubleTap(_) => self.fire_primary = true,
+            _ => {}
         }
     }
 
@@ -473,17 +714,32 @@ impl GameClient for Mk48Game {
         // Allow more sounds to be played in peek.
         self.peek_update_sound_counter = 0;
 
+        #[cfg(debug_assertions)]
+        {
+            layer.graphics.set_visible(!self.dev.screenshot);
+            layer.text.set_visible(!self.dev.screenshot);
+        }
+
         // The distance from player's boat to the closest visible member of each team, for the purpose of sorting and
         // filtering.
         let mut team_proximity: HashMap<TeamId, f32> = HashMap::new();
 
+        self.overview_zoom
+            .set_held(context.keyboard.is_down(GameAction::Overview.default_key()));
+
         // Temporary (will be recalculated after moving ships).
         self.update_camera(
-            context.state.game.player_contact(),
+            self.camera_contact(context),
             elapsed_seconds,
+            context.settings.zoom_speed,
             layer.background.context.cache_frame(),
         );
-        let (camera, _) = self.camera(context.state.game.player_contact(), renderer.aspect_ratio());
+        let (camera, _) = self.camera(
+            self.camera_contact(context),
+            renderer.aspect_ratio(),
+            context.settings.camera_deadzone,
+            context.state.game.world_radius,
+        );
 
         // Update audio volume.
         if Self::maybe_contact_mut(
@@ -495,14 +751,18 @@ impl GameClient for Mk48Game {
         {
             context.audio.set_muted_by_game(false);
             if !context.audio.is_playing(Audio::Ocean) {
-                context.audio.play_looping(Audio::Ocean);
+                context.audio.play_music_looping(Audio::Ocean);
             }
         } else {
             context.audio.set_muted_by_game(true);
             self.last_control = None;
         }
 
-        let debug_latency_entity_id = if false {
+        #[cfg(debug_assertions)]
+        let debug_latency = self.dev.latency;
+        #[cfg(not(debug_assertions))]
+        let debug_latency = false;
+        let debug_latency_entity_id = if debug_latency {
             context.state.game.entity_id
         } else {
             None
@@ -542,14 +802,71 @@ impl GameClient for Mk48Game {
             interp.interpolate(elapsed_seconds, context.state.game.entity_id);
         }
 
+        match (&mut self.camera_center, context.state.game.player_contact()) {
+            (Some((entity_id, center)), Some(player_contact))
+                if *entity_id == player_contact.id() =>
+            {
+                *center = self.camera_centering.update(
+                    *center,
+                    player_contact.transform().position,
+                    elapsed_seconds,
+                );
+            }
+            // Nothing to center on.
+            _ => self.camera_centering = CameraCentering::default(),
+        }
+
         // May have changed due to the above.
-        let (camera, zoom) =
-            self.camera(context.state.game.player_contact(), renderer.aspect_ratio());
+        let (camera, zoom) = self.camera(
+            self.camera_contact(context),
+            renderer.aspect_ratio(),
+            context.settings.camera_deadzone,
+            context.state.game.world_radius,
+        );
+        if let Some(player_contact) = context.state.game.player_contact() {
+            self.camera_center = Some((player_contact.id(), camera));
+        }
+
+        if self.damage_shake > 0.0 {
+            renderer
+                .camera
+                .add_shake(std::mem::take(&mut self.damage_shake), 0.4);
+        }
+        renderer.camera.update_shake(elapsed_seconds);
 
         // Set camera before update layers so they don't get last frame's camera.
         // TODO decouple update and render.
         renderer.camera.update(camera, zoom, renderer.canvas_size());
 
+        // Inset by half the thickness so the view bounds are visible at the edges of the screen.
+        #[cfg(debug_assertions)]
+        if self.dev.hitboxes {
+            let thickness = 0.004 * zoom;
+            let (min, max) = renderer.camera.view_bounds();
+            let (min, max) = (min + thickness * 0.5, max - thickness * 0.5);
+            let corners = [min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)];
+            for (i, &start) in corners.iter().enumerate() {
+                let end = corners[(i + 1) % corners.len()];
+                layer
+                    .graphics
+                    .draw_line(start, end, thickness, rgba(0, 255, 255, 200));
+            }
+        }
+
+        let camera_velocity = self.camera_velocity.update(camera, elapsed_seconds);
+        layer.set_blur(
+            if context.settings.motion_blur && context.settings.animations {
+                motion_blur(
+                    camera_velocity,
+                    zoom,
+                    renderer.aspect_ratio(),
+                    context.settings.reduce_motion,
+                )
+            } else {
+                Vec2::ZERO
+            },
+        );
+
         let (visual_range, visual_restriction, area) =
             if let Some(c) = context.state.game.player_interpolated_contact() {
                 // Use model as input to interpolation (can't interpolate twice).
@@ -589,6 +906,13 @@ impl GameClient for Mk48Game {
             area,
         );
 
+        for particles in [
+            &mut layer.sea_level_particles,
+            &mut layer.airborne_particles,
+        ] {
+            particles.context.jitter = context.settings.particle_jitter;
+        }
+
         let mut anti_aircraft_volume = 0.0;
 
         // Update animations.
@@ -616,15 +940,33 @@ impl GameClient for Mk48Game {
             .trails
             .set_time(context.client.update_seconds);
 
-        for InterpolatedContact { view: contact, .. } in context.state.game.contacts.values() {
+        // Whether all labels should temporarily be shown.
+        let show_all_labels = context
+            .keyboard
+            .is_down(GameAction::ShowLabels.default_key());
+        let color_vision = context.common_settings.color_vision;
+
+        for InterpolatedContact {
+            view: contact,
+            previous_position,
+            ..
+        } in context.state.game.contacts.values()
+        {
             let friendly = context.state.core.is_friendly(contact.player_id());
+            let friendly_color = friendly.then(|| {
+                friendly_color(
+                    context.settings.team_colors,
+                    color_vision,
+                    contact.player_id(),
+                )
+            });
 
-            let color = if friendly {
-                rgb(58, 255, 140)
+            let color = if let Some(friendly_color) = friendly_color {
+                friendly_color
             } else if contact.is_boat() {
                 gray(255)
             } else {
-                rgb(231, 76, 60)
+                color_vision.color(SemanticColor::Enemy)
             };
 
             if let Some(entity_type) = contact.entity_type() {
@@ -639,8 +981,25 @@ impl GameClient for Mk48Game {
                 };
                 let entity_id = contact.id();
                 let data: &'static EntityData = entity_type.data();
+                let icon = ContactIcon::select(
+                    context.settings.contact_icons,
+                    data.kind,
+                    context.state.core.player_id.is_some()
+                        && contact.player_id() == context.state.core.player_id,
+                );
 
-                {
+                if icon != ContactIcon::Sprite {
+                    draw_contact_icon(
+                        &mut layer.graphics,
+                        icon,
+                        contact.transform(),
+                        data.level,
+                        friendly_color,
+                        color_vision,
+                        zoom,
+                        alpha,
+                    );
+                } else {
                     let mut transform = *contact.transform();
                     let settings = &context.settings;
 
@@ -685,12 +1044,12 @@ impl GameClient for Mk48Game {
                         contact,
                         &context.state.game.contacts,
                         &context.state.core,
-                        renderer.camera.center,
+                        Self::audio_listener(&renderer.camera),
                         &mut layer.airborne_particles,
                     );
                 }
 
-                if contact.is_boat() {
+                if contact.is_boat() && icon == ContactIcon::Sprite {
                     for i in 0..data.armaments.len() {
                         let armament = &data.armaments[i];
                         if armament.hidden
@@ -715,7 +1074,13 @@ impl GameClient for Mk48Game {
                         ));
                     }
                 }
-                for (i, turret) in data.turrets.iter().enumerate() {
+                // Simplified icons don't show turrets.
+                let turrets = if icon == ContactIcon::Sprite {
+                    data.turrets.as_slice()
+                } else {
+                    &[]
+                };
+                for (i, turret) in turrets.iter().enumerate() {
                     if let Some(turret_type) = turret.entity_type {
                         let pos = turret.position();
                         sortable_sprites.push(SortableSprite::new_child_entity(
@@ -739,6 +1104,37 @@ impl GameClient for Mk48Game {
                     }
                 }
 
+                #[cfg(debug_assertions)]
+                if self.dev.hitboxes {
+                    Hitbox::new(data, contact.transform()).draw(
+                        &mut layer.graphics,
+                        0.002 * zoom,
+                        rgba(255, 255, 0, 200),
+                    );
+                }
+
+                if let Some(previous_position) =
+                    previous_position.filter(|_| data.kind == EntityKind::Weapon)
+                {
+                    draw_tracer(
+                        &mut layer.graphics,
+                        previous_position,
+                        contact.transform().position,
+                        contact.transform().velocity.to_mps(),
+                        data.width.max(0.001 * zoom),
+                        rgba(255, 230, 170, 150),
+                    );
+                }
+
+                if context.settings.velocity_vectors && !context.settings.cinematic {
+                    draw_velocity_vector(
+                        &mut layer.graphics,
+                        contact.transform(),
+                        0.002 * zoom,
+                        rgba(0, 255, 255, 150),
+                    );
+                }
+
                 // GUI overlays.
                 let overlay_vertical_position = data.radius * 1.2;
 
@@ -754,6 +1150,15 @@ impl GameClient for Mk48Game {
                             let reverse_color = rgba(255, 75, 75, 120);
                             let hud_thickness = 0.0025 * zoom;
 
+                            if let Some(marker) = YouAreHere::new(
+                                contact.transform().position,
+                                self.overview_zoom.zoom_input(self.zoom_input),
+                                context.settings.you_are_here_zoom,
+                                zoom,
+                            ) {
+                                marker.draw(&mut layer.graphics);
+                            }
+
                             // Throttle rings.
                             // 1. Inner
                             layer.graphics.draw_circle(
@@ -879,7 +1284,13 @@ impl GameClient for Mk48Game {
                             let ui_armament = self.ui_state.armament;
                             if let Some((i, mouse_pos)) =
                                 context.mouse.view_position.and_then(|view_pos| {
-                                    let mouse_pos = renderer.camera.to_world_position(view_pos);
+                                    let mouse_pos = Self::aim_target(
+                                        renderer.camera.to_world_position(view_pos),
+                                        contact.transform().position,
+                                        context.mouse.touch_screen,
+                                        &context.common_settings,
+                                        &renderer.camera,
+                                    );
                                     self.find_best_armament(contact, false, mouse_pos, ui_armament)
                                         .zip(Some(mouse_pos))
                                 })
@@ -1067,7 +1478,7 @@ impl GameClient for Mk48Game {
                         }
 
                         // Name
-                        let text = if let Some(player) = context
+                        let name = if let Some(player) = context
                             .state
                             .core
                             .player_or_bot(contact.player_id().unwrap())
@@ -1084,14 +1495,28 @@ impl GameClient for Mk48Game {
                             // This is not meant to happen in production. It is for debugging.
                             format!("{}", contact.player_id().unwrap().0.get())
                         };
-
-                        layer.text.draw(
-                            &text,
-                            contact.transform().position
-                                + Vec2::new(0.0, overlay_vertical_position + 0.035 * zoom),
-                            0.035 * zoom,
-                            color.extend(1.0),
-                        );
+                        let own_label = context.settings.own_label
+                            && context.state.game.entity_id == Some(entity_id);
+                        let contact_label = context
+                            .settings
+                            .contact_label
+                            .with_modifier(show_all_labels)
+                            .with_always_shown(own_label);
+                        let text = contact_label.format(&name, data.level, &data.label);
+                        let text_position = contact.transform().position
+                            + Vec2::new(0.0, overlay_vertical_position + 0.035 * zoom);
+                        let text_height = 0.035 * zoom;
+
+                        // Cull labels that are entirely off screen (they are at most as wide as
+                        // their length times their height).
+                        if renderer
+                            .camera
+                            .is_visible(text_position, text.len() as f32 * text_height)
+                        {
+                            layer
+                                .text
+                                .draw(&text, text_position, text_height, color.extend(1.0));
+                        }
                     }
                     EntityKind::Weapon | EntityKind::Decoy | EntityKind::Aircraft => {
                         let triangle_position = contact.transform().position
@@ -1102,6 +1527,15 @@ impl GameClient for Mk48Game {
                             180f32.to_radians(),
                             color.extend(1.0),
                         );
+
+                        if show_all_labels {
+                            layer.text.draw(
+                                &data.label,
+                                triangle_position + Vec2::new(0.0, 0.035 * zoom),
+                                0.025 * zoom,
+                                color.extend(1.0),
+                            );
+                        }
                     }
                     _ => {}
                 }
@@ -1116,15 +1550,13 @@ impl GameClient for Mk48Game {
                 let amount = {
                     let per_second = data.width * 6.0 + speed * 2.0;
                     let t = context.client.update_seconds;
-                    let time_delta = elapsed_seconds;
 
                     // Essentially a random number based on time.
                     const SUB_STEPS: f32 = 1000.0 * PI;
                     let f = (t as f64 * SUB_STEPS as f64).fract() as f32;
 
-                    // Clamp to 100 just in case floats aren't being very nice.
-                    // Yamato makes ~30 particles per frame (60 fps) so it's plenty.
-                    ((time_delta * per_second + f).floor() as usize).min(100)
+                    // Yamato makes ~30 particles per frame (60 fps) so the limit is plenty.
+                    particle_amount(per_second, elapsed_seconds, f)
                 };
 
                 // Wake/thrust particles and shell trails.
@@ -1172,13 +1604,19 @@ impl GameClient for Mk48Game {
                             let velocity = direction_vector * (speed * 0.75)
                                 + tangent_vector * (speed * r * spread);
 
-                            layer.add(Mk48Particle {
-                                position,
-                                velocity,
-                                radius: 1.0,
-                                color: 1.0,
-                                smoothness: 1.0,
-                            });
+                            add_jittered(
+                                layer,
+                                Mk48Particle {
+                                    position,
+                                    velocity,
+                                    radius: 1.0,
+                                    color: 1.0,
+                                    smoothness: 1.0,
+                                    lifespan: 1.0,
+                                    ..Default::default()
+                                },
+                                &ParticleJitter::WAKE,
+                            );
                         }
                     }
                 }
@@ -1187,20 +1625,26 @@ impl GameClient for Mk48Game {
                 if !contact.altitude().is_submerged() {
                     for exhaust in data.exhausts.iter() {
                         for _ in 0..amount * 2 {
-                            layer.airborne_particles.add(Mk48Particle {
-                                position: contact.transform().position
-                                    + direction_vector * exhaust.position_forward
-                                    + tangent_vector * exhaust.position_side
-                                    + gen_radius(&mut rng, 1.5),
-                                velocity: gen_radius(&mut rng, 6.0),
-                                radius: 1.0,
-                                color: if entity_type == EntityType::OilPlatform {
-                                    -1.0
-                                } else {
-                                    0.4
+                            add_jittered(
+                                &mut layer.airborne_particles,
+                                Mk48Particle {
+                                    position: contact.transform().position
+                                        + direction_vector * exhaust.position_forward
+                                        + tangent_vector * exhaust.position_side
+                                        + gen_radius(&mut rng, 1.5),
+                                    velocity: gen_radius(&mut rng, 6.0),
+                                    radius: 1.0,
+                                    color: if entity_type == EntityType::OilPlatform {
+                                        -1.0
+                                    } else {
+                                        0.4
+                                    },
+                                    smoothness: 1.0,
+                                    lifespan: 1.0,
+                                    ..Default::default()
                                 },
-                                smoothness: 1.0,
-                            });
+                                &ParticleJitter::EXHAUST,
+                            );
                         }
                     }
                 }
@@ -1222,6 +1666,56 @@ impl GameClient for Mk48Game {
             .trails
             .update(&mut layer.airborne_graphics);
 
+        // Team formation, and preview of where one would be placed.
+        let core = &context.state.core;
+        if let Some(formation) = core.formation.as_ref() {
+            let own_slot = core
+                .player_id
+                .and_then(|player_id| core.members.iter().position(|&id| id == player_id));
+            draw_formation(
+                &mut layer.graphics,
+                formation,
+                core.members.len(),
+                own_slot,
+                renderer.camera.zoom,
+                team_color(context.common_settings.color_vision).extend(1.0),
+            );
+        }
+        if let Some((kind, (mouse, contact))) = self.formation_mode.zip(
+            context
+                .mouse
+                .view_position
+                .zip(context.state.game.player_contact()),
+        ) {
+            let preview = place_formation(
+                kind,
+                renderer.camera.to_world_position(mouse),
+                contact.transform().direction.to_radians(),
+            );
+            draw_formation(
+                &mut layer.graphics,
+                &preview,
+                core.members.len(),
+                None,
+                renderer.camera.zoom,
+                gray_a(255, 150),
+            );
+        }
+
+        if context.settings.combat_alert && !context.settings.cinematic {
+            self.combat_alerts.draw(
+                &mut layer.graphics,
+                context
+                    .common_settings
+                    .color_vision
+                    .color(SemanticColor::Danger),
+                renderer.camera.center,
+                renderer.camera.zoom,
+                renderer.aspect_ratio(),
+                context.client.update_seconds,
+            );
+        }
+
         // Play anti-aircraft sfx.
         if anti_aircraft_volume > 0.0 && !context.audio.is_playing(Audio::Aa) {
             context
@@ -1242,20 +1736,35 @@ impl GameClient for Mk48Game {
             );
         }
 
-        // After the above line, mouse world position state may be out-of-date. Recalculate it here.
-        let aim_target = context
-            .mouse
-            .view_position
-            .map(|p| renderer.camera.to_world_position(p));
-
         // Send command later, when lifetimes allow.
         let mut control: Option<Command> = None;
+        let mut formation: Option<FormationDto> = None;
 
         let player_contact = Self::maybe_contact_mut(
             &mut context.state.game.contacts,
             context.state.game.entity_id,
         );
 
+        // After the above line, mouse world position state may be out-of-date. Recalculate it here.
+        let mouse_target = context
+            .mouse
+            .view_position
+            .map(|p| renderer.camera.to_world_position(p));
+        // Mouse sensitivity and invert only apply to aiming weapons, not steering.
+        let aim_target = mouse_target.map(|mouse_position| {
+            player_contact
+                .as_ref()
+                .map_or(mouse_position, |player_contact| {
+                    Self::aim_target(
+                        mouse_position,
+                        player_contact.view.transform().position,
+                        context.mouse.touch_screen,
+                        &context.common_settings,
+                        &renderer.camera,
+                    )
+                })
+        });
+
         crate::armament::update(
             player_contact.as_ref().and_then(|c| c.model.entity_type()),
             &mut self.ui_state.armament,
@@ -1297,7 +1806,7 @@ impl GameClient for Mk48Game {
                 {
                     let current_dir = player_contact.transform().direction;
                     let mut direction_target = Angle::from(
-                        aim_target.unwrap_or_default() - player_contact.transform().position,
+                        mouse_target.unwrap_or_default() - player_contact.transform().position,
                     );
 
                     // Only do when start holding.
@@ -1332,7 +1841,7 @@ impl GameClient for Mk48Game {
                             Velocity::ZERO
                         } else {
                             let mut velocity = Velocity::from_mps(map_ranges(
-                                aim_target
+                                mouse_target
                                     .unwrap_or_default()
                                     .distance(player_contact.transform().position),
                                 player_contact.data().radii(),
@@ -1378,42 +1887,106 @@ impl GameClient for Mk48Game {
             });
 
             if self.control_rate_limiter.update_ready(elapsed_seconds) {
-                let left_click = context.mouse.take_click(MouseButton::Left);
+                let mut left_click = context.mouse.take_click(MouseButton::Left);
+
+                // Place formation instead of firing.
+                if left_click {
+                    if let Some(kind) = self.formation_mode.take() {
+                        left_click = false;
+                        formation = mouse_target.map(|target| {
+                            place_formation(
+                                kind,
+                                target,
+                                player_contact.transform().direction.to_radians(),
+                            )
+                        });
+                    }
+                }
+
+                if left_click {
+                    self.fire_buffer
+                        .press(context.settings.fire_buffer_millis as f32 * 0.001);
+                }
+
+                let primary = std::mem::take(&mut self.fire_primary)
+                    .then(|| player_contact.data().armaments.first())
+                    .flatten()
+                    .map(|armament| armament.entity_type);
 
                 // Get hint before borrow of player_contact().
                 let hint = Some(Hint {
                     aspect: renderer.aspect_ratio(),
                 });
 
-                let current_control = Control {
-                    guidance: Some(*player_contact.guidance()), // TODO don't send if hasn't changed.
-                    submerge: self.ui_state.submerge,
-                    aim_target,
-                    active: self.ui_state.active,
-                    pay: context.keyboard.is_down(Key::C).then_some(Pay),
-                    fire: if left_click
-                        || context
-                            .keyboard
-                            .state(Key::Space)
-                            .combined(context.keyboard.state(Key::E))
-                            .is_down()
-                    {
-                        self.find_best_armament(
-                            player_contact,
-                            true,
-                            aim_target.unwrap_or_default(),
-                            self.ui_state.armament,
-                        )
-                        .map(|i| {
+                let mut shot_aim_target = aim_target;
+                let held = primary.is_some()
+                    || context
+                        .keyboard
+                        .state(Key::Space)
+                        .combined(context.keyboard.state(Key::E))
+                        .is_down();
+                // Taken so finding the armament can borrow self.
+                let mut fire_buffer = std::mem::take(&mut self.fire_buffer);
+                let buffered_fire = fire_buffer.tick(held, || {
+                    self.find_best_armament(
+                        player_contact,
+                        true,
+                        aim_target.unwrap_or_default(),
+                        primary.or(self.ui_state.armament),
+                    )
+                });
+                self.fire_buffer = fire_buffer;
+
+                let fire = if let Some(armament_index) = buffered_fire {
+                    armament_index.map(|i| {
+                        self.fire_rate_limiter.fired(i as u8);
+
+                        Fire {
+                            armament_index: i as u8,
+                        }
+                    })
+                } else if context.settings.auto_fire {
+                    self.ui_state
+                        .armament
+                        .and_then(|armament| {
+                            aim_auto_fire(
+                                player_contact.transform().position,
+                                armament.data().range,
+                                auto_fire_targets(
+                                    player_contact.id(),
+                                    &context.state.game.contacts,
+                                    &context.state.core,
+                                ),
+                                |target| {
+                                    self.find_best_armament(
+                                        player_contact,
+                                        true,
+                                        target,
+                                        self.ui_state.armament,
+                                    )
+                                },
+                            )
+                        })
+                        .map(|(i, target)| {
                             self.fire_rate_limiter.fired(i as u8);
+                            // Aim the shot at the target, not the mouse.
+                            shot_aim_target = Some(target);
 
                             Fire {
                                 armament_index: i as u8,
                             }
                         })
-                    } else {
-                        None
-                    },
+                } else {
+                    None
+                };
+
+                let current_control = Control {
+                    guidance: Some(*player_contact.guidance()), // TODO don't send if hasn't changed.
+                    submerge: self.ui_state.submerge,
+                    aim_target: shot_aim_target,
+                    active: self.ui_state.active,
+                    pay: context.keyboard.is_down(Key::C).then_some(Pay),
+                    fire,
                     hint,
                 };
 
@@ -1448,16 +2021,41 @@ impl GameClient for Mk48Game {
             .cloned()
         {
             UiStatus::Respawning(UiStatusRespawning { death_reason })
+        } else if let Some(spectator) = self.spectator.as_mut() {
+            if spectator.update(
+                &context.state.core.liveboard,
+                elapsed_seconds,
+                thread_rng().gen(),
+            ) {
+                context.send_to_game(Command::Spectate(Spectate {
+                    player_id: spectator.target(),
+                }));
+            }
+            UiStatus::Spectating(UiStatusSpectating {
+                alias: spectator
+                    .target()
+                    .and_then(|player_id| context.state.core.player_or_bot(player_id))
+                    .map(|player| player.alias),
+            })
         } else {
             UiStatus::Spawning
         };
 
+        if let Some(formation) = formation {
+            context.send_to_server(Request::Team(TeamRequest::Formation(Some(formation))));
+        }
+
         if let Some(control) = control {
             context.send_to_game(control);
         }
 
-        self.fps_counter.update(elapsed_seconds);
+        if let Some(fps) = self.fps_counter.update(elapsed_seconds) {
+            // Samples span about a second.
+            self.low_fps_warning.sample(fps, 1.0);
+        }
+        self.render_stats = renderer.stats();
         self.fire_rate_limiter.update(elapsed_seconds);
+        self.fire_buffer.update(elapsed_seconds);
 
         if self.ui_props_rate_limiter.update_ready(elapsed_seconds) {
             self.update_ui_props(context, status);
@@ -1505,9 +2103,31 @@ impl GameClient for Mk48Game {
             UiEvent::OverrideRespawn => {
                 self.respawn_overridden = true;
             }
+            UiEvent::SpectateNext => {
+                if let Some(spectator) = self.spectator.as_mut() {
+                    spectator.next();
+                }
+            }
+            UiEvent::ExportCombatLog => {
+                let format = context.settings.combat_log_format;
+                let file_name = format!("combat_log.{}", format.extension());
+                let contents = self.combat_log.export(format);
+                if let Err(e) = download(&file_name, format.mime_type(), &contents) {
+                    console_error!("could not export combat log: {}", e);
+                }
+            }
+            UiEvent::DismissLowFps => {
+                self.low_fps_warning.dismiss();
+                self.ui_props_rate_limiter.fast_track();
+            }
             UiEvent::Armament(armament) => {
                 self.ui_state.armament = armament;
             }
+            #[cfg(debug_assertions)]
+            UiEvent::Dev(dev) => {
+                self.dev = dev;
+                self.ui_props_rate_limiter.fast_track();
+            }
         }
     }
 }
@@ -0,0 +1,122 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use common::entity::{EntityData, EntitySubKind};
+use common::transform::Transform;
+use glam::{Vec2, Vec4};
+use renderer2d::GraphicLayer;
+
+/// Collision shape of an entity, in world space. Mirrors the server's collision logic (SAMs use
+/// radius collision, everything else uses oriented rectangles). Only compiled in debug builds.
+#[derive(Debug, PartialEq)]
+pub enum Hitbox {
+    Circle { center: Vec2, radius: f32 },
+    Rectangle { corners: [Vec2; 4] },
+}
+
+impl Hitbox {
+    /// Alpha of the fill, relative to the outline.
+    const FILL_ALPHA: f32 = 0.25;
+
+    /// Gets the hitbox of an entity with `data` at `transform`.
+    pub fn new(data: &EntityData, transform: &Transform) -> Self {
+        Self::from_shape(data.sub_kind, data.radius, data.dimensions(), transform)
+    }
+
+    fn from_shape(
+        sub_kind: EntitySubKind,
+        radius: f32,
+        dimensions: Vec2,
+        transform: &Transform,
+    ) -> Self {
+        if sub_kind == EntitySubKind::Sam {
+            Self::Circle {
+                center: transform.position,
+                radius,
+            }
+        } else {
+            let normal = transform.direction.to_vec();
+            let half_length = normal * (dimensions.x * 0.5);
+            let half_width = normal.perp() * (dimensions.y * 0.5);
+            let position = transform.position;
+            Self::Rectangle {
+                corners: [
+                    position + half_length + half_width,
+                    position - half_length + half_width,
+                    position - half_length - half_width,
+                    position + half_length - half_width,
+                ],
+            }
+        }
+    }
+
+    /// Draws the hitbox filled with a translucent `color` and outlined with `color`.
+    pub fn draw(&self, layer: &mut GraphicLayer, thickness: f32, color: Vec4) {
+        let fill = color * Vec4::new(1.0, 1.0, 1.0, Self::FILL_ALPHA);
+        match self {
+            &Self::Circle { center, radius } => {
+                layer.draw_filled_circle(center, radius, fill);
+                layer.draw_circle(center, radius, thickness, color);
+            }
+            Self::Rectangle { corners } => {
+                layer.draw_polygon_filled(corners, fill);
+                for (i, &start) in corners.iter().enumerate() {
+                    let end = corners[(i + 1) % corners.len()];
+                    layer.draw_line(start, end, thickness, color);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::hitbox::Hitbox;
+    use common::angle::Angle;
+    use common::entity::EntitySubKind;
+    use common::transform::Transform;
+    use glam::Vec2;
+
+    #[test]
+    fn test_rectangle_hitbox() {
+        let transform = Transform {
+            position: Vec2::new(10.0, 20.0),
+            direction: Angle::from_radians(std::f32::consts::FRAC_PI_2),
+            ..Transform::default()
+        };
+        let hitbox = Hitbox::from_shape(
+            EntitySubKind::Destroyer,
+            50.0,
+            Vec2::new(100.0, 10.0),
+            &transform,
+        );
+        let corners = if let Hitbox::Rectangle { corners } = hitbox {
+            corners
+        } else {
+            panic!("expected rectangle");
+        };
+
+        // Pointing north, so length is along the y axis.
+        let expected = [
+            Vec2::new(5.0, 70.0),
+            Vec2::new(5.0, -30.0),
+            Vec2::new(15.0, -30.0),
+            Vec2::new(15.0, 70.0),
+        ];
+        for (corner, expected) in corners.into_iter().zip(expected) {
+            assert!(corner.distance(expected) < 0.1, "{} {}", corner, expected);
+        }
+    }
+
+    #[test]
+    fn test_circle_hitbox() {
+        let transform = Transform::from_position(Vec2::new(-5.0, 3.0));
+        assert_eq!(
+            Hitbox::from_shape(EntitySubKind::Sam, 4.0, Vec2::new(8.0, 1.0), &transform),
+            Hitbox::Circle {
+                center: Vec2::new(-5.0, 3.0),
+                radius: 4.0
+            }
+        );
+    }
+}
@@ -4,8 +4,8 @@
 use crate::animation::Animation;
 use crate::audio::Audio;
 use crate::game::{Mk48Game, RendererLayer};
-use crate::particle::Mk48Particle;
-use client_util::audio::AudioPlayer;
+use crate::particle::{add_jittered, Mk48Particle, ParticleJitter};
+use client_util::audio::{AudioListener, AudioPlayer};
 use client_util::context::Context;
 use common::contact::{Contact, ContactTrait};
 use common::entity::EntityId;
@@ -14,7 +14,7 @@ use common::ticks::Ticks;
 use common_util::angle::Angle;
 use common_util::range::map_ranges;
 use glam::Vec2;
-use js_hooks::console_log;
+use js_hooks::console_debug;
 use rand::{thread_rng, Rng};
 use std::collections::HashMap;
 
@@ -30,6 +30,8 @@ pub struct InterpolatedContact {
     /// Idle ticks, i.e. how many updates since last seen. If exceeds entity_type.data().keep_alive(),
     /// assume entity went away.
     pub idle: Ticks,
+    /// Position of the view before the last interpolation, i.e. last frame, if any.
+    pub previous_position: Option<Vec2>,
 }
 
 impl InterpolatedContact {
@@ -41,6 +43,7 @@ impl InterpolatedContact {
             view: contact,
             error: 0.0,
             idle: Ticks::ZERO,
+            previous_position: None,
         }
     }
 
@@ -67,7 +70,7 @@ impl InterpolatedContact {
             .to_mps();
 
         if Some(self.view.id()) == debug_latency_entity_id {
-            console_log!(
+            console_debug!(
                 "err: {:.2}, pos: {:.2}, dir: {:.2}, vel: {:.2}",
                 self.error,
                 positional_inaccuracy.sqrt(),
@@ -148,21 +151,27 @@ impl InterpolatedContact {
                     // Add muzzle flash particles.
                     let amount = 10;
                     for i in 0..amount {
-                        layer.add(Mk48Particle {
-                            position: armament_transform.position
-                                + direction_vector * forward_offset,
-                            velocity: boat_velocity
-                                + direction_vector
-                                    * forward_velocity
-                                    * (i as f32 * (1.0 / amount as f32))
-                                + direction_vector.perp()
-                                    * forward_velocity
-                                    * 0.15
-                                    * (rng.gen::<f32>() - 0.5),
-                            radius: (armament_entity_data.width * 5.0).clamp(1.0, 3.0),
-                            color: -1.0,
-                            smoothness: 1.0,
-                        });
+                        add_jittered(
+                            layer,
+                            Mk48Particle {
+                                position: armament_transform.position
+                                    + direction_vector * forward_offset,
+                                velocity: boat_velocity
+                                    + direction_vector
+                                        * forward_velocity
+                                        * (i as f32 * (1.0 / amount as f32))
+                                    + direction_vector.perp()
+                                        * forward_velocity
+                                        * 0.15
+                                        * (rng.gen::<f32>() - 0.5),
+                                radius: (armament_entity_data.width * 5.0).clamp(1.0, 3.0),
+                                color: -1.0,
+                                smoothness: 1.0,
+                                lifespan: 1.0,
+                                ..Default::default()
+                            },
+                            &ParticleJitter::MUZZLE_FLASH,
+                        );
                     }
                 }
             }
@@ -171,6 +180,8 @@ impl InterpolatedContact {
 
     /// Performs interpolation. Takes the entity id of the player's boat.
     pub fn interpolate(&mut self, elapsed_seconds: f32, player_entity_id: Option<EntityId>) {
+        self.previous_position = Some(self.view.transform().position);
+
         // Don't interpolate view's guidance if this is the player's boat, so that it doesn't jerk around.
         self.view.interpolate_towards(
             &self.model,
@@ -189,7 +200,7 @@ impl Mk48Game {
     /// Fine not to call if audio and animations not desired.
     pub fn play_lost_contact_audio_and_animations(
         &mut self,
-        player_position: Vec2,
+        listener: AudioListener,
         contact: &Contact,
         audio_layer: &AudioPlayer<Audio>,
         animations: &mut Vec<Animation>,
@@ -197,9 +208,8 @@ impl Mk48Game {
     ) {
         if let Some(entity_type) = contact.entity_type() {
             // Contact lost (of a previously known entity type), spawn a splash and make a sound.
-            let volume =
-                Mk48Game::volume_at(player_position.distance(contact.transform().position))
-                    .min(0.25);
+            let position = contact.transform().position;
+            let volume = 0.25;
             let name = match entity_type.data().kind {
                 EntityKind::Boat | EntityKind::Aircraft => "splash",
                 EntityKind::Weapon => match entity_type.data().sub_kind {
@@ -211,7 +221,7 @@ impl Mk48Game {
                     _ => "splash",
                 },
                 EntityKind::Collectible => {
-                    audio_layer.play_with_volume(Audio::Collect, volume);
+                    audio_layer.play_at(Audio::Collect, volume, position, listener);
                     return;
                 }
                 _ => return,
@@ -219,9 +229,9 @@ impl Mk48Game {
 
             let data = entity_type.data();
             if data.kind == EntityKind::Boat {
-                audio_layer.play_with_volume(Audio::ExplosionLong, volume);
+                audio_layer.play_at(Audio::ExplosionLong, volume, position, listener);
             } else {
-                audio_layer.play_with_volume(Audio::ExplosionShort, volume);
+                audio_layer.play_at(Audio::ExplosionShort, volume, position, listener);
             }
 
             // The more damage/health the entity has the larger its explosion is.
@@ -251,16 +261,16 @@ impl Mk48Game {
     pub fn play_new_contact_audio(
         &mut self,
         contact: &Contact,
-        player_position: Vec2,
+        listener: AudioListener,
         context: &Context<Mk48Game>,
         audio_layer: &AudioPlayer<Audio>,
     ) {
-        let position_diff = contact.transform().position - player_position;
+        let position_diff = contact.transform().position - listener.center;
         let direction = Angle::from(position_diff);
         let inbound = (contact.transform().direction - direction + Angle::PI).abs() < Angle::PI_2;
 
         let friendly = context.state.core.is_friendly(contact.player_id());
-        let volume = Mk48Game::volume_at(position_diff.length());
+        let volume = listener.volume_at(contact.transform().position);
 
         if let Some(entity_type) = contact.entity_type() {
             let data: &EntityData = entity_type.data();
@@ -0,0 +1,93 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+/// Detects a sustained low framerate, in order to suggest lowering graphics settings (at most
+/// once per session).
+#[derive(Debug, PartialEq)]
+pub enum LowFpsWarning {
+    /// Waiting for the framerate to stay low, for this many seconds so far.
+    Watching { low_seconds: f32 },
+    /// Warning is currently shown.
+    Shown,
+    /// Warning was dismissed (or acted upon), and won't be shown again.
+    Dismissed,
+}
+
+impl Default for LowFpsWarning {
+    fn default() -> Self {
+        Self::Watching { low_seconds: 0.0 }
+    }
+}
+
+impl LowFpsWarning {
+    /// Framerate below which graphics are considered too slow.
+    const THRESHOLD_FPS: f32 = 25.0;
+    /// How long, in seconds, the framerate must stay low before showing the warning.
+    const SUSTAIN_SECONDS: f32 = 5.0;
+
+    /// Records a `fps` sample, spanning `period` seconds.
+    pub fn sample(&mut self, fps: f32, period: f32) {
+        if let Self::Watching { low_seconds } = self {
+            if fps < Self::THRESHOLD_FPS {
+                *low_seconds += period;
+                if *low_seconds >= Self::SUSTAIN_SECONDS {
+                    *self = Self::Shown;
+                }
+            } else {
+                // A single good sample means it wasn't sustained.
+                *low_seconds = 0.0;
+            }
+        }
+    }
+
+    /// Whether the warning should be shown.
+    pub fn is_shown(&self) -> bool {
+        matches!(self, Self::Shown)
+    }
+
+    /// Hides the warning for the rest of the session.
+    pub fn dismiss(&mut self) {
+        *self = Self::Dismissed;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::low_fps::LowFpsWarning;
+
+    #[test]
+    fn sustained_low() {
+        let mut warning = LowFpsWarning::default();
+        for _ in 0..4 {
+            warning.sample(15.0, 1.0);
+            assert!(!warning.is_shown());
+        }
+        warning.sample(15.0, 1.0);
+        assert!(warning.is_shown());
+    }
+
+    #[test]
+    fn single_dip() {
+        let mut warning = LowFpsWarning::default();
+        for i in 0..30 {
+            // Dips every few seconds, but recovers in between.
+            warning.sample(if i % 4 == 0 { 60.0 } else { 10.0 }, 1.0);
+            assert!(!warning.is_shown());
+        }
+    }
+
+    #[test]
+    fn not_repeated() {
+        let mut warning = LowFpsWarning::default();
+        for _ in 0..5 {
+            warning.sample(10.0, 1.0);
+        }
+        assert!(warning.is_shown());
+        warning.dismiss();
+
+        for _ in 0..60 {
+            warning.sample(10.0, 1.0);
+        }
+        assert!(!warning.is_shown());
+    }
+}
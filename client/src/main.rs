@@ -17,17 +17,34 @@ use crate::ui::{Mk48Route, Mk48Ui};
 mod animation;
 mod armament;
 mod audio;
+mod auto_fire;
 mod background;
+mod combat_alert;
+mod combat_log;
+mod contact_icon;
+#[cfg(debug_assertions)]
+mod dev;
+mod fire_buffer;
+mod formation;
 mod game;
+#[cfg(debug_assertions)]
+mod hitbox;
 mod interpolated;
 mod interpolated_contact;
+mod low_fps;
+mod motion_blur;
 mod particle;
 mod settings;
+mod spectate;
 mod sprite;
 mod state;
+mod team_color;
+mod tracer;
 mod trail;
 mod translation;
 mod ui;
+mod velocity_vector;
+mod you_are_here;
 mod zoom;
 
 fn main() {
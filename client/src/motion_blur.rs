@@ -0,0 +1,104 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use glam::Vec2;
+
+/// Camera speeds, in screens per second, at or below which there is no blur.
+const MIN_SPEED: f32 = 0.5;
+/// Fraction of a second that the blur spans, like a camera's exposure time.
+const EXPOSURE: f32 = 1.0 / 30.0;
+/// Longest blur, as a fraction of the screen.
+const MAX_BLUR: f32 = 0.03;
+
+/// Tracks how fast the camera is moving.
+#[derive(Default)]
+pub struct CameraVelocity {
+    last_camera: Option<Vec2>,
+}
+
+impl CameraVelocity {
+    /// Takes the current `camera` position, and returns its velocity, in meters per second.
+    pub fn update(&mut self, camera: Vec2, elapsed_seconds: f32) -> Vec2 {
+        let last_camera = self.last_camera.replace(camera);
+        match last_camera {
+            Some(last_camera) if elapsed_seconds > 0.0 => (camera - last_camera) / elapsed_seconds,
+            _ => Vec2::ZERO,
+        }
+    }
+}
+
+/// Gets the blur vector, in texture coordinates (see
+/// [`MotionBlurLayer::set_blur`][`renderer::MotionBlurLayer::set_blur`]), given the camera's
+/// velocity in meters per second, `zoom` (half width) and `aspect_ratio` (width / height).
+/// There is never any blur if `reduce_motion` is on.
+pub fn motion_blur(
+    camera_velocity: Vec2,
+    zoom: f32,
+    aspect_ratio: f32,
+    reduce_motion: bool,
+) -> Vec2 {
+    if reduce_motion {
+        return Vec2::ZERO;
+    }
+
+    let screen_size = Vec2::new(zoom, zoom / aspect_ratio) * 2.0;
+    let screens_per_second = camera_velocity / screen_size;
+    let speed = screens_per_second.length();
+    // Also handles infinity and NaN, e.g. if zoom is zero.
+    if !(speed > MIN_SPEED && speed.is_finite()) {
+        return Vec2::ZERO;
+    }
+
+    // Ramp up from the minimum speed, to avoid a sudden jump.
+    let ramp = (speed - MIN_SPEED) / speed;
+    (screens_per_second * (EXPOSURE * ramp)).clamp_length_max(MAX_BLUR)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::motion_blur::{motion_blur, CameraVelocity, MAX_BLUR, MIN_SPEED};
+    use glam::Vec2;
+
+    #[test]
+    fn blur_from_camera_velocity() {
+        let zoom = 500.0;
+
+        // Slow panning isn't blurred.
+        assert_eq!(motion_blur(Vec2::ZERO, zoom, 1.0, false), Vec2::ZERO);
+        let slow = Vec2::new(zoom * 2.0 * MIN_SPEED, 0.0);
+        assert_eq!(motion_blur(slow, zoom, 1.0, false), Vec2::ZERO);
+
+        // Faster panning is blurred more, in the direction of movement.
+        let medium = motion_blur(Vec2::new(zoom * 1.5, 0.0), zoom, 1.0, false);
+        let fast = motion_blur(Vec2::new(zoom * 2.0, 0.0), zoom, 1.0, false);
+        assert!(medium.x > 0.0 && medium.y == 0.0, "{}", medium);
+        assert!(fast.x > medium.x, "{} {}", fast, medium);
+
+        // Vertical movement is relative to screen height.
+        let wide = motion_blur(Vec2::new(0.0, -zoom * 1.5), zoom, 2.0, false);
+        assert!(wide.y < -medium.x, "{} {}", wide, medium);
+
+        // Limited.
+        let teleport = motion_blur(Vec2::new(1e9, 1e9), zoom, 1.0, false);
+        assert!((teleport.length() - MAX_BLUR).abs() < 0.0001);
+        assert_eq!(motion_blur(Vec2::ONE, 0.0, 1.0, false), Vec2::ZERO);
+    }
+
+    #[test]
+    fn no_blur_when_reducing_motion() {
+        for velocity in [Vec2::ZERO, Vec2::new(5000.0, 0.0), Vec2::new(1e9, -1e9)] {
+            assert_eq!(motion_blur(velocity, 500.0, 1.5, true), Vec2::ZERO);
+        }
+    }
+
+    #[test]
+    fn camera_velocity() {
+        let mut velocity = CameraVelocity::default();
+        assert_eq!(velocity.update(Vec2::new(10.0, 0.0), 0.1), Vec2::ZERO);
+        assert_eq!(
+            velocity.update(Vec2::new(20.0, -5.0), 0.5),
+            Vec2::new(20.0, -10.0)
+        );
+        assert_eq!(velocity.update(Vec2::new(30.0, 0.0), 0.0), Vec2::ZERO);
+    }
+}
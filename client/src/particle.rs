@@ -1,31 +1,273 @@
 // SPDX-FileCopyrightText: 2021 Softbear, Inc.
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
-use glam::Vec2;
+use glam::{Vec2, Vec3, Vec4};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use renderer::{derive_vertex, LayerShader, Shader, ShaderBinding};
 use renderer2d::{Camera2d, Particle, ParticleContext, ParticleLayer, Renderer2d};
+use std::ops::RangeInclusive;
+
+/// A particle to add to a [`Mk48ParticleLayer`], which packs it into a [`Mk48ParticleVertex`].
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Mk48Particle {
+    pub position: Vec2,
+    pub velocity: Vec2,
+    /// Possible values:
+    /// -1 to 1: Fire to black
+    ///  0 to 1: Black to white
+    ///
+    /// Ignored if there is a gradient (see [`Mk48Particle::with_gradient`]).
+    pub color: f32,
+    /// Radius in meters (TODO: make sure actually is in meters).
+    pub radius: f32,
+    /// 0 = sharp and stays same size, 1 = smooth and gradually dilutes/expands.
+    pub smoothness: f32,
+    /// Fraction of [`Mk48Particle::LIFESPAN`] to live for (at most 1).
+    pub lifespan: f32,
+    /// RGBA color when emitted, if there is a gradient.
+    pub start_color: Vec4,
+    /// RGBA color when faded out, if there is a gradient.
+    pub end_color: Vec4,
+    /// Constant acceleration (e.g. gravity pulling spray back down), in addition to wind.
+    pub acceleration: Vec2,
+    /// Linear drag coefficient, in addition to the inherent [`Mk48Particle::INHERENT_DRAG`].
+    /// Should be greater than `-INHERENT_DRAG`.
+    pub drag: f32,
+}
 
 derive_vertex!(
-    pub struct Mk48Particle {
-        pub position: Vec2,
-        pub velocity: Vec2,
-        /// Possible values:
-        /// -1 to 1: Fire to black
-        ///  0 to 1: Black to white
-        pub color: f32,
-        /// Radius in meters (TODO: make sure actually is in meters).
-        pub radius: f32,
-        /// 0 = sharp and stays same size, 1 = smooth and gradually dilutes/expands.
-        pub smoothness: f32,
+    /// A [`Mk48Particle`] packed into few attributes, since WebGL only guarantees 8 (including
+    /// `created`). Must match `shaders/particle.vert`.
+    pub struct Mk48ParticleVertex {
+        /// Position and acceleration.
+        position_acceleration: Vec4,
+        /// Velocity and drag.
+        velocity_drag: Vec3,
+        /// Color, radius, smoothness, and lifespan.
+        appearance: Vec4,
+        /// Start and end colors, with two 8 bit channels per component (see [`pack_channels`]).
+        gradient: Vec4,
     }
 );
-impl Particle for Mk48Particle {
-    const LIFESPAN: f32 = 1.25;
+
+impl From<Mk48Particle> for Mk48ParticleVertex {
+    fn from(particle: Mk48Particle) -> Self {
+        let Mk48Particle {
+            position,
+            velocity,
+            color,
+            radius,
+            smoothness,
+            lifespan,
+            start_color: start,
+            end_color: end,
+            acceleration,
+            drag,
+        } = particle;
+        Self {
+            position_acceleration: position.extend(acceleration.x).extend(acceleration.y),
+            velocity_drag: velocity.extend(drag),
+            appearance: Vec4::new(color, radius, smoothness, lifespan),
+            gradient: Vec4::new(
+                pack_channels(start.x, start.y),
+                pack_channels(start.z, start.w),
+                pack_channels(end.x, end.y),
+                pack_channels(end.z, end.w),
+            ),
+        }
+    }
+}
+
+/// Packs two color channels (from 0 to 1) into one float, 8 bits each, which is exact since
+/// floats have 24 bits of precision.
+fn pack_channels(high: f32, low: f32) -> f32 {
+    let quantize = |channel: f32| (channel.clamp(0.0, 1.0) * 255.0).round();
+    quantize(high) * 256.0 + quantize(low)
+}
+
+impl Mk48Particle {
+    /// How long particles live for (see [`Particle::LIFESPAN`]).
+    pub const LIFESPAN: f32 = 1.25;
+
+    /// Interpolates the color, including alpha, from `start_color` to `end_color` over the
+    /// particle's life, instead of using [`Self::color`] (e.g. orange to transparent gray smoke).
+    /// Both being fully transparent black means no gradient.
+    pub fn with_gradient(mut self, start_color: Vec4, end_color: Vec4) -> Self {
+        self.start_color = start_color;
+        self.end_color = end_color;
+        self
+    }
+
+    /// Velocity is damped by a factor of 4 per unit of (lifespan adjusted) time, even if
+    /// [`Self::drag`] is zero.
+    pub const INHERENT_DRAG: f32 = std::f32::consts::LN_2 * 2.0;
+
+    /// Returns true if the particle has a gradient (see [`Self::with_gradient`]).
+    pub fn has_gradient(&self) -> bool {
+        self.start_color != Vec4::ZERO || self.end_color != Vec4::ZERO
+    }
+
+    /// Gets the position after `time` (in lifespan adjusted seconds), not counting wind. Must match
+    /// `shaders/particle.vert`, which integrates drag and acceleration in closed form, instead of
+    /// stepping each particle every frame.
+    pub fn position_at(&self, time: f32) -> Vec2 {
+        let drag = Self::INHERENT_DRAG + self.drag;
+        if drag.abs() < 0.001 {
+            return self.position + self.velocity * time + self.acceleration * (0.5 * time * time);
+        }
+        // (1 - e^(-drag * t)) / drag
+        let damped = (1.0 - (-drag * time).exp()) / drag;
+        self.position + self.velocity * damped + self.acceleration * ((time - damped) / drag)
+    }
+}
+
+/// Bundles the motion, lifespan, and size of a [`Mk48Particle`], e.g. for debris or splashes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParticleConfig {
+    pub velocity: Vec2,
+    /// See [`Mk48Particle::acceleration`].
+    pub acceleration: Vec2,
+    /// See [`Mk48Particle::drag`].
+    pub drag: f32,
+    /// See [`Mk48Particle::lifespan`].
+    pub lifespan: f32,
+    /// See [`Mk48Particle::radius`].
+    pub radius: f32,
+}
+
+impl Default for ParticleConfig {
+    fn default() -> Self {
+        Self {
+            velocity: Vec2::ZERO,
+            acceleration: Vec2::ZERO,
+            drag: 0.0,
+            lifespan: 1.0,
+            radius: 1.0,
+        }
+    }
+}
+
+impl ParticleConfig {
+    /// Creates a white, smooth particle at `position`. Use struct update syntax to change the
+    /// remaining fields, e.g. `Mk48Particle { color: -1.0, ..config.particle(position) }`.
+    pub fn particle(&self, position: Vec2) -> Mk48Particle {
+        Mk48Particle {
+            position,
+            velocity: self.velocity,
+            acceleration: self.acceleration,
+            drag: self.drag,
+            lifespan: self.lifespan,
+            radius: self.radius,
+            color: 1.0,
+            smoothness: 1.0,
+            ..Default::default()
+        }
+    }
+}
+impl Particle for Mk48ParticleVertex {
+    const LIFESPAN: f32 = Mk48Particle::LIFESPAN;
 }
 
 pub type Mk48ParticleLayer = ParticleLayer<Mk48ParticleContext>;
 pub struct Mk48ParticleContext {
     pub wind: Vec2,
+    /// Source of [`ParticleJitter`] randomness.
+    pub rng: StdRng,
+    /// Whether [`add_jittered`] applies jitter (see
+    /// [`Mk48Settings::particle_jitter`][`crate::settings::Mk48Settings::particle_jitter`]).
+    pub jitter: bool,
+}
+
+impl Mk48ParticleContext {
+    pub fn new(wind: Vec2, seed: u64) -> Self {
+        Self {
+            wind,
+            rng: StdRng::seed_from_u64(seed),
+            jitter: false,
+        }
+    }
+}
+
+/// Adds `particle` to `layer`, after applying `jitter` to it (if enabled for the `layer`).
+pub fn add_jittered(
+    layer: &mut Mk48ParticleLayer,
+    particle: Mk48Particle,
+    jitter: &ParticleJitter,
+) {
+    let particle = if layer.context.jitter {
+        jitter.apply(particle, &mut layer.context.rng)
+    } else {
+        particle
+    };
+    layer.add(particle.into());
+}
+
+/// Gets the integer amount of particles to emit over `elapsed_seconds`, at a fractional rate of
+/// `per_second`. `dither` (from 0 to 1) decides whether to round up or down, so that, as long as it
+/// is evenly distributed, the average rate is correct regardless of the framerate. Limited to 100,
+/// in case a large amount of time elapsed (e.g. the tab was throttled).
+pub fn particle_amount(per_second: f32, elapsed_seconds: f32, dither: f32) -> usize {
+    ((elapsed_seconds * per_second + dither).floor() as usize).min(100)
+}
+
+/// Random variation of particles as they are emitted, so they look less uniform. Each range is
+/// a multiplier, and the default is no jitter.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParticleJitter {
+    /// Multiplier of [`Mk48Particle::lifespan`]. Clamped to at most 1, since particles are
+    /// expired after [`Mk48Particle::LIFESPAN`].
+    pub lifespan: RangeInclusive<f32>,
+    /// Multiplier of [`Mk48Particle::radius`].
+    pub radius: RangeInclusive<f32>,
+    /// Multiplier of [`Mk48Particle::velocity`].
+    pub velocity: RangeInclusive<f32>,
+}
+
+impl Default for ParticleJitter {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+impl ParticleJitter {
+    pub const NONE: Self = Self {
+        lifespan: 1.0..=1.0,
+        radius: 1.0..=1.0,
+        velocity: 1.0..=1.0,
+    };
+
+    /// Of wakes and thrust behind moving contacts.
+    pub const WAKE: Self = Self {
+        lifespan: 0.8..=1.0,
+        radius: 0.9..=1.1,
+        velocity: 0.9..=1.1,
+    };
+
+    /// Of exhaust smoke, which varies more than wakes.
+    pub const EXHAUST: Self = Self {
+        lifespan: 0.7..=1.0,
+        radius: 0.8..=1.2,
+        velocity: 1.0..=1.0,
+    };
+
+    /// Of muzzle flashes when firing. Velocity isn't varied, since it includes the boat's.
+    pub const MUZZLE_FLASH: Self = Self {
+        lifespan: 0.6..=1.0,
+        radius: 0.9..=1.1,
+        velocity: 1.0..=1.0,
+    };
+
+    /// Randomly varies `particle`.
+    pub fn apply(&self, mut particle: Mk48Particle, rng: &mut impl Rng) -> Mk48Particle {
+        if self == &Self::NONE {
+            return particle;
+        }
+        particle.lifespan = (particle.lifespan * rng.gen_range(self.lifespan.clone())).min(1.0);
+        particle.radius *= rng.gen_range(self.radius.clone());
+        particle.velocity *= rng.gen_range(self.velocity.clone());
+        particle
+    }
 }
 
 impl LayerShader<Camera2d> for Mk48ParticleContext {
@@ -46,5 +288,199 @@ impl LayerShader<Camera2d> for Mk48ParticleContext {
 }
 
 impl ParticleContext for Mk48ParticleContext {
-    type Particle = Mk48Particle;
+    type Particle = Mk48ParticleVertex;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::particle::{
+        particle_amount, Mk48Particle, Mk48ParticleVertex, ParticleConfig, ParticleJitter,
+    };
+    use glam::{Vec2, Vec3, Vec4};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn particle() -> Mk48Particle {
+        Mk48Particle {
+            position: Vec2::ZERO,
+            velocity: Vec2::new(10.0, 0.0),
+            color: 1.0,
+            radius: 2.0,
+            smoothness: 1.0,
+            lifespan: 1.0,
+            ..Default::default()
+        }
+    }
+
+    fn jitter() -> ParticleJitter {
+        ParticleJitter {
+            lifespan: 0.5..=0.8,
+            radius: 0.5..=1.5,
+            velocity: 0.9..=1.1,
+        }
+    }
+
+    #[test]
+    fn gradient() {
+        assert!(!particle().has_gradient());
+
+        let orange = Vec4::new(0.98, 0.75, 0.0, 1.0);
+        let p = particle().with_gradient(orange, Vec4::new(0.1, 0.1, 0.1, 0.0));
+        assert!(p.has_gradient());
+        assert_eq!(p.start_color, orange);
+
+        // Fading from transparent still counts.
+        assert!(particle()
+            .with_gradient(Vec4::ZERO, Vec4::new(0.0, 0.0, 0.0, 1.0))
+            .has_gradient());
+    }
+
+    #[test]
+    fn packed_vertex() {
+        // WebGL only guarantees 8 attributes.
+        let attributes = include_str!("shaders/particle.vert")
+            .lines()
+            .filter(|line| line.starts_with("attribute "))
+            .count();
+        assert!(attributes <= 8, "{}", attributes);
+
+        let vertex = Mk48ParticleVertex::from(
+            particle().with_gradient(Vec4::new(1.0, 0.5, 0.0, 1.0), Vec4::new(0.1, 0.1, 0.1, 0.0)),
+        );
+        assert_eq!(vertex.appearance, Vec4::new(1.0, 2.0, 1.0, 1.0));
+        assert_eq!(vertex.position_acceleration, Vec4::ZERO);
+        assert_eq!(vertex.velocity_drag, Vec3::new(10.0, 0.0, 0.0));
+        // Unpacks like the shader.
+        let unpack = |packed: f32| {
+            let high = (packed / 256.0).floor();
+            ((high / 255.0), (packed - high * 256.0) / 255.0)
+        };
+        let (r, g) = unpack(vertex.gradient.x);
+        let (b, a) = unpack(vertex.gradient.y);
+        assert!(Vec4::new(r, g, b, a).abs_diff_eq(Vec4::new(1.0, 0.5, 0.0, 1.0), 1.0 / 255.0));
+        let (r, g) = unpack(vertex.gradient.z);
+        let (b, a) = unpack(vertex.gradient.w);
+        assert!(Vec4::new(r, g, b, a).abs_diff_eq(Vec4::new(0.1, 0.1, 0.1, 0.0), 1.0 / 255.0));
+    }
+
+    #[test]
+    fn gravity_and_drag() {
+        // Inherent drag only: position + velocity * (1 - 4^-t) / ln(4).
+        let p = particle();
+        let expected = 10.0 * (1.0 - 0.25f32.powf(2.0)) / 4f32.ln();
+        assert!((p.position_at(2.0).x - expected).abs() < 0.001);
+
+        let config = ParticleConfig {
+            velocity: Vec2::new(0.0, 20.0),
+            acceleration: Vec2::new(0.0, -30.0),
+            ..ParticleConfig::default()
+        };
+        let spray = config.particle(Vec2::ZERO);
+        assert_eq!(spray.position_at(0.0), Vec2::ZERO);
+        assert!(spray.position_at(0.2).y > 0.0);
+        // Gravity pulls it back down.
+        assert!(spray.position_at(3.0).y < 0.0);
+
+        // More drag, less distance.
+        let draggy = Mk48Particle {
+            drag: 2.0,
+            ..particle()
+        };
+        assert!(draggy.position_at(1.0).x < particle().position_at(1.0).x);
+
+        // No drag at all.
+        let frictionless = Mk48Particle {
+            drag: -Mk48Particle::INHERENT_DRAG,
+            acceleration: Vec2::new(0.0, -2.0),
+            ..particle()
+        };
+        let position = frictionless.position_at(2.0);
+        assert!(
+            position.distance(Vec2::new(20.0, -4.0)) < 0.001,
+            "{}",
+            position
+        );
+    }
+
+    #[test]
+    fn jitter_within_range() {
+        let jitter = jitter();
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..100 {
+            let p = jitter.apply(particle(), &mut rng);
+            assert!(jitter.lifespan.contains(&p.lifespan), "{}", p.lifespan);
+            assert!((1.0..=3.0).contains(&p.radius), "{}", p.radius);
+            assert!((9.0..=11.0).contains(&p.velocity.x), "{}", p.velocity);
+        }
+
+        // Can't outlive the layer's expiry.
+        let longer = ParticleJitter {
+            lifespan: 1.5..=2.0,
+            ..ParticleJitter::NONE
+        };
+        assert_eq!(longer.apply(particle(), &mut rng).lifespan, 1.0);
+    }
+
+    #[test]
+    fn jitter_deterministic() {
+        let jitter = jitter();
+        let lifespans = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (0..10)
+                .map(|_| jitter.apply(particle(), &mut rng).lifespan)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(lifespans(7), lifespans(7));
+        assert_ne!(lifespans(7), lifespans(8));
+    }
+
+    #[test]
+    fn no_jitter() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let p = ParticleJitter::default().apply(particle(), &mut rng);
+        assert_eq!(p.lifespan, 1.0);
+        assert_eq!(p.radius, 2.0);
+        assert_eq!(p.velocity, particle().velocity);
+    }
+
+    #[test]
+    fn jitter_presets() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for jitter in [
+            ParticleJitter::WAKE,
+            ParticleJitter::EXHAUST,
+            ParticleJitter::MUZZLE_FLASH,
+        ] {
+            for _ in 0..10 {
+                let p = jitter.apply(particle(), &mut rng);
+                assert!(p.lifespan > 0.0 && p.lifespan <= 1.0, "{:?}", jitter);
+                assert!(p.radius > 0.0, "{:?}", jitter);
+            }
+        }
+    }
+
+    #[test]
+    fn particle_amount_any_framerate() {
+        let per_second = 90.0;
+        for elapsed_seconds in [1.0 / 144.0, 1.0 / 60.0, 1.0 / 30.0, 0.1, 0.5] {
+            // Average over evenly distributed dithers.
+            const N: usize = 1000;
+            let total: usize = (0..N)
+                .map(|i| particle_amount(per_second, elapsed_seconds, i as f32 / N as f32))
+                .sum();
+            let average = total as f32 / N as f32;
+            let expected = per_second * elapsed_seconds;
+            assert!(
+                (average - expected).abs() < 0.01,
+                "{} {} {}",
+                elapsed_seconds,
+                average,
+                expected
+            );
+        }
+
+        // Large caps.
+        assert_eq!(particle_amount(per_second, 5.0, 0.5), 100);
+        assert_eq!(particle_amount(per_second, 0.0, 0.99), 0);
+    }
 }
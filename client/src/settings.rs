@@ -2,26 +2,405 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
 use client_util::browser_storage::BrowserStorages;
+use client_util::key_bindings::GameAction;
+use client_util::keyboard::Key;
 use client_util::setting::Settings;
+use strum_macros::{Display, EnumString};
 
 /// Settings can be set via Javascript (see util/settings.js and page/Settings.svelte).
 #[derive(Clone, PartialEq, Settings)]
 pub struct Mk48Settings {
     pub animations: bool,
+    /// Accessibility assist that fires the selected weapon at the nearest enemy in range.
+    pub auto_fire: bool,
+    /// Fraction of the viewport the player's ship can move within before the camera follows.
+    #[setting(range = "0.0..0.5", finite)]
+    pub camera_deadzone: f32,
     #[setting(no_store)]
     pub cinematic: bool,
+    /// Whether to flash the edge of the screen towards sources of damage.
+    pub combat_alert: bool,
+    /// File format of the exported combat log.
+    pub combat_log_format: CombatLogFormat,
+    /// How other contacts are drawn.
+    pub contact_icons: ContactIcons,
+    /// What is shown in the label above boats.
+    pub contact_label: ContactLabel,
+    /// How long, in milliseconds, to remember a fire input that missed a control tick.
+    #[setting(range = "0..250")]
+    pub fire_buffer_millis: u8,
     pub fps_shown: bool,
+    /// Whether to ask for confirmation before leaving the page while playing.
+    pub leave_confirmation: bool,
+    /// Whether to blur the screen while the camera moves quickly.
+    pub motion_blur: bool,
+    /// Whether to label own boat, even if other labels are hidden.
+    pub own_label: bool,
+    /// Whether to randomly vary the lifespan, size, and velocity of particles (see
+    /// [`ParticleJitter`][`crate::particle::ParticleJitter`]).
+    pub particle_jitter: bool,
+    /// Accessibility option to avoid motion effects (overrides e.g. [`Self::motion_blur`]).
+    pub reduce_motion: bool,
+    /// Whether to show draw calls, triangles, and GPU time, to diagnose performance.
+    pub render_stats_shown: bool,
+    /// How teammates are colored.
+    pub team_colors: TeamColors,
+    /// Whether to draw a line from each contact in the direction it is moving.
+    pub velocity_vectors: bool,
     #[setting(range = "0..3")]
     pub wave_quality: u8,
+    /// Zoom input beyond which a marker is drawn on own boat (1.0 to never draw it).
+    #[setting(range = "0.2..1.0", finite)]
+    pub you_are_here_zoom: f32,
+    /// Zoom input of the [`ZoomPreset::Close`] preset.
+    #[setting(range = "0.2..1.0", finite)]
+    pub zoom_preset_close: f32,
+    /// Zoom input of the [`ZoomPreset::Far`] preset.
+    #[setting(range = "0.2..1.0", finite)]
+    pub zoom_preset_far: f32,
+    /// Zoom input of the [`ZoomPreset::Medium`] preset.
+    #[setting(range = "0.2..1.0", finite)]
+    pub zoom_preset_medium: f32,
+    /// How quickly the camera eases towards the target zoom (higher is faster).
+    #[setting(range = "1.0..30.0", finite)]
+    pub zoom_speed: f32,
 }
 
 impl Default for Mk48Settings {
     fn default() -> Self {
         Self {
             animations: true,
+            auto_fire: false,
+            camera_deadzone: 0.0,
             cinematic: false,
+            combat_alert: true,
+            combat_log_format: CombatLogFormat::default(),
+            contact_icons: ContactIcons::default(),
+            contact_label: ContactLabel::default(),
+            fire_buffer_millis: 100,
             fps_shown: false,
+            leave_confirmation: true,
+            motion_blur: false,
+            own_label: true,
+            particle_jitter: false,
+            reduce_motion: false,
+            render_stats_shown: false,
+            team_colors: TeamColors::default(),
+            velocity_vectors: false,
             wave_quality: 1,
+            you_are_here_zoom: 0.7,
+            zoom_preset_close: 0.3,
+            zoom_preset_far: 1.0,
+            zoom_preset_medium: 0.6,
+            zoom_speed: 6.0,
         }
     }
 }
+
+impl Mk48Settings {
+    /// Gets a copy of these settings with graphics lowered as much as possible, for slow devices.
+    pub fn with_low_graphics(&self) -> Self {
+        Self {
+            animations: false,
+            motion_blur: false,
+            wave_quality: 0,
+            ..self.clone()
+        }
+    }
+
+    /// Applies (and stores) the [`Self::with_low_graphics`] preset.
+    pub fn apply_low_graphics(&mut self, browser_storages: &mut BrowserStorages) {
+        let low = self.with_low_graphics();
+        self.set_animations(low.animations, browser_storages);
+        self.set_motion_blur(low.motion_blur, browser_storages);
+        self.set_wave_quality(low.wave_quality, browser_storages);
+    }
+
+    /// Gets the configured zoom input of a `preset`.
+    pub fn zoom_preset(&self, preset: ZoomPreset) -> f32 {
+        match preset {
+            ZoomPreset::Close => self.zoom_preset_close,
+            ZoomPreset::Medium => self.zoom_preset_medium,
+            ZoomPreset::Far => self.zoom_preset_far,
+        }
+    }
+
+    /// Sets (and stores) the zoom input of a `preset`.
+    pub fn set_zoom_preset(
+        &mut self,
+        preset: ZoomPreset,
+        zoom_input: f32,
+        browser_storages: &mut BrowserStorages,
+    ) {
+        match preset {
+            ZoomPreset::Close => self.set_zoom_preset_close(zoom_input, browser_storages),
+            ZoomPreset::Medium => self.set_zoom_preset_medium(zoom_input, browser_storages),
+            ZoomPreset::Far => self.set_zoom_preset_far(zoom_input, browser_storages),
+        }
+    }
+}
+
+/// File format of the exported combat log (see [`CombatLog`][`crate::combat_log::CombatLog`]).
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Display, EnumString)]
+pub enum CombatLogFormat {
+    /// Comma separated values, for spreadsheets.
+    #[default]
+    Csv,
+    /// Aligned columns, for reading.
+    Text,
+}
+
+impl CombatLogFormat {
+    pub const ALL: [Self; 2] = [Self::Csv, Self::Text];
+
+    /// Human readable name, for the settings menu.
+    pub fn as_human_readable_str(self) -> &'static str {
+        match self {
+            Self::Csv => "CSV Combat Log",
+            Self::Text => "Text Combat Log",
+        }
+    }
+
+    /// File extension, without the dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Text => "txt",
+        }
+    }
+
+    /// MIME type of the file.
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv",
+            Self::Text => "text/plain",
+        }
+    }
+}
+
+/// Content of the text label drawn above boats.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Display, EnumString)]
+pub enum ContactLabel {
+    /// No label at all.
+    None,
+    /// Player name (with team prefix).
+    #[default]
+    Name,
+    /// Player name followed by ship level.
+    NameLevel,
+    /// Player name followed by ship class.
+    NameShipType,
+}
+
+impl ContactLabel {
+    pub const ALL: [Self; 4] = [Self::None, Self::Name, Self::NameLevel, Self::NameShipType];
+
+    /// Builds the label text for a boat, given its player's `name`, and its ship's `level` and
+    /// `ship_label`. Returns an empty string if nothing should be drawn.
+    pub fn format(self, name: &str, level: u8, ship_label: &str) -> String {
+        match self {
+            Self::None => String::new(),
+            Self::Name => name.to_owned(),
+            Self::NameLevel => format!("{} ({})", name, level),
+            Self::NameShipType => format!("{} ({})", name, ship_label),
+        }
+    }
+
+    /// Gets the label content to use, taking into account whether the [`GameAction::ShowLabels`]
+    /// key is held, in which case as much as possible is shown.
+    pub fn with_modifier(self, modifier_held: bool) -> Self {
+        if modifier_held {
+            Self::NameShipType
+        } else {
+            self
+        }
+    }
+
+    /// Gets the label content to use, making sure there is at least a name if `always_shown`
+    /// (e.g. for own boat).
+    pub fn with_always_shown(self, always_shown: bool) -> Self {
+        if always_shown && self == Self::None {
+            Self::Name
+        } else {
+            self
+        }
+    }
+
+    /// Human readable name, for the settings menu.
+    pub fn as_human_readable_str(self) -> &'static str {
+        match self {
+            Self::None => "No Labels",
+            Self::Name => "Name Labels",
+            Self::NameLevel => "Name and Level Labels",
+            Self::NameShipType => "Name and Ship Labels",
+        }
+    }
+}
+
+/// How contacts are drawn.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Display, EnumString)]
+pub enum ContactIcons {
+    /// Ship silhouettes from the sprite sheet.
+    #[default]
+    Silhouettes,
+    /// Simplified colored dots and triangles, which are clearer at a distance.
+    Dots,
+}
+
+impl ContactIcons {
+    pub const ALL: [Self; 2] = [Self::Silhouettes, Self::Dots];
+
+    /// Human readable name, for the settings menu.
+    pub fn as_human_readable_str(self) -> &'static str {
+        match self {
+            Self::Silhouettes => "Ship Silhouettes",
+            Self::Dots => "Simplified Icons",
+        }
+    }
+}
+
+/// How teammates are colored.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Display, EnumString)]
+pub enum TeamColors {
+    /// All teammates share the same color.
+    #[default]
+    Single,
+    /// Each teammate has their own color, derived from their player id.
+    PerMember,
+}
+
+impl TeamColors {
+    pub const ALL: [Self; 2] = [Self::Single, Self::PerMember];
+
+    /// Human readable name, for the settings menu.
+    pub fn as_human_readable_str(self) -> &'static str {
+        match self {
+            Self::Single => "Single Team Color",
+            Self::PerMember => "Color per Teammate",
+        }
+    }
+}
+
+/// A zoom level that can be snapped to with a key (see [`GameAction::ZoomPresetClose`] etc.).
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ZoomPreset {
+    Close,
+    Medium,
+    Far,
+}
+
+impl ZoomPreset {
+    pub const ALL: [Self; 3] = [Self::Close, Self::Medium, Self::Far];
+
+    /// Gets the preset that `key` (already resolved by key bindings) snaps to, if any.
+    pub fn from_key(key: Key) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|preset| preset.action().default_key() == key)
+    }
+
+    /// Gets the [`GameAction`] that snaps to this preset.
+    pub fn action(self) -> GameAction {
+        match self {
+            Self::Close => GameAction::ZoomPresetClose,
+            Self::Medium => GameAction::ZoomPresetMedium,
+            Self::Far => GameAction::ZoomPresetFar,
+        }
+    }
+
+    /// Human readable name, for the settings menu.
+    pub fn as_human_readable_str(self) -> &'static str {
+        match self {
+            Self::Close => "Close",
+            Self::Medium => "Medium",
+            Self::Far => "Far",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::settings::{CombatLogFormat, ContactIcons, ContactLabel, Mk48Settings, TeamColors};
+    use client_util::key_bindings::GameAction;
+    use client_util::keyboard::Key;
+    use std::str::FromStr;
+
+    #[test]
+    fn contact_label_format() {
+        let name = "[TEAM] Player";
+        assert_eq!(ContactLabel::None.format(name, 3, "Fletcher"), "");
+        assert_eq!(ContactLabel::Name.format(name, 3, "Fletcher"), name);
+        assert_eq!(
+            ContactLabel::NameLevel.format(name, 3, "Fletcher"),
+            "[TEAM] Player (3)"
+        );
+        assert_eq!(
+            ContactLabel::NameShipType.format(name, 3, "Fletcher"),
+            "[TEAM] Player (Fletcher)"
+        );
+    }
+
+    #[test]
+    fn contact_label_round_trip() {
+        for label in ContactLabel::ALL {
+            assert_eq!(ContactLabel::from_str(&label.to_string()), Ok(label));
+        }
+        assert!(ContactLabel::from_str("Bogus").is_err());
+
+        for icons in ContactIcons::ALL {
+            assert_eq!(ContactIcons::from_str(&icons.to_string()), Ok(icons));
+        }
+
+        for colors in TeamColors::ALL {
+            assert_eq!(TeamColors::from_str(&colors.to_string()), Ok(colors));
+        }
+
+        for format in CombatLogFormat::ALL {
+            assert_eq!(CombatLogFormat::from_str(&format.to_string()), Ok(format));
+        }
+    }
+
+    #[test]
+    fn label_modifier_shows_all() {
+        let name = "Player";
+        for label in ContactLabel::ALL {
+            let held = label.with_modifier(true).format(name, 2, "Fletcher");
+            assert!(held.contains(name) && held.contains("Fletcher"), "{}", held);
+            assert_eq!(
+                label.with_modifier(false).format(name, 2, "Fletcher"),
+                label.format(name, 2, "Fletcher")
+            );
+        }
+        assert_eq!(GameAction::ShowLabels.default_key(), Key::Shift);
+    }
+
+    #[test]
+    fn own_label_always_shown() {
+        let name = "Me";
+        for label in ContactLabel::ALL {
+            let own = label.with_always_shown(true).format(name, 1, "G5");
+            assert!(own.contains(name), "{}", own);
+
+            // Others are unaffected.
+            assert_eq!(label.with_always_shown(false), label);
+        }
+        assert_eq!(ContactLabel::None.format(name, 1, "G5"), "");
+    }
+
+    #[test]
+    fn low_graphics_preset() {
+        let settings = Mk48Settings {
+            contact_label: ContactLabel::NameLevel,
+            wave_quality: 3,
+            ..Mk48Settings::default()
+        };
+        let low = settings.with_low_graphics();
+        assert!(!low.animations);
+        assert!(!low.motion_blur);
+        assert_eq!(low.wave_quality, 0);
+
+        // Unrelated settings are preserved.
+        assert_eq!(low.contact_label, ContactLabel::NameLevel);
+        assert!(low == low.with_low_graphics());
+    }
+}
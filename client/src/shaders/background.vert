@@ -2,6 +2,7 @@ attribute vec4 position;
 attribute vec2 uv;
 uniform mat3 uCamera;
 uniform mat3 uTexture;
+uniform float uTiling;
 varying vec2 vPosition; // world position.
 varying vec2 vUv; // terrain texture uv.
 varying vec2 vUv2; // sand/grass texture uv.
@@ -10,5 +11,5 @@ void main() {
     gl_Position = position;
     vPosition = (uCamera * vec3(uv, 1.0)).xy;
     vUv = (uTexture * vec3(vPosition, 1.0)).xy;
-    vUv2 = vPosition * 0.005;
+    vUv2 = vPosition * uTiling;
 }
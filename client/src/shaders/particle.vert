@@ -1,21 +1,48 @@
-attribute vec2 position;
-attribute vec2 velocity;
-attribute float color;
-attribute float radius;
-attribute float smoothness;
+// position, acceleration
+attribute vec4 positionAcceleration;
+// velocity, drag
+attribute vec3 velocityDrag;
+// color, radius, smoothness, lifespan (packed to stay within WebGL's 8 attributes).
+attribute vec4 appearance;
+// Start and end colors, with two 8 bit channels per component (see Mk48ParticleVertex).
+attribute vec4 gradient;
 attribute float created;
 uniform mat3 uView;
 uniform vec4 uWind_uTime_uScale;
 varying vec4 vColor;
 varying float vSharpness;
 
+vec2 unpackChannels(float packed) {
+    float high = floor(packed / 256.0);
+    return vec2(high, packed - high * 256.0) * (1.0 / 255.0);
+}
+
 void main() {
-    float time = uWind_uTime_uScale.z - created;
+    float color = appearance.x;
+    float radius = appearance.y;
+    float smoothness = appearance.z;
+    float lifespan = appearance.w;
+    vec2 position = positionAcceleration.xy;
+    vec2 acceleration = positionAcceleration.zw;
+    vec2 velocity = velocityDrag.xy;
+    float drag = velocityDrag.z;
+
+    // Shorter lived particles animate faster.
+    float time = (uWind_uTime_uScale.z - created) / lifespan;
     float pow0_25Time = pow(0.25, time);
 
-    // position + velocity * (1 - 4^-t) / ln(4)
-    #define LN_0_25 0.721347520444
-    vec2 integratedPosition = position + velocity * (pow0_25Time * -LN_0_25 + LN_0_25) + uWind_uTime_uScale.xy * time * time;
+    // Closed form of linear drag (in addition to 4^-t) and acceleration (see Mk48Particle::position_at).
+    #define LN_4 1.38629436112
+    float totalDrag = LN_4 + drag;
+    vec2 integratedPosition;
+    if (abs(totalDrag) < 0.001) {
+        integratedPosition = position + velocity * time + acceleration * (0.5 * time * time);
+    } else {
+        // (1 - e^(-drag * t)) / drag
+        float damped = (1.0 - exp(-totalDrag * time)) / totalDrag;
+        integratedPosition = position + velocity * damped + acceleration * ((time - damped) / totalDrag);
+    }
+    integratedPosition += uWind_uTime_uScale.xy * time * time;
 
     gl_Position = vec4(uView * vec3(integratedPosition, 1.0), 1.0);
     float life = smoothstep(0.0, 1.4, time);
@@ -35,5 +62,12 @@ void main() {
     float alpha = min(size * size * 0.25, 1.0) * ((1.0 - life) * (1.15 - smoothness));
 
     vColor = vec4(solidColor, alpha);
+    if (gradient != vec4(0.0)) {
+        // Gradient, including alpha, over the particle's life (instead of fading out).
+        vec4 startColor = vec4(unpackChannels(gradient.x), unpackChannels(gradient.y));
+        vec4 endColor = vec4(unpackChannels(gradient.z), unpackChannels(gradient.w));
+        vec4 gradientColor = mix(startColor, endColor, life);
+        vColor = vec4(gradientColor.rgb, gradientColor.a * min(size * size * 0.25, 1.0));
+    }
     vSharpness = (1.0 - smoothness) * 0.35 + 0.15;
 }
\ No newline at end of file
@@ -0,0 +1,136 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use core_protocol::dto::LiveboardDto;
+use core_protocol::id::PlayerId;
+
+/// Chooses whose boat to watch when spectating (see `/spectate/`), starting with the leader, and
+/// cycling to a random player on a timer or when asked.
+#[derive(Debug, Default)]
+pub struct Spectator {
+    /// Who is being watched, if anyone.
+    target: Option<PlayerId>,
+    /// Seconds until cycling to another player.
+    countdown: f32,
+    /// Cycle on the next update.
+    next: bool,
+}
+
+impl Spectator {
+    /// How long to watch each player before cycling.
+    const SECONDS_PER_TARGET: f32 = 30.0;
+
+    /// Gets who is being watched.
+    pub fn target(&self) -> Option<PlayerId> {
+        self.target
+    }
+
+    /// Cycles to another player on the next [`update`][`Self::update`].
+    pub fn next(&mut self) {
+        self.next = true;
+    }
+
+    /// Picks a new target if needed, from the players with boats in `liveboard`. `random` is in
+    /// `0.0..1.0`. Returns true if the target changed, so the server should be told.
+    pub fn update(
+        &mut self,
+        liveboard: &[LiveboardDto],
+        elapsed_seconds: f32,
+        random: f32,
+    ) -> bool {
+        self.countdown -= elapsed_seconds;
+
+        let lost = self.target.map_or(true, |target| {
+            !liveboard.iter().any(|l| l.player_id == target)
+        });
+        if !(lost || self.next || self.countdown <= 0.0) {
+            return false;
+        }
+        self.next = false;
+        self.countdown = Self::SECONDS_PER_TARGET;
+
+        let leader = liveboard
+            .iter()
+            .max_by_key(|l| l.score)
+            .map(|l| l.player_id);
+        let others: Vec<PlayerId> = liveboard
+            .iter()
+            .map(|l| l.player_id)
+            .filter(|&player_id| Some(player_id) != self.target)
+            .collect();
+        let target = if self.target.is_none() || others.is_empty() {
+            leader
+        } else {
+            Some(others[((random * others.len() as f32) as usize).min(others.len() - 1)])
+        };
+
+        let changed = target != self.target;
+        self.target = target;
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::spectate::Spectator;
+    use core_protocol::dto::LiveboardDto;
+    use core_protocol::id::PlayerId;
+    use std::num::NonZeroU32;
+
+    fn liveboard(scores: &[(u32, u32)]) -> Vec<LiveboardDto> {
+        scores
+            .iter()
+            .map(|&(id, score)| LiveboardDto {
+                player_id: PlayerId(NonZeroU32::new(id).unwrap()),
+                score,
+                kills: 0,
+                team_captain: false,
+                team_id: None,
+            })
+            .collect()
+    }
+
+    fn id(id: u32) -> Option<PlayerId> {
+        Some(PlayerId(NonZeroU32::new(id).unwrap()))
+    }
+
+    #[test]
+    fn follows_leader_then_cycles() {
+        let mut spectator = Spectator::default();
+        assert!(!spectator.update(&[], 0.1, 0.5));
+        assert_eq!(spectator.target(), None);
+
+        let players = liveboard(&[(1, 100), (2, 500), (3, 50)]);
+        assert!(spectator.update(&players, 0.1, 0.5));
+        assert_eq!(spectator.target(), id(2));
+
+        // Keeps watching until the timer elapses.
+        assert!(!spectator.update(&players, 10.0, 0.5));
+        assert!(spectator.update(&players, 25.0, 0.0));
+        assert_eq!(spectator.target(), id(1));
+
+        // When asked.
+        spectator.next();
+        assert!(spectator.update(&players, 0.1, 0.99));
+        assert_eq!(spectator.target(), id(3));
+    }
+
+    #[test]
+    fn target_lost() {
+        let mut spectator = Spectator::default();
+        assert!(spectator.update(&liveboard(&[(1, 100), (2, 500)]), 0.1, 0.5));
+        assert_eq!(spectator.target(), id(2));
+
+        // Sunk.
+        assert!(spectator.update(&liveboard(&[(1, 100)]), 0.1, 0.5));
+        assert_eq!(spectator.target(), id(1));
+
+        // The only player can't be cycled away from.
+        spectator.next();
+        assert!(!spectator.update(&liveboard(&[(1, 100)]), 0.1, 0.5));
+        assert_eq!(spectator.target(), id(1));
+
+        assert!(spectator.update(&[], 0.1, 0.5));
+        assert_eq!(spectator.target(), None);
+    }
+}
@@ -0,0 +1,109 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::settings::TeamColors;
+use client_util::palette::{ColorVision, SemanticColor};
+use core_protocol::id::PlayerId;
+use glam::Vec3;
+
+/// The single color shared by all teammates, which depends on `color_vision`.
+pub fn team_color(color_vision: ColorVision) -> Vec3 {
+    color_vision.color(SemanticColor::Friendly)
+}
+
+/// Gets a color unique to `player_id`, which is the same on all clients. Consecutive ids are spread
+/// around the color wheel by the golden ratio, so they are easy to tell apart.
+pub fn member_color(player_id: PlayerId) -> Vec3 {
+    const GOLDEN_RATIO_CONJUGATE: f64 = 0.618033988749895;
+    let spread = (player_id.0.get() as f64 * GOLDEN_RATIO_CONJUGATE).fract() as f32;
+    // Avoid reds, which are used for enemy weapons.
+    hsv(0.08 + spread * 0.8, 0.7, 1.0)
+}
+
+/// Gets the color of a friendly contact (i.e. a teammate) belonging to `player_id`, according to
+/// `team_colors`. Falls back to [`team_color`] if there is no player.
+pub fn friendly_color(
+    team_colors: TeamColors,
+    color_vision: ColorVision,
+    player_id: Option<PlayerId>,
+) -> Vec3 {
+    match (team_colors, player_id) {
+        (TeamColors::PerMember, Some(player_id)) => member_color(player_id),
+        _ => team_color(color_vision),
+    }
+}
+
+/// Converts `hue`, `saturation` and `value`, each from 0 to 1, to RGB.
+fn hsv(hue: f32, saturation: f32, value: f32) -> Vec3 {
+    let channel = |n: f32| {
+        let k = (n + hue * 6.0) % 6.0;
+        value - value * saturation * k.min(4.0 - k).clamp(0.0, 1.0)
+    };
+    Vec3::new(channel(5.0), channel(3.0), channel(1.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::settings::TeamColors;
+    use crate::team_color::{friendly_color, hsv, member_color, team_color};
+    use client_util::palette::ColorVision;
+    use core_protocol::id::PlayerId;
+    use glam::Vec3;
+    use std::num::NonZeroU32;
+
+    fn player_id(id: u32) -> PlayerId {
+        PlayerId(NonZeroU32::new(id).unwrap())
+    }
+
+    #[test]
+    fn hsv_primaries() {
+        assert!(hsv(0.0, 1.0, 1.0).abs_diff_eq(Vec3::X, 0.0001));
+        assert!(hsv(1.0 / 3.0, 1.0, 1.0).abs_diff_eq(Vec3::Y, 0.0001));
+        assert!(hsv(2.0 / 3.0, 1.0, 1.0).abs_diff_eq(Vec3::Z, 0.0001));
+        assert!(hsv(0.5, 0.0, 1.0).abs_diff_eq(Vec3::ONE, 0.0001));
+    }
+
+    #[test]
+    fn member_color_stable() {
+        // Pinned, since teammates must agree on each other's colors.
+        assert!(member_color(player_id(1)).abs_diff_eq(Vec3::new(0.3, 0.6874, 1.0), 0.001));
+        for id in [2, 17, 1000, u32::MAX] {
+            assert_eq!(member_color(player_id(id)), member_color(player_id(id)));
+        }
+    }
+
+    #[test]
+    fn member_color_distinct() {
+        let colors: Vec<Vec3> = (1..=8).map(|id| member_color(player_id(id))).collect();
+        for (i, a) in colors.iter().enumerate() {
+            for b in &colors[i + 1..] {
+                assert!(a.distance(*b) > 0.15, "{} {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn falls_back_to_team_color() {
+        let id = Some(player_id(5));
+        let normal = ColorVision::Normal;
+        assert_eq!(
+            friendly_color(TeamColors::Single, normal, id),
+            team_color(normal)
+        );
+        assert_eq!(
+            friendly_color(TeamColors::PerMember, normal, None),
+            team_color(normal)
+        );
+        assert_eq!(
+            friendly_color(TeamColors::PerMember, normal, id),
+            member_color(player_id(5))
+        );
+
+        let deuteranopia = ColorVision::Deuteranopia;
+        assert_ne!(team_color(deuteranopia), team_color(normal));
+        assert_eq!(
+            friendly_color(TeamColors::Single, deuteranopia, id),
+            team_color(deuteranopia)
+        );
+    }
+}
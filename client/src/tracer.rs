@@ -0,0 +1,79 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use glam::{Vec2, Vec4};
+use renderer2d::GraphicLayer;
+
+/// Projectiles slower than this, in meters per second, don't get tracers.
+pub const TRACER_MIN_SPEED: f32 = 100.0;
+/// Tracers are as long as the distance a projectile travels in this many seconds.
+const TRACER_SECONDS: f32 = 0.05;
+
+/// Gets the start (tail) and end (head) of a tracer behind a projectile that moved from
+/// `previous` to `current` position since the last frame, at `speed` (in meters per second).
+/// The tracer is at least as long as the distance moved, so fast projectiles read as streaks
+/// rather than teleporting dots.
+pub fn tracer_endpoints(previous: Vec2, current: Vec2, speed: f32) -> Option<(Vec2, Vec2)> {
+    if speed < TRACER_MIN_SPEED {
+        return None;
+    }
+    let moved = current - previous;
+    let distance = moved.length();
+    if distance < 0.001 {
+        return None;
+    }
+    let length = distance.max(speed * TRACER_SECONDS);
+    Some((current - moved * (length / distance), current))
+}
+
+/// Draws a tracer from `previous` to `current` position (see [`tracer_endpoints`]), fading out
+/// towards the tail.
+pub fn draw_tracer(
+    layer: &mut GraphicLayer,
+    previous: Vec2,
+    current: Vec2,
+    speed: f32,
+    thickness: f32,
+    color: Vec4,
+) {
+    if let Some((start, end)) = tracer_endpoints(previous, current, speed) {
+        layer.draw_line_gradient(start, end, thickness, color.truncate().extend(0.0), color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tracer::{tracer_endpoints, TRACER_MIN_SPEED};
+    use glam::Vec2;
+
+    fn assert_near(a: Vec2, b: Vec2) {
+        assert!(a.distance(b) < 0.01, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn tracer_endpoint() {
+        let previous = Vec2::new(10.0, 20.0);
+        let current = Vec2::new(40.0, 20.0);
+
+        // Moved further than the minimum length, so the tracer spans the movement.
+        let (start, end) = tracer_endpoints(previous, current, 200.0).unwrap();
+        assert_near(start, previous);
+        assert_near(end, current);
+
+        // Faster projectiles get longer tracers, extending behind the previous position.
+        let (start, end) = tracer_endpoints(previous, current, 1000.0).unwrap();
+        assert_near(start, Vec2::new(-10.0, 20.0));
+        assert_near(end, current);
+
+        // Diagonal.
+        let (start, _) = tracer_endpoints(Vec2::ZERO, Vec2::new(3.0, 4.0), 200.0).unwrap();
+        assert_near(start, Vec2::new(-3.0, -4.0));
+
+        // Too slow, or didn't move.
+        assert_eq!(
+            tracer_endpoints(previous, current, TRACER_MIN_SPEED * 0.5),
+            None
+        );
+        assert_eq!(tracer_endpoints(current, current, 1000.0), None);
+    }
+}
@@ -11,6 +11,7 @@ use std::fmt::Display;
 use yew_frontend::s;
 
 pub trait Mk48Translation: Sized {
+    s!(combat_log_export_label);
     fn death_reason(self, death_reason: &DeathReason) -> String;
     fn death_reason_boat(self, alias: PlayerAlias) -> String {
         self.death_reason_collision(&alias)
@@ -191,6 +192,23 @@ impl Mk48Translation for LanguageId {
     }
     */
 
+    fn combat_log_export_label(self) -> &'static str {
+        match self {
+            Arabic => "تصدير سجل القتال",
+            Bork => "Bork the bork log",
+            English => "Export combat log",
+            French => "Exporter le journal de combat",
+            German => "Kampfprotokoll exportieren",
+            Hindi => "युद्ध लॉग निर्यात करें",
+            Italian => "Esporta registro di combattimento",
+            Japanese => "戦闘ログをエクスポート",
+            Russian => "Экспорт журнала боя",
+            SimplifiedChinese => "导出战斗日志",
+            Spanish => "Exportar registro de combate",
+            Vietnamese => "Xuất nhật ký chiến đấu",
+        }
+    }
+
     fn death_reason(self, death_reason: &DeathReason) -> String {
         match death_reason {
             &DeathReason::Boat(alias) => self.death_reason_boat(alias),
@@ -1,19 +1,25 @@
 // SPDX-FileCopyrightText: 2021 Softbear, Inc.
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+#[cfg(debug_assertions)]
+use crate::dev::DevState;
 use crate::game::Mk48Game;
 use crate::translation::Mk48Translation;
 use crate::ui::about_dialog::AboutDialog;
 use crate::ui::changelog_dialog::ChangelogDialog;
+#[cfg(debug_assertions)]
+use crate::ui::dev_overlay::DevOverlay;
 use crate::ui::help_dialog::HelpDialog;
 use crate::ui::hint::Hint;
 pub use crate::ui::instructions::InstructionsProps;
 use crate::ui::levels_dialog::LevelsDialog;
 use crate::ui::logo::logo;
+use crate::ui::low_fps_overlay::LowFpsOverlay;
 use crate::ui::respawn_overlay::RespawnOverlay;
 use crate::ui::settings_dialog::SettingsDialog;
 use crate::ui::ship_controls::ShipControls;
-use crate::ui::ships_dialog::ShipsDialog;
+use crate::ui::ships_dialog::{ShipParam, ShipsDialog};
+use crate::ui::spectate_overlay::SpectateOverlay;
 use crate::ui::status_overlay::StatusOverlay;
 use crate::ui::upgrade_overlay::UpgradeOverlay;
 use client_util::context::Context;
@@ -26,10 +32,13 @@ use core_protocol::id::{LanguageId, TeamId};
 use core_protocol::name::PlayerAlias;
 use engine_macros::SmolRoutable;
 use glam::Vec2;
+use renderer::RenderStats;
 use std::collections::HashMap;
 use stylist::yew::styled_component;
+use web_sys::BeforeUnloadEvent;
 use yew::prelude::*;
 use yew_frontend::component::discord_icon::DiscordIcon;
+use yew_frontend::component::fade::Fade;
 use yew_frontend::component::invitation_icon::InvitationIcon;
 use yew_frontend::component::invitation_link::InvitationLink;
 use yew_frontend::component::language_menu::LanguageMenu;
@@ -48,20 +57,25 @@ use yew_frontend::overlay::leaderboard::LeaderboardOverlay;
 use yew_frontend::overlay::spawn::SpawnOverlay;
 use yew_frontend::overlay::team::TeamsOverlay;
 use yew_frontend::translation::{t, Translation};
+use yew_frontend::window::event_listener::WindowEventListener;
 use yew_router::{Routable, Switch};
 
 mod about_dialog;
 mod changelog_dialog;
+#[cfg(debug_assertions)]
+mod dev_overlay;
 mod help_dialog;
 mod hint;
 mod instructions;
 mod levels_dialog;
 mod logo;
+mod low_fps_overlay;
 mod respawn_overlay;
 mod settings_dialog;
 mod ship_controls;
 mod ship_menu;
 mod ships_dialog;
+mod spectate_overlay;
 mod sprite;
 mod status_overlay;
 mod upgrade_overlay;
@@ -87,54 +101,101 @@ pub fn mk48_ui(props: &PropertiesWrapper<UiProps>) -> Html {
     let margin = "0.75rem";
     let status = props.status.clone();
     let outbound_enabled = Ctw::use_outbound_enabled();
+    let hud_visible = Ctw::use_hud_visible();
+
+    // Prompt before leaving the page mid-game.
+    use_effect_with_deps(
+        |&leave_confirmation| {
+            let listener = leave_confirmation.then(|| {
+                WindowEventListener::new(
+                    "beforeunload",
+                    |event: &BeforeUnloadEvent| {
+                        event.prevent_default();
+                        // Required by some browsers, which don't show the message anyway.
+                        event.set_return_value("Leave game?");
+                    },
+                    true,
+                )
+            });
+            move || drop(listener)
+        },
+        status.leave_confirmation(gctw.settings_cache.leave_confirmation),
+    );
+
+    #[cfg(debug_assertions)]
+    let dev_overlay = props.dev.shown.then(|| {
+        html! {
+            <DevOverlay dev={props.dev} fps={props.fps} contacts={props.contacts}/>
+        }
+    });
+    #[cfg(not(debug_assertions))]
+    let dev_overlay: Option<Html> = None;
 
     html! {
         <>
             if let UiStatus::Playing(playing) = status {
-                <div class={classes!(gctw.settings_cache.cinematic.then_some(cinematic_style))}>
-                    <Positioner position={Position::BottomMiddle{margin}}>
-                        <StatusOverlay
-                            status={playing.clone()}
-                            score={props.score}
-                            fps={gctw.settings_cache.fps_shown.then_some(props.fps)}
-                        />
-                    </Positioner>
-                    <Positioner position={Position::TopMiddle{margin}}>
-                        <UpgradeOverlay status={playing.clone()} score={props.score}/>
-                    </Positioner>
-                    <Positioner position={Position::BottomLeft{margin}}>
-                        <ShipControls status={playing.clone()}/>
-                    </Positioner>
-                    <Positioner position={Position::CenterRight{margin}} flex={Flex::Column}>
-                        <InvitationIcon/>
-                        <ZoomIcon amount={-4}/>
-                        <ZoomIcon amount={4}/>
-                        <VolumeIcon/>
-                        <SettingsIcon<Mk48Route> route={Mk48Route::Settings}/>
-                        <LanguageMenu/>
+                if hud_visible {
+                    <div class={classes!(gctw.settings_cache.cinematic.then_some(cinematic_style))}>
+                        <Positioner position={Position::BottomMiddle{margin}}>
+                            <StatusOverlay
+                                status={playing.clone()}
+                                score={props.score}
+                                fps={gctw.settings_cache.fps_shown.then_some(props.fps)}
+                                render_stats={gctw.settings_cache.render_stats_shown.then_some(props.render_stats)}
+                                auto_fire={gctw.settings_cache.auto_fire}
+                            />
+                        </Positioner>
+                        <Positioner position={Position::TopMiddle{margin}}>
+                            <UpgradeOverlay status={playing.clone()} score={props.score}/>
+                        </Positioner>
+                        <Positioner position={Position::BottomLeft{margin}}>
+                            <ShipControls status={playing.clone()}/>
+                        </Positioner>
+                        <Positioner position={Position::CenterRight{margin}} flex={Flex::Column}>
+                            <InvitationIcon/>
+                            <ZoomIcon amount={-4}/>
+                            <ZoomIcon amount={4}/>
+                            <VolumeIcon/>
+                            <SettingsIcon<Mk48Route> route={Mk48Route::Settings}/>
+                            <LanguageMenu/>
+                        </Positioner>
+                        <Positioner position={Position::TopLeft{margin}} max_width="25%">
+                            <TeamsOverlay
+                                team_proximity={playing.team_proximity.clone()}
+                                label={LanguageId::team_fleet_label as fn(LanguageId) -> &'static str}
+                                name_placeholder={LanguageId::team_fleet_name_placeholder as fn(LanguageId) -> &'static str}
+                            />
+                        </Positioner>
+                        <Positioner position={Position::TopRight{margin}} max_width="25%">
+                            <LeaderboardOverlay/>
+                        </Positioner>
+                        <Positioner position={Position::BottomRight{margin}} align={Align::Left} max_width="25%">
+                            <ChatOverlay label={LanguageId::chat_radio_label as fn(LanguageId) -> &'static str}/>
+                        </Positioner>
+                    </div>
+                    if !gctw.settings_cache.cinematic {
+                        <Hint entity_type={playing.entity_type}/>
+                    }
+                    <Positioner position={Position::Center}>
+                        <Fade visible={props.low_fps} reduce_motion={gctw.settings_cache.reduce_motion}>
+                            <LowFpsOverlay/>
+                        </Fade>
                     </Positioner>
-                    <Positioner position={Position::TopLeft{margin}} max_width="25%">
-                        <TeamsOverlay
-                            team_proximity={playing.team_proximity.clone()}
-                            label={LanguageId::team_fleet_label as fn(LanguageId) -> &'static str}
-                            name_placeholder={LanguageId::team_fleet_name_placeholder as fn(LanguageId) -> &'static str}
-                        />
-                    </Positioner>
-                    <Positioner position={Position::TopRight{margin}} max_width="25%">
-                        <LeaderboardOverlay/>
-                    </Positioner>
-                    <Positioner position={Position::BottomRight{margin}} align={Align::Left} max_width="25%">
-                        <ChatOverlay label={LanguageId::chat_radio_label as fn(LanguageId) -> &'static str}/>
-                    </Positioner>
-                </div>
-                if !gctw.settings_cache.cinematic {
-                    <Hint entity_type={playing.entity_type}/>
                 }
             } else if let UiStatus::Respawning(respawning) = status {
                 <RespawnOverlay status={respawning} score={props.score}/>
                 <Positioner position={Position::TopRight{margin}} max_width="25%">
                     <XButton onclick={gctw.send_ui_event_callback.reform(|_| UiEvent::OverrideRespawn)}/>
                 </Positioner>
+            } else if let UiStatus::Spectating(spectating) = status {
+                if hud_visible {
+                    <Positioner position={Position::TopMiddle{margin}}>
+                        <SpectateOverlay status={spectating}/>
+                    </Positioner>
+                    <Positioner position={Position::TopRight{margin}} max_width="25%">
+                        <LeaderboardOverlay/>
+                    </Positioner>
+                }
             } else {
                 <SpawnOverlay {on_play}>
                     {logo()}
@@ -159,6 +220,9 @@ pub fn mk48_ui(props: &PropertiesWrapper<UiProps>) -> Html {
                     </Positioner>
                 }
             }
+            if let Some(dev_overlay) = dev_overlay {
+                {dev_overlay}
+            }
             <div>
                 <Switch<Mk48Route> render={Switch::render(switch)}/>
             </div>
@@ -176,6 +240,8 @@ pub enum Mk48Route {
     Help,
     #[at("/ships/")]
     Ships,
+    #[at("/ships/:ship/")]
+    Ship { ship: ShipParam },
     #[at("/levels/")]
     Levels,
     #[at("/settings/")]
@@ -216,13 +282,31 @@ pub enum UiEvent {
     /// Go from respawning to spawning.
     #[allow(unused)]
     OverrideRespawn,
+    /// Watch another player, if spectating.
+    SpectateNext,
+    /// Hide the low framerate warning for the rest of the session.
+    DismissLowFps,
+    /// Save the combat log of the last life as a file.
+    ExportCombatLog,
+    /// Change developer debug toggles.
+    #[cfg(debug_assertions)]
+    Dev(DevState),
 }
 
 #[derive(PartialEq, Clone, Default)]
 pub struct UiProps {
     pub fps: f32,
+    /// Whether to suggest lowering graphics settings.
+    pub low_fps: bool,
+    /// Of the last rendered frame.
+    pub render_stats: RenderStats,
     pub score: u32,
     pub status: UiStatus,
+    #[cfg(debug_assertions)]
+    pub dev: DevState,
+    /// Number of known contacts, for developer stats.
+    #[cfg(debug_assertions)]
+    pub contacts: usize,
 }
 
 /// Mutually exclusive statuses.
@@ -232,6 +316,16 @@ pub enum UiStatus {
     Spawning,
     Playing(UiStatusPlaying),
     Respawning(UiStatusRespawning),
+    /// Watching someone else, instead of spawning (see `/spectate/`).
+    Spectating(UiStatusSpectating),
+}
+
+impl UiStatus {
+    /// Whether leaving the page should be confirmed, if `enabled` by the setting. Only true while
+    /// playing, since nothing is lost when dead or on the menu.
+    pub fn leave_confirmation(&self, enabled: bool) -> bool {
+        enabled && matches!(self, Self::Playing(_))
+    }
 }
 
 #[derive(PartialEq, Clone)]
@@ -255,12 +349,24 @@ pub struct UiStatusRespawning {
     pub death_reason: DeathReason,
 }
 
+#[derive(PartialEq, Clone)]
+pub struct UiStatusSpectating {
+    /// Who is being watched, if anyone.
+    pub alias: Option<PlayerAlias>,
+}
+
 impl Mk48Game {
     pub(crate) fn update_ui_props(&self, context: &mut Context<Self>, status: UiStatus) {
         let props = UiProps {
             fps: self.fps_counter.last_sample().unwrap_or(0.0),
+            low_fps: self.low_fps_warning.is_shown(),
+            render_stats: self.render_stats,
             score: context.state.game.score,
             status,
+            #[cfg(debug_assertions)]
+            dev: self.dev,
+            #[cfg(debug_assertions)]
+            contacts: context.state.game.contacts.len(),
         };
 
         context.set_ui_props(props);
@@ -281,6 +387,9 @@ fn switch(routes: &Mk48Route) -> Html {
         Mk48Route::Ships => html! {
             <ShipsDialog/>
         },
+        Mk48Route::Ship { ship } => html! {
+            <ShipsDialog selected={Some(ship.0)}/>
+        },
         Mk48Route::Levels => html! {
             <LevelsDialog/>
         },
@@ -290,3 +399,49 @@ fn switch(routes: &Mk48Route) -> Html {
         Mk48Route::Home => html! {},
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::ui::{
+        InstructionsProps, UiStatus, UiStatusPlaying, UiStatusRespawning, UiStatusSpectating,
+    };
+    use common::altitude::Altitude;
+    use common::angle::Angle;
+    use common::death_reason::DeathReason;
+    use common::entity::EntityType;
+    use common::velocity::Velocity;
+    use glam::Vec2;
+
+    #[test]
+    fn leave_confirmation() {
+        let playing = UiStatus::Playing(UiStatusPlaying {
+            entity_type: EntityType::GFive,
+            velocity: Velocity::ZERO,
+            direction: Angle::ZERO,
+            position: Vec2::ZERO,
+            altitude: Altitude::ZERO,
+            submerge: false,
+            active: true,
+            instruction_props: InstructionsProps {
+                touch: false,
+                basics: false,
+                zoom: false,
+            },
+            armament: None,
+            armament_consumption: Box::new([]),
+            team_proximity: Default::default(),
+        });
+        let respawning = UiStatus::Respawning(UiStatusRespawning {
+            death_reason: DeathReason::Border,
+        });
+
+        assert!(playing.leave_confirmation(true));
+        assert!(!playing.leave_confirmation(false));
+        let spectating = UiStatus::Spectating(UiStatusSpectating { alias: None });
+
+        for status in [UiStatus::Spawning, respawning, spectating] {
+            assert!(!status.leave_confirmation(true));
+            assert!(!status.leave_confirmation(false));
+        }
+    }
+}
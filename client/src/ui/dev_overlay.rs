@@ -0,0 +1,74 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::dev::DevState;
+use crate::ui::UiEvent;
+use crate::Mk48Game;
+use stylist::yew::styled_component;
+use yew::{html, Callback, Properties};
+use yew_frontend::frontend::Gctw;
+
+#[derive(PartialEq, Properties)]
+pub struct DevOverlayProps {
+    pub dev: DevState,
+    pub fps: f32,
+    pub contacts: usize,
+}
+
+/// Checkboxes for developer debug toggles. Only compiled in debug builds.
+#[styled_component(DevOverlay)]
+pub fn dev_overlay(props: &DevOverlayProps) -> Html {
+    let overlay_style = css!(
+        r#"
+        background-color: #00000080;
+        left: 50%;
+        padding: 0.5em;
+        pointer-events: all;
+        position: absolute;
+        top: 0.75rem;
+        transform: translate(-50%, 0);
+        "#
+    );
+
+    let label_style = css!(
+        r#"
+        display: block;
+        user-select: none;
+        "#
+    );
+
+    let ui_event_callback = Gctw::<Mk48Game>::use_ui_event_callback();
+    let toggle = |change: fn(&mut DevState)| -> Callback<_> {
+        let dev = props.dev;
+        ui_event_callback.reform(move |_| {
+            let mut dev = dev;
+            change(&mut dev);
+            UiEvent::Dev(dev)
+        })
+    };
+
+    html! {
+        <div class={overlay_style}>
+            <h3>{"Developer"}</h3>
+            <label class={label_style.clone()}>
+                <input type="checkbox" checked={props.dev.latency} oninput={toggle(|dev| dev.latency = !dev.latency)}/>
+                {"Log Prediction Error"}
+            </label>
+            <label class={label_style.clone()}>
+                <input type="checkbox" checked={props.dev.hitboxes} oninput={toggle(|dev| dev.hitboxes = !dev.hitboxes)}/>
+                {"Hitboxes (Ctrl+Shift+H)"}
+            </label>
+            <label class={label_style.clone()}>
+                <input type="checkbox" checked={props.dev.stats} oninput={toggle(|dev| dev.stats = !dev.stats)}/>
+                {"Stats"}
+            </label>
+            <label class={label_style.clone()}>
+                <input type="checkbox" checked={props.dev.screenshot} oninput={toggle(|dev| dev.screenshot = !dev.screenshot)}/>
+                {"Screenshot Mode"}
+            </label>
+            if props.dev.stats {
+                <p>{format!("{:.1} fps, {} contacts", props.fps, props.contacts)}</p>
+            }
+        </div>
+    }
+}
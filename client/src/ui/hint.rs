@@ -5,7 +5,8 @@ use crate::translation::Mk48Translation;
 use common::entity::EntityType;
 use stylist::yew::styled_component;
 use web_sys::HtmlDivElement;
-use yew::{html, use_effect_with_deps, use_node_ref, Properties};
+use yew::{classes, html, use_effect_with_deps, use_node_ref, Properties};
+use yew_frontend::frontend::Ctw;
 use yew_frontend::translation::t;
 
 #[derive(PartialEq, Properties)]
@@ -49,6 +50,7 @@ pub fn hint(props: &HintProps) -> Html {
     "#
     );
 
+    let overlay_class = Ctw::use_theme().overlay_class();
     let container_ref = use_node_ref();
 
     {
@@ -71,7 +73,7 @@ pub fn hint(props: &HintProps) -> Html {
 
     let data = props.entity_type.data();
     html! {
-        <div class={hint_style} ref={container_ref}>
+        <div class={classes!(hint_style, overlay_class)} ref={container_ref}>
             {t().entity_kind_hint(data.kind, data.sub_kind)}
         </div>
     }
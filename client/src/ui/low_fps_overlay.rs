@@ -0,0 +1,72 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::settings::Mk48Settings;
+use crate::ui::UiEvent;
+use crate::Mk48Game;
+use client_util::browser_storage::BrowserStorages;
+use stylist::yew::styled_component;
+use web_sys::MouseEvent;
+use yew::{classes, html, Html};
+use yew_frontend::frontend::{Ctw, Gctw};
+
+/// Suggests lowering graphics settings, after a sustained low framerate.
+#[styled_component(LowFpsOverlay)]
+pub fn low_fps_overlay() -> Html {
+    let container_style = css!(
+        r#"
+        background-color: #00000080;
+        border-radius: 0.5em;
+        color: white;
+        padding: 1em;
+        pointer-events: all;
+        text-align: center;
+        user-select: none;
+    "#
+    );
+
+    let button_style = css!(
+        r#"
+        background-color: #0075ff;
+        border: 0;
+        border-radius: 0.25em;
+        color: white;
+        cursor: pointer;
+        font-size: 1em;
+        font-weight: bold;
+        margin: 0.5em 0.25em 0 0.25em;
+        padding: 0.5em 0.7em;
+    "#
+    );
+
+    let recreate_renderer_callback = Ctw::use_ctw().recreate_renderer_callback;
+    let gctw = Gctw::<Mk48Game>::use_gctw();
+    let overlay_class = Ctw::use_theme().overlay_class();
+
+    let on_dismiss = gctw
+        .send_ui_event_callback
+        .reform(|_: MouseEvent| UiEvent::DismissLowFps);
+
+    let on_apply = {
+        let change_settings_callback = gctw.change_settings_callback.clone();
+        let on_dismiss = on_dismiss.clone();
+        move |event: MouseEvent| {
+            change_settings_callback.emit(Box::new(
+                |settings: &mut Mk48Settings, browser_storages: &mut BrowserStorages| {
+                    settings.apply_low_graphics(browser_storages);
+                },
+            ));
+            recreate_renderer_callback.emit(());
+            on_dismiss.emit(event);
+        }
+    };
+
+    html! {
+        <div class={classes!(container_style, overlay_class)}>
+            {"The game is running slowly. Lower graphics settings?"}
+            <br/>
+            <button class={button_style.clone()} onclick={on_apply}>{"Use Low Graphics"}</button>
+            <button class={button_style} onclick={on_dismiss}>{"Dismiss"}</button>
+        </div>
+    }
+}
@@ -49,8 +49,23 @@ pub fn respawn_overlay(props: &RespawnOverlayProps) -> Html {
         "#
     );
 
+    let export_style = css!(
+        r#"
+        background-color: transparent;
+        border: 1px solid #ffffff88;
+        border-radius: 0.25em;
+        color: white;
+        cursor: pointer;
+        font-size: 0.9em;
+        margin-top: 1rem;
+        padding: 0.4em 0.7em;
+        "#
+    );
+
     let (_paused, _transitioning, onanimationend) = use_splash_screen();
-    let onclick = Gctw::<Mk48Game>::use_ui_event_callback().reform(UiEvent::Respawn);
+    let ui_event_callback = Gctw::<Mk48Game>::use_ui_event_callback();
+    let onclick = ui_event_callback.reform(UiEvent::Respawn);
+    let on_export_combat_log = ui_event_callback.reform(|_| UiEvent::ExportCombatLog);
     html! {
         <div class={container_style} {onanimationend}>
             <h2 class={reason_style}>{t().death_reason(&props.status.death_reason)}</h2>
@@ -59,6 +74,7 @@ pub fn respawn_overlay(props: &RespawnOverlayProps) -> Html {
                 {onclick}
                 closable={false}
             />
+            <button class={export_style} onclick={on_export_combat_log}>{t().combat_log_export_label()}</button>
             <div id="banner_bottom" style="margin: 5rem auto;"></div>
         </div>
     }
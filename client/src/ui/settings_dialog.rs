@@ -1,19 +1,27 @@
 // SPDX-FileCopyrightText: 2021 Softbear, Inc.
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
-use crate::settings::Mk48Settings;
+use crate::settings::{
+    CombatLogFormat, ContactIcons, ContactLabel, Mk48Settings, TeamColors, ZoomPreset,
+};
 use crate::Mk48Game;
 use client_util::browser_storage::BrowserStorages;
+use client_util::key_bindings::GameAction;
+use client_util::keyboard::Key;
+use client_util::palette::ColorVision;
 use client_util::setting::CommonSettings;
 use core_protocol::dto::ServerDto;
 use core_protocol::id::ServerId;
+use std::num::NonZeroU8;
 use std::str::FromStr;
 use stylist::yew::styled_component;
-use web_sys::{HtmlSelectElement, InputEvent};
+use web_sys::{window, HtmlInputElement, HtmlSelectElement, InputEvent, KeyboardEvent, MouseEvent};
 use yew::virtual_dom::AttrValue;
-use yew::{html, html_nested, Html, TargetCast};
+use yew::{html, html_nested, Callback, Html, TargetCast};
+use yew_frontend::canvas::resolution;
 use yew_frontend::dialog::dialog::Dialog;
 use yew_frontend::frontend::{Ctw, Gctw};
+use yew_frontend::settings_json::{export_settings, import_settings};
 use yew_frontend::translation::{t, Translation};
 
 #[styled_component(SettingsDialog)]
@@ -48,7 +56,7 @@ pub fn settings_dialog() -> Html {
     let t = t();
     let ctw = Ctw::use_ctw();
     let core_state = Ctw::use_core_state();
-    let recreate_renderer_callback = ctw.recreate_renderer_callback;
+    let recreate_renderer_callback = ctw.recreate_renderer_callback.clone();
     let gctw = Gctw::<Mk48Game>::use_gctw();
 
     let cinematic = gctw.settings_cache.cinematic;
@@ -60,6 +68,51 @@ pub fn settings_dialog() -> Html {
         )
     });
 
+    let combat_alert = gctw.settings_cache.combat_alert;
+    let on_toggle_combat_alert = gctw.change_settings_callback.reform(move |_| {
+        Box::new(
+            move |settings: &mut Mk48Settings, browser_storages: &mut BrowserStorages| {
+                settings.set_combat_alert(!combat_alert, browser_storages);
+            },
+        )
+    });
+
+    let leave_confirmation = gctw.settings_cache.leave_confirmation;
+    let on_toggle_leave_confirmation = gctw.change_settings_callback.reform(move |_| {
+        Box::new(
+            move |settings: &mut Mk48Settings, browser_storages: &mut BrowserStorages| {
+                settings.set_leave_confirmation(!leave_confirmation, browser_storages);
+            },
+        )
+    });
+
+    let own_label = gctw.settings_cache.own_label;
+    let on_toggle_own_label = gctw.change_settings_callback.reform(move |_| {
+        Box::new(
+            move |settings: &mut Mk48Settings, browser_storages: &mut BrowserStorages| {
+                settings.set_own_label(!own_label, browser_storages);
+            },
+        )
+    });
+
+    let velocity_vectors = gctw.settings_cache.velocity_vectors;
+    let on_toggle_velocity_vectors = gctw.change_settings_callback.reform(move |_| {
+        Box::new(
+            move |settings: &mut Mk48Settings, browser_storages: &mut BrowserStorages| {
+                settings.set_velocity_vectors(!velocity_vectors, browser_storages);
+            },
+        )
+    });
+
+    let auto_fire = gctw.settings_cache.auto_fire;
+    let on_toggle_auto_fire = gctw.change_settings_callback.reform(move |_| {
+        Box::new(
+            move |settings: &mut Mk48Settings, browser_storages: &mut BrowserStorages| {
+                settings.set_auto_fire(!auto_fire, browser_storages);
+            },
+        )
+    });
+
     let fps_shown = gctw.settings_cache.fps_shown;
     let on_toggle_fps = gctw.change_settings_callback.reform(move |_| {
         Box::new(
@@ -69,6 +122,15 @@ pub fn settings_dialog() -> Html {
         )
     });
 
+    let render_stats_shown = gctw.settings_cache.render_stats_shown;
+    let on_toggle_render_stats = gctw.change_settings_callback.reform(move |_| {
+        Box::new(
+            move |settings: &mut Mk48Settings, browser_storages: &mut BrowserStorages| {
+                settings.set_render_stats_shown(!render_stats_shown, browser_storages);
+            },
+        )
+    });
+
     let animations = gctw.settings_cache.animations;
     let on_toggle_animations = {
         let recreate_renderer_callback = recreate_renderer_callback.clone();
@@ -83,6 +145,33 @@ pub fn settings_dialog() -> Html {
         })
     };
 
+    let motion_blur = gctw.settings_cache.motion_blur;
+    let on_toggle_motion_blur = gctw.change_settings_callback.reform(move |_| {
+        Box::new(
+            move |settings: &mut Mk48Settings, browser_storages: &mut BrowserStorages| {
+                settings.set_motion_blur(!motion_blur, browser_storages);
+            },
+        )
+    });
+
+    let particle_jitter = gctw.settings_cache.particle_jitter;
+    let on_toggle_particle_jitter = gctw.change_settings_callback.reform(move |_| {
+        Box::new(
+            move |settings: &mut Mk48Settings, browser_storages: &mut BrowserStorages| {
+                settings.set_particle_jitter(!particle_jitter, browser_storages);
+            },
+        )
+    });
+
+    let reduce_motion = gctw.settings_cache.reduce_motion;
+    let on_toggle_reduce_motion = gctw.change_settings_callback.reform(move |_| {
+        Box::new(
+            move |settings: &mut Mk48Settings, browser_storages: &mut BrowserStorages| {
+                settings.set_reduce_motion(!reduce_motion, browser_storages);
+            },
+        )
+    });
+
     let wave_quality = gctw.settings_cache.wave_quality;
     let on_set_wave_quality = {
         let recreate_renderer_callback = recreate_renderer_callback.clone();
@@ -101,6 +190,121 @@ pub fn settings_dialog() -> Html {
             })
     };
 
+    let contact_label = gctw.settings_cache.contact_label;
+    let on_set_contact_label = gctw
+        .change_settings_callback
+        .reform(move |event: InputEvent| {
+            let value = event.target_unchecked_into::<HtmlSelectElement>().value();
+            Box::new(
+                move |settings: &mut Mk48Settings, browser_storages: &mut BrowserStorages| {
+                    if let Ok(contact_label) = ContactLabel::from_str(&value) {
+                        settings.set_contact_label(contact_label, browser_storages);
+                    }
+                },
+            )
+        });
+
+    let contact_icons = gctw.settings_cache.contact_icons;
+    let on_set_contact_icons = gctw
+        .change_settings_callback
+        .reform(move |event: InputEvent| {
+            let value = event.target_unchecked_into::<HtmlSelectElement>().value();
+            Box::new(
+                move |settings: &mut Mk48Settings, browser_storages: &mut BrowserStorages| {
+                    if let Ok(contact_icons) = ContactIcons::from_str(&value) {
+                        settings.set_contact_icons(contact_icons, browser_storages);
+                    }
+                },
+            )
+        });
+
+    let team_colors = gctw.settings_cache.team_colors;
+    let on_set_team_colors = gctw
+        .change_settings_callback
+        .reform(move |event: InputEvent| {
+            let value = event.target_unchecked_into::<HtmlSelectElement>().value();
+            Box::new(
+                move |settings: &mut Mk48Settings, browser_storages: &mut BrowserStorages| {
+                    if let Ok(team_colors) = TeamColors::from_str(&value) {
+                        settings.set_team_colors(team_colors, browser_storages);
+                    }
+                },
+            )
+        });
+
+    let combat_log_format = gctw.settings_cache.combat_log_format;
+    let on_set_combat_log_format =
+        gctw.change_settings_callback
+            .reform(move |event: InputEvent| {
+                let value = event.target_unchecked_into::<HtmlSelectElement>().value();
+                Box::new(
+                    move |settings: &mut Mk48Settings, browser_storages: &mut BrowserStorages| {
+                        if let Ok(combat_log_format) = CombatLogFormat::from_str(&value) {
+                            settings.set_combat_log_format(combat_log_format, browser_storages);
+                        }
+                    },
+                )
+            });
+
+    let zoom_presets = ZoomPreset::ALL.map(|preset| {
+        let zoom_input = gctw.settings_cache.zoom_preset(preset);
+        let on_set = gctw
+            .change_settings_callback
+            .reform(move |event: InputEvent| {
+                let value = event.target_unchecked_into::<HtmlSelectElement>().value();
+                Box::new(
+                    move |settings: &mut Mk48Settings, browser_storages: &mut BrowserStorages| {
+                        if let Ok(zoom_input) = f32::from_str(&value) {
+                            settings.set_zoom_preset(preset, zoom_input, browser_storages);
+                        }
+                    },
+                )
+            });
+        (preset, zoom_input, on_set)
+    });
+
+    let you_are_here_zoom = gctw.settings_cache.you_are_here_zoom;
+    let on_set_you_are_here_zoom =
+        gctw.change_settings_callback
+            .reform(move |event: InputEvent| {
+                let value = event.target_unchecked_into::<HtmlSelectElement>().value();
+                Box::new(
+                    move |settings: &mut Mk48Settings, browser_storages: &mut BrowserStorages| {
+                        if let Ok(you_are_here_zoom) = f32::from_str(&value) {
+                            settings.set_you_are_here_zoom(you_are_here_zoom, browser_storages);
+                        }
+                    },
+                )
+            });
+
+    let camera_deadzone = gctw.settings_cache.camera_deadzone;
+    let on_set_camera_deadzone = gctw
+        .change_settings_callback
+        .reform(move |event: InputEvent| {
+            let value = event.target_unchecked_into::<HtmlSelectElement>().value();
+            Box::new(
+                move |settings: &mut Mk48Settings, browser_storages: &mut BrowserStorages| {
+                    if let Ok(camera_deadzone) = f32::from_str(&value) {
+                        settings.set_camera_deadzone(camera_deadzone, browser_storages);
+                    }
+                },
+            )
+        });
+
+    let fire_buffer_millis = gctw.settings_cache.fire_buffer_millis;
+    let on_set_fire_buffer_millis =
+        gctw.change_settings_callback
+            .reform(move |event: InputEvent| {
+                let value = event.target_unchecked_into::<HtmlSelectElement>().value();
+                Box::new(
+                    move |settings: &mut Mk48Settings, browser_storages: &mut BrowserStorages| {
+                        if let Ok(fire_buffer_millis) = u8::from_str(&value) {
+                            settings.set_fire_buffer_millis(fire_buffer_millis, browser_storages);
+                        }
+                    },
+                )
+            });
+
     let chat_dialog_shown = ctw.setting_cache.chat_dialog_shown;
     let on_toggle_chat = ctw.change_common_settings_callback.reform(move |_| {
         Box::new(
@@ -110,6 +314,163 @@ pub fn settings_dialog() -> Html {
         )
     });
 
+    let streamer_mode = ctw.setting_cache.streamer_mode;
+    let on_toggle_streamer_mode = ctw.change_common_settings_callback.reform(move |_| {
+        Box::new(
+            move |settings: &mut CommonSettings, browser_storages: &mut BrowserStorages| {
+                settings.set_streamer_mode(!streamer_mode, browser_storages);
+            },
+        )
+    });
+
+    let muted = ctw.setting_cache.muted;
+    let on_toggle_muted = ctw.change_common_settings_callback.reform(move |_| {
+        Box::new(
+            move |settings: &mut CommonSettings, browser_storages: &mut BrowserStorages| {
+                settings.set_muted(!muted, browser_storages);
+            },
+        )
+    });
+
+    let sfx_volume = ctw.setting_cache.sfx_volume;
+    let on_set_sfx_volume = ctw
+        .change_common_settings_callback
+        .reform(|event: InputEvent| {
+            let value = event
+                .target_unchecked_into::<HtmlInputElement>()
+                .value_as_number() as f32;
+            Box::new(
+                move |settings: &mut CommonSettings, browser_storages: &mut BrowserStorages| {
+                    settings.set_sfx_volume(value, browser_storages);
+                },
+            )
+        });
+
+    let music_volume = ctw.setting_cache.music_volume;
+    let on_set_music_volume = ctw
+        .change_common_settings_callback
+        .reform(|event: InputEvent| {
+            let value = event
+                .target_unchecked_into::<HtmlInputElement>()
+                .value_as_number() as f32;
+            Box::new(
+                move |settings: &mut CommonSettings, browser_storages: &mut BrowserStorages| {
+                    settings.set_music_volume(value, browser_storages);
+                },
+            )
+        });
+
+    let mute_on_focus_loss = ctw.setting_cache.mute_on_focus_loss;
+    let on_toggle_mute_on_focus_loss = ctw.change_common_settings_callback.reform(move |_| {
+        Box::new(
+            move |settings: &mut CommonSettings, browser_storages: &mut BrowserStorages| {
+                settings.set_mute_on_focus_loss(!mute_on_focus_loss, browser_storages);
+            },
+        )
+    });
+
+    let mouse_sensitivity = ctw.setting_cache.mouse_sensitivity;
+    let on_set_mouse_sensitivity =
+        ctw.change_common_settings_callback
+            .reform(|event: InputEvent| {
+                let value = event
+                    .target_unchecked_into::<HtmlInputElement>()
+                    .value_as_number() as f32;
+                Box::new(
+                    move |settings: &mut CommonSettings, browser_storages: &mut BrowserStorages| {
+                        let valid = CommonSettings::MOUSE_SENSITIVITIES;
+                        settings.set_mouse_sensitivity(
+                            value.clamp(*valid.start(), *valid.end()),
+                            browser_storages,
+                        );
+                    },
+                )
+            });
+
+    let ui_scale = ctw.setting_cache.ui_scale;
+    let on_set_ui_scale = ctw
+        .change_common_settings_callback
+        .reform(|event: InputEvent| {
+            let value = event
+                .target_unchecked_into::<HtmlInputElement>()
+                .value_as_number() as f32;
+            Box::new(
+                move |settings: &mut CommonSettings, browser_storages: &mut BrowserStorages| {
+                    settings.set_ui_scale(value, browser_storages);
+                },
+            )
+        });
+
+    let zoom_per_notch = ctw.setting_cache.zoom_per_notch;
+    let on_set_zoom_per_notch = ctw
+        .change_common_settings_callback
+        .reform(|event: InputEvent| {
+            let value = event
+                .target_unchecked_into::<HtmlInputElement>()
+                .value_as_number() as f32;
+            Box::new(
+                move |settings: &mut CommonSettings, browser_storages: &mut BrowserStorages| {
+                    settings.set_zoom_per_notch(value, browser_storages);
+                },
+            )
+        });
+
+    let zoom_speed = gctw.settings_cache.zoom_speed;
+    let on_set_zoom_speed = gctw.change_settings_callback.reform(|event: InputEvent| {
+        let value = event
+            .target_unchecked_into::<HtmlInputElement>()
+            .value_as_number() as f32;
+        Box::new(
+            move |settings: &mut Mk48Settings, browser_storages: &mut BrowserStorages| {
+                settings.set_zoom_speed(value, browser_storages);
+            },
+        )
+    });
+
+    let invert_y = ctw.setting_cache.invert_y;
+    let on_toggle_invert_y = ctw.change_common_settings_callback.reform(move |_| {
+        Box::new(
+            move |settings: &mut CommonSettings, browser_storages: &mut BrowserStorages| {
+                settings.set_invert_y(!invert_y, browser_storages);
+            },
+        )
+    });
+
+    let color_vision = ctw.setting_cache.color_vision;
+    let on_set_color_vision =
+        ctw.change_common_settings_callback
+            .reform(move |event: InputEvent| {
+                let value = event.target_unchecked_into::<HtmlSelectElement>().value();
+                Box::new(
+                    move |settings: &mut CommonSettings, browser_storages: &mut BrowserStorages| {
+                        if let Ok(color_vision) = ColorVision::from_str(&value) {
+                            settings.set_color_vision(color_vision, browser_storages);
+                        }
+                    },
+                )
+            });
+
+    let key_bindings = ctw.setting_cache.key_bindings;
+    let key_binding_inputs = GameAction::ALL.map(|action| {
+        let on_set = ctw
+            .change_common_settings_callback
+            .reform(move |event: KeyboardEvent| {
+                // Tab still moves focus.
+                let key = Key::try_from_key_code(event.key_code()).filter(|&key| key != Key::Tab);
+                if key.is_some() {
+                    event.prevent_default();
+                }
+                Box::new(
+                    move |settings: &mut CommonSettings, browser_storages: &mut BrowserStorages| {
+                        if let Some(key) = key {
+                            settings.set_key_binding(action, key, browser_storages);
+                        }
+                    },
+                )
+            });
+        (action, on_set)
+    });
+
     let antialias = ctw.setting_cache.antialias;
     let on_toggle_antialias = {
         let recreate_renderer_callback = recreate_renderer_callback.clone();
@@ -124,6 +485,36 @@ pub fn settings_dialog() -> Html {
         })
     };
 
+    let high_contrast = ctw.setting_cache.high_contrast;
+    let on_toggle_high_contrast = ctw.change_common_settings_callback.reform(move |_| {
+        Box::new(
+            move |settings: &mut CommonSettings, browser_storages: &mut BrowserStorages| {
+                settings.set_high_contrast(!high_contrast, browser_storages);
+            },
+        )
+    });
+
+    let fps_limit = ctw.setting_cache.fps_limit;
+    let on_set_fps_limit = ctw
+        .change_common_settings_callback
+        .reform(move |event: InputEvent| {
+            let value = event.target_unchecked_into::<HtmlSelectElement>().value();
+            Box::new(
+                move |settings: &mut CommonSettings, browser_storages: &mut BrowserStorages| {
+                    // Anything else (e.g. empty) is uncapped.
+                    settings.set_fps_limit(u8::from_str(&value).ok(), browser_storages);
+                },
+            )
+        });
+
+    let resolution_divisor = ctw.setting_cache.canvas_resolution_divisor();
+    let on_set_resolution_divisor =
+        ctw.set_resolution_divisor_callback
+            .reform(move |event: InputEvent| {
+                let value = event.target_unchecked_into::<HtmlSelectElement>().value();
+                NonZeroU8::from_str(&value).unwrap_or(resolution_divisor)
+            });
+
     let selected_server_id = ctw.setting_cache.server_id;
     let on_select_server_id = {
         ctw.set_server_id_callback.reform(move |event: InputEvent| {
@@ -132,6 +523,28 @@ pub fn settings_dialog() -> Html {
         })
     };
 
+    let on_export_settings = {
+        let exported = export_settings(&ctw.setting_cache, &gctw.settings_cache);
+        Callback::from(move |_: MouseEvent| {
+            if let Some(clipboard) = window().unwrap().navigator().clipboard() {
+                let _ = clipboard.write_text(&exported);
+            }
+        })
+    };
+
+    let on_import_settings = {
+        let ctw = ctw.clone();
+        let gctw = gctw.clone();
+        Callback::from(move |_: MouseEvent| {
+            let window = window().unwrap();
+            if let Ok(Some(json)) = window.prompt_with_message("Paste exported settings:") {
+                if let Err(e) = import_settings(&json, &ctw, &gctw) {
+                    let _ = window.alert_with_message(&format!("Invalid settings: {}", e));
+                }
+            }
+        })
+    };
+
     html! {
             <Dialog title={t.settings_title()}>
                 <h3>{"General"}</h3>
@@ -141,16 +554,185 @@ pub fn settings_dialog() -> Html {
                     {"Show FPS Counter"}
                 </label>
 
+                <label class={label_style.clone()} title="Draw calls, triangles, and GPU time (if supported)">
+                    <input type="checkbox" checked={render_stats_shown} oninput={on_toggle_render_stats}/>
+                    {"Show Render Stats"}
+                </label>
+
                 <label class={label_style.clone()}>
                     <input type="checkbox" checked={chat_dialog_shown} oninput={on_toggle_chat}/>
                     {"Show Radio"}
                 </label>
 
+                <label class={label_style.clone()} title="Hide invitation links, precise coordinates, and team messages with codes">
+                    <input type="checkbox" checked={streamer_mode} oninput={on_toggle_streamer_mode}/>
+                    {"Streamer Mode"}
+                </label>
+
+                <label class={label_style.clone()}>
+                    <input type="checkbox" checked={muted} oninput={on_toggle_muted}/>
+                    {"Mute"}
+                </label>
+
+                <label class={label_style.clone()}>
+                    <input type="range" min="0" max="1" step="0.05" value={sfx_volume.to_string()} oninput={on_set_sfx_volume}/>
+                    {"Sound Effects Volume"}
+                </label>
+
+                <label class={label_style.clone()}>
+                    <input type="range" min="0" max="1" step="0.05" value={music_volume.to_string()} oninput={on_set_music_volume}/>
+                    {"Music Volume"}
+                </label>
+
+                <label class={label_style.clone()}>
+                    <input type="checkbox" checked={mute_on_focus_loss} oninput={on_toggle_mute_on_focus_loss}/>
+                    {"Mute When Unfocused"}
+                </label>
+
                 <label class={label_style.clone()}>
                     <input type="checkbox" checked={cinematic} oninput={on_toggle_cinematic}/>
                     {"Cinematic Mode"}
                 </label>
 
+                <label class={label_style.clone()}>
+                    <input type="checkbox" checked={combat_alert} oninput={on_toggle_combat_alert}/>
+                    {"Combat Alerts"}
+                </label>
+
+                <label class={label_style.clone()}>
+                    <input type="checkbox" checked={leave_confirmation} oninput={on_toggle_leave_confirmation}/>
+                    {"Confirm Before Leaving"}
+                </label>
+
+                <label class={label_style.clone()}>
+                    <input type="checkbox" checked={own_label} oninput={on_toggle_own_label}/>
+                    {"Always Label Own Ship"}
+                </label>
+
+                <label class={label_style.clone()}>
+                    <input type="checkbox" checked={velocity_vectors} oninput={on_toggle_velocity_vectors}/>
+                    {"Show Velocity Vectors"}
+                </label>
+
+                <label class={label_style.clone()}>
+                    <input type="checkbox" checked={auto_fire} oninput={on_toggle_auto_fire}/>
+                    {"Auto-Fire at Nearest Enemy"}
+                </label>
+
+                <label class={label_style.clone()}>
+                    <input type="range" min={CommonSettings::MOUSE_SENSITIVITIES.start().to_string()} max={CommonSettings::MOUSE_SENSITIVITIES.end().to_string()} step="0.05" value={mouse_sensitivity.to_string()} oninput={on_set_mouse_sensitivity}/>
+                    {"Mouse Sensitivity"}
+                </label>
+
+                <label class={label_style.clone()}>
+                    <input type="checkbox" checked={invert_y} oninput={on_toggle_invert_y}/>
+                    {"Invert Mouse Y"}
+                </label>
+
+                <label class={label_style.clone()}>
+                    <input type="range" min="0.1" max="2" step="0.05" value={zoom_per_notch.to_string()} oninput={on_set_zoom_per_notch}/>
+                    {"Zoom Step"}
+                </label>
+
+                <label class={label_style.clone()}>
+                    <input type="range" min="1" max="30" step="1" value={zoom_speed.to_string()} oninput={on_set_zoom_speed}/>
+                    {"Zoom Smoothing Speed"}
+                </label>
+
+                <select
+                    value={contact_label.to_string()}
+                    oninput={on_set_contact_label}
+                    class={select_style.clone()}
+                >
+                    {ContactLabel::ALL.into_iter().map(|contact_label| html_nested!{
+                        <option value={contact_label.to_string()}>{contact_label.as_human_readable_str()}</option>
+                    }).collect::<Html>()}
+                </select>
+
+                <select
+                    value={contact_icons.to_string()}
+                    oninput={on_set_contact_icons}
+                    class={select_style.clone()}
+                >
+                    {ContactIcons::ALL.into_iter().map(|contact_icons| html_nested!{
+                        <option value={contact_icons.to_string()}>{contact_icons.as_human_readable_str()}</option>
+                    }).collect::<Html>()}
+                </select>
+
+                <select
+                    value={color_vision.to_string()}
+                    oninput={on_set_color_vision}
+                    class={select_style.clone()}
+                >
+                    {ColorVision::ALL.into_iter().map(|color_vision| html_nested!{
+                        <option value={color_vision.to_string()}>{color_vision.as_human_readable_str()}</option>
+                    }).collect::<Html>()}
+                </select>
+
+                <select
+                    value={team_colors.to_string()}
+                    oninput={on_set_team_colors}
+                    class={select_style.clone()}
+                >
+                    {TeamColors::ALL.into_iter().map(|team_colors| html_nested!{
+                        <option value={team_colors.to_string()}>{team_colors.as_human_readable_str()}</option>
+                    }).collect::<Html>()}
+                </select>
+
+                <select
+                    value={combat_log_format.to_string()}
+                    oninput={on_set_combat_log_format}
+                    class={select_style.clone()}
+                >
+                    {CombatLogFormat::ALL.into_iter().map(|combat_log_format| html_nested!{
+                        <option value={combat_log_format.to_string()}>{combat_log_format.as_human_readable_str()}</option>
+                    }).collect::<Html>()}
+                </select>
+
+                <select
+                    value={camera_deadzone.to_string()}
+                    oninput={on_set_camera_deadzone}
+                    class={select_style.clone()}
+                >
+                    <option value={"0"}>{"No Camera Deadzone"}</option>
+                    <option value={"0.1"}>{"Small Camera Deadzone"}</option>
+                    <option value={"0.2"}>{"Medium Camera Deadzone"}</option>
+                    <option value={"0.3"}>{"Large Camera Deadzone"}</option>
+                </select>
+
+                <select
+                    value={you_are_here_zoom.to_string()}
+                    oninput={on_set_you_are_here_zoom}
+                    class={select_style.clone()}
+                >
+                    <option value={"1"}>{"No Position Marker"}</option>
+                    <option value={"0.7"}>{"Mark Position When Far Zoomed Out"}</option>
+                    <option value={"0.5"}>{"Mark Position When Zoomed Out"}</option>
+                </select>
+
+                {zoom_presets.into_iter().map(|(preset, zoom_input, on_set)| html_nested!{
+                    <select
+                        value={zoom_input.to_string()}
+                        oninput={on_set}
+                        class={select_style.clone()}
+                    >
+                        {[0.3f32, 0.45, 0.6, 0.8, 1.0].into_iter().map(|option| html_nested!{
+                            <option value={option.to_string()}>{format!("{} Zoom Preset: {}%", preset.as_human_readable_str(), (option * 100.0).round())}</option>
+                        }).collect::<Html>()}
+                    </select>
+                }).collect::<Html>()}
+
+                <select
+                    value={fire_buffer_millis.to_string()}
+                    oninput={on_set_fire_buffer_millis}
+                    class={select_style.clone()}
+                >
+                    <option value={0}>{"No Fire Buffer"}</option>
+                    <option value={50}>{"Short Fire Buffer"}</option>
+                    <option value={100}>{"Normal Fire Buffer"}</option>
+                    <option value={200}>{"Long Fire Buffer"}</option>
+                </select>
+
                 <select
                     value={selected_server_id.map(|s| AttrValue::Owned(s.to_string())).unwrap_or(AttrValue::Static("unknown"))}
                     oninput={on_select_server_id}
@@ -169,6 +751,24 @@ pub fn settings_dialog() -> Html {
                     }).collect::<Html>()}
                 </select>
 
+                <h3>{"Key Bindings"}</h3>
+
+                {key_binding_inputs.into_iter().map(|(action, on_set)| html_nested!{
+                    <label class={label_style.clone()}>
+                        <input
+                            type="text"
+                            readonly={true}
+                            size={10}
+                            value={key_bindings.key(action).to_string()}
+                            onkeydown={on_set}
+                        />
+                        {" "}{action.as_human_readable_str()}
+                        if key_bindings.is_conflicting(action) {
+                            <span style="color: #ff6666;">{" (Conflicts With Another Key)"}</span>
+                        }
+                    </label>
+                }).collect::<Html>()}
+
                 <h3>{"Graphics"}</h3>
 
                 <label class={label_style.clone()}>
@@ -181,6 +781,62 @@ pub fn settings_dialog() -> Html {
                     {"Antialiasing"}
                 </label>
 
+                <label class={label_style.clone()}>
+                    <input type="checkbox" checked={motion_blur} oninput={on_toggle_motion_blur}/>
+                    {"Motion Blur"}
+                </label>
+
+                <label class={label_style.clone()}>
+                    <input type="checkbox" checked={particle_jitter} oninput={on_toggle_particle_jitter}/>
+                    {"Particle Variation"}
+                </label>
+
+                <label class={label_style.clone()}>
+                    <input type="checkbox" checked={reduce_motion} oninput={on_toggle_reduce_motion}/>
+                    {"Reduce Motion"}
+                </label>
+
+                <label class={label_style.clone()}>
+                    <input type="checkbox" checked={high_contrast} oninput={on_toggle_high_contrast}/>
+                    {"High Contrast"}
+                </label>
+
+                <label class={label_style.clone()}>
+                    <input type="range" min="0.75" max="1.5" step="0.05" value={ui_scale.to_string()} oninput={on_set_ui_scale}/>
+                    {format!("UI Scale ({}%)", (ui_scale * 100.0).round())}
+                </label>
+
+                <select
+                    value={fps_limit.map(|fps_limit| fps_limit.to_string()).unwrap_or_default()}
+                    oninput={on_set_fps_limit}
+                    class={select_style.clone()}
+                >
+                    <option value="">{"Uncapped FPS"}</option>
+                    {[30u8, 60, 120].into_iter().map(|fps_limit| html_nested!{
+                        <option value={fps_limit.to_string()}>{format!("{} FPS Limit", fps_limit)}</option>
+                    }).collect::<Html>()}
+                </select>
+
+                <select
+                    value={resolution_divisor.to_string()}
+                    oninput={on_set_resolution_divisor}
+                    class={select_style.clone()}
+                >
+                    {CommonSettings::RESOLUTION_DIVISORS.filter_map(NonZeroU8::new).map(|resolution_divisor| {
+                        let (width, height) = resolution(resolution_divisor);
+                        let fraction = if resolution_divisor.get() == 1 {
+                            String::from("Full")
+                        } else {
+                            format!("1/{}", resolution_divisor)
+                        };
+                        html_nested!{
+                            <option value={resolution_divisor.to_string()}>
+                                {format!("{} Resolution ({}x{})", fraction, width, height)}
+                            </option>
+                        }
+                    }).collect::<Html>()}
+                </select>
+
                 <select
                     value={wave_quality.to_string()}
                     oninput={on_set_wave_quality}
@@ -191,6 +847,11 @@ pub fn settings_dialog() -> Html {
                     <option value={2}>{"Great Waves"}</option>
                     <option value={3}>{"Fantastic Waves"}</option>
                 </select>
+
+                <h3>{"Transfer"}</h3>
+
+                <button onclick={on_export_settings} class={select_style.clone()}>{"Copy Settings"}</button>
+                <button onclick={on_import_settings} class={select_style.clone()}>{"Import Settings"}</button>
     /*
                 <select value={$resolution} on:change={e => resolution.set(parseFloat(e.target.value))}>
                     {#each [1.0, 0.5] as res}
@@ -4,20 +4,53 @@
 use crate::armament::{group_armaments, Group};
 use crate::translation::Mk48Translation;
 use crate::ui::sprite::Sprite;
+use crate::ui::Mk48Route;
 use common::altitude::Altitude;
 use common::entity::{EntityData, EntityKind, EntityType};
 use common::ticks::Ticks;
 use common::velocity::Velocity;
 use core_protocol::id::LanguageId;
+use std::fmt;
+use std::str::FromStr;
 use stylist::yew::styled_component;
 use stylist::StyleSource;
-use yew::{html, html_nested, Html};
+use web_sys::{HtmlInputElement, InputEvent};
+use yew::{html, html_nested, use_state, Callback, Html, Properties, TargetCast};
 use yew_frontend::component::link::Link;
+use yew_frontend::component::route_link::RouteLink;
 use yew_frontend::dialog::dialog::Dialog;
 use yew_frontend::translation::t;
 
+/// [`EntityType`] of a boat as a route parameter, e.g. `/ships/fletcher/`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ShipParam(pub EntityType);
+
+impl fmt::Display for ShipParam {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0.as_str())
+    }
+}
+
+impl FromStr for ShipParam {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        EntityType::from_str(s)
+            .filter(|entity_type| entity_type.data().kind == EntityKind::Boat)
+            .map(Self)
+            .ok_or(())
+    }
+}
+
+#[derive(PartialEq, Properties)]
+pub struct ShipsDialogProps {
+    /// Ship to show the details of, instead of listing all ships.
+    #[prop_or_default]
+    pub selected: Option<EntityType>,
+}
+
 #[styled_component(ShipsDialog)]
-pub fn ships_dialog() -> Html {
+pub fn ships_dialog(props: &ShipsDialogProps) -> Html {
     let t = t();
     let table_style = css!(
         r#"
@@ -27,23 +60,59 @@ pub fn ships_dialog() -> Html {
 		"#
     );
 
+    let search_style = css!(
+        r#"
+        border-radius: 0.25em;
+        box-sizing: border-box;
+        font-size: 1em;
+        padding: 0.5em;
+        width: 100%;
+        "#
+    );
+
+    let query = use_state(String::new);
+    let oninput = {
+        let query = query.clone();
+        Callback::from(move |event: InputEvent| {
+            query.set(event.target_unchecked_into::<HtmlInputElement>().value());
+        })
+    };
+
     html! {
         <Dialog title={"Ships"}>
-            <p>{"The following is a list of all ships in the game, and their weapons. Note that certain values are approximate and may be affected by other factors. For example, weapon damage depends on hit location."}</p>
-
-            <table>
-                {EntityType::iter().filter(|t| t.data().kind == EntityKind::Boat).map(|entity_type| html_nested!{
-                    <tr>
-                        <td>
-                            {entity_card(t, &table_style, entity_type, None)}
-                        </td>
-                    </tr>
-                }).collect::<Html>()}
-            </table>
+            if let Some(entity_type) = props.selected {
+                <RouteLink<Mk48Route> route={Mk48Route::Ships}>{"All ships"}</RouteLink<Mk48Route>>
+                {entity_card(t, &table_style, entity_type, None)}
+            } else {
+                <p>{"The following is a list of all ships in the game, and their weapons. Note that certain values are approximate and may be affected by other factors. For example, weapon damage depends on hit location."}</p>
+
+                <input type="text" placeholder={"Search ships"} autocomplete="off" value={(*query).clone()} class={search_style} {oninput}/>
+
+                <table>
+                    {EntityType::iter().filter(|t| t.data().kind == EntityKind::Boat && fuzzy_match(&query, &t.data().label)).map(|entity_type| html_nested!{
+                        <tr>
+                            <td>
+                                {entity_card(t, &table_style, entity_type, None)}
+                                <RouteLink<Mk48Route> route={Mk48Route::Ship{ship: ShipParam(entity_type)}}>{"Details"}</RouteLink<Mk48Route>>
+                            </td>
+                        </tr>
+                    }).collect::<Html>()}
+                </table>
+            }
         </Dialog>
     }
 }
 
+/// Whether all characters of `query` appear in `label`, in order, ignoring case and whitespace.
+fn fuzzy_match(query: &str, label: &str) -> bool {
+    let mut label = label.chars().flat_map(char::to_lowercase);
+    query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .all(|q| label.any(|c| c == q))
+}
+
 fn entity_card(
     t: LanguageId,
     table_style: &StyleSource,
@@ -127,3 +196,50 @@ fn entity_card(
         </table>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::ui::ships_dialog::{fuzzy_match, ShipParam};
+    use crate::ui::Mk48Route;
+    use common::entity::EntityType;
+    use std::str::FromStr;
+    use yew_router::Routable;
+
+    #[test]
+    fn fuzzy() {
+        assert!(fuzzy_match("", "Fletcher"));
+        assert!(fuzzy_match("fltch", "Fletcher"));
+        assert!(fuzzy_match("FLE TCHER", "Fletcher"));
+        assert!(!fuzzy_match("rehctelf", "Fletcher"));
+        assert!(!fuzzy_match("fletchers", "Fletcher"));
+    }
+
+    #[test]
+    fn ship_route() {
+        let ship = ShipParam(EntityType::Fletcher);
+        assert_eq!(
+            Mk48Route::recognize("/ships/fletcher/"),
+            Some(Mk48Route::Ship { ship })
+        );
+        assert_eq!(Mk48Route::Ship { ship }.to_path(), "/ships/fletcher/");
+        assert_eq!(Mk48Route::recognize("/ships/"), Some(Mk48Route::Ships));
+        // Unknown ship types fall through.
+        assert_eq!(Mk48Route::recognize("/ships/bogus/"), Some(Mk48Route::Home));
+        assert_eq!(
+            Mk48Route::recognize("/ships/mark18/"),
+            Some(Mk48Route::Home)
+        );
+    }
+
+    #[test]
+    fn ship_lookup() {
+        assert_eq!(
+            ShipParam::from_str("fletcher"),
+            Ok(ShipParam(EntityType::Fletcher))
+        );
+        assert_eq!(ShipParam::from_str("bogus"), Err(()));
+        // Not boats.
+        assert_eq!(ShipParam::from_str("mark18"), Err(()));
+        assert_eq!(ShipParam::from_str("oilPlatform"), Err(()));
+    }
+}
@@ -0,0 +1,65 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::ui::{UiEvent, UiStatusSpectating};
+use crate::Mk48Game;
+use stylist::yew::styled_component;
+use web_sys::MouseEvent;
+use yew::{classes, html, Html, Properties};
+use yew_frontend::frontend::{Ctw, Gctw};
+
+#[derive(Properties, PartialEq)]
+pub struct SpectateOverlayProps {
+    pub status: UiStatusSpectating,
+}
+
+/// Shows who is being watched while spectating, instead of the spawn menu.
+#[styled_component(SpectateOverlay)]
+pub fn spectate_overlay(props: &SpectateOverlayProps) -> Html {
+    let container_style = css!(
+        r#"
+        background-color: #00000080;
+        border-radius: 0.5em;
+        color: white;
+        padding: 1em;
+        pointer-events: all;
+        text-align: center;
+        user-select: none;
+    "#
+    );
+
+    let button_style = css!(
+        r#"
+        background-color: #0075ff;
+        border: 0;
+        border-radius: 0.25em;
+        color: white;
+        cursor: pointer;
+        display: inline-block;
+        font-size: 1em;
+        font-weight: bold;
+        margin: 0.5em 0.25em 0 0.25em;
+        padding: 0.5em 0.7em;
+        text-decoration: none;
+    "#
+    );
+
+    let gctw = Gctw::<Mk48Game>::use_gctw();
+    let overlay_class = Ctw::use_theme().overlay_class();
+    let on_next = gctw
+        .send_ui_event_callback
+        .reform(|_: MouseEvent| UiEvent::SpectateNext);
+
+    html! {
+        <div class={classes!(container_style, overlay_class)}>
+            if let Some(alias) = props.status.alias {
+                {format!("Spectating {}", alias.as_str())}
+            } else {
+                {"Waiting for players..."}
+            }
+            <br/>
+            <button class={button_style.clone()} onclick={on_next}>{"Next Player"}</button>
+            <a class={button_style} href="/">{"Play"}</a>
+        </div>
+    }
+}
@@ -2,17 +2,24 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
 use crate::ui::UiStatusPlaying;
+use client_util::streamer_mode::StreamerMode;
 use common::entity::EntityData;
+use common::terrain::Terrain;
 use common::util::level_to_score;
 use glam::Vec2;
+use renderer::RenderStats;
 use yew::{function_component, html, Properties};
 use yew_frontend::component::meter::Meter;
+use yew_frontend::frontend::Ctw;
 use yew_frontend::translation::{t, Translation};
 
 #[derive(Properties, PartialEq)]
 pub struct StatusProps {
     pub score: u32,
     pub fps: Option<f32>,
+    pub render_stats: Option<RenderStats>,
+    /// Whether auto-fire is active.
+    pub auto_fire: bool,
     pub status: UiStatusPlaying,
 }
 
@@ -20,6 +27,7 @@ pub struct StatusProps {
 pub fn status_overlay(props: &StatusProps) -> Html {
     let t = t();
     let status = &props.status;
+    let streamer_mode = Ctw::use_ctw().setting_cache.streamer();
     let level = status.entity_type.data().level;
     let next_level = level + 1;
     let level_score = level_to_score(level);
@@ -39,11 +47,19 @@ pub fn status_overlay(props: &StatusProps) -> Html {
                 {" — "}
                 {format!("{}° [{}]", status.direction.to_bearing(), status.direction.to_cardinal())}
                 {" — "}
-                {fmt_position(status.position)}
+                {fmt_position(status.position, streamer_mode)}
                 if let Some(fps) = props.fps {
                     {" — "}
                     {format!("{:.1} fps", fps)}
                 }
+                if let Some(render_stats) = props.render_stats {
+                    {" — "}
+                    {fmt_render_stats(render_stats)}
+                }
+                if props.auto_fire {
+                    {" — "}
+                    <span style="color: #f1c40f;">{"Auto-Fire"}</span>
+                }
             </h2>
             if next_level <= EntityData::MAX_BOAT_LEVEL {
                 <Meter value={progress}>{t.upgrade_to_level_progress((progress * 100.0) as u8, next_level as u32)}</Meter>
@@ -52,7 +68,22 @@ pub fn status_overlay(props: &StatusProps) -> Html {
     }
 }
 
-fn fmt_position(position: Vec2) -> String {
+/// Formats `stats` compactly, omitting GPU time if unsupported.
+fn fmt_render_stats(stats: RenderStats) -> String {
+    let mut ret = format!("{} draws, {} tris", stats.draw_calls, stats.triangles);
+    if let Some(gpu_millis) = stats.gpu_millis {
+        ret += &format!(", {:.1}ms GPU", gpu_millis);
+    }
+    ret
+}
+
+/// Formats `position` as coordinates, or as a grid cell if `streamer_mode` hides them.
+fn fmt_position(position: Vec2, streamer_mode: StreamerMode) -> String {
+    if streamer_mode.hides_coordinates() {
+        // Use the max radius so cells don't move as the world resizes.
+        return StreamerMode::grid_cell(position, Terrain::max_world_radius());
+    }
+
     fn fmt_coordinate(coordinate: f32, positive: char, negative: char) -> String {
         format!(
             "{}{}",
@@ -70,3 +101,38 @@ fn fmt_position(position: Vec2) -> String {
         fmt_coordinate(position.y, 'N', 'S')
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::ui::status_overlay::{fmt_position, fmt_render_stats};
+    use client_util::streamer_mode::StreamerMode;
+    use glam::Vec2;
+    use renderer::RenderStats;
+
+    #[test]
+    fn streamer_mode_hides_coordinates() {
+        let position = Vec2::new(1234.0, -567.0);
+        assert_eq!(
+            fmt_position(position, StreamerMode::new(false)),
+            "(1234E, 567S)"
+        );
+        let hidden = fmt_position(position, StreamerMode::new(true));
+        assert!(
+            !hidden.contains("1234") && !hidden.contains("567"),
+            "{}",
+            hidden
+        );
+    }
+
+    #[test]
+    fn render_stats() {
+        let mut stats = RenderStats {
+            draw_calls: 42,
+            triangles: 12345,
+            gpu_millis: None,
+        };
+        assert_eq!(fmt_render_stats(stats), "42 draws, 12345 tris");
+        stats.gpu_millis = Some(3.14);
+        assert_eq!(fmt_render_stats(stats), "42 draws, 12345 tris, 3.1ms GPU");
+    }
+}
@@ -0,0 +1,71 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use common::transform::Transform;
+use glam::{Vec2, Vec4};
+use renderer2d::GraphicLayer;
+
+/// Velocity vectors show where contacts will be after this many seconds.
+pub const VELOCITY_VECTOR_SECONDS: f32 = 5.0;
+
+/// Gets the position `transform` would reach after `seconds`, at its current velocity and
+/// direction (reversing goes backwards).
+pub fn velocity_vector_end(transform: &Transform, seconds: f32) -> Vec2 {
+    transform.position + transform.direction.to_vec() * (transform.velocity.to_mps() * seconds)
+}
+
+/// Draws a line from `transform`'s position in the direction it is moving, scaled by speed.
+pub fn draw_velocity_vector(
+    layer: &mut GraphicLayer,
+    transform: &Transform,
+    thickness: f32,
+    color: Vec4,
+) {
+    if transform.velocity.to_mps() == 0.0 {
+        return;
+    }
+    let end = velocity_vector_end(transform, VELOCITY_VECTOR_SECONDS);
+    layer.draw_line(transform.position, end, thickness, color);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::velocity_vector::velocity_vector_end;
+    use common::angle::Angle;
+    use common::transform::Transform;
+    use common::velocity::Velocity;
+    use glam::Vec2;
+
+    fn assert_near(a: Vec2, b: Vec2) {
+        assert!(a.distance(b) < 0.1, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn velocity_vector_endpoint() {
+        let position = Vec2::new(100.0, -50.0);
+        let forward = Transform {
+            position,
+            direction: Angle::ZERO,
+            velocity: Velocity::from_mps(10.0),
+        };
+        assert_near(
+            velocity_vector_end(&forward, 5.0),
+            position + Vec2::new(50.0, 0.0),
+        );
+
+        // Reversing points backwards.
+        let reverse = Transform {
+            velocity: Velocity::from_mps(-4.0),
+            direction: Angle::from_radians(std::f32::consts::PI * 0.5),
+            ..forward
+        };
+        assert_near(
+            velocity_vector_end(&reverse, 2.0),
+            position + Vec2::new(0.0, -8.0),
+        );
+
+        // Stationary.
+        let stopped = Transform::from_position(position);
+        assert_eq!(velocity_vector_end(&stopped, 5.0), position);
+    }
+}
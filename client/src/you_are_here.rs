@@ -0,0 +1,59 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use glam::Vec2;
+use renderer::rgba;
+use renderer2d::GraphicLayer;
+use std::f32::consts::PI;
+
+/// Marks the player's own boat, so it can be located while zoomed far out.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct YouAreHere {
+    /// Position of the player's boat.
+    pub position: Vec2,
+    /// Size of the marker, such that it is a constant size on screen.
+    pub size: f32,
+}
+
+impl YouAreHere {
+    /// Gets the marker to draw at `position`, if `zoom_input` (the fraction of the boat's visual
+    /// range that is shown) exceeds `threshold`. `zoom` is that of the camera.
+    pub fn new(position: Vec2, zoom_input: f32, threshold: f32, zoom: f32) -> Option<Self> {
+        (zoom_input > threshold).then_some(Self {
+            position,
+            size: 0.02 * zoom,
+        })
+    }
+
+    /// Draws a ring around the boat, and an arrow above it, pointing down.
+    pub fn draw(&self, layer: &mut GraphicLayer) {
+        let color = rgba(255, 255, 255, 120);
+        layer.draw_circle(self.position, self.size, self.size * 0.1, color);
+        layer.draw_triangle(
+            self.position + Vec2::new(0.0, self.size * 2.0),
+            Vec2::splat(self.size),
+            PI,
+            color,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::you_are_here::YouAreHere;
+    use glam::Vec2;
+
+    #[test]
+    fn shown_beyond_threshold() {
+        let position = Vec2::new(100.0, -50.0);
+        assert_eq!(YouAreHere::new(position, 0.6, 0.7, 500.0), None);
+        assert_eq!(YouAreHere::new(position, 0.7, 0.7, 500.0), None);
+
+        let marker = YouAreHere::new(position, 1.0, 0.7, 500.0).unwrap();
+        assert_eq!(marker.position, position);
+        assert!(marker.size > 0.0);
+
+        // Disabled.
+        assert_eq!(YouAreHere::new(position, 1.0, 1.0, 500.0), None);
+    }
+}
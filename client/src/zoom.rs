@@ -2,8 +2,11 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
 use crate::game::Mk48Game;
+use crate::settings::{Mk48Settings, ZoomPreset};
+use client_util::keyboard::Key;
 use common::contact::{Contact, ContactTrait};
 use glam::Vec2;
+use renderer2d::Camera2d;
 
 impl Mk48Game {
     const MIN_ZOOM: f32 = 0.216; // 0.6×sqrt(1÷.6)^−4 aka 4 full steps to min zoom
@@ -11,37 +14,57 @@ impl Mk48Game {
     pub const DEFAULT_ZOOM_INPUT: f32 = 0.6; // not changed
     const ZOOM_SPEED: f32 = 1.2909944; // sqrt(1÷.6) aka 2 full steps to max zoom
     pub const MENU_VISUAL_RANGE: f32 = 300.0;
+    /// Zoom input to use while the overview key is held (as zoomed out as possible).
+    pub const OVERVIEW_ZOOM_INPUT: f32 = Self::MAX_ZOOM;
 
-    /// Gets the proper camera to display the game.
+    /// Gets the proper camera to display the game. If `deadzone` (a fraction of the viewport) is
+    /// nonzero, the camera only moves once the player's ship leaves a box of that size around the
+    /// center of the screen. The camera never strays so far from the world, even if
+    /// `world_radius` shrinks, that none of it is visible (see [`clamp_to_world`]).
     pub(crate) fn camera(
         &self,
         player_contact: Option<&Contact>,
         aspect_ratio: f32,
+        deadzone: f32,
+        world_radius: f32,
     ) -> (Vec2, f32) {
+        let effective_zoom = if aspect_ratio > 1.0 {
+            self.interpolated_zoom * aspect_ratio
+        } else {
+            self.interpolated_zoom
+        };
+
         let camera = if let Some(player_contact) = player_contact {
-            player_contact.transform().position
+            let position = player_contact.transform().position;
+            match self.camera_center {
+                Some((entity_id, center)) if entity_id == player_contact.id() => {
+                    let half_size = Vec2::new(effective_zoom, effective_zoom / aspect_ratio);
+                    Camera2d::deadzone_follow(center, position, half_size * deadzone)
+                }
+                _ => position,
+            }
+        } else if let Some((_, center)) = self.camera_center {
+            center
         } else {
             self.saved_camera
                 .map(|camera| camera.0)
                 .unwrap_or(Vec2::ZERO)
         };
 
-        let effective_zoom = if aspect_ratio > 1.0 {
-            self.interpolated_zoom * aspect_ratio
-        } else {
-            self.interpolated_zoom
-        };
-
-        (camera, effective_zoom)
+        // Half the shorter side of the viewport.
+        let margin = effective_zoom / aspect_ratio.max(1.0);
+        (clamp_to_world(camera, world_radius, margin), effective_zoom)
     }
 
-    /// Interpolates the zoom level closer as if delta_seconds elapsed.
+    /// Interpolates the zoom level closer as if delta_seconds elapsed, at a rate of `zoom_speed`
+    /// (see [`Mk48Settings::zoom_speed`]).
     /// If the player's ship exists, it's camera info is cached, such that it may be returned
     /// even after that ship sinks.
     pub fn update_camera(
         &mut self,
         player_contact: Option<&Contact>,
         delta_seconds: f32,
+        zoom_speed: f32,
         snap: bool,
     ) {
         let zoom = if let Some(player_contact) = player_contact {
@@ -53,13 +76,12 @@ impl Mk48Game {
             saved_camera.1
         } else {
             Self::MENU_VISUAL_RANGE
-        } * self.truncated_zoom_input();
+        } * self.overview_zoom.zoom_input(self.truncated_zoom_input());
 
         if snap {
             self.interpolated_zoom = zoom;
         } else {
-            self.interpolated_zoom +=
-                (zoom - self.interpolated_zoom) * (6.0 * delta_seconds).min(1.0);
+            self.interpolated_zoom = ease(self.interpolated_zoom, zoom, zoom_speed, delta_seconds);
         }
     }
 
@@ -69,9 +91,239 @@ impl Mk48Game {
         self.zoom_input = next_zoom_input.clamp(Self::MIN_ZOOM, Self::MAX_ZOOM);
     }
 
+    /// Gets the zoom input that `key` snaps to, if it is one of the zoom preset keys (see
+    /// [`ZoomPreset::from_key`]). Setting it replaces the player's zoom input, so the camera eases
+    /// towards it, until the player zooms manually again.
+    pub(crate) fn preset_zoom_input(settings: &Mk48Settings, key: Key) -> Option<f32> {
+        ZoomPreset::from_key(key).map(|preset| {
+            settings
+                .zoom_preset(preset)
+                .clamp(Self::MIN_ZOOM, Self::MAX_ZOOM)
+        })
+    }
+
     // Get exact zoom if you don't use pinch to zoom.
     fn truncated_zoom_input(&self) -> f32 {
         const P: f64 = 65536.0;
         ((self.zoom_input as f64 * P).floor() * (1.0 / P)) as f32
     }
 }
+
+/// Temporarily zooms out while a key is held, restoring the previous zoom on release.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct OverviewZoom {
+    held: bool,
+}
+
+impl OverviewZoom {
+    /// Call every frame with whether the overview key is held.
+    pub fn set_held(&mut self, held: bool) {
+        self.held = held;
+    }
+
+    /// Gets the zoom input to use instead of the player's chosen `zoom_input`.
+    pub fn zoom_input(&self, zoom_input: f32) -> f32 {
+        if self.held {
+            // Never zoom in, in case the player is already zoomed out.
+            zoom_input.max(Mk48Game::OVERVIEW_ZOOM_INPUT)
+        } else {
+            zoom_input
+        }
+    }
+}
+
+/// Eases the camera back onto the player's boat on request, clearing any offset (e.g. from the
+/// deadzone).
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct CameraCentering {
+    active: bool,
+}
+
+impl CameraCentering {
+    /// How quickly the camera closes the gap (see [`ease`]).
+    const RATE: f32 = 15.0;
+    /// Distance, in meters, within which the camera is considered centered.
+    const EPSILON: f32 = 0.5;
+
+    /// Starts centering, e.g. when the center camera key is pressed.
+    pub fn start(&mut self) {
+        self.active = true;
+    }
+
+    /// Gets the camera `center` eased towards the boat's `position`, as if `delta_seconds`
+    /// elapsed. Once centered, stops and leaves the camera be.
+    pub fn update(&mut self, center: Vec2, position: Vec2, delta_seconds: f32) -> Vec2 {
+        if !self.active {
+            return center;
+        }
+        let eased = Vec2::new(
+            ease(center.x, position.x, Self::RATE, delta_seconds),
+            ease(center.y, position.y, Self::RATE, delta_seconds),
+        );
+        if eased.distance(position) < Self::EPSILON {
+            self.active = false;
+            position
+        } else {
+            eased
+        }
+    }
+}
+
+/// Moves `current` closer to `target`, as if `delta_seconds` elapsed, closing the gap at an
+/// exponential `rate`. Unlike a fixed fraction per frame, the result doesn't depend on how time is
+/// split into frames.
+pub(crate) fn ease(current: f32, target: f32, rate: f32, delta_seconds: f32) -> f32 {
+    target + (current - target) * (-rate * delta_seconds).exp()
+}
+
+/// Moves `camera` the least amount to keep it no more than `margin` outside the world, which is a
+/// circle of `world_radius` that the server may change at any time. With a `margin` of half the
+/// viewport, the world stays on screen, yet a boat near (or just beyond) the border stays centered.
+pub(crate) fn clamp_to_world(camera: Vec2, world_radius: f32, margin: f32) -> Vec2 {
+    camera.clamp_length_max((world_radius + margin).max(0.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::Mk48Game;
+    use crate::settings::Mk48Settings;
+    use crate::zoom::{clamp_to_world, ease, CameraCentering, OverviewZoom};
+    use client_util::keyboard::Key;
+    use glam::Vec2;
+    use renderer2d::Camera2d;
+
+    #[test]
+    fn overview_hold_release() {
+        let mut overview = OverviewZoom::default();
+        let zoom_input = Mk48Game::DEFAULT_ZOOM_INPUT;
+        assert_eq!(overview.zoom_input(zoom_input), zoom_input);
+
+        // Hold.
+        overview.set_held(true);
+        assert_eq!(
+            overview.zoom_input(zoom_input),
+            Mk48Game::OVERVIEW_ZOOM_INPUT
+        );
+
+        // Still held, after player zoomed in.
+        assert_eq!(
+            overview.zoom_input(zoom_input * 0.5),
+            Mk48Game::OVERVIEW_ZOOM_INPUT
+        );
+
+        // Release restores previous zoom.
+        overview.set_held(false);
+        assert_eq!(overview.zoom_input(zoom_input * 0.5), zoom_input * 0.5);
+    }
+
+    #[test]
+    fn zoom_preset_keys() {
+        let settings = Mk48Settings {
+            zoom_preset_close: 0.3,
+            zoom_preset_medium: 0.5,
+            zoom_preset_far: 0.9,
+            ..Mk48Settings::default()
+        };
+        let preset = |key| Mk48Game::preset_zoom_input(&settings, key);
+        assert_eq!(preset(Key::J), Some(0.3));
+        assert_eq!(preset(Key::K), Some(0.5));
+        assert_eq!(preset(Key::L), Some(0.9));
+        assert_eq!(preset(Key::U), None);
+
+        // Within limits.
+        let settings = Mk48Settings {
+            zoom_preset_close: 0.0,
+            zoom_preset_far: 5.0,
+            ..settings
+        };
+        let preset = |key| Mk48Game::preset_zoom_input(&settings, key);
+        assert_eq!(preset(Key::J), Some(Mk48Game::MIN_ZOOM));
+        assert_eq!(preset(Key::L), Some(Mk48Game::MAX_ZOOM));
+    }
+
+    #[test]
+    fn ease_any_framerate() {
+        let ease_frames = |delta_seconds: &[f32]| {
+            delta_seconds.iter().fold(100.0, |zoom, &delta_seconds| {
+                ease(zoom, 300.0, 6.0, delta_seconds)
+            })
+        };
+
+        let smooth = ease_frames(&[1.0 / 60.0; 60]);
+        for frames in [
+            &[1.0 / 144.0; 144][..],
+            &[0.5, 0.25, 0.125, 0.125],
+            &[0.9, 0.0, 0.1],
+            &[1.0],
+        ] {
+            let zoom = ease_frames(frames);
+            assert!((zoom - smooth).abs() < 0.01, "{} {}", zoom, smooth);
+        }
+
+        // Never overshoots, even after a long pause.
+        assert!(ease_frames(&[0.1]) < 300.0);
+        assert!((ease_frames(&[100.0]) - 300.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn world_radius_clamps_camera() {
+        let camera = Vec2::new(600.0, -800.0);
+        assert_eq!(clamp_to_world(camera, 2000.0, 0.0), camera);
+        assert_eq!(clamp_to_world(camera, 1000.0, 0.0), camera);
+
+        // World shrinks.
+        let clamped = clamp_to_world(camera, 500.0, 0.0);
+        assert!(
+            clamped.abs_diff_eq(Vec2::new(300.0, -400.0), 0.001),
+            "{}",
+            clamped
+        );
+
+        // World grows back (camera isn't moved).
+        assert_eq!(clamp_to_world(clamped, 1000.0, 0.0), clamped);
+        assert_eq!(clamp_to_world(camera, 0.0, 0.0), Vec2::ZERO);
+    }
+
+    #[test]
+    fn world_border_keeps_boat_centered() {
+        // Boat on, or slightly beyond, the border stays centered.
+        let margin = 300.0;
+        for boat in [Vec2::new(1000.0, 0.0), Vec2::new(0.0, -1200.0)] {
+            assert_eq!(clamp_to_world(boat, 1000.0, margin), boat);
+        }
+
+        // Too far out to see the world, so the camera stops at the margin.
+        let clamped = clamp_to_world(Vec2::new(2000.0, 0.0), 1000.0, margin);
+        assert!(
+            clamped.abs_diff_eq(Vec2::new(1300.0, 0.0), 0.001),
+            "{}",
+            clamped
+        );
+    }
+
+    #[test]
+    fn center_camera() {
+        let position = Vec2::new(100.0, 50.0);
+        let offset = position + Vec2::new(30.0, -20.0);
+
+        // Does nothing unless started.
+        let mut centering = CameraCentering::default();
+        assert_eq!(centering.update(offset, position, 0.1), offset);
+
+        // Eases towards the boat.
+        centering.start();
+        let eased = centering.update(offset, position, 0.05);
+        assert!(eased.distance(position) < offset.distance(position));
+        assert!(eased.distance(position) > 0.0);
+
+        // Ends up exactly on the boat, with the offset cleared, then stops.
+        let mut center = eased;
+        for _ in 0..60 {
+            center = centering.update(center, position, 1.0 / 60.0);
+        }
+        assert_eq!(center, position);
+        assert_eq!(centering, CameraCentering::default());
+        let drifted = position + Vec2::X;
+        assert_eq!(centering.update(drifted, position, 0.1), drifted);
+    }
+}
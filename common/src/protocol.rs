@@ -6,6 +6,7 @@ use crate::death_reason::DeathReason;
 use crate::entity::*;
 use crate::guidance::Guidance;
 use crate::terrain::{ChunkId, SerializedChunk};
+use core_protocol::id::PlayerId;
 use glam::Vec2;
 use serde::{Deserialize, Serialize};
 
@@ -20,6 +21,8 @@ pub struct Update {
     pub death_reason: Option<DeathReason>,
     /// Player's current score.
     pub score: u32,
+    /// Number of boats the player sank since spawning.
+    pub kills: u32,
     /// Current world border radius.
     pub world_radius: f32,
     pub terrain: Box<TerrainUpdate>,
@@ -35,6 +38,7 @@ pub type TerrainUpdate = [(ChunkId, SerializedChunk)];
 pub enum Command {
     Control(Control),
     Spawn(Spawn),
+    Spectate(Spectate),
     Upgrade(Upgrade),
 }
 
@@ -89,6 +93,13 @@ pub struct Spawn {
     pub entity_type: EntityType,
 }
 
+/// Watch another player's boat while not alive, without spawning.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct Spectate {
+    /// Who to follow, or `None` to stop spectating.
+    pub player_id: Option<PlayerId>,
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct Upgrade {
     /// What to upgrade to. Must be an affordable boat of higher level.
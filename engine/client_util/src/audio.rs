@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
 use crate::visibility::VisibilityEvent;
+use glam::Vec2;
 use js_sys::ArrayBuffer;
 use sprite_sheet::AudioSprite;
 use std::cell::RefCell;
@@ -13,6 +14,7 @@ use wasm_bindgen::{JsCast, JsValue};
 use wasm_bindgen_futures::{future_to_promise, JsFuture};
 use web_sys::{
     AudioBuffer, AudioBufferSourceNode, AudioContext, AudioContextState, Event, GainNode, Response,
+    StereoPannerNode,
 };
 
 /// A macro-generated enum representing all audio sprites.
@@ -36,20 +38,21 @@ pub struct AudioPlayer<A: Audio> {
 struct Inner<A: Audio> {
     context: AudioContext,
     sfx_gain: GainNode,
-    _music_gain: GainNode,
+    music_gain: GainNode,
     track: Option<AudioBuffer>,
     /// Audio indexed by [`Audio::index`].
     playing: Box<[Vec<AudioBufferSourceNode>]>,
-    /// What volume is or is ramping up/down to.
-    volume_target: f32,
-    /// The game wants to mute all audio.
-    muted_by_game: bool,
-    /// Whether muted because the page is unfocused.
-    muted_by_visibility: bool,
-    /// Whether muted due to conflicting with an advertisement's audio.
-    muted_by_ad: bool,
-    /// Volume (kept up to date with the corresponding setting.
+    /// What sound effect and music volumes are or are ramping up/down to.
+    volume_targets: (f32, f32),
+    /// Why audio is muted, if at all.
+    muted: Muted,
+    /// Volume (kept up to date with the corresponding setting).
     volume_setting: f32,
+    /// Sound effect volume, relative to `volume_setting` (kept up to date with the corresponding
+    /// setting).
+    sfx_volume_setting: f32,
+    /// Music volume, relative to `volume_setting` (kept up to date with the corresponding setting).
+    music_volume_setting: f32,
     spooky: PhantomData<A>,
 }
 
@@ -66,14 +69,14 @@ impl<A: Audio> Default for AudioPlayer<A> {
                 let inner = Rc::new(RefCell::new(Some(Inner {
                     context,
                     sfx_gain,
-                    _music_gain: music_gain,
+                    music_gain,
                     track: None,
                     playing: vec![Vec::new(); std::mem::variant_count::<A>()].into_boxed_slice(),
-                    muted_by_game: false,
-                    muted_by_visibility: false,
-                    muted_by_ad: false,
-                    volume_target: 0.0,
+                    muted: Muted::default(),
+                    volume_targets: (0.0, 0.0),
                     volume_setting: 0.0,
+                    sfx_volume_setting: 1.0,
+                    music_volume_setting: 1.0,
                     spooky: PhantomData,
                 })));
 
@@ -130,17 +133,36 @@ impl<A: Audio> AudioPlayer<A> {
 
     /// Plays a particular sound once, with a specified volume.
     pub fn play_with_volume(&self, audio: A, volume: f32) {
-        Inner::play(&self.inner, audio, volume, false);
+        Inner::play(&self.inner, audio, volume, 0.0, false, false);
     }
 
     /// Plays a particular sound once, with a specified volume and delay in seconds.
     pub fn play_with_volume_and_delay(&self, audio: A, volume: f32, _delay: f32) {
-        Inner::play(&self.inner, audio, volume, false);
+        Inner::play(&self.inner, audio, volume, 0.0, false, false);
     }
 
     /// Plays a particular sound in a loop.
     pub fn play_looping(&self, audio: A) {
-        Inner::play(&self.inner, audio, 1.0, true);
+        Inner::play(&self.inner, audio, 1.0, 0.0, true, false);
+    }
+
+    /// Plays a particular sound once, with a specified volume, as if it came from `position` in
+    /// the world. It is panned and attenuated relative to the `listener`, or skipped entirely if too
+    /// far away to matter.
+    pub fn play_at(&self, audio: A, volume: f32, position: Vec2, listener: AudioListener) {
+        if let Some((pan, attenuation)) = listener.pan_and_attenuation(position) {
+            Inner::play(&self.inner, audio, volume * attenuation, pan, false, false);
+        }
+    }
+
+    /// Plays a particular sound once, at the music volume instead of the sound effect volume.
+    pub fn play_music(&self, audio: A) {
+        Inner::play(&self.inner, audio, 1.0, 0.0, false, true);
+    }
+
+    /// Plays a particular sound in a loop, at the music volume instead of the sound effect volume.
+    pub fn play_music_looping(&self, audio: A) {
+        Inner::play(&self.inner, audio, 1.0, 0.0, true, true);
     }
 
     pub fn is_playing(&self, audio: A) -> bool {
@@ -157,10 +179,18 @@ impl<A: Audio> AudioPlayer<A> {
         }
     }
 
-    // Sets a multiplier for the volume of all sounds.
-    pub(crate) fn set_volume_setting(&self, volume_setting: f32) {
+    /// Sets a multiplier for the volume of all sounds, and additional multipliers for sound
+    /// effects and music.
+    pub(crate) fn set_volume_settings(
+        &self,
+        volume_setting: f32,
+        sfx_volume_setting: f32,
+        music_volume_setting: f32,
+    ) {
         if let Some(inner) = self.inner.borrow_mut().as_mut() {
             inner.volume_setting = volume_setting;
+            inner.sfx_volume_setting = sfx_volume_setting;
+            inner.music_volume_setting = music_volume_setting;
             inner.update_volume();
         }
     }
@@ -168,56 +198,162 @@ impl<A: Audio> AudioPlayer<A> {
     /// For the game to mute/unmute all audio.
     pub fn set_muted_by_game(&self, muted_by_game: bool) {
         if let Some(inner) = self.inner.borrow_mut().as_mut() {
-            inner.muted_by_game = muted_by_game;
+            inner.muted.by_game = muted_by_game;
             inner.update_volume();
         }
     }
 
     pub(crate) fn peek_visibility(&self, event: &VisibilityEvent) {
         if let Some(inner) = self.inner.borrow_mut().as_mut() {
-            inner.muted_by_visibility = match event {
+            inner.muted.by_visibility = match event {
                 VisibilityEvent::Visible(visible) => !visible,
             };
             inner.update_volume();
         }
     }
 
+    /// Mutes/unmutes all audio when the window loses/regains focus, independently of visibility.
+    pub(crate) fn set_muted_by_focus(&self, muted_by_focus: bool) {
+        if let Some(inner) = self.inner.borrow_mut().as_mut() {
+            inner.muted.by_focus = muted_by_focus;
+            inner.update_volume();
+        }
+    }
+
     pub fn set_muted_by_ad(&self, muted_by_ad: bool) {
         if let Some(inner) = self.inner.borrow_mut().as_mut() {
-            inner.muted_by_ad = muted_by_ad;
+            inner.muted.by_ad = muted_by_ad;
+            inner.update_volume();
+        }
+    }
+
+    /// Mutes/unmutes all audio, independently of other reasons to mute (kept up to date with the
+    /// corresponding setting).
+    pub(crate) fn set_muted_by_user(&self, muted_by_user: bool) {
+        if let Some(inner) = self.inner.borrow_mut().as_mut() {
+            inner.muted.by_user = muted_by_user;
             inner.update_volume();
         }
     }
 }
 
-impl<A: Audio> Inner<A> {
-    fn recalculate_volume(&self) -> f32 {
-        if self.muted_by_game || self.muted_by_visibility || self.muted_by_ad {
+/// Where positional sounds (see [`AudioPlayer::play_at`]) are heard from, such as the camera.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AudioListener {
+    /// Position in world space.
+    pub center: Vec2,
+    /// Half the width of the view in world space (like `Camera2d::zoom`).
+    pub zoom: f32,
+}
+
+impl AudioListener {
+    /// Sounds further than this many [`Self::zoom`]s away are skipped.
+    const MAX_DISTANCE: f32 = 2.0;
+
+    /// Gets the volume multiplier of a sound at `position`, for sounds that can't be played with
+    /// [`AudioPlayer::play_at`] (e.g. one looping sound for several sources). Zero if too far away.
+    pub fn volume_at(&self, position: Vec2) -> f32 {
+        self.pan_and_attenuation(position)
+            .map_or(0.0, |(_, attenuation)| attenuation)
+    }
+
+    /// Gets the stereo pan (-1 is left, 1 is right) and volume multiplier of a sound at `position`,
+    /// or `None` if it is too far away. Volume fades linearly to zero at [`Self::MAX_DISTANCE`].
+    fn pan_and_attenuation(&self, position: Vec2) -> Option<(f32, f32)> {
+        let offset = (position - self.center) / self.zoom.max(f32::EPSILON);
+        let distance = offset.length();
+        (distance < Self::MAX_DISTANCE).then(|| {
+            (
+                offset.x.clamp(-1.0, 1.0),
+                1.0 - distance / Self::MAX_DISTANCE,
+            )
+        })
+    }
+}
+
+/// Reasons to mute all audio.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+struct Muted {
+    /// The game wants to mute all audio.
+    by_game: bool,
+    /// Whether muted because the page is hidden.
+    by_visibility: bool,
+    /// Whether muted because the window is unfocused (if the player opted in).
+    by_focus: bool,
+    /// Whether muted due to conflicting with an advertisement's audio.
+    by_ad: bool,
+    /// Whether the user muted all audio (e.g. with the mute key).
+    by_user: bool,
+}
+
+impl Muted {
+    /// Whether muted for any reason.
+    fn is_muted(&self) -> bool {
+        self.by_game || self.by_visibility || self.by_focus || self.by_ad || self.by_user
+    }
+
+    /// Gets the volume to play at, given the volume setting.
+    fn volume(&self, volume_setting: f32) -> f32 {
+        if self.is_muted() {
             0.0
         } else {
-            self.volume_setting
+            volume_setting
         }
     }
 
+    /// Gets the sound effect and music volumes to play at, given the volume settings. Muting
+    /// doesn't change the settings, so unmuting restores them.
+    fn volumes(
+        &self,
+        volume_setting: f32,
+        sfx_volume_setting: f32,
+        music_volume_setting: f32,
+    ) -> (f32, f32) {
+        let volume = self.volume(volume_setting);
+        (volume * sfx_volume_setting, volume * music_volume_setting)
+    }
+}
+
+impl<A: Audio> Inner<A> {
+    fn recalculate_volume(&self) -> f32 {
+        self.muted.volume(self.volume_setting)
+    }
+
     fn update_volume(&mut self) {
-        let new_volume = self.recalculate_volume();
-        if new_volume != self.volume_target {
-            self.volume_target = new_volume;
-            if let Err(_e) = self
-                .sfx_gain
-                .gain()
-                .linear_ramp_to_value_at_time(new_volume, self.context.current_time() + 1.5)
-            {
-                #[cfg(debug_assertions)]
-                js_hooks::console_log!("could not linear ramp audio: {:?}", _e);
-                self.sfx_gain.gain().set_value(new_volume);
+        let new_volumes = self.muted.volumes(
+            self.volume_setting,
+            self.sfx_volume_setting,
+            self.music_volume_setting,
+        );
+        if new_volumes != self.volume_targets {
+            self.volume_targets = new_volumes;
+            let end_time = self.context.current_time() + 1.5;
+            for (gain, new_volume) in [
+                (&self.sfx_gain, new_volumes.0),
+                (&self.music_gain, new_volumes.1),
+            ] {
+                if let Err(_e) = gain
+                    .gain()
+                    .linear_ramp_to_value_at_time(new_volume, end_time)
+                {
+                    #[cfg(debug_assertions)]
+                    js_hooks::console_warn!("could not linear ramp audio: {:?}", _e);
+                    gain.gain().set_value(new_volume);
+                }
             }
         }
     }
 
-    /// Plays a particular sound, optionally in a loop. This is private, since looping is never
-    /// determined at runtime.
-    fn play(rc: &Rc<RefCell<Option<Self>>>, audio: A, volume: f32, looping: bool) {
+    /// Plays a particular sound, panned by `pan` (-1 to 1), optionally in a loop and/or as music.
+    /// This is private, since looping is never determined at runtime.
+    fn play(
+        rc: &Rc<RefCell<Option<Self>>>,
+        audio: A,
+        volume: f32,
+        pan: f32,
+        looping: bool,
+        music: bool,
+    ) {
         if let Some(inner) = rc.borrow_mut().as_mut() {
             if inner.recalculate_volume() == 0.0 {
                 return;
@@ -242,7 +378,24 @@ impl<A: Audio> Inner<A> {
                 gain.gain().set_value(volume);
                 let _ = source.connect_with_audio_node(&gain);
 
-                let _ = gain.connect_with_audio_node(&inner.sfx_gain);
+                let destination = if music {
+                    &inner.music_gain
+                } else {
+                    &inner.sfx_gain
+                };
+                match (pan != 0.0)
+                    .then(|| StereoPannerNode::new(&inner.context).ok())
+                    .flatten()
+                {
+                    Some(panner) => {
+                        panner.pan().set_value(pan);
+                        let _ = gain.connect_with_audio_node(&panner);
+                        let _ = panner.connect_with_audio_node(destination);
+                    }
+                    None => {
+                        let _ = gain.connect_with_audio_node(destination);
+                    }
+                }
 
                 if looping {
                     source.set_loop(true);
@@ -295,3 +448,82 @@ impl<A: Audio> Inner<A> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::audio::{AudioListener, Muted};
+    use glam::Vec2;
+
+    #[test]
+    fn muted_by_user_and_ad() {
+        let mut muted = Muted::default();
+        assert!(!muted.is_muted());
+        assert_eq!(muted.volume(0.5), 0.5);
+
+        muted.by_user = true;
+        assert!(muted.is_muted());
+        assert_eq!(muted.volume(0.5), 0.0);
+
+        // Unmuting by the user doesn't override the ad.
+        muted.by_ad = true;
+        muted.by_user = false;
+        assert!(muted.is_muted());
+
+        muted.by_ad = false;
+        assert!(!muted.is_muted());
+    }
+
+    #[test]
+    fn pan_and_attenuation() {
+        let listener = AudioListener {
+            center: Vec2::new(100.0, 0.0),
+            zoom: 50.0,
+        };
+        assert_eq!(
+            listener.pan_and_attenuation(Vec2::new(100.0, 0.0)),
+            Some((0.0, 1.0))
+        );
+        // At the right edge of the view.
+        assert_eq!(
+            listener.pan_and_attenuation(Vec2::new(150.0, 0.0)),
+            Some((1.0, 0.5))
+        );
+        // Off screen to the left, but still audible.
+        assert_eq!(
+            listener.pan_and_attenuation(Vec2::new(25.0, 0.0)),
+            Some((-1.0, 0.25))
+        );
+        // Too far.
+        assert_eq!(listener.pan_and_attenuation(Vec2::new(100.0, 100.0)), None);
+        assert_eq!(listener.volume_at(Vec2::new(100.0, 100.0)), 0.0);
+        assert_eq!(listener.volume_at(Vec2::new(150.0, 0.0)), 0.5);
+    }
+
+    #[test]
+    fn volumes() {
+        let mut muted = Muted::default();
+        assert_eq!(muted.volumes(0.5, 1.0, 0.5), (0.5, 0.25));
+
+        muted.by_user = true;
+        assert_eq!(muted.volumes(0.5, 1.0, 0.5), (0.0, 0.0));
+
+        // Unmuting restores the chosen volumes.
+        muted.by_user = false;
+        assert_eq!(muted.volumes(0.5, 1.0, 0.5), (0.5, 0.25));
+    }
+
+    #[test]
+    fn muted_by_focus_and_ad() {
+        let mut muted = Muted::default();
+        muted.by_focus = true;
+        muted.by_ad = true;
+        assert_eq!(muted.volume(0.5), 0.0);
+
+        // Regaining focus doesn't override the ad.
+        muted.by_focus = false;
+        assert!(muted.is_muted());
+
+        muted.by_ad = false;
+        assert_eq!(muted.volume(0.5), 0.5);
+    }
+}
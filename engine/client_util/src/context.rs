@@ -11,9 +11,12 @@ use crate::mouse::MouseState;
 use crate::reconn_web_socket::ReconnWebSocket;
 use crate::setting::CommonSettings;
 use crate::visibility::VisibilityState;
-use core_protocol::dto::{LeaderboardDto, LiveboardDto, MessageDto, PlayerDto, ServerDto, TeamDto};
+use core_protocol::dto::{
+    FormationDto, LeaderboardDto, LiveboardDto, MessageDto, PlayerDto, ServerDto, TeamDto,
+};
 use core_protocol::id::{CohortId, InvitationId, LoginType, PeriodId, PlayerId, ServerId, TeamId};
 use core_protocol::name::PlayerAlias;
+use core_protocol::owned::Owned;
 use core_protocol::rpc::{
     ChatUpdate, ClientRequest, ClientUpdate, InvitationUpdate, LeaderboardUpdate, LiveboardUpdate,
     PlayerUpdate, Request, SystemUpdate, TeamUpdate, Update, WebSocketQuery,
@@ -58,6 +61,38 @@ pub struct Context<G: GameClient + ?Sized> {
 pub struct ClientState {
     /// Time of last or current update.
     pub update_seconds: f32,
+    /// Time between the last and current update, capped to avoid spikes after pauses.
+    pub delta_seconds: f32,
+    /// The next update follows a pause (e.g. a hidden tab).
+    resuming: bool,
+}
+
+impl ClientState {
+    /// Shortest delta, to avoid dividing by zero.
+    const MIN_DELTA_SECONDS: f32 = 0.001;
+    /// Longest delta, e.g. after the tab was hidden.
+    const MAX_DELTA_SECONDS: f32 = 0.5;
+
+    /// Longest delta of the first frame after [`Self::resume`], so nothing jumps.
+    const RESUME_DELTA_SECONDS: f32 = 1.0 / 60.0;
+
+    /// Advances to a new frame at `time_seconds`, returning the delta time.
+    pub(crate) fn advance(&mut self, time_seconds: f32) -> f32 {
+        let max_delta_seconds = if std::mem::take(&mut self.resuming) {
+            Self::RESUME_DELTA_SECONDS
+        } else {
+            Self::MAX_DELTA_SECONDS
+        };
+        self.delta_seconds =
+            (time_seconds - self.update_seconds).clamp(Self::MIN_DELTA_SECONDS, max_delta_seconds);
+        self.update_seconds = time_seconds;
+        self.delta_seconds
+    }
+
+    /// Frames are resuming after a pause, so the next delta should be short.
+    pub(crate) fn resume(&mut self) {
+        self.resuming = true;
+    }
 }
 
 /// Obtained from server via websocket.
@@ -76,10 +111,18 @@ pub struct CoreState {
     pub members: Box<[PlayerId]>,
     pub joiners: Box<[PlayerId]>,
     pub joins: Box<[TeamId]>,
+    /// Set by the team captain.
+    pub formation: Option<FormationDto>,
+    /// Message of the day and its version, if any.
+    pub motd: Option<(u32, Owned<str>)>,
     /// TODO: Deprecate `pub`
     pub leaderboards: [Box<[LeaderboardDto]>; std::mem::variant_count::<PeriodId>()],
     pub liveboard: Vec<LiveboardDto>,
-    pub messages: HistoryBuffer<MessageDto, 9>,
+    /// Chat scrollback, oldest first to be evicted.
+    pub messages: HistoryBuffer<MessageDto, 200>,
+    /// Whether the next received messages are the server's back-fill following a reconnect, which
+    /// may repeat preserved [`Self::messages`].
+    backfilling: bool,
     pub(crate) players: HashMap<PlayerId, PlayerDto>,
     pub real_players: u32,
     pub teams: HashMap<TeamId, TeamDto>,
@@ -166,6 +209,59 @@ impl CoreState {
     pub fn leaderboard(&self, period_id: PeriodId) -> &[LeaderboardDto] {
         &self.leaderboards[period_id as usize]
     }
+
+    /// Text of the system message marking where the chat scrollback was reconnected.
+    const RECONNECTED_TEXT: &'static str = "Reconnected";
+
+    /// Gets a fresh state, following a reconnect. The chat scrollback is preserved, so players don't
+    /// lose the conversation, and the point of reconnection is marked (unless already marked, or
+    /// there were no messages).
+    fn reconnected(&mut self) -> Self {
+        let mut messages = std::mem::take(&mut self.messages);
+        if let Some(last) = messages
+            .recent()
+            .filter(|m| !Self::is_reconnected_marker(m))
+        {
+            let marker = MessageDto {
+                alias: PlayerAlias::new_unsanitized("Server"),
+                date_sent: last.date_sent,
+                player_id: None,
+                team_captain: false,
+                team_name: None,
+                text: String::from(Self::RECONNECTED_TEXT),
+                whisper: false,
+            };
+            messages.write(marker);
+        }
+        Self {
+            backfilling: messages.len() != 0,
+            messages,
+            ..Self::default()
+        }
+    }
+
+    fn is_reconnected_marker(message: &MessageDto) -> bool {
+        message.player_id.is_none() && message.text == Self::RECONNECTED_TEXT
+    }
+
+    /// Adds `received` messages to the chat scrollback, skipping those that were preserved across a
+    /// reconnect and then back-filled by the server. Only the first batch after a reconnect is
+    /// checked, so players can still repeat themselves.
+    fn receive_messages(&mut self, received: Vec<MessageDto>) {
+        let backfilling = std::mem::take(&mut self.backfilling);
+        let preserved = self.messages.len();
+        for message in received {
+            let duplicate = backfilling
+                && self.messages.oldest_ordered().take(preserved).any(|m| {
+                    m.date_sent == message.date_sent
+                        && m.player_id == message.player_id
+                        && m.text == message.text
+                });
+            if !duplicate {
+                self.messages.write(message);
+            }
+        }
+    }
 }
 
 impl<G: GameClient> Apply<Update<G::GameUpdate>> for ServerState<G> {
@@ -181,7 +277,7 @@ impl<G: GameClient> Apply<Update<G::GameUpdate>> for ServerState<G> {
                     ChatUpdate::Received(received) => {
                         // Need to use into_vec since
                         // https://github.com/rust-lang/rust/issues/59878 is incomplete.
-                        core.messages.extend(received.into_vec());
+                        core.receive_messages(received.into_vec());
                     }
                     _ => {}
                 }
@@ -195,6 +291,9 @@ impl<G: GameClient> Apply<Update<G::GameUpdate>> for ServerState<G> {
                     core.cohort_id = Some(cohort_id);
                     core.player_id = Some(player_id);
                 }
+                ClientUpdate::Motd { version, text } => {
+                    core.motd = Some((version, text));
+                }
                 _ => {}
             },
             Update::Game(update) => {
@@ -282,6 +381,9 @@ impl<G: GameClient> Apply<Update<G::GameUpdate>> for ServerState<G> {
                 TeamUpdate::Joins(joins) => {
                     core.joins = joins;
                 }
+                TeamUpdate::Formation(formation) => {
+                    core.formation = formation;
+                }
                 TeamUpdate::AddedOrUpdated(added_or_updated) => {
                     for team in added_or_updated.into_vec() {
                         core.teams.insert(team.team_id, team);
@@ -296,6 +398,16 @@ impl<G: GameClient> Apply<Update<G::GameUpdate>> for ServerState<G> {
             },
         }
     }
+
+    /// Resets the state, except for the chat scrollback (see [`CoreState`]).
+    fn reset(&mut self) {
+        use rc_borrow_mut::RcBorrowMut;
+        let core = Rc::borrow_mut(&mut self.core).reconnected();
+        *self = Self {
+            game: G::GameState::default(),
+            core: Rc::new(core),
+        };
+    }
 }
 
 impl<G: GameClient> Context<G> {
@@ -305,6 +417,13 @@ impl<G: GameClient> Context<G> {
         settings: G::GameSettings,
         frontend: Box<dyn Frontend<G::UiProps> + 'static>,
     ) -> Self {
+        // Remembered so it survives navigation and later visits.
+        let referrer = CommonSettings::attributed_referrer(
+            common_settings.referrer,
+            frontend.get_real_referrer(),
+        );
+        common_settings.set_referrer(referrer, &mut browser_storages);
+
         let (host, server_id) = Self::compute_websocket_host(&common_settings, None, &*frontend);
         let socket = ReconnWebSocket::new(host, common_settings.protocol, None);
         common_settings.set_server_id(server_id, &mut browser_storages);
@@ -352,7 +471,7 @@ impl<G: GameClient> Context<G> {
             invitation_id: invitation_id(),
             login_type: oauth2_code.is_some().then_some(LoginType::Discord),
             login_id: oauth2_code,
-            referrer: frontend.get_real_referrer(),
+            referrer: common_settings.referrer,
         };
 
         let web_socket_query_url = serde_urlencoded::to_string(&web_socket_query).unwrap();
@@ -447,3 +566,111 @@ impl<'a> std::ops::Deref for StrongCoreState<'a> {
         &*self.inner
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::context::{ClientState, CoreState};
+    use core_protocol::dto::MessageDto;
+    use core_protocol::id::PlayerId;
+    use core_protocol::name::PlayerAlias;
+    use std::num::NonZeroU32;
+
+    #[test]
+    fn delta_seconds() {
+        let mut client = ClientState::default();
+        client.advance(10.0);
+        assert_eq!(client.update_seconds, 10.0);
+
+        let delta = client.advance(10.016);
+        assert!((delta - 0.016).abs() < 0.0001);
+        assert_eq!(client.delta_seconds, delta);
+
+        // Same time.
+        assert_eq!(client.advance(10.016), ClientState::MIN_DELTA_SECONDS);
+
+        // Spike after a long pause is capped, but time still advances.
+        assert_eq!(client.advance(100.0), ClientState::MAX_DELTA_SECONDS);
+        assert_eq!(client.update_seconds, 100.0);
+        assert!((client.advance(100.02) - 0.02).abs() < 0.0001);
+    }
+
+    fn message(player_id: u32, date_sent: u64, text: &str) -> MessageDto {
+        MessageDto {
+            alias: PlayerAlias::new_unsanitized("Player"),
+            date_sent,
+            player_id: Some(PlayerId(NonZeroU32::new(player_id).unwrap())),
+            team_captain: false,
+            team_name: None,
+            text: String::from(text),
+            whisper: false,
+        }
+    }
+
+    fn texts(core: &CoreState) -> Vec<&str> {
+        core.messages
+            .oldest_ordered()
+            .map(|m| m.text.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn reconnect_keeps_chat() {
+        // Nothing to mark.
+        assert_eq!(CoreState::default().reconnected().messages.len(), 0);
+
+        let mut core = CoreState::default();
+        core.receive_messages(vec![message(1, 10, "hi"), message(2, 11, "hello")]);
+
+        let mut core = core.reconnected();
+        assert_eq!(texts(&core), ["hi", "hello", CoreState::RECONNECTED_TEXT]);
+        assert!(core.messages.recent().unwrap().player_id.is_none());
+
+        // Resetting again (e.g. while terminated) doesn't pile up markers.
+        let mut core = core.reconnected();
+        assert_eq!(texts(&core), ["hi", "hello", CoreState::RECONNECTED_TEXT]);
+
+        // Back-filled messages aren't duplicated.
+        core.receive_messages(vec![message(2, 11, "hello"), message(3, 12, "back")]);
+        assert_eq!(
+            texts(&core),
+            ["hi", "hello", CoreState::RECONNECTED_TEXT, "back"]
+        );
+
+        // Only the back-fill is deduplicated, not later messages (or repeats within the back-fill).
+        core.receive_messages(vec![message(3, 12, "back")]);
+        assert_eq!(
+            texts(&core),
+            ["hi", "hello", CoreState::RECONNECTED_TEXT, "back", "back"]
+        );
+    }
+
+    #[test]
+    fn resume_delta_seconds() {
+        let mut client = ClientState::default();
+        client.advance(10.0);
+
+        // Only the first frame after resuming is shortened, even below the usual cap.
+        client.resume();
+        assert_eq!(client.advance(10.1), ClientState::RESUME_DELTA_SECONDS);
+        assert_eq!(client.update_seconds, 10.1);
+        assert!((client.advance(10.2) - 0.1).abs() < 0.0001);
+
+        // Resuming is consumed by a short frame too.
+        client.resume();
+        client.advance(10.201);
+        assert_eq!(client.advance(20.0), ClientState::MAX_DELTA_SECONDS);
+    }
+
+    #[test]
+    fn chat_history_limit() {
+        let mut core = CoreState::default();
+        let capacity = core.messages.capacity();
+        core.receive_messages(
+            (0..capacity as u64 + 50)
+                .map(|i| message(1, i, &i.to_string()))
+                .collect(),
+        );
+        assert_eq!(core.messages.len(), capacity);
+        assert_eq!(texts(&core)[0], "50");
+    }
+}
@@ -12,10 +12,13 @@ use core_protocol::rpc::ClientUpdate;
 use renderer::{Camera, Layer, Renderer};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use yew::Html;
 
 /// A modular game client-side.
 pub trait GameClient: Sized + 'static {
     const GAME_ID: GameId;
+    /// Version of the game, e.g. for bug reports.
+    const VERSION: &'static str;
 
     /// Audio files to play.
     #[cfg(feature = "audio")]
@@ -108,6 +111,12 @@ pub trait GameClient: Sized + 'static {
         self.render(elapsed_seconds, context, renderer, renderer_layer);
     }
 
+    /// Renders the page at `path` (e.g. `/custom/`), or returns `None` to fall back to the
+    /// engine's default for the route.
+    fn switch_route(_path: &str) -> Option<Html> {
+        None
+    }
+
     /// Peek at a UI event before it is applied to `UiState`.
     fn ui(
         &mut self,
@@ -12,13 +12,14 @@ use crate::mouse::{MouseButton, MouseEvent as GameClientMouseEvent};
 use crate::reconn_web_socket::ReconnWebSocket;
 use crate::setting::CommonSettings;
 use crate::setting::Settings;
+use crate::touch_gesture::{TouchGesture, TouchGestures};
 use crate::visibility::VisibilityEvent;
 use common_util::range::map_ranges;
 use core_protocol::id::{PlayerId, ServerId, TeamId};
 use core_protocol::name::TeamName;
 use core_protocol::rpc::{
     ChatRequest, ClientRequest, ClientUpdate, InvitationRequest, PlayerRequest, Request,
-    TeamRequest, Update,
+    SystemUpdate, TeamRequest, Update,
 };
 use core_protocol::web_socket::WebSocketProtocol;
 use glam::{IVec2, Vec2};
@@ -26,7 +27,8 @@ use js_sys::Function;
 use renderer::Renderer;
 use wasm_bindgen::{JsCast, JsValue};
 use web_sys::{
-    Event, FocusEvent, HtmlInputElement, KeyboardEvent, MouseEvent, Touch, TouchEvent, WheelEvent,
+    Event, FocusEvent, HtmlInputElement, KeyboardEvent, MouseEvent, MouseEventInit, Touch,
+    TouchEvent, WheelEvent,
 };
 
 pub struct Infrastructure<G: GameClient> {
@@ -38,6 +40,7 @@ pub struct Infrastructure<G: GameClient> {
     /// Id of the [`Touch`] associated with the second earliest finger to make contact with the touch
     /// screen in a gesture, used to emulate right click.
     right_touch_id: Option<i32>,
+    touch_gestures: TouchGestures,
     renderer: Renderer<G::Camera>,
     renderer_layer: G::RendererLayer,
     statistic_fps_monitor: FpsMonitor,
@@ -66,13 +69,17 @@ impl<G: GameClient> Infrastructure<G> {
 
         // Finally create context with common and game settings.
         let mut context = Context::new(browser_storages, common_settings, game_settings, frontend);
-        let renderer_layer = game.init_layer(&mut renderer, &mut context);
+        let mut renderer_layer = game.init_layer(&mut renderer, &mut context);
+
+        // Compile shaders while still loading, so they don't cause hitches mid-game.
+        renderer.warmup(&mut renderer_layer);
 
         Ok(Self {
             game,
             context,
             left_touch_id: None,
             right_touch_id: None,
+            touch_gestures: TouchGestures::default(),
             renderer,
             renderer_layer,
             statistic_fps_monitor: FpsMonitor::new(60.0),
@@ -83,18 +90,50 @@ impl<G: GameClient> Infrastructure<G> {
     pub fn recreate_renderer(&mut self) -> Result<(), String> {
         self.renderer = Renderer::new(self.context.common_settings.antialias)?;
         self.renderer_layer = self.game.init_layer(&mut self.renderer, &mut self.context);
+        self.renderer.warmup(&mut self.renderer_layer);
         Ok(())
     }
 
     pub fn frame(&mut self, time_seconds: f32) {
+        #[cfg(feature = "audio")]
+        self.context.audio.set_volume_settings(
+            self.context.common_settings.volume,
+            self.context.common_settings.sfx_volume,
+            self.context.common_settings.music_volume,
+        );
         #[cfg(feature = "audio")]
         self.context
             .audio
-            .set_volume_setting(self.context.common_settings.volume);
+            .set_muted_by_user(self.context.common_settings.muted);
 
-        let elapsed_seconds = (time_seconds - self.context.client.update_seconds).clamp(0.001, 0.5);
-        self.context.client.update_seconds = time_seconds;
+        let elapsed_seconds = self.context.client.advance(time_seconds);
+        if let Some(gesture) = self
+            .touch_gestures
+            .update(self.context.client.update_seconds)
+        {
+            self.touch_gesture(gesture);
+        }
+        self.update_network(time_seconds);
 
+        self.renderer
+            .pre_prepare(&mut self.renderer_layer, time_seconds);
+        self.game.tick(
+            elapsed_seconds,
+            &mut self.context,
+            &mut self.renderer,
+            &mut self.renderer_layer,
+        );
+        self.renderer.render(&mut self.renderer_layer);
+
+        if let Some(fps) = self.statistic_fps_monitor.update(elapsed_seconds) {
+            self.context
+                .send_to_server(Request::Client(ClientRequest::TallyFps(fps)));
+        }
+    }
+
+    /// Processes inbound messages, without rendering. Called by [`Self::frame`], and periodically
+    /// while the page is hidden (when there are no frames) to keep the connection alive.
+    pub fn update_network(&mut self, time_seconds: f32) {
         for inbound in self
             .context
             .socket
@@ -154,29 +193,25 @@ impl<G: GameClient> Infrastructure<G> {
                     &mut self.renderer_layer,
                 );
             }
+            if let Update::System(SystemUpdate::Removed(removed)) = &inbound {
+                // Forget the last chosen server if it is gone, so reloading auto-selects.
+                let last_server_id = self.context.common_settings.last_server_id;
+                if CommonSettings::validate_server_id(last_server_id, removed) != last_server_id {
+                    self.context
+                        .common_settings
+                        .set_last_server_id(None, &mut self.context.browser_storages);
+                }
+            }
             self.context.state.apply(inbound);
         }
-
-        self.renderer
-            .pre_prepare(&mut self.renderer_layer, time_seconds);
-        self.game.tick(
-            elapsed_seconds,
-            &mut self.context,
-            &mut self.renderer,
-            &mut self.renderer_layer,
-        );
-        self.renderer.render(&mut self.renderer_layer);
-
-        if let Some(fps) = self.statistic_fps_monitor.update(elapsed_seconds) {
-            self.context
-                .send_to_server(Request::Client(ClientRequest::TallyFps(fps)));
-        }
     }
 
-    pub fn keyboard(&mut self, event: KeyboardEvent) {
+    /// Returns true if the [`GameAction::ToggleHud`][`crate::key_bindings::GameAction::ToggleHud`]
+    /// key was pressed, so the HUD should be toggled.
+    pub fn keyboard(&mut self, event: KeyboardEvent) -> bool {
         if let Some(target) = event.target() {
             if target.is_instance_of::<HtmlInputElement>() {
-                return;
+                return false;
             }
         }
 
@@ -186,7 +221,31 @@ impl<G: GameClient> Infrastructure<G> {
             "keydown" | "keyup" => {
                 let down = type_ == "keydown";
 
-                if let Some(key) = Key::try_from_key_code(event.key_code()) {
+                if let Some(physical_key) = Key::try_from_key_code(event.key_code()) {
+                    // Don't block CTRL+C, CTRL+V, etc.
+                    if !(event.ctrl_key()
+                        && matches!(physical_key, Key::C | Key::F | Key::R | Key::V | Key::X))
+                    {
+                        event.prevent_default();
+                        event.stop_propagation();
+                    }
+
+                    // Apply the player's key bindings before the game sees the key.
+                    let key = match self
+                        .context
+                        .common_settings
+                        .key_bindings
+                        .resolve(physical_key)
+                    {
+                        Some(key) => key,
+                        None => return false,
+                    };
+
+                    // Not seen by the game.
+                    if self.context.common_settings.is_hud_key(key) && !event.ctrl_key() {
+                        return down && !event.repeat();
+                    }
+
                     let e = GameClientKeyboardEvent {
                         key,
                         ctrl: event.ctrl_key(),
@@ -204,12 +263,19 @@ impl<G: GameClient> Infrastructure<G> {
                             Key::EqualsPlus if e.ctrl => self.raw_zoom(-1.0),
                             _ => {}
                         }
-                    }
 
-                    // Don't block CTRL+C, CTRL+V, etc.
-                    if !(e.ctrl && matches!(e.key, Key::C | Key::F | Key::R | Key::V | Key::X)) {
-                        event.prevent_default();
-                        event.stop_propagation();
+                        // Holding the mute key shouldn't toggle it repeatedly.
+                        #[cfg(feature = "audio")]
+                        if let Some(muted) = self
+                            .context
+                            .common_settings
+                            .muted_after_key(key)
+                            .filter(|_| !event.repeat())
+                        {
+                            self.context
+                                .common_settings
+                                .set_muted(muted, &mut self.context.browser_storages);
+                        }
                     }
 
                     self.game.peek_keyboard(&e, &mut self.context);
@@ -218,12 +284,18 @@ impl<G: GameClient> Infrastructure<G> {
             }
             _ => {}
         }
+        false
     }
 
     pub fn keyboard_focus(&mut self, event: FocusEvent) {
-        if event.type_() == "blur" {
+        let blur = event.type_() == "blur";
+        if blur {
             self.context.keyboard.reset();
         }
+        #[cfg(feature = "audio")]
+        self.context
+            .audio
+            .set_muted_by_focus(blur && self.context.common_settings.mute_on_focus_loss);
     }
 
     pub fn mouse(&mut self, event: MouseEvent) {
@@ -310,7 +382,7 @@ impl<G: GameClient> Infrastructure<G> {
             }
         }
 
-        if let Some((first, second)) = left_touch
+        let pinch_distance = left_touch
             .as_ref()
             .map(|t| IVec2::new(t.client_x(), t.client_y()).as_vec2())
             .zip(
@@ -318,17 +390,34 @@ impl<G: GameClient> Infrastructure<G> {
                     .as_ref()
                     .map(|t| IVec2::new(t.client_x(), t.client_y()).as_vec2()),
             )
-        {
-            let pinch_distance = first.distance(second);
+            .map(|(first, second)| first.distance(second));
+        let touching = target_touches.length() > 0;
+        if let Some(delta) = self.context.mouse.pinch.update(pinch_distance, touching) {
+            self.raw_zoom(delta);
+        }
 
-            if let Some(previous_pinch_distance) = self.context.mouse.pinch_distance {
-                let delta = 0.03 * (previous_pinch_distance - pinch_distance);
-                self.raw_zoom(delta);
+        if target_touches.length() > 1 || self.context.mouse.pinch.is_pinching() {
+            self.touch_gestures.cancel();
+        } else {
+            let position = left_touch
+                .as_ref()
+                .or(right_touch.as_ref())
+                .map(|t| IVec2::new(t.client_x(), t.client_y()).as_vec2());
+            if let Some(gesture) = self
+                .touch_gestures
+                .touch(position, self.context.client.update_seconds)
+            {
+                self.touch_gesture(gesture);
             }
+        }
 
-            self.context.mouse.pinch_distance = Some(pinch_distance);
-        } else {
-            self.context.mouse.pinch_distance = None;
+        if self.context.mouse.pinch.is_pinching() {
+            // Neither aim nor steer until all touches end.
+            self.left_touch_id = left_touch.map(|t| t.identifier());
+            self.right_touch_id = right_touch.map(|t| t.identifier());
+            self.context.mouse.cancel(MouseButton::Left);
+            self.context.mouse.cancel(MouseButton::Right);
+            return;
         }
 
         macro_rules! process_touch {
@@ -397,6 +486,47 @@ impl<G: GameClient> Infrastructure<G> {
         );
     }
 
+    /// Issues the events of a [`TouchGesture`].
+    fn touch_gesture(&mut self, gesture: TouchGesture) {
+        let client_to_view = |position: Vec2| {
+            let position = position.as_ivec2();
+            Self::client_coordinate_to_view(position.x, position.y)
+        };
+        let e = match gesture {
+            TouchGesture::DoubleTap(position) => {
+                GameClientMouseEvent::DoubleTap(client_to_view(position))
+            }
+            TouchGesture::LongPress(position) => {
+                GameClientMouseEvent::LongPress(client_to_view(position))
+            }
+        };
+        self.game.peek_mouse(&e, &mut self.context, &self.renderer);
+        self.context.mouse.apply(e);
+
+        match gesture {
+            // The first tap was already a click, but don't fire again for the second.
+            TouchGesture::DoubleTap(_) => self.context.mouse.cancel(MouseButton::Left),
+            TouchGesture::LongPress(position) => Self::context_menu(position),
+        }
+    }
+
+    /// Dispatches a `contextmenu` event at `position` (in client pixels), which the browser would
+    /// have done for a long-press if touches weren't prevented. Unlike pressing
+    /// [`MouseButton::Right`], this doesn't steer.
+    fn context_menu(position: Vec2) {
+        let mut init = MouseEventInit::new();
+        init.bubbles(true)
+            .cancelable(true)
+            .button(2)
+            .client_x(position.x as i32)
+            .client_y(position.y as i32);
+        if let Some(target) = js_hooks::document().element_from_point(position.x, position.y) {
+            if let Ok(event) = MouseEvent::new_with_mouse_event_init_dict("contextmenu", &init) {
+                let _ = target.dispatch_event(&event);
+            }
+        }
+    }
+
     /// For detecting when the browser tab becomes hidden.
     pub fn visibility_change(&mut self, _: Event) {
         // Written with the intention that errors bias towards visible=true.
@@ -406,6 +536,9 @@ impl<G: GameClient> Infrastructure<G> {
             .peek_visibility(&e, &mut self.context, &self.renderer);
         #[cfg(feature = "audio")]
         self.context.audio.peek_visibility(&e);
+        if visible {
+            self.context.client.resume();
+        }
         self.context.visibility.apply(e)
     }
 
@@ -419,20 +552,10 @@ impl<G: GameClient> Infrastructure<G> {
     /// Converts page position (from event) to view position (-1..1).
     fn client_coordinate_to_view(x: i32, y: i32) -> Vec2 {
         let rect = js_hooks::canvas().get_bounding_client_rect();
-
-        Vec2::new(
-            map_ranges(
-                x as f32,
-                rect.x() as f32..rect.x() as f32 + rect.width() as f32,
-                -1.0..1.0,
-                false,
-            ),
-            map_ranges(
-                y as f32,
-                rect.y() as f32 + rect.height() as f32..rect.y() as f32,
-                -1.0..1.0,
-                false,
-            ),
+        client_to_view(
+            IVec2::new(x, y).as_vec2(),
+            Vec2::new(rect.x() as f32, rect.y() as f32),
+            Vec2::new(rect.width() as f32, rect.height() as f32),
         )
     }
 
@@ -460,12 +583,8 @@ impl<G: GameClient> Infrastructure<G> {
 
     pub fn wheel(&mut self, event: WheelEvent) {
         event.prevent_default();
-        // each wheel step is 53 pixels.
-        // do 0.5 or 1.0 raw zoom.
-        let steps: f64 = event.delta_y() * (1.0 / 53.0);
-        let sign = 1f64.copysign(steps);
-        let steps = steps.abs().clamp(1.0, 2.0).floor() * sign;
-        self.raw_zoom(steps as f32 * 0.5)
+        let notches = wheel_notches(event.delta_y(), event.delta_mode());
+        self.raw_zoom(notches * self.context.common_settings.zoom_per_notch)
     }
 
     /// Sends any request to the server.
@@ -570,6 +689,9 @@ impl<G: GameClient> Infrastructure<G> {
         self.context
             .common_settings
             .set_server_id(server_id, &mut self.context.browser_storages);
+        self.context
+            .common_settings
+            .set_last_server_id(server_id, &mut self.context.browser_storages);
     }
 
     /// Simulates dropping of one or both websockets.
@@ -577,3 +699,70 @@ impl<G: GameClient> Infrastructure<G> {
         self.context.socket.simulate_drop();
     }
 }
+
+/// Maps client (CSS pixel) coordinates to view space (-1 to 1, y up), given the `position` and
+/// `size` of the canvas element in client coordinates. The drawing buffer size (e.g. resolution
+/// divisor) doesn't matter, since the canvas element always covers the same area.
+fn client_to_view(client: Vec2, position: Vec2, size: Vec2) -> Vec2 {
+    Vec2::new(
+        map_ranges(client.x, position.x..position.x + size.x, -1.0..1.0, false),
+        map_ranges(client.y, position.y + size.y..position.y, -1.0..1.0, false),
+    )
+}
+
+/// Converts a wheel `delta` in `delta_mode` (see [`WheelEvent::delta_mode`]) to notches of a
+/// typical mouse wheel, so that the same motion zooms the same amount on mice and trackpads.
+/// Trackpads send many small deltas, which become fractions of a notch.
+fn wheel_notches(delta: f64, delta_mode: u32) -> f32 {
+    // Each notch is at least 53 pixels or 3 lines.
+    const PIXELS_PER_NOTCH: f64 = 53.0;
+    const LINES_PER_NOTCH: f64 = 3.0;
+    // Browsers disagree on pixels per notch (e.g. Chrome on Windows reports 100), and fast flicks
+    // shouldn't zoom all the way at once, so no event counts as more than one notch.
+    const MAX_NOTCHES: f64 = 1.0;
+
+    let notches = match delta_mode {
+        WheelEvent::DOM_DELTA_LINE => delta / LINES_PER_NOTCH,
+        WheelEvent::DOM_DELTA_PAGE => delta,
+        _ => delta / PIXELS_PER_NOTCH,
+    };
+    notches.clamp(-MAX_NOTCHES, MAX_NOTCHES) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::infrastructure::{client_to_view, wheel_notches};
+    use glam::Vec2;
+    use web_sys::WheelEvent;
+
+    #[test]
+    fn client_to_view_corners() {
+        let position = Vec2::new(10.0, 20.0);
+        let size = Vec2::new(800.0, 600.0);
+        assert_eq!(
+            client_to_view(position, position, size),
+            Vec2::new(-1.0, 1.0)
+        );
+        assert_eq!(
+            client_to_view(position + size, position, size),
+            Vec2::new(1.0, -1.0)
+        );
+        assert_eq!(
+            client_to_view(position + size * 0.5, position, size),
+            Vec2::ZERO
+        );
+    }
+
+    #[test]
+    fn wheel_notches_normalized() {
+        assert_eq!(wheel_notches(53.0, WheelEvent::DOM_DELTA_PIXEL), 1.0);
+        assert_eq!(wheel_notches(-3.0, WheelEvent::DOM_DELTA_LINE), -1.0);
+        assert_eq!(wheel_notches(1.0, WheelEvent::DOM_DELTA_PAGE), 1.0);
+        // Trackpads zoom a little at a time.
+        assert!(wheel_notches(2.0, WheelEvent::DOM_DELTA_PIXEL).abs() < 0.1);
+        // Chrome on Windows.
+        assert_eq!(wheel_notches(100.0, WheelEvent::DOM_DELTA_PIXEL), 1.0);
+        assert_eq!(wheel_notches(1000.0, WheelEvent::DOM_DELTA_PIXEL), 1.0);
+        assert_eq!(wheel_notches(-10.0, WheelEvent::DOM_DELTA_PAGE), -1.0);
+    }
+}
@@ -6,6 +6,8 @@ use core_protocol::name::Referrer;
 use js_hooks::{document, window};
 use std::num::NonZeroU32;
 use std::str::FromStr;
+use wasm_bindgen::JsCast;
+use web_sys::HtmlAnchorElement;
 
 /// Gets the domain name component of a host string e.g. mk48.io
 pub fn domain_name_of(host: &str) -> String {
@@ -41,6 +43,31 @@ pub fn invitation_id() -> Option<InvitationId> {
         .map(InvitationId)
 }
 
+/// Returns if the path requests spectating (watching without playing). The game decides who to
+/// watch, and shouldn't offer to spawn.
+/// Path should resemble /spectate/
+pub fn is_spectating() -> bool {
+    window()
+        .location()
+        .pathname()
+        .map(|p| p.starts_with("/spectate/"))
+        .unwrap_or(false)
+}
+
+/// Prompts the user to save `contents`, of `mime_type` (e.g. `"text/csv"`), as a file named
+/// `file_name`.
+pub fn download(file_name: &str, mime_type: &str, contents: &str) -> Result<(), String> {
+    let anchor: HtmlAnchorElement = document()
+        .create_element("a")
+        .map_err(|e| format!("{:?}", e))?
+        .unchecked_into();
+    let contents = String::from(js_sys::encode_uri_component(contents));
+    anchor.set_href(&format!("data:{};charset=utf-8,{}", mime_type, contents));
+    anchor.set_download(file_name);
+    anchor.click();
+    Ok(())
+}
+
 /// Gets the HTTP referrer.
 pub fn referrer() -> Option<Referrer> {
     Referrer::new(&document().referrer())
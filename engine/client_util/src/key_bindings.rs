@@ -0,0 +1,269 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::keyboard::Key;
+use std::fmt;
+use std::str::FromStr;
+use strum_macros::{Display, EnumString};
+
+/// Something a player can do by pressing a key, which can be rebound (see [`KeyBindings`]).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Display, EnumString)]
+pub enum GameAction {
+    MoveForward,
+    MoveBackward,
+    TurnLeft,
+    TurnRight,
+    Stop,
+    ZoomIn,
+    ZoomOut,
+    /// Toggles [`CommonSettings::muted`][`crate::setting::CommonSettings::muted`].
+    Mute,
+    /// Hides or shows the HUD/UI overlays, leaving only the game.
+    ToggleHud,
+    /// Eases the camera back onto own boat.
+    CenterCamera,
+    /// While held, zooms all the way out.
+    Overview,
+    /// While held, shows all labels.
+    ShowLabels,
+    ZoomPresetClose,
+    ZoomPresetMedium,
+    ZoomPresetFar,
+}
+
+impl GameAction {
+    pub const ALL: [Self; 15] = [
+        Self::MoveForward,
+        Self::MoveBackward,
+        Self::TurnLeft,
+        Self::TurnRight,
+        Self::Stop,
+        Self::ZoomIn,
+        Self::ZoomOut,
+        Self::Mute,
+        Self::ToggleHud,
+        Self::CenterCamera,
+        Self::Overview,
+        Self::ShowLabels,
+        Self::ZoomPresetClose,
+        Self::ZoomPresetMedium,
+        Self::ZoomPresetFar,
+    ];
+
+    /// The key games expect for this action, which is also the default binding.
+    pub fn default_key(self) -> Key {
+        match self {
+            Self::MoveForward => Key::W,
+            Self::MoveBackward => Key::S,
+            Self::TurnLeft => Key::A,
+            Self::TurnRight => Key::D,
+            Self::Stop => Key::X,
+            Self::ZoomIn => Key::PageUp,
+            Self::ZoomOut => Key::PageDown,
+            Self::Mute => Key::M,
+            Self::ToggleHud => Key::H,
+            // Unlike most letters, not already bound (e.g. C pays).
+            Self::CenterCamera => Key::Home,
+            Self::Overview => Key::V,
+            Self::ShowLabels => Key::Shift,
+            Self::ZoomPresetClose => Key::J,
+            Self::ZoomPresetMedium => Key::K,
+            Self::ZoomPresetFar => Key::L,
+        }
+    }
+
+    /// Human readable name, for the settings menu.
+    pub fn as_human_readable_str(self) -> &'static str {
+        match self {
+            Self::MoveForward => "Move Forward",
+            Self::MoveBackward => "Move Backward",
+            Self::TurnLeft => "Turn Left",
+            Self::TurnRight => "Turn Right",
+            Self::Stop => "Stop",
+            Self::ZoomIn => "Zoom In",
+            Self::ZoomOut => "Zoom Out",
+            Self::Mute => "Mute",
+            Self::ToggleHud => "Hide UI",
+            Self::CenterCamera => "Center Camera",
+            Self::Overview => "Hold for Overview",
+            Self::ShowLabels => "Hold for Labels",
+            Self::ZoomPresetClose => "Close Zoom",
+            Self::ZoomPresetMedium => "Medium Zoom",
+            Self::ZoomPresetFar => "Far Zoom",
+        }
+    }
+}
+
+/// Which physical [`Key`] triggers each [`GameAction`] (see
+/// [`CommonSettings::key_bindings`][`crate::setting::CommonSettings::key_bindings`]). Stored as a
+/// list like `MoveForward=W,TurnLeft=A`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct KeyBindings([Key; GameAction::ALL.len()]);
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self(GameAction::ALL.map(GameAction::default_key))
+    }
+}
+
+impl KeyBindings {
+    /// Gets the key bound to `action`.
+    pub fn key(&self, action: GameAction) -> Key {
+        self.0[action as usize]
+    }
+
+    /// Binds `action` to `key`, without affecting other bindings (even if they conflict).
+    pub fn with_binding(mut self, action: GameAction, key: Key) -> Self {
+        self.0[action as usize] = key;
+        self
+    }
+
+    /// Resolves a physical `key` to the key the game expects (the [`GameAction::default_key`] of
+    /// the action it is bound to). Returns `None` if `key` is the default key of an action that
+    /// was rebound, so it no longer triggers that action. Keys that aren't involved in any binding
+    /// are unchanged.
+    pub fn resolve(&self, key: Key) -> Option<Key> {
+        if let Some(action) = GameAction::ALL.into_iter().find(|&a| self.key(a) == key) {
+            Some(action.default_key())
+        } else if GameAction::ALL.into_iter().any(|a| a.default_key() == key) {
+            None
+        } else {
+            Some(key)
+        }
+    }
+
+    /// Gets all pairs of different actions bound to the same key, so the player can be warned.
+    /// Only the first action of each pair is triggered by the key.
+    pub fn conflicts(&self) -> Vec<(GameAction, GameAction)> {
+        let mut conflicts = Vec::new();
+        for (i, &a) in GameAction::ALL.iter().enumerate() {
+            for &b in &GameAction::ALL[i + 1..] {
+                if self.key(a) == self.key(b) {
+                    conflicts.push((a, b));
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// Returns true if `action` is bound to the same key as another action (see
+    /// [`conflicts`][`Self::conflicts`]).
+    pub fn is_conflicting(&self, action: GameAction) -> bool {
+        let key = self.key(action);
+        GameAction::ALL
+            .into_iter()
+            .any(|other| other != action && self.key(other) == key)
+    }
+}
+
+impl fmt::Display for KeyBindings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, action) in GameAction::ALL.into_iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{}={}", action, self.key(action))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for KeyBindings {
+    type Err = ();
+
+    /// Actions that are missing keep their default binding, and unknown actions are ignored (in
+    /// case they were removed), but malformed bindings are an error.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut bindings = Self::default();
+        for binding in s.split(',').filter(|b| !b.is_empty()) {
+            let (action, key) = binding.split_once('=').ok_or(())?;
+            let key = Key::from_str(key).map_err(|_| ())?;
+            if let Ok(action) = GameAction::from_str(action) {
+                bindings = bindings.with_binding(action, key);
+            }
+        }
+        Ok(bindings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::key_bindings::{GameAction, KeyBindings};
+    use crate::keyboard::Key;
+    use std::str::FromStr;
+
+    #[test]
+    fn resolve() {
+        let default = KeyBindings::default();
+        assert_eq!(default.resolve(Key::W), Some(Key::W));
+        assert_eq!(default.resolve(Key::J), Some(Key::J));
+        assert!(default.conflicts().is_empty());
+
+        let rebound = default.with_binding(GameAction::TurnLeft, Key::J);
+        assert_eq!(rebound.key(GameAction::TurnLeft), Key::J);
+        assert_eq!(rebound.resolve(Key::J), Some(Key::A));
+        // No longer turns left.
+        assert_eq!(rebound.resolve(Key::A), None);
+        assert_eq!(rebound.resolve(Key::D), Some(Key::D));
+        // Not involved in bindings.
+        assert_eq!(rebound.resolve(Key::Left), Some(Key::Left));
+
+        // Swap.
+        let swapped = default
+            .with_binding(GameAction::TurnLeft, Key::D)
+            .with_binding(GameAction::TurnRight, Key::A);
+        assert_eq!(swapped.resolve(Key::A), Some(Key::D));
+        assert_eq!(swapped.resolve(Key::D), Some(Key::A));
+    }
+
+    #[test]
+    fn conflicts() {
+        let bindings = KeyBindings::default()
+            .with_binding(GameAction::Stop, Key::W)
+            .with_binding(GameAction::ZoomOut, Key::PageUp);
+        assert_eq!(
+            bindings.conflicts(),
+            [
+                (GameAction::MoveForward, GameAction::Stop),
+                (GameAction::ZoomIn, GameAction::ZoomOut)
+            ]
+        );
+        assert!(bindings.is_conflicting(GameAction::Stop));
+        assert!(!bindings.is_conflicting(GameAction::TurnLeft));
+        // First one wins.
+        assert_eq!(bindings.resolve(Key::W), Some(Key::W));
+    }
+
+    #[test]
+    fn round_trip() {
+        let bindings = KeyBindings::default().with_binding(GameAction::MoveForward, Key::Up);
+        let string = bindings.to_string();
+        assert!(
+            string.starts_with("MoveForward=Up,MoveBackward=S,"),
+            "{}",
+            string
+        );
+        assert_eq!(KeyBindings::from_str(&string), Ok(bindings));
+
+        // Partial and unknown.
+        assert_eq!(
+            KeyBindings::from_str("TurnRight=L,Jump=Space"),
+            Ok(KeyBindings::default().with_binding(GameAction::TurnRight, Key::L))
+        );
+        assert_eq!(KeyBindings::from_str(""), Ok(KeyBindings::default()));
+        assert!(KeyBindings::from_str("TurnRight").is_err());
+        assert!(KeyBindings::from_str("TurnRight=NotAKey").is_err());
+    }
+
+    #[test]
+    fn feature_keys() {
+        // Keys of features like muting are bound like any other action, so conflicts are caught.
+        let bindings = KeyBindings::default().with_binding(GameAction::Overview, Key::M);
+        assert_eq!(
+            bindings.conflicts(),
+            [(GameAction::Mute, GameAction::Overview)]
+        );
+        assert_eq!(bindings.resolve(Key::M), Some(Key::M));
+        assert_eq!(bindings.resolve(Key::V), None);
+    }
+}
@@ -3,10 +3,10 @@
 
 use crate::apply::Apply;
 use std::num::NonZeroU8;
-use strum_macros::Display;
+use strum_macros::{Display, EnumString};
 
 /// Each variant is a possible key. Not guaranteed to support all keys.
-#[derive(Copy, Clone, Eq, PartialEq, Display)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Display, EnumString)]
 pub enum Key {
     A,
     B,
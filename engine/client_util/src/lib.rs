@@ -24,10 +24,15 @@ pub mod infrastructure;
 pub mod joined;
 pub mod joystick;
 pub mod js_util;
+pub mod key_bindings;
 pub mod keyboard;
 pub mod mouse;
+pub mod palette;
+pub mod pulse;
 pub mod rate_limiter;
 pub mod reconn_web_socket;
 pub mod setting;
+pub mod streamer_mode;
+pub mod touch_gesture;
 pub mod visibility;
 pub mod web_socket;
@@ -90,6 +90,13 @@ pub enum MouseEvent {
     DeltaPixels(Vec2),
     /// For touchscreen devices.
     Touch,
+    /// Two quick taps at about the same position in view space (-1..1). The first tap is also a
+    /// click of [`MouseButton::Left`] (it can't be told apart from a single tap in time), but
+    /// the second isn't.
+    DoubleTap(Vec2),
+    /// Touching without moving for a while at a position in view space (-1..1). Also dispatches
+    /// a `contextmenu` event, like a right click, but doesn't press [`MouseButton::Right`].
+    LongPress(Vec2),
 }
 
 /// The state of the mouse i.e. buttons and position.
@@ -99,8 +106,8 @@ pub struct MouseState {
     /// Position in view space (-1..1).
     /// None if mouse isn't on game.
     pub view_position: Option<Vec2>,
-    /// During a pinch to zoom gesture, stores last distance value.
-    pub(crate) pinch_distance: Option<f32>,
+    /// Two finger pinch to zoom gesture on a touch screen.
+    pub(crate) pinch: Pinch,
     /// Whether the player is interacting with the game via a touch-screen.
     pub touch_screen: bool,
 }
@@ -169,4 +176,94 @@ impl MouseState {
     pub fn is_up(&self, button: MouseButton) -> bool {
         self.state(button).is_up()
     }
+
+    /// Releases a button without it counting as a click, e.g. when a touch turns into a gesture.
+    pub(crate) fn cancel(&mut self, button: MouseButton) {
+        *self.state_mut(button) = MouseButtonState::Up;
+    }
+}
+
+/// Turns the changing distance between two touches into zooming.
+#[derive(Debug, Default)]
+pub(crate) struct Pinch {
+    /// Distance between the two touches when they started or last zoomed.
+    distance: Option<f32>,
+    /// Whether the touches moved enough to be a pinch. Stays true until all touches end, so the
+    /// remaining touch doesn't aim or steer.
+    pinching: bool,
+}
+
+impl Pinch {
+    /// Raw zoom (see [`MouseEvent::Wheel`]) per halving of the distance between touches.
+    const ZOOM_PER_HALVING: f32 = 2.0;
+    /// How much (in halvings) the distance must change before two touches count as a pinch, so
+    /// that touches meant to aim and steer don't zoom.
+    const THRESHOLD: f32 = 0.15;
+
+    /// Updates with the `distance` between two touches, or [`None`] if there aren't two. Ends the
+    /// gesture if not `touching` at all. Returns how much to zoom, if any.
+    pub(crate) fn update(&mut self, distance: Option<f32>, touching: bool) -> Option<f32> {
+        if !touching {
+            *self = Self::default();
+            return None;
+        }
+        let distance = match distance {
+            Some(distance) if distance > 0.0 => distance,
+            _ => {
+                // Start over if the second touch comes back.
+                self.distance = None;
+                return None;
+            }
+        };
+        let previous = if let Some(previous) = self.distance {
+            previous
+        } else {
+            self.distance = Some(distance);
+            return None;
+        };
+        let halvings = (previous / distance).log2();
+        if !self.pinching && halvings.abs() < Self::THRESHOLD {
+            return None;
+        }
+        self.pinching = true;
+        self.distance = Some(distance);
+        Some(halvings * Self::ZOOM_PER_HALVING)
+    }
+
+    /// Whether touches are zooming instead of aiming or steering.
+    pub(crate) fn is_pinching(&self) -> bool {
+        self.pinching
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mouse::Pinch;
+
+    #[test]
+    fn pinch() {
+        let mut pinch = Pinch::default();
+        assert_eq!(pinch.update(None, true), None);
+        assert_eq!(pinch.update(Some(100.0), true), None);
+        // Not enough to be a pinch.
+        assert_eq!(pinch.update(Some(105.0), true), None);
+        assert!(!pinch.is_pinching());
+
+        // Spreading zooms in, including the movement before it was a pinch.
+        assert_eq!(pinch.update(Some(200.0), true), Some(-2.0));
+        assert!(pinch.is_pinching());
+        // Any movement zooms once pinching.
+        assert!(pinch.update(Some(190.0), true).unwrap() > 0.0);
+
+        // Lifting a finger stops zooming, but doesn't aim or steer.
+        assert_eq!(pinch.update(None, true), None);
+        assert!(pinch.is_pinching());
+        assert_eq!(pinch.update(Some(50.0), true), None);
+        assert_eq!(pinch.update(Some(25.0), true), Some(2.0));
+
+        // Lifting all fingers ends the gesture.
+        assert_eq!(pinch.update(None, false), None);
+        assert!(!pinch.is_pinching());
+        assert_eq!(pinch.update(Some(100.0), true), None);
+    }
 }
@@ -0,0 +1,108 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use glam::Vec3;
+use renderer::rgb_hex;
+use strum_macros::{Display, EnumString};
+
+/// What something means, as opposed to what color it actually is (see [`ColorVision::color`]).
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SemanticColor {
+    /// Teammates, and their health.
+    Friendly,
+    /// Enemy weapons and other hostile things, and their health.
+    Enemy,
+    /// Warnings, such as incoming fire.
+    Danger,
+}
+
+impl SemanticColor {
+    pub const ALL: [Self; 3] = [Self::Friendly, Self::Enemy, Self::Danger];
+}
+
+/// Type of color vision to choose colors for (see
+/// [`CommonSettings::color_vision`][`crate::setting::CommonSettings::color_vision`]). The
+/// colorblind palettes avoid relying on the distinctions each type can't see, such as red and green.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Display, EnumString)]
+pub enum ColorVision {
+    #[default]
+    Normal,
+    /// Red-blind.
+    Protanopia,
+    /// Green-blind.
+    Deuteranopia,
+    /// Blue-blind.
+    Tritanopia,
+}
+
+impl ColorVision {
+    pub const ALL: [Self; 4] = [
+        Self::Normal,
+        Self::Protanopia,
+        Self::Deuteranopia,
+        Self::Tritanopia,
+    ];
+
+    /// Gets the RGB hex (e.g. for CSS) of `color`.
+    pub fn hex(self, color: SemanticColor) -> u32 {
+        use SemanticColor::*;
+        match (self, color) {
+            (Self::Normal, Friendly) => 0x3aff8c,
+            (Self::Normal, Enemy | Danger) => 0xe74c3c,
+            // Blue and orange, which are bright enough to stand out against the ocean.
+            (Self::Protanopia, Friendly) => 0x56b4e9,
+            (Self::Protanopia, Enemy | Danger) => 0xe69f00,
+            (Self::Deuteranopia, Friendly) => 0x56b4e9,
+            (Self::Deuteranopia, Enemy | Danger) => 0xd55e00,
+            // Red and green are fine, but green is hard to tell apart from the blue ocean.
+            (Self::Tritanopia, Friendly) => 0x9ff5e4,
+            (Self::Tritanopia, Enemy | Danger) => 0xff2a4f,
+        }
+    }
+
+    /// Gets `color` as RGB, for rendering.
+    pub fn color(self, color: SemanticColor) -> Vec3 {
+        rgb_hex(self.hex(color))
+    }
+
+    /// Human readable name, for the settings menu.
+    pub fn as_human_readable_str(self) -> &'static str {
+        match self {
+            Self::Normal => "Normal Colors",
+            Self::Protanopia => "Protanopia Colors",
+            Self::Deuteranopia => "Deuteranopia Colors",
+            Self::Tritanopia => "Tritanopia Colors",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::palette::{ColorVision, SemanticColor};
+    use std::str::FromStr;
+
+    #[test]
+    fn distinct() {
+        for color_vision in ColorVision::ALL {
+            assert_ne!(
+                color_vision.hex(SemanticColor::Friendly),
+                color_vision.hex(SemanticColor::Enemy),
+                "{}",
+                color_vision
+            );
+            for color in SemanticColor::ALL {
+                assert!(color_vision.hex(color) <= 0xffffff);
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        for color_vision in ColorVision::ALL {
+            assert_eq!(
+                ColorVision::from_str(&color_vision.to_string()),
+                Ok(color_vision)
+            );
+        }
+    }
+}
@@ -0,0 +1,38 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use std::f32::consts::PI;
+
+/// Returns a smoothly pulsing value in `0.0..=1.0` that starts (and ends) each `period` at 0.0 and
+/// peaks at 1.0 halfway through. Since it depends only on `time` (e.g. `ClientState::update_seconds`)
+/// and not on frame count, all effects using it blink in sync regardless of framerate.
+pub fn pulse(time: f32, period: f32) -> f32 {
+    debug_assert!(period > 0.0);
+    let phase = (time / period).fract();
+    0.5 - 0.5 * (phase * (2.0 * PI)).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pulse::pulse;
+
+    #[test]
+    fn test_pulse_bounded() {
+        for i in 0..1000 {
+            let p = pulse(i as f32 * 0.0137, 0.7);
+            assert!((0.0..=1.0).contains(&p), "{}", p);
+        }
+        assert!(pulse(0.0, 2.0) < 0.0001);
+        assert!(pulse(1.0, 2.0) > 0.9999);
+    }
+
+    #[test]
+    fn test_pulse_periodic() {
+        let period = 1.5;
+        for i in 0..100 {
+            let t = i as f32 * 0.031;
+            assert!((pulse(t, period) - pulse(t + period, period)).abs() < 0.001);
+            assert!((pulse(t, period) - pulse(t + 3.0 * period, period)).abs() < 0.001);
+        }
+    }
+}
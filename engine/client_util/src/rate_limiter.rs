@@ -48,6 +48,18 @@ impl RateLimiter {
         ret
     }
 
+    /// Like [`Self::update_ready`], but also ready if the elapsed time falls short of the period
+    /// by up to `tolerance` (a fraction of the period). Useful if updates arrive at a jittery rate
+    /// close to the period, such as animation frames.
+    pub fn update_ready_with_tolerance(&mut self, elapsed: f32, tolerance: f32) -> bool {
+        self.update(elapsed);
+        let ret = self.elapsed >= self.period * (1.0 - tolerance);
+        if ret {
+            self.elapsed = (self.elapsed - self.period).max(0.0) % self.period;
+        }
+        ret
+    }
+
     /// Takes how much time passed, in seconds, since last update. Returns a iterator of possibly
     /// multiple times to do the rate-limited action. Useful if called less frequently than period.
     /// Will iterate up to a second worth of updates max.
@@ -75,4 +87,19 @@ mod tests {
         assert!(!limiter.update_ready(0.06));
         assert_eq!(limiter.iter_updates(0.15).count(), 2);
     }
+
+    #[test]
+    fn test_update_ready_with_tolerance() {
+        let mut limiter = RateLimiter::new(1.0 / 60.0);
+        assert!(limiter.update_ready_with_tolerance(0.0, 0.1));
+
+        // Jittery frames at the same rate as the limit aren't dropped.
+        for elapsed in [0.0165, 0.0168, 0.0164, 0.0169] {
+            assert!(limiter.update_ready_with_tolerance(elapsed, 0.1));
+        }
+
+        // Frames at twice the rate of the limit are halved.
+        assert!(!limiter.update_ready_with_tolerance(0.0083, 0.1));
+        assert!(limiter.update_ready_with_tolerance(0.0084, 0.1));
+    }
 }
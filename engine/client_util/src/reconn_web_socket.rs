@@ -19,6 +19,10 @@ pub struct ReconnWebSocket<I, O, S> {
     preamble: Option<O>,
     tries: u8,
     next_try: f32,
+    /// Time of the last [`update`][`Self::update`], for [`Self::reconnect_status`].
+    last_update: f32,
+    /// Randomizes the delay between tries.
+    backoff: Backoff,
     _spooky: PhantomData<S>,
 }
 
@@ -28,7 +32,7 @@ where
     O: 'static + Serialize + Clone,
     S: Apply<I>,
 {
-    const MAX_TRIES: u8 = 5;
+    pub const MAX_TRIES: u8 = 5;
     const SECONDS_PER_TRY: f32 = 1.0;
 
     pub fn new(host: String, protocol: WebSocketProtocol, preamble: Option<O>) -> Self {
@@ -45,6 +49,8 @@ where
             was_closed: false,
             tries: 0,
             next_try: 0.0,
+            last_update: 0.0,
+            backoff: Backoff::new(Self::SECONDS_PER_TRY, Backoff::DEFAULT_JITTER),
             _spooky: PhantomData,
         }
     }
@@ -64,6 +70,21 @@ where
             && (1..=Self::MAX_TRIES).contains(&self.tries)
     }
 
+    /// Gets the progress of reconnecting, if [`Self::is_reconnecting`].
+    pub fn reconnect_status(&self) -> Option<ReconnectStatus> {
+        self.is_reconnecting().then(|| ReconnectStatus {
+            tries: self.tries,
+            seconds_until_retry: (self.inner.is_error() && self.tries < Self::MAX_TRIES)
+                .then(|| ReconnectStatus::countdown(self.next_try - self.last_update)),
+        })
+    }
+
+    /// Makes the next reconnection attempt happen on the next [`update`][`Self::update`], instead
+    /// of waiting for the delay between tries. Does nothing if not waiting to reconnect.
+    pub fn retry_now(&mut self) {
+        self.next_try = self.next_try.min(self.last_update);
+    }
+
     /// Returns whether the underlying connection is closed and reconnection attempts have been
     /// exhausted.
     pub fn is_terminated(&self) -> bool {
@@ -85,6 +106,7 @@ where
         }
 
         self.reconnect_if_necessary(state, time_seconds);
+        self.last_update = time_seconds;
         self.inner.receive_updates()
     }
 
@@ -112,9 +134,10 @@ where
     /// pending messages.
     fn reconnect_if_necessary(&mut self, state: &mut S, time_seconds: f32) {
         if self.inner.state() == State::Open {
-            // Reconnected, forget tries.
+            // Reconnected, forget tries. The first try after the connection drops is jittered too,
+            // since that's when all clients of a restarting server would retry at once.
             self.tries = 0;
-            self.next_try = time_seconds + Self::SECONDS_PER_TRY * 0.5;
+            self.next_try = time_seconds + self.backoff.delay(js_sys::Math::random() as f32) * 0.5;
         } else if time_seconds < self.next_try {
             // Wait...
         } else if self.inner.is_error() && self.tries < Self::MAX_TRIES {
@@ -124,7 +147,7 @@ where
                 self.inner.send(p.clone());
             }
             self.tries += 1;
-            self.next_try = time_seconds + Self::SECONDS_PER_TRY;
+            self.next_try = time_seconds + self.backoff.delay(js_sys::Math::random() as f32);
         } else if self.is_terminated() {
             // Stop trying, stop giving the impression of working.
             state.reset();
@@ -142,3 +165,114 @@ impl<I, O, S> Drop for ReconnWebSocket<I, O, S> {
         self.inner.close();
     }
 }
+
+/// Progress of reconnecting a [`ReconnWebSocket`], for showing to the player.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ReconnectStatus {
+    /// Number of reconnection attempts so far (starting at 1).
+    pub tries: u8,
+    /// Whole seconds until the next attempt, or `None` if an attempt is in progress.
+    pub seconds_until_retry: Option<u32>,
+}
+
+impl ReconnectStatus {
+    /// After this many attempts, refreshing the page may be more likely to help.
+    const SUGGEST_REFRESH_TRIES: u8 = 3;
+
+    /// Returns true if the player should be told to try refreshing the page.
+    pub fn suggests_refresh(&self) -> bool {
+        self.tries >= Self::SUGGEST_REFRESH_TRIES
+    }
+
+    /// Rounds a delay in seconds up, so the countdown doesn't reach zero before the attempt.
+    fn countdown(seconds: f32) -> u32 {
+        seconds.max(0.0).ceil() as u32
+    }
+}
+
+/// Delay between reconnection attempts, randomized so that clients don't all retry at once
+/// (e.g. after a server restart).
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Backoff {
+    /// Delay in seconds, before jitter.
+    base: f32,
+    /// Maximum jitter as a fraction of `base`.
+    jitter: f32,
+}
+
+impl Backoff {
+    const DEFAULT_JITTER: f32 = 0.25;
+
+    fn new(base: f32, jitter: f32) -> Self {
+        Self {
+            base,
+            jitter: if jitter.is_finite() {
+                jitter.clamp(0.0, 1.0)
+            } else {
+                0.0
+            },
+        }
+    }
+
+    /// Gets the delay in seconds, given a `random` number in `0.0..1.0`.
+    fn delay(&self, random: f32) -> f32 {
+        self.base * (1.0 + self.jitter * (random * 2.0 - 1.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::reconn_web_socket::{Backoff, ReconnectStatus};
+
+    /// Deterministic stand-in for `Math.random()`.
+    fn randoms(seed: u32) -> impl Iterator<Item = f32> {
+        let mut state = seed;
+        std::iter::repeat_with(move || {
+            state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+            (state >> 8) as f32 / (1u32 << 24) as f32
+        })
+    }
+
+    #[test]
+    fn backoff_within_jitter() {
+        let backoff = Backoff::new(2.0, 0.25);
+        let delays: Vec<f32> = randoms(42).take(100).map(|r| backoff.delay(r)).collect();
+        for &delay in &delays {
+            assert!((1.5..=2.5).contains(&delay), "{}", delay);
+        }
+
+        // Actually spread out.
+        let min = delays.iter().copied().fold(f32::INFINITY, f32::min);
+        let max = delays.iter().copied().fold(0.0, f32::max);
+        assert!(max - min > 0.5, "{} {}", min, max);
+
+        assert_eq!(backoff.delay(0.0), 1.5);
+        assert_eq!(backoff.delay(0.5), 2.0);
+    }
+
+    #[test]
+    fn backoff_jitter_clamped() {
+        assert_eq!(Backoff::new(1.0, 0.0).delay(0.9), 1.0);
+        assert_eq!(Backoff::new(1.0, f32::NAN).delay(0.9), 1.0);
+
+        // Never negative.
+        let backoff = Backoff::new(1.0, 5.0);
+        assert!(randoms(7).take(100).all(|r| backoff.delay(r) >= 0.0));
+    }
+
+    #[test]
+    fn reconnect_status() {
+        assert_eq!(ReconnectStatus::countdown(0.0), 0);
+        assert_eq!(ReconnectStatus::countdown(-0.1), 0);
+        assert_eq!(ReconnectStatus::countdown(0.2), 1);
+        assert_eq!(ReconnectStatus::countdown(1.0), 1);
+        assert_eq!(ReconnectStatus::countdown(1.01), 2);
+
+        let status = |tries| ReconnectStatus {
+            tries,
+            seconds_until_retry: None,
+        };
+        assert!(!status(1).suggests_refresh());
+        assert!(status(3).suggests_refresh());
+    }
+}
@@ -2,20 +2,42 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
 use crate::browser_storage::BrowserStorages;
+use crate::key_bindings::{GameAction, KeyBindings};
+use crate::keyboard::Key;
+use crate::palette::ColorVision;
+use crate::streamer_mode::StreamerMode;
 use core_protocol::id::{ArenaId, CohortId, LanguageId, ServerId, SessionId};
-use core_protocol::name::PlayerAlias;
+use core_protocol::name::{PlayerAlias, Referrer};
 use core_protocol::web_socket::WebSocketProtocol;
 pub use engine_macros::Settings;
+use glam::Vec2;
+use std::collections::HashMap;
+use std::num::NonZeroU8;
+use std::ops::RangeInclusive;
 
 /// Settings backed by local storage.
 pub trait Settings: Sized {
     /// Loads all settings from local storage.
     fn load(l: &BrowserStorages, default: Self) -> Self;
+
+    /// Gets the settings in local storage as pairs of keys and values, for moving them to another
+    /// device. Excludes settings marked `#[setting(no_export)]`.
+    fn export(&self) -> Vec<(&'static str, String)>;
+
+    /// Sets and stores any settings in `pairs` that [`export`][`Self::export`] would include.
+    /// Unknown keys and invalid values are ignored, so exports from other versions can be imported.
+    fn import(&mut self, pairs: &HashMap<String, String>, browser_storages: &mut BrowserStorages);
 }
 
 // Useful if you don't want settings.
 impl Settings for () {
     fn load(_: &BrowserStorages, _: Self) -> Self {}
+
+    fn export(&self) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+
+    fn import(&mut self, _: &HashMap<String, String>, _: &mut BrowserStorages) {}
 }
 
 /// Settings of the infrastructure, common to all games.
@@ -29,21 +51,53 @@ pub struct CommonSettings {
     /// Volume preference (0 to 1).
     #[setting(range = "0.0..1.0", finite)]
     pub volume: f32,
+    /// Sound effect volume preference (0 to 1), relative to [`Self::volume`].
+    #[setting(range = "0.0..1.0", finite)]
+    pub sfx_volume: f32,
+    /// Music volume preference (0 to 1), relative to [`Self::volume`].
+    #[setting(range = "0.0..1.0", finite)]
+    pub music_volume: f32,
+    /// Whether the player muted all audio, regardless of [`Self::volume`].
+    pub muted: bool,
+    /// Whether to mute audio while the window is unfocused (e.g. alt-tabbed away).
+    pub mute_on_focus_loss: bool,
+    /// Which keys trigger which [`GameAction`]s, including [`GameAction::Mute`] and
+    /// [`GameAction::ToggleHud`].
+    pub key_bindings: KeyBindings,
+    /// How far aim moves with the mouse (see [`Self::adjust_aim`]).
+    #[setting(range = "0.1..2.0", finite)]
+    pub mouse_sensitivity: f32,
+    /// Whether to invert the vertical axis of the mouse.
+    pub invert_y: bool,
+    /// Raw zoom (see [`MouseEvent::Wheel`][`crate::mouse::MouseEvent::Wheel`]) per notch of a
+    /// mouse wheel, or equivalent trackpad motion.
+    #[setting(range = "0.1..2.0", finite)]
+    pub zoom_per_notch: f32,
     /// Last [`CohortId`].
-    #[setting(optional)]
+    #[setting(optional, no_export)]
     pub cohort_id: Option<CohortId>,
+    /// Last known [`Referrer`], so visits without one are still attributed to it.
+    #[setting(optional, no_export)]
+    pub referrer: Option<Referrer>,
     /// Last-used/chosen [`ServerId`].
     #[setting(optional, volatile)]
     pub server_id: Option<ServerId>,
-    /// Not manually set by the player.
+    /// Last manually chosen [`ServerId`], which is reconnected to after reloading.
     #[setting(optional)]
+    pub last_server_id: Option<ServerId>,
+    /// Not manually set by the player.
+    #[setting(optional, no_export)]
     pub arena_id: Option<ArenaId>,
     /// Not manually set by the player. Not accessible via arbitrary getter/setter as doing so would
     /// pull BigUint64Array into the JS shim, breaking compatibility with old devices.
-    #[setting(optional)]
+    #[setting(optional, no_export)]
     pub session_id: Option<SessionId>,
     /// Whether to set antialias rendering option.
     pub antialias: bool,
+    /// Type of color vision to choose colors for (see [`ColorVision::color`]).
+    pub color_vision: ColorVision,
+    /// Whether to make overlay backgrounds opaque and text bolder, for legibility.
+    pub high_contrast: bool,
     /// Websocket protocol.
     #[setting(volatile)]
     pub protocol: WebSocketProtocol,
@@ -53,6 +107,20 @@ pub struct CommonSettings {
     pub chat_dialog_shown: bool,
     /// Whether leaderboard menu is open.
     pub leaderboard_dialog_shown: bool,
+    /// Version of the last message of the day that was dismissed.
+    #[setting(optional)]
+    pub dismissed_motd_version: Option<u32>,
+    /// Whether to hide identifying information from viewers of a stream (see [`StreamerMode`]).
+    pub streamer_mode: bool,
+    /// Maximum frames per second to render, to save battery. `None` is uncapped.
+    #[setting(optional)]
+    pub fps_limit: Option<u8>,
+    /// Canvas resolution = window dimension / resolution divisor. Higher is faster, but blurrier.
+    #[setting(range = "1..4")]
+    pub resolution_divisor: u8,
+    /// Multiplier of the base font size, which the whole UI is sized relative to.
+    #[setting(range = "0.75..1.5", finite)]
+    pub ui_scale: f32,
 }
 
 impl Default for CommonSettings {
@@ -61,15 +129,32 @@ impl Default for CommonSettings {
             alias: None,
             language: LanguageId::default(),
             volume: 0.5,
+            sfx_volume: 1.0,
+            music_volume: 1.0,
+            muted: false,
+            mute_on_focus_loss: true,
+            key_bindings: KeyBindings::default(),
+            mouse_sensitivity: 1.0,
+            invert_y: false,
+            zoom_per_notch: 0.5,
             cohort_id: None,
+            referrer: None,
             server_id: None,
+            last_server_id: None,
             arena_id: None,
             session_id: None,
             antialias: true,
+            color_vision: ColorVision::default(),
+            high_contrast: false,
             protocol: WebSocketProtocol::default(),
             team_dialog_shown: true,
             chat_dialog_shown: true,
             leaderboard_dialog_shown: true,
+            dismissed_motd_version: None,
+            streamer_mode: false,
+            fps_limit: None,
+            resolution_divisor: 1,
+            ui_scale: 1.0,
         }
     }
 }
@@ -79,4 +164,233 @@ impl CommonSettings {
     pub(crate) fn session_tuple(&self) -> Option<(ArenaId, SessionId)> {
         self.arena_id.zip(self.session_id)
     }
+
+    /// Gets the [`ServerId`] to request on startup, given the one from the current `session` and
+    /// the `persisted` one from a previous visit.
+    pub fn startup_server_id(
+        session: Option<ServerId>,
+        persisted: Option<ServerId>,
+    ) -> Option<ServerId> {
+        session.or(persisted)
+    }
+
+    /// Gets the [`Referrer`] to report, given the `stored` one from a previous visit and the one
+    /// `found` in the URL or document. An empty referrer never replaces a stored one.
+    pub fn attributed_referrer(
+        stored: Option<Referrer>,
+        found: Option<Referrer>,
+    ) -> Option<Referrer> {
+        found.filter(|r| !r.is_empty()).or(stored)
+    }
+
+    /// Valid values of [`Self::resolution_divisor`].
+    pub const RESOLUTION_DIVISORS: RangeInclusive<u8> = 1..=4;
+
+    /// Gets [`Self::resolution_divisor`], clamped to [`Self::RESOLUTION_DIVISORS`].
+    pub fn canvas_resolution_divisor(&self) -> NonZeroU8 {
+        let valid = Self::RESOLUTION_DIVISORS;
+        NonZeroU8::new(self.resolution_divisor.clamp(*valid.start(), *valid.end())).unwrap()
+    }
+
+    /// Valid values of [`Self::mouse_sensitivity`]. The minimum keeps the mouse from stopping.
+    pub const MOUSE_SENSITIVITIES: RangeInclusive<f32> = 0.1..=2.0;
+
+    /// Applies [`Self::mouse_sensitivity`] and [`Self::invert_y`] to the `offset` of the aim from
+    /// the player. Sensitivity curves distances up to `reach` (e.g. the edge of the view), so low
+    /// sensitivity aims finer near the player without limiting how far the player can aim.
+    pub fn adjust_aim(&self, offset: Vec2, reach: f32) -> Vec2 {
+        let valid = Self::MOUSE_SENSITIVITIES;
+        let sensitivity = if self.mouse_sensitivity.is_finite() {
+            self.mouse_sensitivity.clamp(*valid.start(), *valid.end())
+        } else {
+            1.0
+        };
+        let offset = if self.invert_y {
+            Vec2::new(offset.x, -offset.y)
+        } else {
+            offset
+        };
+        let distance = offset.length();
+        if distance == 0.0 || distance >= reach {
+            return offset;
+        }
+        let curved = reach * (distance / reach).powf(sensitivity.recip());
+        offset * (curved / distance)
+    }
+
+    /// Gets the [`StreamerMode`] corresponding to [`Self::streamer_mode`].
+    pub fn streamer(&self) -> StreamerMode {
+        StreamerMode::new(self.streamer_mode)
+    }
+
+    /// Binds `action` to `key`, leaving other bindings alone. Check
+    /// [`KeyBindings::conflicts`] to warn about conflicts.
+    pub fn set_key_binding(
+        &mut self,
+        action: GameAction,
+        key: Key,
+        browser_storages: &mut BrowserStorages,
+    ) {
+        self.set_key_bindings(
+            self.key_bindings.with_binding(action, key),
+            browser_storages,
+        );
+    }
+
+    /// Gets the new value of [`Self::muted`] if `key` (already resolved with
+    /// [`KeyBindings::resolve`]) triggers [`GameAction::Mute`].
+    pub fn muted_after_key(&self, key: Key) -> Option<bool> {
+        (key == GameAction::Mute.default_key()).then_some(!self.muted)
+    }
+
+    /// Returns true if `key` (already resolved with [`KeyBindings::resolve`]) triggers
+    /// [`GameAction::ToggleHud`].
+    pub fn is_hud_key(&self, key: Key) -> bool {
+        key == GameAction::ToggleHud.default_key()
+    }
+
+    /// Returns `server_id` unless it is one of the `removed` servers, in which case `None`
+    /// (auto-select). Servers may be added incrementally, so absence from the known servers alone
+    /// doesn't mean `server_id` is gone.
+    pub(crate) fn validate_server_id(
+        server_id: Option<ServerId>,
+        removed: &[ServerId],
+    ) -> Option<ServerId> {
+        server_id.filter(|server_id| !removed.contains(server_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::key_bindings::GameAction;
+    use crate::keyboard::Key;
+    use crate::setting::CommonSettings;
+    use core_protocol::id::ServerId;
+    use core_protocol::name::Referrer;
+    use glam::Vec2;
+    use std::num::NonZeroU8;
+    use std::str::FromStr;
+
+    fn server_id(n: u8) -> ServerId {
+        ServerId(NonZeroU8::new(n).unwrap())
+    }
+
+    #[test]
+    fn canvas_resolution_divisor() {
+        let divisor = |resolution_divisor| {
+            CommonSettings {
+                resolution_divisor,
+                ..CommonSettings::default()
+            }
+            .canvas_resolution_divisor()
+            .get()
+        };
+        assert_eq!(divisor(0), 1);
+        assert_eq!(divisor(2), 2);
+        assert_eq!(divisor(9), 4);
+    }
+
+    #[test]
+    fn startup_server_id() {
+        assert_eq!(CommonSettings::startup_server_id(None, None), None);
+        assert_eq!(
+            CommonSettings::startup_server_id(None, Some(server_id(2))),
+            Some(server_id(2))
+        );
+        assert_eq!(
+            CommonSettings::startup_server_id(Some(server_id(1)), Some(server_id(2))),
+            Some(server_id(1))
+        );
+    }
+
+    #[test]
+    fn attributed_referrer() {
+        let referrer = |s: &str| Some(Referrer::from_str(s).unwrap());
+        assert_eq!(CommonSettings::attributed_referrer(None, None), None);
+        assert_eq!(
+            CommonSettings::attributed_referrer(None, referrer("crazygames")),
+            referrer("crazygames")
+        );
+        // Kept when navigating or revisiting without one.
+        assert_eq!(
+            CommonSettings::attributed_referrer(referrer("crazygames"), None),
+            referrer("crazygames")
+        );
+        assert_eq!(
+            CommonSettings::attributed_referrer(referrer("crazygames"), referrer("")),
+            referrer("crazygames")
+        );
+        assert_eq!(
+            CommonSettings::attributed_referrer(referrer("crazygames"), referrer("google")),
+            referrer("google")
+        );
+    }
+
+    #[test]
+    fn adjust_aim() {
+        let mut settings = CommonSettings::default();
+        let offset = Vec2::new(0.0, -5.0);
+        assert_eq!(settings.adjust_aim(offset, 10.0), offset);
+
+        settings.mouse_sensitivity = 0.5;
+        settings.invert_y = true;
+        assert!(settings
+            .adjust_aim(offset, 10.0)
+            .abs_diff_eq(Vec2::new(0.0, 2.5), 0.0001));
+
+        // Reach isn't limited.
+        assert_eq!(
+            settings.adjust_aim(Vec2::new(10.0, 0.0), 10.0),
+            Vec2::new(10.0, 0.0)
+        );
+        assert_eq!(
+            settings.adjust_aim(Vec2::new(20.0, 0.0), 10.0),
+            Vec2::new(20.0, 0.0)
+        );
+
+        // Never stops the mouse.
+        settings.mouse_sensitivity = 0.0;
+        assert!(settings.adjust_aim(offset, 10.0).y > 0.0);
+        settings.mouse_sensitivity = f32::NAN;
+        assert_eq!(settings.adjust_aim(offset, 10.0), Vec2::new(0.0, 5.0));
+    }
+
+    #[test]
+    fn validate_server_id() {
+        let removed = [server_id(2)];
+
+        assert_eq!(
+            CommonSettings::validate_server_id(Some(server_id(1)), &removed),
+            Some(server_id(1))
+        );
+        // Stored server is gone, so auto-select.
+        assert_eq!(
+            CommonSettings::validate_server_id(Some(server_id(2)), &removed),
+            None
+        );
+        assert_eq!(CommonSettings::validate_server_id(None, &removed), None);
+    }
+
+    #[test]
+    fn mute_key_toggles() {
+        let mut settings = CommonSettings::default();
+        assert_eq!(settings.muted_after_key(Key::M), Some(true));
+        assert_eq!(settings.muted_after_key(Key::N), None);
+
+        settings.muted = true;
+        assert_eq!(settings.muted_after_key(Key::M), Some(false));
+
+        // Rebound keys are resolved to the default key before reaching here.
+        settings.key_bindings = settings.key_bindings.with_binding(GameAction::Mute, Key::N);
+        let resolved = settings.key_bindings.resolve(Key::N).unwrap();
+        assert_eq!(settings.muted_after_key(resolved), Some(false));
+        assert_eq!(settings.key_bindings.resolve(Key::M), None);
+    }
+
+    #[test]
+    fn hud_key() {
+        let settings = CommonSettings::default();
+        assert!(settings.is_hud_key(Key::H));
+        assert!(!settings.is_hud_key(Key::Y));
+    }
 }
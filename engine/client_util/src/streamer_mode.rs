@@ -0,0 +1,149 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use core_protocol::dto::MessageDto;
+use glam::Vec2;
+use std::borrow::Cow;
+
+/// Decides what identifying information to hide from viewers of a stream, such as invitation links,
+/// precise coordinates, and codes in team messages (see
+/// [`CommonSettings::streamer_mode`][`crate::setting::CommonSettings::streamer_mode`]).
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct StreamerMode {
+    pub enabled: bool,
+}
+
+impl StreamerMode {
+    /// Replaces hidden text.
+    pub const MASK: &'static str = "••••••";
+    /// Path that precedes an invitation id in an invitation link.
+    const INVITATION_PATH: &'static str = "/invite/";
+    /// Number of grid cells along each axis of the world (see [`Self::grid_cell`]).
+    const GRID_CELLS: usize = 26;
+
+    /// Hides things only if `enabled`.
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+
+    /// Masks the invitation ids of any invitation links in `text`.
+    pub fn mask_invitation_links(self, text: &str) -> Cow<str> {
+        if !self.enabled || !text.contains(Self::INVITATION_PATH) {
+            return Cow::Borrowed(text);
+        }
+        let mut masked = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(index) = rest.find(Self::INVITATION_PATH) {
+            let (before, after) = rest.split_at(index + Self::INVITATION_PATH.len());
+            masked.push_str(before);
+            masked.push_str(Self::MASK);
+            let end = after
+                .find(|c: char| c.is_whitespace() || c == '/')
+                .unwrap_or(after.len());
+            rest = &after[end..];
+        }
+        masked.push_str(rest);
+        Cow::Owned(masked)
+    }
+
+    /// Returns true if coordinates should be shown as a [`Self::grid_cell`] instead.
+    pub fn hides_coordinates(self) -> bool {
+        self.enabled
+    }
+
+    /// Formats `position` as a coarse grid cell, like a map index (e.g. "M14"). Columns are
+    /// lettered from west to east and rows numbered from north to south, over a square world
+    /// extending `world_radius` from the origin.
+    pub fn grid_cell(position: Vec2, world_radius: f32) -> String {
+        let cells = Self::GRID_CELLS as f32;
+        let cell = |coordinate: f32| {
+            (((coordinate / world_radius + 1.0) * 0.5 * cells) as usize).min(Self::GRID_CELLS - 1)
+        };
+        let column = (b'A' + cell(position.x) as u8) as char;
+        let row = cell(-position.y) + 1;
+        format!("{}{}", column, row)
+    }
+
+    /// Returns true if `message` should not be shown, because it is a team message that may contain
+    /// a code (for joining a team, etc.).
+    pub fn hides_message(self, message: &MessageDto) -> bool {
+        self.enabled && message.whisper && contains_code(&message.text)
+    }
+}
+
+/// Returns true if `text` contains an invitation link, or something that looks like a code (4 or
+/// more letters and digits, at least one of which is a digit).
+fn contains_code(text: &str) -> bool {
+    text.contains(StreamerMode::INVITATION_PATH)
+        || text
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .any(|word| word.len() >= 4 && word.contains(|c: char| c.is_ascii_digit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::streamer_mode::StreamerMode;
+    use core_protocol::dto::MessageDto;
+    use core_protocol::name::PlayerAlias;
+    use glam::Vec2;
+
+    const ON: StreamerMode = StreamerMode { enabled: true };
+    const OFF: StreamerMode = StreamerMode { enabled: false };
+
+    #[test]
+    fn mask_invitation_links() {
+        let text = "join https://mk48.io/invite/1234567/ or mk48.io/invite/42";
+        assert_eq!(OFF.mask_invitation_links(text), text);
+        let masked = ON.mask_invitation_links(text);
+        assert_eq!(
+            masked,
+            "join https://mk48.io/invite/••••••/ or mk48.io/invite/••••••"
+        );
+        assert!(!masked.contains("1234567") && !masked.contains("42"));
+        assert_eq!(ON.mask_invitation_links("no links"), "no links");
+    }
+
+    #[test]
+    fn grid_cell() {
+        assert!(!OFF.hides_coordinates());
+        assert!(ON.hides_coordinates());
+
+        assert_eq!(
+            StreamerMode::grid_cell(Vec2::new(-1000.0, 1000.0), 1000.0),
+            "A1"
+        );
+        assert_eq!(
+            StreamerMode::grid_cell(Vec2::new(1000.0, -1000.0), 1000.0),
+            "Z26"
+        );
+        assert_eq!(StreamerMode::grid_cell(Vec2::ZERO, 1000.0), "N14");
+        // Nearby positions share a cell, so don't reveal precise coordinates.
+        assert_eq!(
+            StreamerMode::grid_cell(Vec2::new(3.0, 4.0), 1000.0),
+            StreamerMode::grid_cell(Vec2::new(20.0, 30.0), 1000.0)
+        );
+    }
+
+    #[test]
+    fn hides_message() {
+        let message = |text: &str, whisper: bool| MessageDto {
+            alias: PlayerAlias::new_unsanitized("Player"),
+            date_sent: 0,
+            player_id: None,
+            team_captain: false,
+            team_name: None,
+            text: String::from(text),
+            whisper,
+        };
+
+        for text in ["team code AB12", "mk48.io/invite/42", "use 98765"] {
+            assert!(ON.hides_message(&message(text, true)), "{}", text);
+            assert!(!OFF.hides_message(&message(text, true)), "{}", text);
+            // Only team messages are suppressed.
+            assert!(!ON.hides_message(&message(text, false)), "{}", text);
+        }
+        for text in ["hello", "go to 12", "need backup"] {
+            assert!(!ON.hides_message(&message(text, true)), "{}", text);
+        }
+    }
+}
@@ -0,0 +1,169 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::mouse::MouseButtonState;
+use glam::Vec2;
+
+/// A gesture made with a single finger on a touch screen. Positions are in client pixels.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TouchGesture {
+    /// Two quick taps at about the same position.
+    DoubleTap(Vec2),
+    /// Touching without moving for a while.
+    LongPress(Vec2),
+}
+
+/// Recognizes [`TouchGesture`]s from the position of a single touch over time.
+#[derive(Debug, Default)]
+pub struct TouchGestures {
+    /// The current touch, if any.
+    press: Option<Press>,
+    /// Where and when the last tap ended, for recognizing a [`TouchGesture::DoubleTap`].
+    last_tap: Option<(Vec2, f32)>,
+    /// Ignore touches until all of them end (e.g. after a pinch).
+    cancelled: bool,
+}
+
+#[derive(Debug)]
+struct Press {
+    start: Vec2,
+    time: f32,
+    /// Moved too far to be a tap or long-press.
+    moved: bool,
+    /// Already recognized as a [`TouchGesture::LongPress`].
+    long: bool,
+}
+
+impl TouchGestures {
+    /// Maximum seconds between the end of one tap and the start of the next.
+    const DOUBLE_TAP_SECONDS: f32 = 0.3;
+    /// Maximum pixels between the two taps of a [`TouchGesture::DoubleTap`].
+    const DOUBLE_TAP_DISTANCE: f32 = 30.0;
+    /// Minimum seconds to hold a [`TouchGesture::LongPress`].
+    const LONG_PRESS_SECONDS: f32 = 0.5;
+    /// Maximum pixels a touch can move and still tap or long-press.
+    const SLOP: f32 = 10.0;
+
+    /// Updates with the `position` of the only touch, or [`None`] if it ended. Returns a
+    /// [`TouchGesture::DoubleTap`] when the second tap ends.
+    pub fn touch(&mut self, position: Option<Vec2>, time: f32) -> Option<TouchGesture> {
+        if let Some(position) = position {
+            if self.cancelled {
+                return None;
+            }
+            let press = self.press.get_or_insert(Press {
+                start: position,
+                time,
+                moved: false,
+                long: false,
+            });
+            press.moved |= position.distance(press.start) > Self::SLOP;
+            return None;
+        }
+
+        self.cancelled = false;
+        let press = self.press.take()?;
+        if press.moved || press.long || time > press.time + MouseButtonState::MAX_CLICK_TIME {
+            self.last_tap = None;
+            return None;
+        }
+        match self.last_tap.take() {
+            Some((last, end))
+                if press.time <= end + Self::DOUBLE_TAP_SECONDS
+                    && press.start.distance(last) <= Self::DOUBLE_TAP_DISTANCE =>
+            {
+                Some(TouchGesture::DoubleTap(press.start))
+            }
+            _ => {
+                self.last_tap = Some((press.start, time));
+                None
+            }
+        }
+    }
+
+    /// Returns a [`TouchGesture::LongPress`] once the current touch is held long enough. Must be
+    /// called regularly, since a touch that doesn't move has no events.
+    pub fn update(&mut self, time: f32) -> Option<TouchGesture> {
+        let press = self.press.as_mut()?;
+        if press.moved || press.long || time < press.time + Self::LONG_PRESS_SECONDS {
+            return None;
+        }
+        press.long = true;
+        Some(TouchGesture::LongPress(press.start))
+    }
+
+    /// Stops recognizing gestures until all touches end, e.g. if more than one finger is down.
+    pub fn cancel(&mut self) {
+        self.press = None;
+        self.last_tap = None;
+        self.cancelled = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::touch_gesture::{TouchGesture, TouchGestures};
+    use glam::Vec2;
+
+    #[test]
+    fn double_tap() {
+        let mut gestures = TouchGestures::default();
+        let tap = |gestures: &mut TouchGestures, position: Vec2, time: f32| {
+            assert_eq!(gestures.touch(Some(position), time), None);
+            gestures.touch(None, time + 0.1)
+        };
+
+        assert_eq!(tap(&mut gestures, Vec2::ZERO, 0.0), None);
+        assert_eq!(
+            tap(&mut gestures, Vec2::new(5.0, 5.0), 0.3),
+            Some(TouchGesture::DoubleTap(Vec2::new(5.0, 5.0)))
+        );
+        // Doesn't chain into another double-tap.
+        assert_eq!(tap(&mut gestures, Vec2::ZERO, 0.6), None);
+
+        // Too slow.
+        assert_eq!(tap(&mut gestures, Vec2::ZERO, 2.0), None);
+        assert_eq!(tap(&mut gestures, Vec2::ZERO, 2.5), None);
+
+        // Too far.
+        assert_eq!(tap(&mut gestures, Vec2::new(100.0, 0.0), 2.7), None);
+
+        // Not a tap if held too long.
+        assert_eq!(gestures.touch(Some(Vec2::new(100.0, 0.0)), 2.9), None);
+        assert_eq!(gestures.touch(None, 3.4), None);
+    }
+
+    #[test]
+    fn long_press() {
+        let mut gestures = TouchGestures::default();
+        assert_eq!(gestures.touch(Some(Vec2::ZERO), 0.0), None);
+        // Small movement is fine.
+        assert_eq!(gestures.touch(Some(Vec2::new(3.0, 4.0)), 0.2), None);
+        assert_eq!(gestures.update(0.4), None);
+        assert_eq!(
+            gestures.update(0.5),
+            Some(TouchGesture::LongPress(Vec2::ZERO))
+        );
+        // Only once.
+        assert_eq!(gestures.update(0.6), None);
+        assert_eq!(gestures.touch(None, 1.0), None);
+
+        // Moving too far cancels it.
+        assert_eq!(gestures.touch(Some(Vec2::ZERO), 2.0), None);
+        assert_eq!(gestures.touch(Some(Vec2::new(20.0, 0.0)), 2.2), None);
+        assert_eq!(gestures.update(3.0), None);
+        assert_eq!(gestures.touch(None, 3.0), None);
+
+        // So does another finger, until all fingers lift.
+        assert_eq!(gestures.touch(Some(Vec2::ZERO), 4.0), None);
+        gestures.cancel();
+        assert_eq!(gestures.touch(Some(Vec2::ZERO), 4.1), None);
+        assert_eq!(gestures.update(5.0), None);
+        assert_eq!(gestures.touch(None, 5.0), None);
+        assert_eq!(gestures.touch(Some(Vec2::ZERO), 6.0), None);
+        assert_eq!(
+            gestures.update(6.5),
+            Some(TouchGesture::LongPress(Vec2::ZERO))
+        );
+    }
+}
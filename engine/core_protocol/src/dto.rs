@@ -40,6 +40,8 @@ impl Ord for LeaderboardDto {
 pub struct LiveboardDto {
     pub player_id: PlayerId,
     pub score: u32,
+    /// Number of kills since spawning.
+    pub kills: u32,
     pub team_captain: bool,
     pub team_id: Option<TeamId>,
 }
@@ -58,6 +60,7 @@ impl Ord for LiveboardDto {
                 self.team_id
                     .cmp(&other.team_id)
                     .then_with(|| self.team_captain.cmp(&other.team_captain))
+                    .then_with(|| self.kills.cmp(&other.kills))
             })
         })
     }
@@ -75,11 +78,13 @@ mod test {
             LiveboardDto {
                 player_id: PlayerId(NonZeroU32::new(2).unwrap()),
                 score: 3,
+                kills: 1,
                 team_captain: true,
                 team_id: Some(TeamId(NonZeroU32::new(1).unwrap())),
             } < LiveboardDto {
                 player_id: PlayerId(NonZeroU32::new(1).unwrap()),
                 score: 5,
+                kills: 0,
                 team_captain: false,
                 team_id: None,
             }
@@ -175,6 +180,33 @@ pub struct TeamDto {
     pub closed: bool,
 }
 
+/// A formation, set by a team captain, that team members can line up on.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FormationDto {
+    pub kind: FormationKind,
+    /// Position of the captain's slot, in world units.
+    pub x: f32,
+    pub y: f32,
+    /// Direction the formation faces, in radians.
+    pub angle: f32,
+}
+
+impl FormationDto {
+    /// Returns whether all values are finite, i.e. safe to use.
+    pub fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.angle.is_finite()
+    }
+}
+
+/// Shape of a [`FormationDto`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum FormationKind {
+    /// Side by side.
+    Line,
+    /// V shape, with the captain at the front.
+    Wedge,
+}
+
 /// Filter daily metrics.
 // TODO: Not a DTO?
 #[derive(Copy, Clone, Debug, Serialize, Deserialize)]
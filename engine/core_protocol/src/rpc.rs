@@ -162,6 +162,8 @@ pub enum Update<GU> {
 pub enum TeamRequest {
     Accept(PlayerId),
     Create(TeamName),
+    /// Sets or clears (captain only) the team's formation.
+    Formation(Option<FormationDto>),
     Join(TeamId),
     Kick(PlayerId),
     Leave,
@@ -175,6 +177,8 @@ pub enum TeamUpdate {
     Accepted(PlayerId),
     AddedOrUpdated(Owned<[TeamDto]>),
     Created(TeamId, TeamName),
+    /// The team's current formation, if any, for members only.
+    Formation(Option<FormationDto>),
     /// A complete enumeration of joiners, for the team captain only.
     Joiners(Box<[PlayerId]>),
     Joining(TeamId),
@@ -282,6 +286,11 @@ pub enum ClientUpdate {
     AliasSet(PlayerAlias),
     EvalSnippet(Owned<str>),
     FpsTallied,
+    /// Message of the day, to be shown once per `version`. Empty `text` clears it.
+    Motd {
+        version: u32,
+        text: Owned<str>,
+    },
     SessionCreated {
         arena_id: ArenaId,
         cohort_id: CohortId,
@@ -366,6 +375,13 @@ mod admin {
         SetAllowWebSocketJson(bool),
         SetDistributeLoad(bool),
         SetGameClient(minicdn::EmbeddedMiniCdn),
+        /// Set the message of the day, shown to connected clients and those that connect later.
+        /// Bump `version` to show it again to those who dismissed a previous one. Empty `text`
+        /// clears it.
+        SetMotd {
+            version: u32,
+            text: Owned<str>,
+        },
         SetRedirect(Option<ServerId>),
         SetSnippet {
             cohort_id: Option<CohortId>,
@@ -387,6 +403,7 @@ mod admin {
         GameClientSet(u64),
         GamesRequested(Box<[(GameId, f32)]>),
         HttpServerRestarting,
+        MotdSet,
         PlayerAliasOverridden(PlayerAlias),
         PlayerModeratorOverridden(bool),
         PlayerMuted(usize),
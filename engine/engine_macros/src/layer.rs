@@ -76,6 +76,10 @@ impl Parse for LayerInput {
 }
 
 pub(crate) fn derive_layer(input: LayerInput) -> TokenStream {
+    layer_impl(input).into()
+}
+
+fn layer_impl(input: LayerInput) -> proc_macro2::TokenStream {
     let LayerInput {
         ident,
         named: FieldsNamed { named, .. },
@@ -85,6 +89,7 @@ pub(crate) fn derive_layer(input: LayerInput) -> TokenStream {
     let mut pre_prepares = Vec::with_capacity(named.len());
     let mut pre_renders = Vec::with_capacity(named.len());
     let mut renders = Vec::with_capacity(named.len());
+    let mut warmups = Vec::with_capacity(named.len());
 
     fn is_field_attr(a: &Attribute) -> bool {
         a.path.segments.len() == 1 && a.path.segments[0].ident == "layer"
@@ -114,6 +119,9 @@ pub(crate) fn derive_layer(input: LayerInput) -> TokenStream {
         renders.push(quote! {
             self.#ident.render(renderer);
         });
+        warmups.push(quote! {
+            self.#ident.warmup(renderer);
+        });
     }
 
     let (impl_decl, bound) = bound.map_or_else(
@@ -127,7 +135,7 @@ pub(crate) fn derive_layer(input: LayerInput) -> TokenStream {
         quote!(renderer)
     };
 
-    let output = quote! {
+    quote! {
         #impl_decl for #ident {
             fn pre_prepare(&mut self, renderer: &#c::Renderer<#bound>) {
                 #(#pre_prepares)*
@@ -140,7 +148,51 @@ pub(crate) fn derive_layer(input: LayerInput) -> TokenStream {
             fn render(&mut self, renderer: &#c::Renderer<#bound>) {
                 #(#renders)*
             }
+
+            fn warmup(&mut self, renderer: &#c::Renderer<#bound>) {
+                #(#warmups)*
+            }
         }
-    };
-    output.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::layer::{layer_impl, LayerInput};
+
+    #[test]
+    fn warmup_each_layer() {
+        let input: LayerInput = syn::parse_str(
+            r#"
+            struct Layers {
+                background: BackgroundLayer,
+                particles: ParticleLayer,
+                sprites: SpriteLayer,
+            }
+            "#,
+        )
+        .unwrap();
+        let output = layer_impl(input).to_string();
+        for field in ["background", "particles", "sprites"] {
+            let warmup = format!("self . {} . warmup (renderer) ;", field);
+            assert!(output.contains(&warmup), "{} not in {}", warmup, output);
+        }
+    }
+
+    #[test]
+    fn warmup_marked_layers() {
+        let input: LayerInput = syn::parse_str(
+            r#"
+            struct Layers {
+                #[layer]
+                graphics: GraphicLayer,
+                time: f32,
+            }
+            "#,
+        )
+        .unwrap();
+        let output = layer_impl(input).to_string();
+        assert!(output.contains("self . graphics . warmup (renderer) ;"));
+        assert!(!output.contains("self . time"));
+    }
 }
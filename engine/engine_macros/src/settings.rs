@@ -18,6 +18,8 @@ pub(crate) fn derive_settings(input: TokenStream) -> TokenStream {
             let mut getters = Vec::with_capacity(named.len());
             let mut setters = Vec::with_capacity(named.len());
             let mut validators = Vec::with_capacity(named.len());
+            let mut exporters = Vec::with_capacity(named.len());
+            let mut importers = Vec::with_capacity(named.len());
 
             for Field {
                 ident, ty, attrs, ..
@@ -31,6 +33,8 @@ pub(crate) fn derive_settings(input: TokenStream) -> TokenStream {
 
                 let mut storage = quote! { local };
                 let mut optional = false;
+                // Only settings in local storage are exported.
+                let mut exported = true;
                 let mut validations = Vec::new();
 
                 for attribute in attrs.into_iter().filter(|a| a.path.is_ident("setting")) {
@@ -64,8 +68,12 @@ pub(crate) fn derive_settings(input: TokenStream) -> TokenStream {
                                         optional = true;
                                     } else if path.is_ident("volatile") {
                                         storage = quote! { session };
+                                        exported = false;
                                     } else if path.is_ident("no_store") {
                                         storage = quote! { no_op };
+                                        exported = false;
+                                    } else if path.is_ident("no_export") {
+                                        exported = false;
                                     } else {
                                         panic!("Unexpected path: {}", path.get_ident().unwrap());
                                     }
@@ -129,6 +137,36 @@ pub(crate) fn derive_settings(input: TokenStream) -> TokenStream {
                     validators.push(validator);
                 }
 
+                if exported {
+                    let (exporter, importer) = if optional {
+                        (
+                            quote! {
+                                if let Some(value) = &self.#ident {
+                                    pairs.push((#ident_string, value.to_string()));
+                                }
+                            },
+                            quote! {
+                                if let Some(value) = pairs.get(#ident_string).and_then(|v| v.parse().ok()) {
+                                    self.#setter_name(Some(value), browser_storages);
+                                }
+                            },
+                        )
+                    } else {
+                        (
+                            quote! {
+                                pairs.push((#ident_string, self.#ident.to_string()));
+                            },
+                            quote! {
+                                if let Some(value) = pairs.get(#ident_string).and_then(|v| v.parse().ok()) {
+                                    self.#setter_name(value, browser_storages);
+                                }
+                            },
+                        )
+                    };
+                    exporters.push(exporter);
+                    importers.push(importer);
+                }
+
                 loaders.push(loader);
                 getters.push(getter);
                 setters.push(setter);
@@ -141,6 +179,20 @@ pub(crate) fn derive_settings(input: TokenStream) -> TokenStream {
                             #(#loaders)*
                         }
                     }
+
+                    fn export(&self) -> Vec<(&'static str, String)> {
+                        let mut pairs = Vec::new();
+                        #(#exporters)*
+                        pairs
+                    }
+
+                    fn import(
+                        &mut self,
+                        pairs: &std::collections::HashMap<String, String>,
+                        browser_storages: &mut BrowserStorages,
+                    ) {
+                        #(#importers)*
+                    }
                 }
 
                 impl #ident {
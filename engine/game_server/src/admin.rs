@@ -330,6 +330,19 @@ impl<G: GameArenaService> AdminRepo<G> {
         }
     }
 
+    fn set_motd(
+        clients: &mut ClientRepo<G>,
+        version: u32,
+        text: Arc<str>,
+    ) -> Result<AdminUpdate, &'static str> {
+        if text.len() > 1024 {
+            Err("motd too long")
+        } else {
+            clients.set_motd(version, text);
+            Ok(AdminUpdate::MotdSet)
+        }
+    }
+
     /// Request summary of metrics for the current calendar calendar hour.
     fn request_summary(
         infrastructure: &mut Infrastructure<G>,
@@ -641,6 +654,11 @@ impl<G: GameArenaService> Handler<ParameterizedAdminRequest> for Infrastructure<
                 referrer,
                 snippet,
             ))),
+            AdminRequest::SetMotd { version, text } => Box::pin(fut::ready(AdminRepo::set_motd(
+                &mut self.context_service.context.clients,
+                version,
+                text,
+            ))),
             // Handle asynchronous requests (i.e. those that access database).
             AdminRequest::RequestSeries {
                 game_id,
@@ -61,6 +61,10 @@ pub struct ClientRepo<G: GameArenaService> {
     database_rate_limiter: RateLimiter,
     pending_session_write: Vec<SessionItem>,
     pub(crate) snippets: HashMap<(Option<CohortId>, Option<Referrer>), Arc<str>>,
+    /// Message of the day and its version.
+    motd: Option<(u32, Arc<str>)>,
+    /// Message of the day to broadcast at the next update, because it changed.
+    motd_update: Option<(u32, Arc<str>)>,
     /// Where to log traces to.
     trace_log: Option<Arc<str>>,
     _spooky: PhantomData<G>,
@@ -78,6 +82,8 @@ impl<G: GameArenaService> ClientRepo<G> {
             database_rate_limiter: RateLimiter::new(Duration::from_secs(30), 0),
             pending_session_write: Vec::new(),
             snippets: Self::load_default_snippets(),
+            motd: None,
+            motd_update: None,
             trace_log: trace_log.map(Into::into),
             _spooky: PhantomData,
         }
@@ -298,6 +304,15 @@ impl<G: GameArenaService> ClientRepo<G> {
             });
         }
 
+        if let Some((version, text)) = self.motd.as_ref() {
+            let _ = register_observer.send(ObserverUpdate::Send {
+                message: Update::Client(ClientUpdate::Motd {
+                    version: *version,
+                    text: Arc::clone(text),
+                }),
+            });
+        }
+
         // Change status to connected.
         let new_status = ClientStatus::Connected {
             observer: register_observer.clone(),
@@ -398,6 +413,13 @@ impl<G: GameArenaService> ClientRepo<G> {
         }
     }
 
+    /// Sets the message of the day (empty `text` clears it), which is sent to clients as they
+    /// connect, and broadcast to those already connected at the next update.
+    pub(crate) fn set_motd(&mut self, version: u32, text: Arc<str>) {
+        self.motd = Some((version, Arc::clone(&text))).filter(|(_, text)| !text.trim().is_empty());
+        self.motd_update = Some((version, text));
+    }
+
     /// Update all clients with game state.
     pub(crate) fn update(
         &mut self,
@@ -434,6 +456,7 @@ impl<G: GameArenaService> ClientRepo<G> {
             .collect();
         let liveboard_update = liveboard.delta(&*players, &*teams);
         let leaderboard_update: Vec<_> = leaderboard.deltas_nondestructive().collect();
+        let motd_update = self.motd_update.take();
 
         let players = &*players;
         players.players.maybe_par_iter().for_each(
@@ -482,7 +505,7 @@ impl<G: GameArenaService> ClientRepo<G> {
                         }
                     }
 
-                    if let Some((chat_update, (members, joiners, joins))) =
+                    if let Some((chat_update, (members, joiners, joins, formation))) =
                         player_chat_team_updates.get(&player_id)
                     {
                         if let Some(chat_update) = chat_update {
@@ -515,6 +538,12 @@ impl<G: GameArenaService> ClientRepo<G> {
                                 )),
                             });
                         }
+
+                        if let Some(formation) = formation {
+                            let _ = observer.send(ObserverUpdate::Send {
+                                message: Update::Team(TeamUpdate::Formation(*formation)),
+                            });
+                        }
                     } else {
                         debug_assert!(
                             false,
@@ -540,6 +569,15 @@ impl<G: GameArenaService> ClientRepo<G> {
                         });
                     }
 
+                    if let Some((version, text)) = motd_update.as_ref() {
+                        let _ = observer.send(ObserverUpdate::Send {
+                            message: Update::Client(ClientUpdate::Motd {
+                                version: *version,
+                                text: Arc::clone(text),
+                            }),
+                        });
+                    }
+
                     if let Some((added, removed)) = server_delta.as_ref() {
                         if !added.is_empty() {
                             let _ = observer.send(ObserverUpdate::Send {
@@ -56,6 +56,7 @@ impl<G: GameArenaService> LiveboardRepo<G> {
                 team_id: player.team_id(),
                 player_id: player.player_id,
                 score: player.score,
+                kills: player.kills,
             })
         }));
 
@@ -378,6 +378,8 @@ impl<G: GameArenaService> Debug for PlayerTuple<G> {
 pub struct PlayerData<G: GameArenaService> {
     pub player_id: PlayerId,
     pub score: u32,
+    /// Number of kills since spawning, for the liveboard.
+    pub kills: u32,
     /// Whether the player was alive last time we checked.
     pub(crate) was_alive: bool,
     /// Whether the player was out of game last time we checked.
@@ -397,6 +399,7 @@ impl<G: GameArenaService> PlayerData<G> {
         Self {
             player_id,
             score: G::DEFAULT_SCORE,
+            kills: 0,
             was_alive: false,
             was_out_of_game: false,
             was_ever_alive: false,
@@ -7,7 +7,7 @@ use crate::player::{PlayerData, PlayerRepo};
 use crate::unwrap_or_return;
 use crate::util::diff_small_n;
 use atomic_refcell::AtomicRefMut;
-use core_protocol::dto::TeamDto;
+use core_protocol::dto::{FormationDto, TeamDto};
 use core_protocol::id::{PlayerId, TeamId};
 use core_protocol::name::TeamName;
 use core_protocol::rpc::{TeamRequest, TeamUpdate};
@@ -24,6 +24,8 @@ pub struct TeamData<G: GameArenaService> {
     pub members: OrderedSet<PlayerId>,
     /// In order of request. They are never reordered.
     joiners: OrderedSet<PlayerId>,
+    /// Set by the captain.
+    pub formation: Option<FormationDto>,
     _spooky: PhantomData<G>,
 }
 
@@ -34,6 +36,7 @@ impl<G: GameArenaService> TeamData<G> {
             name,
             members: OrderedSet::new_with_one(captain),
             joiners: OrderedSet::new(),
+            formation: None,
             _spooky: PhantomData,
         }
     }
@@ -94,6 +97,8 @@ pub struct ClientTeamData {
     previous_joiners: OrderedSet<PlayerId>,
     /// For diffing.
     previous_joins: VecDeque<TeamId>,
+    /// For diffing.
+    previous_formation: Option<FormationDto>,
 }
 
 impl Drop for PlayerTeamData {
@@ -305,6 +310,32 @@ impl<G: GameArenaService> TeamRepo<G> {
         }
     }
 
+    fn set_formation(
+        &mut self,
+        req_player_id: PlayerId,
+        formation: Option<FormationDto>,
+        players: &mut PlayerRepo<G>,
+    ) -> Result<TeamUpdate, &'static str> {
+        if !formation.map(|f| f.is_finite()).unwrap_or(true) {
+            return Err("invalid formation");
+        }
+
+        let req_player = players
+            .borrow_player(req_player_id)
+            .ok_or("requesting player nonexistent")?;
+
+        let team_id = req_player.team_id().ok_or("not in team")?;
+        let team = self.teams.get_mut(&team_id).ok_or_else(|| {
+            debug_assert!(false, "team id should have been cleared");
+            "nonexistent team"
+        })?;
+        if !team.is_captain(req_player_id) {
+            return Err("not captain");
+        }
+        team.formation = formation;
+        Ok(TeamUpdate::Formation(formation))
+    }
+
     fn create_team(
         &mut self,
         req_player_id: PlayerId,
@@ -517,6 +548,9 @@ impl<G: GameArenaService> TeamRepo<G> {
                 self.promote_player(req_player_id, player_id, players)
             }
             TeamRequest::Create(name) => self.create_team(req_player_id, name, players),
+            TeamRequest::Formation(formation) => {
+                self.set_formation(req_player_id, formation, players)
+            }
             TeamRequest::Kick(player_id) => self.kick_player(req_player_id, player_id, players),
             TeamRequest::Leave => self.quit_team(req_player_id, players),
             TeamRequest::Reject(player_id) => {
@@ -561,7 +595,7 @@ impl<G: GameArenaService> TeamRepo<G> {
         }
     }
 
-    /// Return delta in members, joiners, and joins for a given player.
+    /// Return delta in members, joiners, joins, and formation for a given player.
     /// Only returns [`None`] at the outer level if the player doesn't exist or isn't a real player.
     pub(crate) fn player_delta(
         &mut self,
@@ -571,6 +605,7 @@ impl<G: GameArenaService> TeamRepo<G> {
         Option<OrderedSet<PlayerId>>,
         Option<OrderedSet<PlayerId>>,
         Option<VecDeque<TeamId>>,
+        Option<Option<FormationDto>>,
     )> {
         let mut player = players.borrow_player_mut(player_id)?;
         let player = &mut *player;
@@ -587,8 +622,9 @@ impl<G: GameArenaService> TeamRepo<G> {
         let previous_members = &mut team.previous_members;
         let previous_joiners = &mut team.previous_joiners;
         let previous_joins = &mut team.previous_joins;
+        let previous_formation = &mut team.previous_formation;
 
-        let (members, joiners, joins) = match &player.team.status {
+        let (members, joiners, joins, formation) = match &player.team.status {
             PlayerTeamStatus::Teamed { team_id } => {
                 if let Some(team) = self.teams.get_mut(team_id) {
                     let joiners = if team.is_captain(player_id) {
@@ -598,15 +634,15 @@ impl<G: GameArenaService> TeamRepo<G> {
                     };
 
                     // In a team, not joining any other team.
-                    (&team.members, joiners, &*EMPTY_TEAMS)
+                    (&team.members, joiners, &*EMPTY_TEAMS, team.formation)
                 } else {
                     debug_assert!(false, "player's team doesn't exist");
-                    (&EMPTY_PLAYERS, &EMPTY_PLAYERS, &*EMPTY_TEAMS)
+                    (&EMPTY_PLAYERS, &EMPTY_PLAYERS, &*EMPTY_TEAMS, None)
                 }
             }
             PlayerTeamStatus::Solo { joins } => {
-                // Not in a team, don't have members or joiners.
-                (&EMPTY_PLAYERS, &EMPTY_PLAYERS, joins)
+                // Not in a team, don't have members, joiners, or a formation.
+                (&EMPTY_PLAYERS, &EMPTY_PLAYERS, joins, None)
             }
         };
 
@@ -623,6 +659,10 @@ impl<G: GameArenaService> TeamRepo<G> {
                 *previous_joins = joins.clone();
                 joins.clone()
             }),
+            (formation != *previous_formation).then(|| {
+                *previous_formation = formation;
+                formation
+            }),
         ))
     }
 
@@ -660,6 +700,7 @@ mod test {
     use crate::game_service::{GameArenaService, MockGame};
     use crate::player::{PlayerData, PlayerRepo, PlayerTuple};
     use crate::team::TeamRepo;
+    use core_protocol::dto::{FormationDto, FormationKind};
     use core_protocol::id::{PlayerId, TeamId};
     use core_protocol::name::TeamName;
     use core_protocol::rpc::{TeamRequest, TeamUpdate};
@@ -737,6 +778,41 @@ mod test {
 
         assert_eq!(teams.teams.len(), 2);
 
+        // Only captains can set formations, and only finite ones.
+        let formation = FormationDto {
+            kind: FormationKind::Wedge,
+            x: 10.0,
+            y: -5.0,
+            angle: 1.0,
+        };
+        let res = teams.handle_team_request(
+            existing_players[0].0,
+            TeamRequest::Formation(Some(formation)),
+            &mut players,
+        );
+        assert!(
+            matches!(res, Ok(TeamUpdate::Formation(Some(_)))),
+            "{:?}",
+            res
+        );
+        assert_eq!(teams.teams[&team1_id].formation, Some(formation));
+        let res = teams.handle_team_request(
+            existing_players[0].0,
+            TeamRequest::Formation(Some(FormationDto {
+                x: f32::NAN,
+                ..formation
+            })),
+            &mut players,
+        );
+        assert!(res.is_err(), "{:?}", res);
+        let res = teams.handle_team_request(
+            existing_players[2].0,
+            TeamRequest::Formation(None),
+            &mut players,
+        );
+        assert!(res.is_err(), "{:?}", res);
+        assert_eq!(teams.teams[&team1_id].formation, Some(formation));
+
         for i in 20..20 + MockGame::TEAM_JOINERS_MAX {
             let res = teams.handle_team_request(
                 existing_players[i].0,
@@ -10,6 +10,8 @@
 
 use js_sys::Reflect;
 use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use web_sys::{Document, HtmlCanvasElement, Window};
@@ -52,30 +54,172 @@ pub fn error_message(error: &JsValue) -> Option<String> {
         .and_then(JsValue::as_string)
 }
 
+/// Severity of a log message, from least to most severe.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[repr(u8)]
+pub enum LogLevel {
+    /// Very verbose, e.g. per-frame details.
+    Trace,
+    /// Useful while developing.
+    Debug,
+    /// Noteworthy events.
+    Info,
+    /// Something went wrong, but was recovered from.
+    Warn,
+    /// Something went wrong.
+    Error,
+}
+
+impl LogLevel {
+    /// All levels, from least to most severe.
+    pub const ALL: [Self; 5] = [
+        Self::Trace,
+        Self::Debug,
+        Self::Info,
+        Self::Warn,
+        Self::Error,
+    ];
+
+    /// Default minimum level: everything in debug builds, and no debug spam in release builds.
+    pub const DEFAULT: Self = if cfg!(debug_assertions) {
+        Self::Trace
+    } else {
+        Self::Info
+    };
+
+    fn from_u8(n: u8) -> Self {
+        Self::ALL[(n as usize).min(Self::ALL.len() - 1)]
+    }
+}
+
+impl Default for LogLevel {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Trace => "trace",
+            Self::Debug => "debug",
+            Self::Info => "info",
+            Self::Warn => "warn",
+            Self::Error => "error",
+        })
+    }
+}
+
+impl FromStr for LogLevel {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|level| level.to_string().eq_ignore_ascii_case(s))
+            .ok_or(())
+    }
+}
+
+/// Messages below this [`LogLevel`] are not logged.
+static MIN_LOG_LEVEL: AtomicU8 = AtomicU8::new(LogLevel::DEFAULT as u8);
+
+/// Sets the minimum [`LogLevel`] of messages that are logged.
+pub fn set_log_level(level: LogLevel) {
+    MIN_LOG_LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// Gets the minimum [`LogLevel`] of messages that are logged.
+pub fn log_level() -> LogLevel {
+    LogLevel::from_u8(MIN_LOG_LEVEL.load(Ordering::Relaxed))
+}
+
+/// Returns if messages of `level` are logged.
+pub fn log_enabled(level: LogLevel) -> bool {
+    level >= log_level()
+}
+
+/// Log to JavaScript's console at a given [`LogLevel`], if it is enabled.
+#[macro_export]
+macro_rules! console_log_level {
+    ($level:expr, $($t:tt)*) => {
+        if $crate::log_enabled($level) {
+            $crate::level_args($level, &format_args!($($t)*))
+        }
+    };
+}
+
 /// Log an error to JavaScript's console. Use this instead of [`eprintln!`].
 #[macro_export]
 macro_rules! console_error {
     ($($t:tt)*) => {
-        $crate::log_args(&format_args!($($t)*))
+        $crate::console_log_level!($crate::LogLevel::Error, $($t)*)
+    };
+}
+
+/// Log a warning to JavaScript's console.
+#[macro_export]
+macro_rules! console_warn {
+    ($($t:tt)*) => {
+        $crate::console_log_level!($crate::LogLevel::Warn, $($t)*)
     };
 }
 
 /// Log to JavaScript's console. Use this instead of [`println!`].
 #[macro_export]
+macro_rules! console_info {
+    ($($t:tt)*) => {
+        $crate::console_log_level!($crate::LogLevel::Info, $($t)*)
+    };
+}
+
+/// Log debugging information to JavaScript's console.
+#[macro_export]
+macro_rules! console_debug {
+    ($($t:tt)*) => {
+        $crate::console_log_level!($crate::LogLevel::Debug, $($t)*)
+    };
+}
+
+/// Log verbose debugging information to JavaScript's console.
+#[macro_export]
+macro_rules! console_trace {
+    ($($t:tt)*) => {
+        $crate::console_log_level!($crate::LogLevel::Trace, $($t)*)
+    };
+}
+
+/// Log to JavaScript's console. Same as [`console_info!`].
+#[macro_export]
 macro_rules! console_log {
     ($($t:tt)*) => {
-        $crate::log_args(&format_args!($($t)*))
+        $crate::console_info!($($t)*)
     };
 }
 
 #[wasm_bindgen]
 extern "C" {
+    #[wasm_bindgen(js_namespace = console)]
+    fn debug(s: &str);
+
     #[wasm_bindgen(js_namespace = console)]
     fn error(s: &str);
 
     #[wasm_bindgen(js_namespace = console)]
     fn log(s: &str);
 
+    #[wasm_bindgen(js_namespace = console)]
+    fn warn(s: &str);
+}
+
+#[doc(hidden)]
+pub fn level_args(level: LogLevel, args: &fmt::Arguments) {
+    match level {
+        LogLevel::Trace | LogLevel::Debug => debug(&args.to_string()),
+        LogLevel::Info => log_args(args),
+        LogLevel::Warn => warn(&args.to_string()),
+        LogLevel::Error => error_args(args),
+    }
 }
 
 #[doc(hidden)]
@@ -87,3 +231,33 @@ pub fn error_args(args: &fmt::Arguments) {
 pub fn log_args(args: &fmt::Arguments) {
     log(&args.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{log_enabled, log_level, set_log_level, LogLevel};
+    use std::str::FromStr;
+
+    #[test]
+    fn log_level_filter() {
+        set_log_level(LogLevel::Warn);
+        assert_eq!(log_level(), LogLevel::Warn);
+        assert!(!log_enabled(LogLevel::Trace));
+        assert!(!log_enabled(LogLevel::Debug));
+        assert!(!log_enabled(LogLevel::Info));
+        assert!(log_enabled(LogLevel::Warn));
+        assert!(log_enabled(LogLevel::Error));
+
+        set_log_level(LogLevel::Trace);
+        assert!(LogLevel::ALL.into_iter().all(log_enabled));
+        set_log_level(LogLevel::DEFAULT);
+    }
+
+    #[test]
+    fn log_level_round_trip() {
+        for level in LogLevel::ALL {
+            assert_eq!(LogLevel::from_str(&level.to_string()), Ok(level));
+        }
+        assert_eq!(LogLevel::from_str("WARN"), Ok(LogLevel::Warn));
+        assert!(LogLevel::from_str("verbose").is_err());
+    }
+}
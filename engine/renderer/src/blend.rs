@@ -0,0 +1,42 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::gl::*;
+
+/// How a [`Layer`][`crate::Layer`]'s output is combined with what was drawn before it (see
+/// [`Renderer::with_blend_mode`][`crate::Renderer::with_blend_mode`]).
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum BlendMode {
+    /// Draws over, weighted by alpha. The default for all [`Layer`][`crate::Layer`]s.
+    #[default]
+    AlphaBlend,
+    /// Adds to, so overlapping draws get brighter. Good for fire, explosions, sparks, etc.
+    Additive,
+}
+
+impl BlendMode {
+    /// Gets the source and destination factors to pass to `blend_func`. Colors are premultiplied by
+    /// alpha.
+    pub(crate) fn blend_func(self) -> (u32, u32) {
+        match self {
+            Self::AlphaBlend => (Gl::ONE, Gl::ONE_MINUS_SRC_ALPHA),
+            Self::Additive => (Gl::ONE, Gl::ONE),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blend::BlendMode;
+    use crate::gl::*;
+
+    #[test]
+    fn blend_func() {
+        assert_eq!(BlendMode::default(), BlendMode::AlphaBlend);
+        assert_eq!(
+            BlendMode::AlphaBlend.blend_func(),
+            (Gl::ONE, Gl::ONE_MINUS_SRC_ALPHA)
+        );
+        assert_eq!(BlendMode::Additive.blend_func(), (Gl::ONE, Gl::ONE));
+    }
+}
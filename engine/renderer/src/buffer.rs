@@ -4,9 +4,11 @@
 use crate::attribs::Attribs;
 use crate::gl::*;
 use crate::index::Index;
+use crate::render_stats::RenderStats;
 use crate::renderer::Renderer;
 use crate::vertex::Vertex;
 use bytemuck::Pod;
+use std::cell::Cell;
 use std::convert::TryInto;
 use std::marker::PhantomData;
 use std::mem::size_of;
@@ -313,7 +315,7 @@ impl<V: Vertex, I: Index> TriangleBuffer<V, I> {
 
     /// Binds the [`TriangleBuffer`] to draw triangles.
     pub fn bind<'a, C>(&'a self, renderer: &'a Renderer<C>) -> TriangleBufferBinding<'a, V, I> {
-        TriangleBufferBinding::new(&renderer.gl, &renderer.ovao, self)
+        TriangleBufferBinding::new(&renderer.gl, &renderer.ovao, &renderer.frame_stats, self)
     }
 
     /// Copies a whole [`MeshBuilder`] into the buffer. The [`MeshBuilder`] must have indices.
@@ -339,11 +341,17 @@ impl<V: Vertex, I: Index> TriangleBuffer<V, I> {
 pub struct TriangleBufferBinding<'a, V: Vertex, I: Index> {
     gl: &'a Gl,
     ovao: &'a Ovao,
+    stats: &'a Cell<RenderStats>,
     buffer: &'a TriangleBuffer<V, I>,
 }
 
 impl<'a, V: Vertex, I: Index> TriangleBufferBinding<'a, V, I> {
-    fn new(gl: &'a Gl, ovao: &'a Ovao, buffer: &'a TriangleBuffer<V, I>) -> Self {
+    fn new(
+        gl: &'a Gl,
+        ovao: &'a Ovao,
+        stats: &'a Cell<RenderStats>,
+        buffer: &'a TriangleBuffer<V, I>,
+    ) -> Self {
         // Make sure buffer was unbound.
         debug_assert!(gl
             .get_parameter(Ovao::VERTEX_ARRAY_BINDING_OES)
@@ -351,7 +359,12 @@ impl<'a, V: Vertex, I: Index> TriangleBufferBinding<'a, V, I> {
             .is_null());
 
         ovao.bind_vertex_array_oes(Some(&buffer.vao));
-        Self { gl, ovao, buffer }
+        Self {
+            gl,
+            ovao,
+            stats,
+            buffer,
+        }
     }
 
     /// Draws triangles.
@@ -364,9 +377,11 @@ impl<'a, V: Vertex, I: Index> TriangleBufferBinding<'a, V, I> {
                 I::GL_ENUM,
                 0,
             );
+            RenderStats::record_draw(self.stats, self.buffer.indices.len() / 3);
         } else if !self.buffer.vertices.is_empty() {
             self.gl
-                .draw_arrays(primitive, 0, self.buffer.vertices.len() as i32)
+                .draw_arrays(primitive, 0, self.buffer.vertices.len() as i32);
+            RenderStats::record_draw(self.stats, self.buffer.vertices.len() / 3);
         }
     }
 }
@@ -3,9 +3,11 @@
 
 use crate::attribs::Attribs;
 use crate::gl::*;
+use crate::render_stats::RenderStats;
 use crate::renderer::Renderer;
 use crate::vertex::Vertex;
 use std::borrow::{Borrow, Cow};
+use std::cell::Cell;
 use std::collections::VecDeque;
 use std::mem::size_of;
 use web_sys::{WebGlBuffer, WebGlVertexArrayObject};
@@ -110,7 +112,7 @@ impl<V: Vertex + Copy> PointDeque<V> {
     /// Binds the [`PointDeque`] to draw points.
     pub fn bind<'a, C>(&'a mut self, renderer: &'a Renderer<C>) -> PointDequeBinding<'a, V> {
         self.buffer(renderer);
-        PointDequeBinding::new(&renderer.gl, &renderer.ovao, self)
+        PointDequeBinding::new(&renderer.gl, &renderer.ovao, &renderer.frame_stats, self)
     }
 
     /// Called by bind.
@@ -247,11 +249,17 @@ impl<V: Vertex + Copy> PointDeque<V> {
 pub struct PointDequeBinding<'a, V: Vertex> {
     gl: &'a Gl,
     ovao: &'a Ovao,
+    stats: &'a Cell<RenderStats>,
     deque: &'a PointDeque<V>,
 }
 
 impl<'a, V: Vertex> PointDequeBinding<'a, V> {
-    fn new(gl: &'a Gl, ovao: &'a Ovao, deque: &'a PointDeque<V>) -> Self {
+    fn new(
+        gl: &'a Gl,
+        ovao: &'a Ovao,
+        stats: &'a Cell<RenderStats>,
+        deque: &'a PointDeque<V>,
+    ) -> Self {
         // Make sure buffer was unbound.
         debug_assert!(gl
             .get_parameter(Ovao::VERTEX_ARRAY_BINDING_OES)
@@ -259,7 +267,12 @@ impl<'a, V: Vertex> PointDequeBinding<'a, V> {
             .is_null());
 
         ovao.bind_vertex_array_oes(Some(&deque.vao));
-        Self { gl, ovao, deque }
+        Self {
+            gl,
+            ovao,
+            stats,
+            deque,
+        }
     }
 
     /// Draws points.
@@ -269,7 +282,8 @@ impl<'a, V: Vertex> PointDequeBinding<'a, V> {
             let points = self.deque.head - self.deque.tail;
             if points > 0 {
                 self.gl
-                    .draw_arrays(Gl::POINTS, self.deque.tail as i32, points as i32)
+                    .draw_arrays(Gl::POINTS, self.deque.tail as i32, points as i32);
+                RenderStats::record_draw(self.stats, 0);
             }
         } else {
             // [tail, len)
@@ -277,12 +291,14 @@ impl<'a, V: Vertex> PointDequeBinding<'a, V> {
             if points > 0 {
                 self.gl
                     .draw_arrays(Gl::POINTS, self.deque.tail as i32, points as i32);
+                RenderStats::record_draw(self.stats, 0);
             }
 
             // [0, head)
             let points = self.deque.head;
             if points > 0 {
                 self.gl.draw_arrays(Gl::POINTS, 0, points as i32);
+                RenderStats::record_draw(self.stats, 0);
             }
         }
     }
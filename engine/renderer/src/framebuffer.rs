@@ -111,8 +111,9 @@ impl Framebuffer {
             renderbuffer
         });
 
-        // Must unbind framebuffer (unlike many other unbinds) or draws would go to it.
-        gl.bind_framebuffer(Gl::FRAMEBUFFER, None);
+        // Must unbind framebuffer (unlike many other unbinds) or draws would go to it. Restores any
+        // outer binding, e.g. when created inside [`Renderer::render_to`].
+        renderer.rebind_framebuffer();
 
         Self {
             color,
@@ -193,6 +194,11 @@ impl Framebuffer {
         FramebufferBinding::new(renderer, self)
     }
 
+    /// Dimensions set by [`set_viewport`][`Self::set_viewport`].
+    pub(crate) fn dimensions(&self) -> UVec2 {
+        self.dimensions
+    }
+
     /// Gets the texture that the [`Framebuffer`] renders to.
     pub fn as_texture(&self) -> &Texture {
         match &self.color {
@@ -236,11 +242,8 @@ impl Framebuffer {
             Gl::NEAREST,
         );
 
-        // Unbind read and write.
-        if other.is_some() {
-            gl.bind_framebuffer(Gl::DRAW_FRAMEBUFFER, None);
-        }
-        gl.bind_framebuffer(Gl::READ_FRAMEBUFFER, None);
+        // Unbind read and write, restoring any outer binding.
+        renderer.rebind_framebuffer();
     }
 }
 
@@ -252,13 +255,19 @@ pub struct FramebufferBinding<'a, C> {
 }
 
 impl<'a, C> FramebufferBinding<'a, C> {
-    fn new(renderer: &'a Renderer<C>, framebuffer: &'a Framebuffer) -> Self {
+    pub(crate) fn new(renderer: &'a Renderer<C>, framebuffer: &'a Framebuffer) -> Self {
         // Set viewport and bind framebuffer.
         renderer.set_viewport(framebuffer.dimensions);
         renderer
             .gl
             .bind_framebuffer(Gl::FRAMEBUFFER, Some(&framebuffer.framebuffer));
 
+        // Remember it, so nested bindings restore it when they're dropped.
+        renderer
+            .bound_framebuffers
+            .borrow_mut()
+            .push((framebuffer.framebuffer.clone(), framebuffer.dimensions));
+
         Self {
             renderer,
             _framebuffer: framebuffer,
@@ -270,8 +279,18 @@ impl<'a, C> FramebufferBinding<'a, C> {
 
 impl<'a, C> Drop for FramebufferBinding<'a, C> {
     fn drop(&mut self) {
-        // Reset viewport and unbind framebuffer.
-        self.renderer.set_viewport(self.renderer.canvas_size());
-        self.renderer.gl.bind_framebuffer(Gl::FRAMEBUFFER, None);
+        let mut bound = self.renderer.bound_framebuffers.borrow_mut();
+        bound.pop();
+
+        // Reset viewport and rebind outer framebuffer, or unbind if there isn't one.
+        if let Some((framebuffer, dimensions)) = bound.last() {
+            self.renderer.set_viewport(*dimensions);
+            self.renderer
+                .gl
+                .bind_framebuffer(Gl::FRAMEBUFFER, Some(framebuffer));
+        } else {
+            self.renderer.set_viewport(self.renderer.canvas_size());
+            self.renderer.gl.bind_framebuffer(Gl::FRAMEBUFFER, None);
+        }
     }
 }
@@ -27,11 +27,14 @@ pub(crate) type Ani = web_sys::ExtTextureFilterAnisotropic;
 #[macro_use]
 mod gl {
     use wasm_bindgen::JsCast;
-    use web_sys::{AngleInstancedArrays, OesVertexArrayObject, WebGlRenderingContext};
+    use web_sys::{
+        AngleInstancedArrays, ExtDisjointTimerQuery, OesVertexArrayObject, WebGlRenderingContext,
+    };
 
     pub(crate) type Gl = WebGlRenderingContext;
     pub(crate) type Aia = AngleInstancedArrays;
     pub(crate) type Ovao = OesVertexArrayObject;
+    pub(crate) type Tq = ExtDisjointTimerQuery;
 
     /// Name of context for get_context call.
     pub(crate) const GL_NAME: &str = "webgl";
@@ -46,6 +49,8 @@ mod gl {
     pub(crate) trait GlCompat {
         fn get_extension_aia(&self) -> Aia;
         fn get_extension_ovao(&self) -> Ovao;
+        /// Returns [`None`] if unsupported.
+        fn get_extension_tq(&self) -> Option<Tq>;
     }
 
     impl GlCompat for Gl {
@@ -61,6 +66,11 @@ mod gl {
                 .unwrap()
                 .unchecked_into::<Ovao>()
         }
+        fn get_extension_tq(&self) -> Option<Tq> {
+            self.get_extension("EXT_disjoint_timer_query")
+                .unwrap()
+                .map(|e| e.unchecked_into::<Tq>())
+        }
     }
 }
 
@@ -69,12 +79,14 @@ mod gl {
 mod gl {
     use wasm_bindgen::JsValue;
     use web_sys::{
-        HtmlCanvasElement, HtmlImageElement, WebGl2RenderingContext, WebGlVertexArrayObject,
+        HtmlCanvasElement, HtmlImageElement, WebGl2RenderingContext, WebGlQuery,
+        WebGlVertexArrayObject,
     };
 
     pub(crate) type Gl = WebGl2RenderingContext;
     pub(crate) type Aia = Gl;
     pub(crate) type Ovao = Gl;
+    pub(crate) type Tq = Gl;
 
     pub(crate) const GL_NAME: &str = "webgl2";
     macro_rules! gl_title {
@@ -104,6 +116,8 @@ mod gl {
         ) -> Result<(), JsValue>;
         fn get_extension_ovao(&self) -> Ovao;
         fn get_extension_aia(&self) -> Aia;
+        /// Returns [`None`] if unsupported.
+        fn get_extension_tq(&self) -> Option<Tq>;
     }
 
     impl GlCompat for Gl {
@@ -149,6 +163,12 @@ mod gl {
         fn get_extension_aia(&self) -> Aia {
             self.clone()
         }
+        fn get_extension_tq(&self) -> Option<Tq> {
+            // Only need to enable it, the queries themselves are built in.
+            self.get_extension("EXT_disjoint_timer_query_webgl2")
+                .unwrap()
+                .map(|_| self.clone())
+        }
     }
 
     pub(crate) trait AiaCompat {
@@ -226,4 +246,39 @@ mod gl {
             self.is_vertex_array(array_object)
         }
     }
+
+    pub(crate) trait TqCompat {
+        const TIME_ELAPSED_EXT: u32;
+        const GPU_DISJOINT_EXT: u32;
+        const QUERY_RESULT_EXT: u32;
+        const QUERY_RESULT_AVAILABLE_EXT: u32;
+        fn create_query_ext(&self) -> Option<WebGlQuery>;
+        fn delete_query_ext(&self, query: Option<&WebGlQuery>);
+        fn begin_query_ext(&self, target: u32, query: &WebGlQuery);
+        fn end_query_ext(&self, target: u32);
+        fn get_query_object_ext(&self, query: &WebGlQuery, pname: u32) -> JsValue;
+    }
+
+    impl TqCompat for Gl {
+        // Not in web_sys since they are from EXT_disjoint_timer_query_webgl2.
+        const TIME_ELAPSED_EXT: u32 = 0x88BF;
+        const GPU_DISJOINT_EXT: u32 = 0x8FBB;
+        const QUERY_RESULT_EXT: u32 = Self::QUERY_RESULT;
+        const QUERY_RESULT_AVAILABLE_EXT: u32 = Self::QUERY_RESULT_AVAILABLE;
+        fn create_query_ext(&self) -> Option<WebGlQuery> {
+            self.create_query()
+        }
+        fn delete_query_ext(&self, query: Option<&WebGlQuery>) {
+            self.delete_query(query)
+        }
+        fn begin_query_ext(&self, target: u32, query: &WebGlQuery) {
+            self.begin_query(target, query)
+        }
+        fn end_query_ext(&self, target: u32) {
+            self.end_query(target)
+        }
+        fn get_query_object_ext(&self, query: &WebGlQuery, pname: u32) -> JsValue {
+            self.get_query_parameter(query, pname)
+        }
+    }
 }
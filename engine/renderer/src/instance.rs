@@ -5,10 +5,11 @@ use crate::buffer::*;
 use crate::camera::Camera;
 use crate::gl::*;
 use crate::index::Index;
+use crate::render_stats::RenderStats;
 use crate::renderer::{Layer, LayerShader, Renderer};
 use crate::shader::Shader;
 use crate::vertex::Vertex;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use std::hash::Hash;
 use web_sys::{WebGlBuffer, WebGlVertexArrayObject};
@@ -221,7 +222,7 @@ impl<M: Vertex> InstanceBuffer<M> {
             drop(instance_binding);
         }
 
-        InstanceBufferBinding::new(gl, aia, ovao, self, triangle_buffer)
+        InstanceBufferBinding::new(gl, aia, ovao, &renderer.frame_stats, self, triangle_buffer)
     }
 
     /// Copies instances into the [`InstanceBuffer`].
@@ -234,6 +235,7 @@ impl<M: Vertex> InstanceBuffer<M> {
 pub struct InstanceBufferBinding<'a, V: Vertex, I: Index, M: Vertex> {
     aia: &'a Aia,
     ovao: &'a Ovao,
+    stats: &'a Cell<RenderStats>,
     triangle_buffer: &'a TriangleBuffer<V, I>,
     buffer: &'a InstanceBuffer<M>,
 }
@@ -243,6 +245,7 @@ impl<'a, V: Vertex, I: Index, M: Vertex> InstanceBufferBinding<'a, V, I, M> {
         gl: &'a Gl,
         aia: &'a Aia,
         ovao: &'a Ovao,
+        stats: &'a Cell<RenderStats>,
         buffer: &'a InstanceBuffer<M>,
         triangle_buffer: &'a TriangleBuffer<V, I>,
     ) -> Self {
@@ -257,6 +260,7 @@ impl<'a, V: Vertex, I: Index, M: Vertex> InstanceBufferBinding<'a, V, I, M> {
         Self {
             aia,
             ovao,
+            stats,
             triangle_buffer,
             buffer,
         }
@@ -273,13 +277,23 @@ impl<'a, V: Vertex, I: Index, M: Vertex> InstanceBufferBinding<'a, V, I, M> {
                 0,
                 self.buffer.instances.len() as i32,
             );
+            RenderStats::record_draw(
+                self.stats,
+                (self.triangle_buffer.indices.len() / 3)
+                    .saturating_mul(self.buffer.instances.len()),
+            );
         } else if !self.triangle_buffer.vertices.is_empty() {
             self.aia.draw_arrays_instanced_angle(
                 primitive,
                 0,
                 self.triangle_buffer.vertices.len() as i32,
                 self.buffer.instances.len() as i32,
-            )
+            );
+            RenderStats::record_draw(
+                self.stats,
+                (self.triangle_buffer.vertices.len() / 3)
+                    .saturating_mul(self.buffer.instances.len()),
+            );
         }
     }
 }
@@ -0,0 +1,143 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::renderer::{Layer, Renderer};
+use std::any::TypeId;
+use std::ops::{Deref, DerefMut};
+
+/// Which types of [`Layer`][`crate::Layer`]s are disabled (see
+/// [`Renderer::set_layer_enabled`][`crate::Renderer::set_layer_enabled`]). All are enabled by
+/// default.
+#[derive(Debug, Default)]
+pub(crate) struct LayerToggles {
+    /// Only a handful of layers are ever disabled, so a [`Vec`] is faster than a set.
+    disabled: Vec<TypeId>,
+}
+
+impl LayerToggles {
+    /// Enables or disables layers of type `L`.
+    pub fn set_enabled<L: 'static>(&mut self, enabled: bool) {
+        let id = TypeId::of::<L>();
+        let index = self.disabled.iter().position(|&d| d == id);
+        match (enabled, index) {
+            (true, Some(index)) => {
+                self.disabled.swap_remove(index);
+            }
+            (false, None) => self.disabled.push(id),
+            _ => {}
+        }
+    }
+
+    /// Returns if layers of type `L` are enabled.
+    pub fn is_enabled<L: 'static>(&self) -> bool {
+        !self.disabled.contains(&TypeId::of::<L>())
+    }
+}
+
+/// Wraps a [`Layer`] so it can be hidden on its own, e.g. for a screenshot mode, unlike
+/// [`Renderer::set_layer_enabled`] which affects all layers of a type. Hidden layers skip drawing
+/// but keep their place in the order. Only layers that check
+/// [`is_layer_enabled`][`Renderer::is_layer_enabled`] can be hidden.
+pub struct Toggle<L> {
+    layer: L,
+    visible: bool,
+}
+
+impl<L> Toggle<L> {
+    /// Wraps a visible `layer`.
+    pub fn new(layer: L) -> Self {
+        Self {
+            layer,
+            visible: true,
+        }
+    }
+
+    /// Shows or hides the layer, starting next frame.
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    /// Returns if the layer is shown (see [`set_visible`][`Self::set_visible`]).
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+}
+
+impl<L> Deref for Toggle<L> {
+    type Target = L;
+
+    fn deref(&self) -> &Self::Target {
+        &self.layer
+    }
+}
+
+impl<L> DerefMut for Toggle<L> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.layer
+    }
+}
+
+impl<C, L: Layer<C> + 'static> Layer<C> for Toggle<L> {
+    fn pre_prepare(&mut self, renderer: &Renderer<C>) {
+        self.layer.pre_prepare(renderer);
+    }
+
+    fn pre_render(&mut self, renderer: &Renderer<C>) {
+        self.layer.pre_render(renderer);
+    }
+
+    fn render(&mut self, renderer: &Renderer<C>) {
+        if self.visible {
+            self.layer.render(renderer);
+        } else {
+            // Still render, so the layer clears what it accumulated this frame.
+            renderer.render_disabled(&mut self.layer);
+        }
+    }
+
+    fn warmup(&mut self, renderer: &Renderer<C>) {
+        self.layer.warmup(renderer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::layer_toggles::LayerToggles;
+
+    /// Stands in for a layer, counting its draw calls.
+    #[derive(Default)]
+    struct Counter<const N: usize>(usize);
+
+    impl<const N: usize> Counter<N> {
+        fn render(&mut self, toggles: &LayerToggles) {
+            if toggles.is_enabled::<Self>() {
+                self.0 += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn disabled_layer_skips_draws() {
+        let mut toggles = LayerToggles::default();
+        let mut a = Counter::<0>::default();
+        let mut b = Counter::<1>::default();
+
+        let mut frame = |toggles: &LayerToggles| {
+            a.render(toggles);
+            b.render(toggles);
+            (a.0, b.0)
+        };
+
+        assert_eq!(frame(&toggles), (1, 1));
+
+        toggles.set_enabled::<Counter<0>>(false);
+        // Disabling twice is harmless.
+        toggles.set_enabled::<Counter<0>>(false);
+        assert!(!toggles.is_enabled::<Counter<0>>());
+        assert!(toggles.is_enabled::<Counter<1>>());
+        assert_eq!(frame(&toggles), (1, 2));
+
+        toggles.set_enabled::<Counter<0>>(true);
+        assert_eq!(frame(&toggles), (2, 3));
+    }
+}
@@ -19,12 +19,17 @@ mod gl;
 mod srgb_layer;
 
 mod attribs;
+mod blend;
 mod buffer;
 mod camera;
 mod deque;
 mod framebuffer;
 mod index;
 mod instance;
+mod layer_toggles;
+mod motion_blur_layer;
+mod render_stats;
+mod render_target;
 mod renderer;
 mod rgb;
 mod shader;
@@ -39,12 +44,17 @@ pub use attribs::*;
 pub use srgb_layer::*;
 
 // Re-export to provide a simpler api.
+pub use blend::*;
 pub use buffer::*;
 pub use camera::*;
 pub use deque::*;
 pub use framebuffer::*;
 pub use index::*;
 pub use instance::*;
+pub use layer_toggles::Toggle;
+pub use motion_blur_layer::*;
+pub use render_stats::RenderStats;
+pub use render_target::*;
 pub use renderer::*;
 pub use rgb::*;
 pub use shader::*;
@@ -0,0 +1,107 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::gl::*;
+use crate::{Framebuffer, Layer, Renderer, Shader, TriangleBuffer};
+use glam::{vec2, Vec2};
+use std::ops::{Deref, DerefMut};
+
+/// Draws its inner [`Layer`] to an offscreen [`Framebuffer`], and then to the screen, blurred
+/// along a direction (e.g. that of camera movement). Draws directly to the screen, without
+/// any overhead, while there is no blur.
+pub struct MotionBlurLayer<I> {
+    blur: Vec2,
+    buffer: TriangleBuffer<Vec2>,
+    framebuffer: Framebuffer,
+    /// The inner [`Layer`] passed to [`new`][`Self::new`].
+    pub inner: I,
+    shader: Shader,
+}
+
+impl<I> MotionBlurLayer<I> {
+    /// Creates a new [`MotionBlurLayer`].
+    pub fn new<C>(renderer: &Renderer<C>, inner: I) -> Self {
+        // Create a buffer that 1 triangle.
+        let mut buffer = TriangleBuffer::new(renderer);
+        buffer.buffer(
+            renderer,
+            &[vec2(-1.0, 3.0), vec2(-1.0, -1.0), vec2(3.0, -1.0)],
+            &[],
+        );
+
+        let shader = renderer.create_shader(
+            include_str!("shaders/motion_blur.vert"),
+            include_str!("shaders/motion_blur.frag"),
+        );
+
+        Self {
+            blur: Vec2::ZERO,
+            buffer,
+            framebuffer: Framebuffer::new(renderer, true),
+            inner,
+            shader,
+        }
+    }
+
+    /// Sets the blur vector, in texture coordinates (a length of 1.0 would span the whole
+    /// screen). Set to [`Vec2::ZERO`] to disable blurring.
+    pub fn set_blur(&mut self, blur: Vec2) {
+        self.blur = blur;
+    }
+
+    fn is_blurred(&self) -> bool {
+        self.blur != Vec2::ZERO
+    }
+}
+
+impl<I> Deref for MotionBlurLayer<I> {
+    type Target = I;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<I> DerefMut for MotionBlurLayer<I> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl<C, I: Layer<C>> Layer<C> for MotionBlurLayer<I> {
+    fn pre_prepare(&mut self, renderer: &Renderer<C>) {
+        self.inner.pre_prepare(renderer);
+    }
+
+    fn warmup(&mut self, renderer: &Renderer<C>) {
+        self.inner.warmup(renderer);
+        self.shader.warmup(renderer);
+    }
+
+    fn pre_render(&mut self, renderer: &Renderer<C>) {
+        self.inner.pre_render(renderer);
+        if self.is_blurred() {
+            self.framebuffer
+                .set_viewport(renderer, renderer.canvas_size());
+        }
+    }
+
+    fn render(&mut self, renderer: &Renderer<C>) {
+        if !self.is_blurred() {
+            self.inner.render(renderer);
+            return;
+        }
+
+        let binding = self.framebuffer.bind(renderer);
+        renderer.gl.clear(Gl::COLOR_BUFFER_BIT);
+        self.inner.render(renderer);
+        drop(binding);
+
+        let binding = self.buffer.bind(renderer);
+        if let Some(shader) = self.shader.bind(renderer) {
+            shader.uniform_texture("uSampler", self.framebuffer.as_texture(), 0);
+            shader.uniform2f("uBlur", self.blur);
+            binding.draw();
+        }
+    }
+}
@@ -0,0 +1,125 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::gl::*;
+use std::cell::Cell;
+use std::collections::VecDeque;
+use web_sys::WebGlQuery;
+
+/// Statistics about rendering a frame, for diagnosing performance (see
+/// [`Renderer::stats`][`crate::Renderer::stats`]).
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct RenderStats {
+    /// Number of draw calls, including instanced ones.
+    pub draw_calls: u32,
+    /// Number of triangles drawn, including those of every instance (points aren't counted).
+    pub triangles: u32,
+    /// Milliseconds the GPU took to render a recent frame, or [`None`] if
+    /// `EXT_disjoint_timer_query` isn't supported. Lags a few frames behind, since the GPU renders
+    /// asynchronously.
+    pub gpu_millis: Option<f32>,
+}
+
+impl RenderStats {
+    /// Records a draw call of `triangles` into `stats`.
+    pub(crate) fn record_draw(stats: &Cell<Self>, triangles: u32) {
+        let mut s = stats.get();
+        s.draw_calls += 1;
+        s.triangles = s.triangles.saturating_add(triangles);
+        stats.set(s);
+    }
+}
+
+/// Measures how long the GPU takes to render frames with `EXT_disjoint_timer_query`.
+pub(crate) struct GpuTimer {
+    tq: Tq,
+    /// Query measuring the current frame, if any.
+    active: Option<WebGlQuery>,
+    /// Queries of previous frames that haven't got a result yet, oldest first.
+    pending: VecDeque<WebGlQuery>,
+}
+
+impl GpuTimer {
+    /// Don't measure more frames while this many are still pending, in case results stop coming.
+    const MAX_PENDING: usize = 4;
+
+    /// Returns [`None`] if `EXT_disjoint_timer_query` isn't supported.
+    pub(crate) fn new(gl: &Gl) -> Option<Self> {
+        Some(Self {
+            tq: gl.get_extension_tq()?,
+            active: None,
+            pending: VecDeque::new(),
+        })
+    }
+
+    /// Starts measuring a frame.
+    pub(crate) fn begin(&mut self) {
+        if self.active.is_some() || self.pending.len() >= Self::MAX_PENDING {
+            return;
+        }
+        if let Some(query) = self.tq.create_query_ext() {
+            self.tq.begin_query_ext(Tq::TIME_ELAPSED_EXT, &query);
+            self.active = Some(query);
+        }
+    }
+
+    /// Stops measuring the frame started by [`begin`][`Self::begin`].
+    pub(crate) fn end(&mut self) {
+        if let Some(query) = self.active.take() {
+            self.tq.end_query_ext(Tq::TIME_ELAPSED_EXT);
+            self.pending.push_back(query);
+        }
+    }
+
+    /// Returns the milliseconds of the newest frame that finished measuring since the last call,
+    /// if any.
+    pub(crate) fn poll(&mut self, gl: &Gl) -> Option<f32> {
+        let mut nanos = None;
+        while let Some(query) = self.pending.front() {
+            let available = self
+                .tq
+                .get_query_object_ext(query, Tq::QUERY_RESULT_AVAILABLE_EXT)
+                .as_bool()
+                .unwrap_or(false);
+            if !available {
+                break;
+            }
+            nanos = self
+                .tq
+                .get_query_object_ext(query, Tq::QUERY_RESULT_EXT)
+                .as_f64();
+            self.tq.delete_query_ext(self.pending.pop_front().as_ref());
+        }
+
+        // Results are meaningless if something else (e.g. a power state change) happened.
+        let disjoint = gl
+            .get_parameter(Tq::GPU_DISJOINT_EXT)
+            .ok()
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        nanos
+            .filter(|_| !disjoint)
+            .map(|nanos| (nanos * 1e-6) as f32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::render_stats::RenderStats;
+    use std::cell::Cell;
+
+    #[test]
+    fn record_draw() {
+        let stats = Cell::new(RenderStats::default());
+        RenderStats::record_draw(&stats, 2);
+        RenderStats::record_draw(&stats, 100);
+        assert_eq!(
+            stats.get(),
+            RenderStats {
+                draw_calls: 2,
+                triangles: 102,
+                gpu_millis: None,
+            }
+        );
+    }
+}
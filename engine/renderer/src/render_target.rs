@@ -0,0 +1,45 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::framebuffer::Framebuffer;
+use crate::renderer::Renderer;
+use crate::texture::Texture;
+use glam::UVec2;
+
+/// An offscreen [`Texture`] of a chosen size, that [`Layer`][`crate::Layer`]s can be drawn to with
+/// [`Renderer::render_to`] (e.g. for bloom or low resolution reflections). The result can be used
+/// as input to another [`Shader`][`crate::Shader`] via [`texture`][`Self::texture`].
+pub struct RenderTarget {
+    framebuffer: Framebuffer,
+}
+
+impl RenderTarget {
+    /// Creates a new [`RenderTarget`] with `dimensions` in pixels. `linear_filter` specifies if its
+    /// [`texture`][`Self::texture`] uses linear filtering.
+    pub fn new<C>(renderer: &Renderer<C>, dimensions: UVec2, linear_filter: bool) -> Self {
+        let mut framebuffer = Framebuffer::new(renderer, linear_filter);
+        framebuffer.set_viewport(renderer, dimensions);
+        Self { framebuffer }
+    }
+
+    /// Dimensions in pixels.
+    pub fn dimensions(&self) -> UVec2 {
+        self.framebuffer.dimensions()
+    }
+
+    /// Changes the dimensions (e.g. when the canvas is resized). Does nothing if they are the same.
+    ///
+    /// NOTE: this clears the [`RenderTarget`].
+    pub fn resize<C>(&mut self, renderer: &Renderer<C>, dimensions: UVec2) {
+        self.framebuffer.set_viewport(renderer, dimensions);
+    }
+
+    /// Gets the texture that the [`RenderTarget`] renders to.
+    pub fn texture(&self) -> &Texture {
+        self.framebuffer.as_texture()
+    }
+
+    pub(crate) fn framebuffer(&self) -> &Framebuffer {
+        &self.framebuffer
+    }
+}
@@ -1,8 +1,13 @@
 // SPDX-FileCopyrightText: 2021 Softbear, Inc.
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use crate::blend::BlendMode;
 use crate::camera::Camera;
+use crate::framebuffer::FramebufferBinding;
 use crate::gl::*;
+use crate::layer_toggles::LayerToggles;
+use crate::render_stats::{GpuTimer, RenderStats};
+use crate::render_target::RenderTarget;
 use crate::shader::{Shader, ShaderBinding};
 pub use engine_macros::Layer;
 use glam::*;
@@ -10,7 +15,7 @@ use js_hooks::error_message;
 use linear_map::LinearMap;
 use std::cell::{Cell, RefCell};
 use wasm_bindgen::JsCast;
-use web_sys::HtmlCanvasElement;
+use web_sys::{HtmlCanvasElement, WebGlFramebuffer};
 
 /// Contains things that can be drawn. Can be derived on structs that have fields which also
 /// implement it. If your struct has non [`Layer`] fields you can label the [`Layer`] fields with
@@ -31,6 +36,12 @@ pub trait Layer<C> {
 
     /// Renders the [`Layer`].
     fn render(&mut self, renderer: &Renderer<C>);
+
+    /// Called once after creation, while loading. Useful for compiling shaders and uploading
+    /// geometry ahead of time, so that first use doesn't cause a hitch.
+    fn warmup(&mut self, renderer: &Renderer<C>) {
+        let _ = renderer;
+    }
 }
 
 /// Extends a [`Layer`] with a custom [`Shader`].
@@ -79,6 +90,18 @@ pub struct Renderer<C> {
     pub time_delta: f32,
     /// Cache of static shaders.
     shader_cache: RefCell<LinearMap<(&'static str, &'static str), Shader>>,
+    /// Current [`BlendMode`].
+    blend_mode: Cell<BlendMode>,
+    /// Types of [`Layer`]s that shouldn't render.
+    layer_toggles: RefCell<LayerToggles>,
+    /// [`RenderStats`] of the frame being rendered.
+    pub(crate) frame_stats: Cell<RenderStats>,
+    /// [`RenderStats`] of the last rendered frame.
+    stats: RenderStats,
+    /// Measures [`RenderStats::gpu_millis`] if supported.
+    gpu_timer: Option<GpuTimer>,
+    /// Stack of currently bound framebuffers and their dimensions, innermost last.
+    pub(crate) bound_framebuffers: RefCell<Vec<(WebGlFramebuffer, UVec2)>>,
     /// WebGL doesn't support antialiasing with srgb.
     #[cfg(feature = "srgb")]
     pub(crate) antialiasing: Option<i32>,
@@ -124,6 +147,7 @@ impl<C: Camera> Renderer<C> {
             .map(|_| KhrParallelShaderCompile);
 
         let ovao = gl.get_extension_ovao();
+        let gpu_timer = GpuTimer::new(&gl);
 
         // WebGL2 has this built in by default. In WebGL we only need to enable it, not save it.
         #[cfg(all(not(feature = "webgl2"), feature = "srgb"))]
@@ -143,7 +167,8 @@ impl<C: Camera> Renderer<C> {
         gl.enable(Gl::BLEND);
 
         // First argument is Gl::SRC_ALPHA if not premultiplied alpha, Gl::ONE if premultiplied(?).
-        gl.blend_func(Gl::ONE, Gl::ONE_MINUS_SRC_ALPHA);
+        let (src, dst) = BlendMode::default().blend_func();
+        gl.blend_func(src, dst);
 
         let res = Ok(Self {
             canvas,
@@ -156,6 +181,12 @@ impl<C: Camera> Renderer<C> {
             time: 0.0,
             time_delta: 0.0,
             shader_cache: Default::default(),
+            blend_mode: Default::default(),
+            layer_toggles: Default::default(),
+            frame_stats: Default::default(),
+            stats: Default::default(),
+            gpu_timer,
+            bound_framebuffers: Default::default(),
             #[cfg(feature = "srgb")]
             antialiasing,
             #[cfg(feature = "anisotropy")]
@@ -167,6 +198,33 @@ impl<C: Camera> Renderer<C> {
 }
 
 impl<C> Renderer<C> {
+    /// Enables or disables all [`Layer`]s of type `L` at runtime, e.g. to find out which one is
+    /// expensive. Disabled layers skip their [`render`][`Layer::render`] entirely. Only layers that
+    /// check [`is_layer_enabled`][`Self::is_layer_enabled`] can be disabled.
+    pub fn set_layer_enabled<L: 'static>(&mut self, enabled: bool) {
+        self.layer_toggles.get_mut().set_enabled::<L>(enabled);
+    }
+
+    /// Returns if [`Layer`]s of type `L` are enabled (see
+    /// [`set_layer_enabled`][`Self::set_layer_enabled`]).
+    pub fn is_layer_enabled<L: 'static>(&self) -> bool {
+        self.layer_toggles.borrow().is_enabled::<L>()
+    }
+
+    /// Calls [`render`][`Layer::render`] on `layer` as if layers of its type were disabled, so it
+    /// discards what it would have drawn.
+    pub(crate) fn render_disabled<L: Layer<C> + 'static>(&self, layer: &mut L) {
+        let enabled = self.is_layer_enabled::<L>();
+        self.layer_toggles.borrow_mut().set_enabled::<L>(false);
+        layer.render(self);
+        self.layer_toggles.borrow_mut().set_enabled::<L>(enabled);
+    }
+
+    /// Returns [`RenderStats`] of the last rendered frame.
+    pub fn stats(&self) -> RenderStats {
+        self.stats
+    }
+
     /// Returns if highp is supported in a fragment shader.
     #[deprecated = "should assume fragment has highp"]
     pub fn fragment_has_highp(&self) -> bool {
@@ -274,12 +332,52 @@ impl<C> Renderer<C> {
         self.gl.enable(Gl::CULL_FACE)
     }
 
+    /// Draws with `blend_mode` while calling `f`, restoring the previous [`BlendMode`] afterwards
+    /// so [`Layer`]s drawn later aren't affected.
+    pub fn with_blend_mode<R>(&self, blend_mode: BlendMode, f: impl FnOnce() -> R) -> R {
+        let previous = self.blend_mode.get();
+        self.set_blend_mode(blend_mode);
+        let ret = f();
+        self.set_blend_mode(previous);
+        ret
+    }
+
+    fn set_blend_mode(&self, blend_mode: BlendMode) {
+        if self.blend_mode.replace(blend_mode) != blend_mode {
+            let (src, dst) = blend_mode.blend_func();
+            self.gl.blend_func(src, dst);
+        }
+    }
+
+    /// Clears `target` to the background color and makes all draws while calling `f` go to it,
+    /// restoring the previous framebuffer and viewport afterwards. Can be nested, including inside
+    /// [`Layer`]s that draw to their own framebuffer. `f` may also create or resize
+    /// [`Framebuffer`][`crate::framebuffer::Framebuffer`]s, which keep `target` bound.
+    pub fn render_to<R>(&self, target: &RenderTarget, f: impl FnOnce(&Self) -> R) -> R {
+        let binding = FramebufferBinding::new(self, target.framebuffer());
+        self.gl.clear(Gl::COLOR_BUFFER_BIT);
+        let ret = f(self);
+        drop(binding);
+        ret
+    }
+
     /// Sets the background color to RGBA with components 0.0-1.0. Will take effect at the start of
     /// the next render.
     pub fn set_background_color(&mut self, color: Vec4) {
         self.gl.clear_color(color.x, color.y, color.z, color.w);
     }
 
+    /// Binds the innermost framebuffer bound with a
+    /// [`FramebufferBinding`][`crate::framebuffer::FramebufferBinding`], or the canvas if there
+    /// isn't one, after another framebuffer was bound temporarily (e.g. to create it).
+    pub(crate) fn rebind_framebuffer(&self) {
+        let bound = self.bound_framebuffers.borrow();
+        self.gl.bind_framebuffer(
+            Gl::FRAMEBUFFER,
+            bound.last().map(|(framebuffer, _)| framebuffer),
+        );
+    }
+
     /// Not useful outside renderer. Use a framebuffer instead.
     pub(crate) fn set_viewport(&self, viewport: UVec2) {
         let size = viewport.as_ivec2();
@@ -296,9 +394,27 @@ impl<C> Renderer<C> {
         layer.pre_prepare(self);
     }
 
+    /// Calls [`Layer::warmup`] and then [`Shader::warmup`]s every [`Shader`] created with
+    /// [`create_shader`][`Self::create_shader`].
+    #[doc(hidden)]
+    pub fn warmup(&mut self, layer: &mut impl Layer<C>) {
+        layer.warmup(self);
+        for shader in self.shader_cache.borrow().values() {
+            shader.warmup(self);
+        }
+    }
+
     /// Calls [`Layer::pre_render`], sets viewport, clears screen and calls [`Layer::render`].
     #[doc(hidden)]
     pub fn render(&mut self, layer: &mut impl Layer<C>) {
+        // Keep the last known GPU time until a newer one is available.
+        let mut gpu_millis = self.stats.gpu_millis;
+        if let Some(gpu_timer) = &mut self.gpu_timer {
+            gpu_millis = gpu_timer.poll(&self.gl).or(gpu_millis);
+            gpu_timer.begin();
+        }
+        self.frame_stats.take();
+
         // Pre-render such as allocating textures.
         layer.pre_render(self);
 
@@ -308,6 +424,14 @@ impl<C> Renderer<C> {
 
         // Render everything.
         layer.render(self);
+
+        if let Some(gpu_timer) = &mut self.gpu_timer {
+            gpu_timer.end();
+        }
+        self.stats = RenderStats {
+            gpu_millis,
+            ..self.frame_stats.take()
+        };
     }
 }
 
@@ -5,7 +5,7 @@ use crate::gl::*;
 use crate::renderer::Renderer;
 use crate::texture::{Texture, TextureBinding};
 use glam::*;
-use js_hooks::console_log;
+use js_hooks::console_warn;
 use linear_map::LinearMap;
 use std::cell::{Cell, RefCell, RefMut};
 use std::mem;
@@ -64,6 +64,21 @@ impl Shader {
         }
         Some(ShaderBinding::new(gl, &self.0))
     }
+
+    /// Waits for the [`Shader`] to finish compiling and linking, and binds it once, so that the
+    /// first real [`bind`][`Self::bind`] doesn't cause a hitch. Only call while loading.
+    pub fn warmup<C>(&self, renderer: &Renderer<C>) {
+        // Not passing the KHR extension makes the query wait for linking.
+        if !self.0.link_done.get()
+            && self
+                .0
+                .query_link_status(&renderer.gl, None)
+                .unwrap_or_else(|e| panic!("{}", e))
+        {
+            self.0.link_done.set(true);
+        }
+        drop(self.bind(renderer));
+    }
 }
 
 impl ShaderInner {
@@ -82,7 +97,7 @@ impl ShaderInner {
             r.entry(name).or_insert_with(|| {
                 let uniform = gl.get_uniform_location(program, name);
                 if uniform.is_none() && cfg!(debug_assertions) {
-                    console_log!("warning: uniform {} does not exist or is not in use", name);
+                    console_warn!("uniform {} does not exist or is not in use", name);
                 }
                 uniform
             })
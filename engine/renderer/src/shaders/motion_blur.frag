@@ -0,0 +1,17 @@
+precision mediump float;
+
+varying vec2 vUv;
+uniform sampler2D uSampler;
+// Blur vector in texture coordinates, centered on each fragment.
+uniform vec2 uBlur;
+
+#define SAMPLES 8
+
+void main() {
+    vec4 sum = vec4(0.0);
+    for (int i = 0; i < SAMPLES; i++) {
+        float t = float(i) / float(SAMPLES - 1) - 0.5;
+        sum += texture2D(uSampler, vUv + uBlur * t);
+    }
+    gl_FragColor = sum * (1.0 / float(SAMPLES));
+}
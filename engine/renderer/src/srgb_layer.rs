@@ -102,6 +102,10 @@ impl<C, I: Layer<C>> Layer<C> for SrgbLayer<I> {
         self.inner.pre_prepare(renderer);
     }
 
+    fn warmup(&mut self, renderer: &Renderer<C>) {
+        self.inner.warmup(renderer);
+    }
+
     fn pre_render(&mut self, renderer: &Renderer<C>) {
         self.inner.pre_render(renderer);
         #[cfg(feature = "webgl2")]
@@ -265,31 +265,22 @@ impl Texture {
     /// to this function instead of coloring in a [`Shader`][`crate::shader::Shader`] so
     /// emoji colors are preserved.
     pub fn from_text<C>(renderer: &Renderer<C>, text: &str, color: [u8; 4]) -> Self {
-        let (canvas, context) = create_canvas();
-
-        const FONT: &str = "30px Arial";
-        const HEIGHT: u32 = 36; // 32 -> 36 to fit "😊".
-
-        context.set_font(FONT);
-        context.set_text_baseline("bottom");
-        let text_width = context.measure_text(text).unwrap().width();
-
-        let canvas_width = text_width as u32 + 2;
-        canvas.set_width(canvas_width);
-        canvas.set_height(HEIGHT);
-
-        let color_string = rgba_array_to_css(color);
-
-        context.set_fill_style(&JsValue::from_str(&color_string));
-        context.set_font(FONT);
-        context.set_text_baseline("bottom");
+        Self::from_text_outlined(renderer, text, color, None)
+    }
 
-        context
-            .fill_text(text, 1.0, (HEIGHT - 1) as f64)
-            .expect("could not fill text on canvas");
+    /// Like [`from_text`][`Self::from_text`] but with an optional [`TextOutline`], for contrast
+    /// against busy backgrounds. The outline makes the texture [`TextOutline::width`] pixels
+    /// larger on every side.
+    pub fn from_text_outlined<C>(
+        renderer: &Renderer<C>,
+        text: &str,
+        color: [u8; 4],
+        outline: Option<TextOutline>,
+    ) -> Self {
+        let canvas = text_canvas(text, color, outline);
 
         let format = TextureFormat::Rgba;
-        let dimensions = UVec2::new(canvas_width, HEIGHT);
+        let dimensions = UVec2::new(canvas.width(), canvas.height());
 
         let gl = &renderer.gl;
         let texture = Self::new(gl, dimensions, format);
@@ -325,6 +316,45 @@ impl Texture {
         texture
     }
 
+    /// Draws `text` like [`from_text_outlined`][`Self::from_text_outlined`] but into part of an
+    /// existing [`TextureFormat::Rgba`] [`Texture`] (e.g. an atlas of text), with its top left
+    /// corner at `offset` pixels. The part is [`text_dimensions`] in size, and must fit. The
+    /// [`Texture`] must have been created with [`Texture::new_empty`] and allocated with
+    /// [`Texture::realloc_with_opt_bytes`].
+    pub fn write_text<C>(
+        &self,
+        renderer: &Renderer<C>,
+        text: &str,
+        color: [u8; 4],
+        outline: Option<TextOutline>,
+        offset: UVec2,
+    ) {
+        debug_assert!(matches!(self.format, TextureFormat::Rgba));
+        debug_assert!((offset + text_dimensions(text, outline))
+            .cmple(self.dimensions())
+            .all());
+        let canvas = text_canvas(text, color, outline);
+
+        let gl = &renderer.gl;
+        gl.bind_texture(Gl::TEXTURE_2D, Some(self.inner()));
+        gl.pixel_storei(Gl::UNPACK_PREMULTIPLY_ALPHA_WEBGL, 1); // Canvas isn't premultiplied.
+
+        gl.tex_sub_image_2d_with_u32_and_u32_and_canvas(
+            Gl::TEXTURE_2D,
+            0,
+            offset.x as i32,
+            offset.y as i32,
+            self.format.src_format(),
+            Gl::UNSIGNED_BYTE,
+            &canvas,
+        )
+        .expect("could not draw canvas to texture");
+
+        gl.pixel_storei(Gl::UNPACK_PREMULTIPLY_ALPHA_WEBGL, 0);
+
+        unbind_texture_cfg_debug(gl);
+    }
+
     /// Loads an [`TextureFormat::Rgba`] [`Texture`] from `img_url`. You may specify a `placeholder`
     /// color for use before the image loads. You may also specify `repeating` if the loaded image
     /// has power of 2 dimensions.
@@ -520,6 +550,81 @@ impl Texture {
     }
 }
 
+/// Font of [`Texture::from_text`].
+const TEXT_FONT: &str = "30px Arial";
+/// Height of [`Texture::from_text`] in pixels (excluding any [`TextOutline`]). 32 -> 36 to fit "😊".
+pub const TEXT_HEIGHT: u32 = 36;
+
+/// An outline around text (see [`Texture::from_text_outlined`]).
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct TextOutline {
+    /// 8 bit RGBA color.
+    pub color: [u8; 4],
+    /// Width in pixels, outside of the glyphs.
+    pub width: u32,
+}
+
+/// Measures the width of `text`, in pixels, as [`Texture::from_text`] would draw it (excluding
+/// padding).
+pub fn text_width(text: &str) -> f32 {
+    thread_local! {
+        // Creating a canvas per measurement would be too slow.
+        static CONTEXT: CanvasRenderingContext2d = {
+            let (_, context) = create_canvas();
+            context.set_font(TEXT_FONT);
+            context
+        };
+    }
+    CONTEXT.with(|context| context.measure_text(text).unwrap().width() as f32)
+}
+
+/// Gets the dimensions, in pixels, of [`Texture::from_text_outlined`].
+pub fn text_dimensions(text: &str, outline: Option<TextOutline>) -> UVec2 {
+    let outline_width = outline.map_or(0, |o| o.width);
+    UVec2::new(
+        text_width(text) as u32 + 2 + outline_width * 2,
+        TEXT_HEIGHT + outline_width * 2,
+    )
+}
+
+/// Draws `text` onto a temporary canvas of [`text_dimensions`] (see
+/// [`Texture::from_text_outlined`]).
+fn text_canvas(text: &str, color: [u8; 4], outline: Option<TextOutline>) -> HtmlCanvasElement {
+    let (canvas, context) = create_canvas();
+
+    const FONT: &str = TEXT_FONT;
+    const HEIGHT: u32 = TEXT_HEIGHT;
+    let outline_width = outline.map_or(0, |o| o.width);
+
+    let dimensions = text_dimensions(text, outline);
+    canvas.set_width(dimensions.x);
+    canvas.set_height(dimensions.y);
+
+    let color_string = rgba_array_to_css(color);
+
+    context.set_fill_style(&JsValue::from_str(&color_string));
+    context.set_font(FONT);
+    context.set_text_baseline("bottom");
+
+    let x = (1 + outline_width) as f64;
+    let y = (HEIGHT - 1 + outline_width) as f64;
+    if let Some(outline) = outline {
+        // Strokes are centered on the glyph edges, and the fill covers the inner half.
+        context.set_stroke_style(&JsValue::from_str(&rgba_array_to_css(outline.color)));
+        context.set_line_width((outline.width * 2) as f64);
+        context.set_line_join("round");
+        context
+            .stroke_text(text, x, y)
+            .expect("could not stroke text on canvas");
+    }
+
+    context
+        .fill_text(text, x, y)
+        .expect("could not fill text on canvas");
+
+    canvas
+}
+
 /// Creates a temporary canvas for drawing and then converting into a texture.
 fn create_canvas() -> (HtmlCanvasElement, CanvasRenderingContext2d) {
     let canvas: HtmlCanvasElement = document()
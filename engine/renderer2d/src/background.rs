@@ -4,7 +4,7 @@
 use crate::camera_2d::Camera2d;
 use crate::Renderer2d;
 use glam::{uvec2, vec2, vec4, IVec2, UVec2, Vec2};
-use js_hooks::console_log;
+use js_hooks::console_trace;
 use renderer::{
     derive_vertex, Framebuffer, Layer, LayerShader, MeshBuilder, Shader, TriangleBuffer,
 };
@@ -52,27 +52,57 @@ pub struct BackgroundLayer<X: BackgroundContext> {
     frame_cache: Option<FrameCache>,
     /// The [`BackgroundContext`] passed to [`new`][`Self::new`].
     pub context: X,
+    /// How fast the background moves relative to the camera (see
+    /// [`with_parallax`][`BackgroundLayer::with_parallax`]).
+    parallax_factor: f32,
     shader: Shader,
     shader_loaded: bool,
+    /// Passed to the shader as `uTiling` (see [`set_tiling`][`BackgroundLayer::set_tiling`]).
+    tiles_per_world_unit: f32,
 }
 
 impl<X: BackgroundContext> BackgroundLayer<X> {
-    /// Shader must take uCamera and uMiddle_uDerivative uniforms.
+    /// Shader must take uCamera and uMiddle_uDerivative uniforms, and may take a uTiling uniform
+    /// (see [`set_tiling`][`Self::set_tiling`]).
     pub fn new(renderer: &Renderer2d, context: X) -> Self {
+        Self::with_parallax(renderer, context, 1.0)
+    }
+
+    /// Like [`new`][`Self::new`], but the background moves `parallax_factor` times as fast as the
+    /// camera, e.g. `0.5` for something far below the surface. Stack multiple [`BackgroundLayer`]s
+    /// with different factors for a sense of depth. A factor of `1.0` is the same as
+    /// [`new`][`Self::new`]. Other factors don't support
+    /// [`cache_frame`][`BackgroundContext::cache_frame`], since the background doesn't scroll with
+    /// the camera.
+    pub fn with_parallax(renderer: &Renderer2d, context: X, parallax_factor: f32) -> Self {
         let shader = context.create(renderer);
         let shader_loaded = false;
 
         let mut buffer = TriangleBuffer::new(renderer);
         buffer_viewport(renderer, &mut buffer);
 
-        let frame_cache = context.cache_frame().then(|| FrameCache::new(renderer));
+        let frame_cache =
+            (context.cache_frame() && parallax_factor == 1.0).then(|| FrameCache::new(renderer));
 
         Self {
             buffer,
             context,
             frame_cache,
+            parallax_factor,
             shader,
             shader_loaded,
+            tiles_per_world_unit: 1.0,
+        }
+    }
+
+    /// Sets the `uTiling` uniform, which shaders can multiply world positions (from uCamera) by to
+    /// get uvs of a repeating [`Texture`][`renderer::Texture`], so it tiles across the world and
+    /// scrolls with the camera instead of stretching across the screen. Defaults to `1.0`.
+    pub fn set_tiling(&mut self, tiles_per_world_unit: f32) {
+        if tiles_per_world_unit != self.tiles_per_world_unit {
+            self.tiles_per_world_unit = tiles_per_world_unit;
+            // Redraw the whole frame cache.
+            self.shader_loaded = false;
         }
     }
 }
@@ -96,8 +126,18 @@ fn buffer_viewport_with_uv<F: FnMut(Vec2) -> Vec2>(
     );
 }
 
-impl<X: BackgroundContext> Layer<Camera2d> for BackgroundLayer<X> {
+impl<X: BackgroundContext + 'static> Layer<Camera2d> for BackgroundLayer<X> {
+    fn warmup(&mut self, renderer: &Renderer2d) {
+        self.shader.warmup(renderer);
+    }
+
     fn render(&mut self, renderer: &Renderer2d) {
+        if !renderer.is_layer_enabled::<Self>() {
+            // The frame cache will be stale, so redraw all of it once enabled.
+            self.shader_loaded = false;
+            return;
+        }
+
         let mut wrote_none: bool = false;
 
         // Write to frame cache or main screen.
@@ -106,9 +146,18 @@ impl<X: BackgroundContext> Layer<Camera2d> for BackgroundLayer<X> {
                 let just_loaded = !self.shader_loaded;
                 self.shader_loaded = true;
 
+                // Parallax moves the camera slower (or faster), without changing its zoom.
+                let parallax = (self.parallax_factor != 1.0)
+                    .then(|| renderer.camera.parallax(self.parallax_factor));
+
                 let mut buffer = &self.buffer;
-                let mut camera_matrix = &renderer.camera.camera_matrix;
-                let mut middle = renderer.camera.center;
+                let (mut camera_matrix, mut middle) = match &parallax {
+                    Some((matrix, center)) => (matrix, *center),
+                    None => (
+                        &renderer.camera.camera_matrix,
+                        renderer.camera.render_center(),
+                    ),
+                };
 
                 let _fbb = if let Some(frame_cache) = &mut self.frame_cache {
                     // Update the frame cached (resize texture and compute read/write buffers).
@@ -139,6 +188,7 @@ impl<X: BackgroundContext> Layer<Camera2d> for BackgroundLayer<X> {
                 };
 
                 shader.uniform_matrix3f("uCamera", camera_matrix);
+                shader.uniform1f("uTiling", self.tiles_per_world_unit);
 
                 // Pack middle and derivative.
                 // Derivative same between aligned/unaligned cameras.
@@ -267,12 +317,12 @@ impl FrameCache {
             if let Some(delta) = maybe_delta {
                 if delta.abs().cmpge(viewport.as_ivec2()).any() {
                     if DEBUG {
-                        console_log!("invalidated: out of range");
+                        console_trace!("invalidated: out of range");
                     }
                     None
                 } else if let Some(inv) = invalidation {
                     if DEBUG {
-                        console_log!("invalidated: {:?}", inv);
+                        console_trace!("invalidated: {:?}", inv);
                     }
                     match inv {
                         Invalidation::All => None,
@@ -283,7 +333,7 @@ impl FrameCache {
                 }
             } else {
                 if DEBUG {
-                    console_log!("invalidated: zoom");
+                    console_trace!("invalidated: zoom");
                 }
                 None
             }
@@ -360,7 +410,7 @@ impl FrameCache {
         );
 
         if DEBUG && pixels_drawn > 0 {
-            console_log!("drawing {} pixels", pixels_drawn);
+            console_trace!("drawing {} pixels", pixels_drawn);
         }
 
         // Undo scroll when reading from buffer.
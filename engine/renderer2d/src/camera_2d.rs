@@ -5,21 +5,34 @@ use glam::{vec2, IVec2, Mat3, UVec2, Vec2};
 use renderer::viewport_to_aspect;
 use renderer::Camera;
 use renderer::ShaderBinding;
+use std::f32::consts::TAU;
 
 /// A 2 dimensional camera.
 #[derive(Default)]
 pub struct Camera2d {
-    /// The [camera matrix](https://en.wikipedia.org/wiki/Camera_matrix).
+    /// The [camera matrix](https://en.wikipedia.org/wiki/Camera_matrix), including any
+    /// [shake][`Self::add_shake`].
     pub camera_matrix: Mat3,
-    /// The center of the [`Camera2d`]'s view in world space.
+    /// The center of the [`Camera2d`]'s view in world space, excluding any
+    /// [shake][`Self::add_shake`].
     pub center: Vec2,
-    /// The inverse of the [camera matrix](https://en.wikipedia.org/wiki/Camera_matrix).
+    /// The inverse of the [camera matrix](https://en.wikipedia.org/wiki/Camera_matrix), including
+    /// any [shake][`Self::add_shake`].
     pub view_matrix: Mat3,
     /// The width and height in pixels of the screen.
     pub viewport: UVec2,
-    /// The width of the [`Camera2d`]'s view in world space.
+    /// Half the width of the [`Camera2d`]'s view in world space, so larger values are further
+    /// zoomed out. Clamped to the [zoom limits][`Self::set_zoom_limits`].
     pub zoom: f32,
     pub(crate) aligned: Camera2dAligned,
+    /// Half the size of the box, in world space, that [`follow`][`Self::follow`] lets its target
+    /// move within without moving the camera.
+    follow_deadzone: f32,
+    shake: CameraShake,
+    /// Offset of the rendered view from [`Self::center`] in world space, due to shake.
+    shake_offset: Vec2,
+    /// Minimum and maximum [`Self::zoom`], if any (see [`Self::set_zoom_limits`]).
+    zoom_limits: Option<(f32, f32)>,
 }
 
 impl Camera for Camera2d {
@@ -33,20 +46,22 @@ impl Camera2d {
     /// Updates the [`Camera2d`] with a `center`, `zoom`, and `viewport`. Get `viewport` from
     /// [`Renderer::canvas_size`][`renderer::Renderer::canvas_size`]
     pub fn update(&mut self, center: Vec2, zoom: f32, viewport: UVec2) {
+        let zoom = self.clamp_zoom(zoom);
         let aspect = viewport_to_aspect(viewport);
+        let shake_offset = self.shake.offset() * zoom;
         let View {
             camera_matrix,
-            center,
             view_matrix,
-        } = View::new(center, zoom, aspect);
+            ..
+        } = View::new(center + shake_offset, zoom, aspect);
 
         // Scale changing only happens when either the width or height of the camera changes.
         // This causes an invalidation of delta pixels. In the future this kind of transformation
         // could be captured and used in background.rs.
         let scale_changed = zoom != self.zoom || viewport != self.viewport;
-        let aligned = self
-            .aligned
-            .updated(center, zoom, aspect, viewport, scale_changed);
+        let aligned =
+            self.aligned
+                .updated(center + shake_offset, zoom, aspect, viewport, scale_changed);
 
         // Recreate self to ensure all fields change.
         *self = Self {
@@ -56,17 +71,137 @@ impl Camera2d {
             viewport,
             zoom,
             aligned,
+            follow_deadzone: self.follow_deadzone,
+            shake: std::mem::take(&mut self.shake),
+            shake_offset,
+            zoom_limits: self.zoom_limits,
+        }
+    }
+
+    /// Limits [`Self::zoom`] (half the width of the view in world space) to `min..=max` in all
+    /// subsequent [`update`][`Self::update`]s, so players can't zoom in or out absurdly far. The
+    /// current zoom is clamped immediately. Limits given in the wrong order are swapped.
+    pub fn set_zoom_limits(&mut self, min: f32, max: f32) {
+        debug_assert!(
+            min > 0.0 && max > 0.0,
+            "invalid zoom limits {}..={}",
+            min,
+            max
+        );
+        // `f32::min` and `f32::max` also ignore a NaN limit, which would make clamping panic.
+        self.zoom_limits = Some((min.min(max), max.max(min)));
+        if self.viewport != UVec2::ZERO && self.clamp_zoom(self.zoom) != self.zoom {
+            self.update(self.center, self.zoom, self.viewport);
+        }
+    }
+
+    /// Clamps `zoom` to the [zoom limits][`Self::set_zoom_limits`], if any. Useful for
+    /// clamping accumulated input (e.g. mouse wheel deltas) so zooming back doesn't lag.
+    pub fn clamp_zoom(&self, zoom: f32) -> f32 {
+        if let Some((min, max)) = self.zoom_limits {
+            zoom.clamp(min, max)
+        } else {
+            zoom
+        }
+    }
+
+    /// Shakes the rendered view, e.g. when something explodes, for `duration` seconds. The shake
+    /// decays linearly from `intensity`, which is a fraction of [`Self::zoom`]. Overlapping shakes
+    /// add up, to at most the [max shake][`Self::set_max_shake`]. Doesn't affect [`Self::center`],
+    /// [`Self::to_world_position`], etc. Requires calling [`update_shake`][`Self::update_shake`].
+    pub fn add_shake(&mut self, intensity: f32, duration: f32) {
+        self.shake.add(intensity, duration);
+    }
+
+    /// Sets the maximum intensity of the sum of all [shakes][`Self::add_shake`].
+    pub fn set_max_shake(&mut self, max_intensity: f32) {
+        self.shake.max_intensity = max_intensity.max(0.0);
+    }
+
+    /// Advances [shakes][`Self::add_shake`] by `dt` seconds. Call it every frame before
+    /// [`update`][`Self::update`].
+    pub fn update_shake(&mut self, dt: f32) {
+        self.shake.update(dt);
+    }
+
+    /// Gets the center to pass to [`update`][`Self::update`] to follow `target` smoothly instead of
+    /// snapping to it, which hides jitter in its position. `smoothing` is roughly how many seconds
+    /// the camera lags behind, independent of frame rate (`dt` is seconds since the last frame).
+    /// A `smoothing` of `0.0` snaps. The camera doesn't move while `target` is within the
+    /// [`follow_deadzone`][`Self::set_follow_deadzone`].
+    pub fn follow(&self, target: Vec2, dt: f32, smoothing: f32) -> Vec2 {
+        let goal = Self::deadzone_follow(self.center, target, Vec2::splat(self.follow_deadzone));
+
+        if smoothing <= 0.0 {
+            return goal;
         }
+        self.center
+            .lerp(goal, 1.0 - (-dt.max(0.0) / smoothing).exp())
+    }
+
+    /// Moves the camera from `center` as little as possible to keep `target` within a box of
+    /// `half_size` around it.
+    pub fn deadzone_follow(center: Vec2, target: Vec2, half_size: Vec2) -> Vec2 {
+        center.clamp(target - half_size, target + half_size)
+    }
+
+    /// Sets the half size of the box, in world space, around the center that
+    /// [`follow`][`Self::follow`]'s target can move within without moving the camera, to avoid
+    /// micro-jitter. Defaults to `0.0`.
+    pub fn set_follow_deadzone(&mut self, radius: f32) {
+        self.follow_deadzone = radius.max(0.0);
     }
 
     /// Convert a position in view space (`-1.0..1.0`) to world space.
     pub fn to_world_position(&self, view_position: Vec2) -> Vec2 {
-        self.camera_matrix.transform_point2(view_position)
+        // Undo shake.
+        self.camera_matrix.transform_point2(view_position) - self.shake_offset
     }
 
     /// Convert a position in world space to view space (`-1.0..1.0`).
     pub fn to_view_position(&self, world_position: Vec2) -> Vec2 {
-        self.view_matrix.transform_point2(world_position)
+        // Undo shake.
+        self.view_matrix
+            .transform_point2(world_position + self.shake_offset)
+    }
+
+    /// Converts a position in world space to screen space, given the size of the screen
+    /// (`viewport`), e.g. in CSS pixels for positioning HTML over the canvas. Screen space has its
+    /// origin at the top left corner of the screen and its y-axis pointing **down**, unlike world and
+    /// view space.
+    pub fn world_to_screen(&self, world: Vec2, viewport: Vec2) -> Vec2 {
+        let view = self.to_view_position(world);
+        vec2(view.x + 1.0, 1.0 - view.y) * 0.5 * viewport
+    }
+
+    /// Converts a position in screen space (see [`world_to_screen`][`Self::world_to_screen`]) to
+    /// world space, given the size of the screen (`viewport`).
+    pub fn screen_to_world(&self, screen: Vec2, viewport: Vec2) -> Vec2 {
+        let normalized = screen / viewport * 2.0;
+        self.to_world_position(vec2(normalized.x - 1.0, 1.0 - normalized.y))
+    }
+
+    /// Returns true if `world_position` is within the view, extended by `margin` in world space
+    /// on all sides. A positive `margin` is useful for things drawn around the point (e.g.
+    /// labels), and a negative one for keeping something inside the edges of the screen.
+    pub fn is_visible(&self, world_position: Vec2, margin: f32) -> bool {
+        (world_position - self.center)
+            .abs()
+            .cmple(self.half_size() + margin)
+            .all()
+    }
+
+    /// Returns the minimum and maximum corners of the view in world space (ignoring shake), e.g.
+    /// for debugging what [`Self::is_visible`] considers visible.
+    pub fn view_bounds(&self) -> (Vec2, Vec2) {
+        let half_size = self.half_size();
+        (self.center - half_size, self.center + half_size)
+    }
+
+    /// Half the width and height of the view in world space.
+    fn half_size(&self) -> Vec2 {
+        let aspect = viewport_to_aspect(self.viewport);
+        vec2(self.zoom, self.zoom / aspect)
     }
 
     /// 1 world space unit in pixels.
@@ -90,7 +225,84 @@ impl Camera2d {
     pub(crate) fn subpixel_uv_diff(&self) -> Vec2 {
         self.aligned
             .view_matrix
-            .transform_vector2(self.aligned.center - self.center)
+            .transform_vector2(self.aligned.center - self.render_center())
+    }
+
+    /// Gets the camera matrix and center of a view that moves `factor` times as fast as this one,
+    /// for parallax.
+    pub(crate) fn parallax(&self, factor: f32) -> (Mat3, Vec2) {
+        let View {
+            camera_matrix,
+            center,
+            ..
+        } = View::new(
+            self.render_center() * factor,
+            self.zoom,
+            viewport_to_aspect(self.viewport),
+        );
+        (camera_matrix, center)
+    }
+
+    /// Returns the center of the rendered view, including any shake.
+    pub(crate) fn render_center(&self) -> Vec2 {
+        self.center + self.shake_offset
+    }
+}
+
+/// Shakes a [`Camera2d`] (see [`Camera2d::add_shake`]).
+struct CameraShake {
+    max_intensity: f32,
+    /// Intensity, duration, and remaining seconds of each shake.
+    shakes: Vec<(f32, f32, f32)>,
+    /// Pseudo-random state, for the direction of the offset.
+    seed: u32,
+}
+
+impl Default for CameraShake {
+    fn default() -> Self {
+        Self {
+            max_intensity: Self::DEFAULT_MAX_INTENSITY,
+            shakes: Vec::new(),
+            seed: 0,
+        }
+    }
+}
+
+impl CameraShake {
+    const DEFAULT_MAX_INTENSITY: f32 = 0.05;
+
+    fn add(&mut self, intensity: f32, duration: f32) {
+        if intensity > 0.0 && duration > 0.0 {
+            self.shakes.push((intensity, duration, duration));
+        }
+    }
+
+    /// Sum of all decaying shakes, clamped to the maximum.
+    fn intensity(&self) -> f32 {
+        self.shakes
+            .iter()
+            .map(|&(intensity, duration, remaining)| intensity * remaining / duration)
+            .sum::<f32>()
+            .min(self.max_intensity)
+    }
+
+    fn update(&mut self, dt: f32) {
+        for (_, _, remaining) in &mut self.shakes {
+            *remaining -= dt;
+        }
+        self.shakes.retain(|&(_, _, remaining)| remaining > 0.0);
+        // Linear congruential generator.
+        self.seed = self.seed.wrapping_mul(1664525).wrapping_add(1013904223);
+    }
+
+    /// Offset relative to zoom, which is exactly zero once all shakes expire.
+    fn offset(&self) -> Vec2 {
+        let intensity = self.intensity();
+        if intensity <= 0.0 {
+            return Vec2::ZERO;
+        }
+        let angle = (self.seed >> 8) as f32 * (TAU / (1 << 24) as f32);
+        vec2(angle.cos(), angle.sin()) * intensity
     }
 }
 
@@ -174,3 +386,232 @@ fn round_to_pixel(mut pos: Vec2, prev: Vec2, zoom: f32, viewport: UVec2) -> (Vec
 
     (pos, delta)
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::camera_2d::Camera2d;
+    use glam::{vec2, UVec2, Vec2};
+
+    fn camera() -> Camera2d {
+        let mut camera = Camera2d::default();
+        // 200x100 meters, centered on (50, 50).
+        camera.update(vec2(50.0, 50.0), 100.0, UVec2::new(800, 400));
+        camera
+    }
+
+    #[test]
+    fn deadzone_follow() {
+        let center = vec2(100.0, 50.0);
+        let half_size = vec2(20.0, 10.0);
+
+        // Inside.
+        for target in [
+            center,
+            center + vec2(19.0, -9.0),
+            center + vec2(-20.0, 10.0),
+        ] {
+            assert_eq!(Camera2d::deadzone_follow(center, target, half_size), center);
+        }
+
+        // Only moves enough to put the target on the edge of the box.
+        assert_eq!(
+            Camera2d::deadzone_follow(center, center + vec2(25.0, 0.0), half_size),
+            center + vec2(5.0, 0.0)
+        );
+        assert_eq!(
+            Camera2d::deadzone_follow(center, center + vec2(-30.0, 13.0), half_size),
+            center + vec2(-10.0, 3.0)
+        );
+
+        // Disabled.
+        let target = vec2(-3.0, 7.0);
+        assert_eq!(
+            Camera2d::deadzone_follow(Vec2::ZERO, target, Vec2::ZERO),
+            target
+        );
+    }
+
+    #[test]
+    fn follow() {
+        let (mut camera, mut fast) = (camera(), camera());
+        let target = vec2(150.0, 50.0);
+
+        // Snaps without smoothing.
+        assert_eq!(camera.follow(target, 1.0 / 60.0, 0.0), target);
+
+        // Moves part of the way.
+        let center = camera.follow(target, 1.0 / 60.0, 0.5);
+        assert!(center.x > 50.0 && center.x < 150.0, "{}", center);
+        assert_eq!(center.y, 50.0);
+
+        // Frame rate independent.
+        for _ in 0..4 {
+            let center = fast.follow(target, 1.0 / 120.0, 0.5);
+            fast.update(center, fast.zoom, fast.viewport);
+        }
+        let slow = camera.follow(target, 1.0 / 30.0, 0.5);
+        assert!(fast.center.distance(slow) < 0.1, "{} {}", fast.center, slow);
+
+        // Deadzone.
+        camera.set_follow_deadzone(10.0);
+        let within = vec2(55.0, 42.0);
+        assert_eq!(camera.follow(within, 1.0 / 60.0, 0.0), camera.center);
+        assert_eq!(
+            camera.follow(vec2(70.0, 50.0), 1.0 / 60.0, 0.0),
+            vec2(60.0, 50.0)
+        );
+
+        // Survives update.
+        camera.update(camera.center, camera.zoom, camera.viewport);
+        assert_eq!(camera.follow(within, 1.0 / 60.0, 0.0), camera.center);
+    }
+
+    #[test]
+    fn shake() {
+        let mut camera = camera();
+        let center = camera.center;
+        let world = vec2(75.0, 60.0);
+        let view = camera.to_view_position(world);
+
+        camera.add_shake(0.02, 0.5);
+        camera.add_shake(0.02, 1.0);
+        camera.update_shake(0.1);
+        camera.update(center, camera.zoom, camera.viewport);
+
+        // Rendered view moved, but not the logical one.
+        assert_ne!(camera.render_center(), center);
+        assert_eq!(camera.center, center);
+        assert!(camera.to_view_position(world).distance(view) < 0.0001);
+        assert!(camera.to_world_position(view).distance(world) < 0.001);
+
+        // Overlapping shakes sum, up to the max.
+        let offset = camera.render_center().distance(center);
+        assert!(offset > 0.02 * camera.zoom, "{}", offset);
+        camera.set_max_shake(0.01);
+        camera.update(center, camera.zoom, camera.viewport);
+        let offset = camera.render_center().distance(center);
+        assert!((offset - 0.01 * camera.zoom).abs() < 0.001, "{}", offset);
+
+        // Decays.
+        camera.set_max_shake(1.0);
+        camera.update_shake(0.6);
+        camera.update(center, camera.zoom, camera.viewport);
+        let offset = camera.render_center().distance(center);
+        assert!(offset > 0.0 && offset < 0.01 * camera.zoom, "{}", offset);
+
+        // Returns exactly to center.
+        camera.update_shake(0.5);
+        camera.update(center, camera.zoom, camera.viewport);
+        assert_eq!(camera.render_center(), center);
+        assert_eq!(camera.subpixel_uv_diff(), vec2(0.0, 0.0));
+    }
+
+    #[test]
+    fn world_to_screen() {
+        let camera = camera();
+        let viewport = vec2(800.0, 400.0);
+
+        // Origin is top left, y down.
+        let corners = [
+            (vec2(-50.0, 100.0), vec2(0.0, 0.0)),
+            (vec2(150.0, 100.0), vec2(800.0, 0.0)),
+            (vec2(150.0, 0.0), vec2(800.0, 400.0)),
+            (vec2(50.0, 50.0), vec2(400.0, 200.0)),
+        ];
+        for (world, screen) in corners {
+            assert!(camera.world_to_screen(world, viewport).distance(screen) < 0.001);
+            assert!(camera.screen_to_world(screen, viewport).distance(world) < 0.001);
+        }
+
+        // CSS pixels may differ from canvas pixels.
+        let half = viewport * 0.5;
+        assert!(
+            camera
+                .world_to_screen(vec2(150.0, 0.0), half)
+                .distance(half)
+                < 0.001
+        );
+    }
+
+    #[test]
+    fn parallax() {
+        let mut camera = camera();
+        let (matrix, center) = camera.parallax(1.0);
+        assert_eq!(matrix, camera.camera_matrix);
+        assert_eq!(center, camera.center);
+
+        let (matrix, center) = camera.parallax(0.5);
+        assert_eq!(center, vec2(25.0, 25.0));
+        assert!(matrix.transform_point2(vec2(0.0, 0.0)).distance(center) < 0.001);
+
+        // Moves half as far as the camera.
+        camera.update(vec2(70.0, 30.0), camera.zoom, camera.viewport);
+        let (_, moved) = camera.parallax(0.5);
+        assert!(
+            moved.distance(center + vec2(10.0, -10.0)) < 0.001,
+            "{}",
+            moved
+        );
+    }
+
+    #[test]
+    fn zoom_limits() {
+        let mut camera = camera();
+        assert_eq!(camera.clamp_zoom(1e6), 1e6);
+
+        // Re-clamps immediately.
+        camera.set_zoom_limits(10.0, 50.0);
+        assert_eq!(camera.zoom, 50.0);
+        assert!(camera.is_visible(vec2(99.0, 50.0), 0.0));
+        assert!(!camera.is_visible(vec2(101.0, 50.0), 0.0));
+
+        camera.update(camera.center, 1.0, camera.viewport);
+        assert_eq!(camera.zoom, 10.0);
+        camera.update(camera.center, 1000.0, camera.viewport);
+        assert_eq!(camera.zoom, 50.0);
+        assert_eq!(camera.clamp_zoom(30.0), 30.0);
+
+        // Swapped limits.
+        camera.set_zoom_limits(40.0, 20.0);
+        assert_eq!(camera.zoom, 40.0);
+        assert_eq!(camera.clamp_zoom(1.0), 20.0);
+    }
+
+    #[test]
+    fn visible_inside() {
+        let camera = camera();
+        for point in [vec2(50.0, 50.0), vec2(-49.0, 99.0), vec2(150.0, 0.0)] {
+            assert!(camera.is_visible(point, 0.0), "{}", point);
+        }
+    }
+
+    #[test]
+    fn visible_outside() {
+        let camera = camera();
+        for point in [vec2(-51.0, 50.0), vec2(50.0, 101.0), vec2(1000.0, -1000.0)] {
+            assert!(!camera.is_visible(point, 0.0), "{}", point);
+        }
+    }
+
+    #[test]
+    fn visible_margin() {
+        let camera = camera();
+
+        // Just outside, but within the margin band.
+        let outside = vec2(155.0, 50.0);
+        assert!(!camera.is_visible(outside, 0.0));
+        assert!(camera.is_visible(outside, 10.0));
+        assert!(!camera.is_visible(outside, 4.0));
+
+        // Just inside, but within the (negative) margin band.
+        let inside = vec2(50.0, 95.0);
+        assert!(camera.is_visible(inside, 0.0));
+        assert!(!camera.is_visible(inside, -10.0));
+    }
+
+    #[test]
+    fn view_bounds() {
+        let camera = camera();
+        assert_eq!(camera.view_bounds(), (vec2(-50.0, 0.0), vec2(150.0, 100.0)));
+    }
+}
@@ -10,9 +10,9 @@ use std::f32::consts::PI;
 use std::ops::Range;
 
 derive_vertex!(
-    struct PosColor {
-        pos: Vec2,
-        color: Vec4,
+    pub(crate) struct PosColor {
+        pub(crate) pos: Vec2,
+        pub(crate) color: Vec4,
     }
 );
 
@@ -23,6 +23,8 @@ pub struct GraphicLayer<I: Index = u16> {
     buffer: TriangleBuffer<PosColor, I>,
     /// Cached in pre_prepare.
     zoom: f32,
+    /// Opacity of the whole layer (see [`GraphicLayer::set_alpha`]).
+    alpha: f32,
 }
 
 impl<I: Index> GraphicLayer<I> {
@@ -38,9 +40,16 @@ impl<I: Index> GraphicLayer<I> {
             mesh: MeshBuilder::new(),
             buffer: TriangleBuffer::new(renderer),
             zoom: 0.0,
+            alpha: 1.0,
         }
     }
 
+    /// Sets the opacity of everything in the layer, which multiplies the alpha of each color
+    /// (e.g. for fading it in and out). Defaults to `1.0`.
+    pub fn set_alpha(&mut self, alpha: f32) {
+        self.alpha = alpha;
+    }
+
     /// Draws a triangle centered on `center`, with a base of `scale.x`, a height of `scale.y` and
     /// rotated by `angle`. An `angle` of 0 is pointing
     pub fn draw_triangle(&mut self, center: Vec2, scale: Vec2, angle: f32, color: Vec4) {
@@ -121,6 +130,32 @@ impl<I: Index> GraphicLayer<I> {
         );
     }
 
+    /// Like [`draw_line`][`Self::draw_line`] but made of `dash_len` long dashes separated by
+    /// `gap_len` long gaps (in world units), starting with a dash at `start`. Dashes shorter than
+    /// `thickness` (including those of segments shorter than `thickness`) are drawn as dots. A
+    /// `gap_len` of `0.0` draws a solid line. Dashes and gaps of very long lines are lengthened
+    /// proportionally, to draw at most 1000 dashes.
+    pub fn draw_dashed_line(
+        &mut self,
+        start: Vec2,
+        end: Vec2,
+        thickness: f32,
+        color: Vec4,
+        dash_len: f32,
+        gap_len: f32,
+    ) {
+        let diff = end - start;
+        let length = diff.length();
+        let direction = diff.normalize_or_zero();
+        let angle = diff.y.atan2(diff.x);
+
+        for dash in dashes(length, dash_len, gap_len) {
+            let center = start + direction * ((dash.start + dash.end) * 0.5);
+            let dash_length = (dash.end - dash.start).max(thickness);
+            self.draw_rectangle(center, Vec2::new(dash_length, thickness), angle, color);
+        }
+    }
+
     /// Like [`draw_line`][`Self::draw_line`] but endpoints are rounded. If `extend`, start and end
     /// will be the centers of the semicircle endpoints (aka longer than a normal line).
     pub fn draw_rounded_line(
@@ -188,6 +223,59 @@ impl<I: Index> GraphicLayer<I> {
         self.draw_arc(line_end, adjusted_radius, angle_range, arc_thickness, e);
     }
 
+    /// Draws a quadratic Bezier curve from `p0` to `p2`, pulled towards `p1`, as `segments` lines
+    /// (by default, based on the length of the curve on screen).
+    pub fn draw_bezier_quadratic(
+        &mut self,
+        p0: Vec2,
+        p1: Vec2,
+        p2: Vec2,
+        thickness: f32,
+        color: Vec4,
+        segments: Option<usize>,
+    ) {
+        self.draw_bezier(&[p0, p1, p2], thickness, color, segments);
+    }
+
+    /// Like [`draw_bezier_quadratic`][`Self::draw_bezier_quadratic`] but cubic, from `p0` to `p3`,
+    /// pulled towards `p1` and then `p2`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_bezier_cubic(
+        &mut self,
+        p0: Vec2,
+        p1: Vec2,
+        p2: Vec2,
+        p3: Vec2,
+        thickness: f32,
+        color: Vec4,
+        segments: Option<usize>,
+    ) {
+        self.draw_bezier(&[p0, p1, p2, p3], thickness, color, segments);
+    }
+
+    fn draw_bezier(
+        &mut self,
+        control: &[Vec2],
+        thickness: f32,
+        color: Vec4,
+        segments: Option<usize>,
+    ) {
+        let segments = segments
+            .unwrap_or_else(|| {
+                // The curve is no longer than its control polygon.
+                let length: f32 = control.windows(2).map(|w| w[0].distance(w[1])).sum();
+                default_bezier_segments(length / self.zoom)
+            })
+            .max(1);
+
+        let mut start = control[0];
+        for i in 1..=segments {
+            let end = bezier(control, i as f32 / segments as f32);
+            self.draw_line(start, end, thickness, color);
+            start = end;
+        }
+    }
+
     /// Draw an arc.
     pub fn draw_arc(
         &mut self,
@@ -200,6 +288,27 @@ impl<I: Index> GraphicLayer<I> {
         self.draw_arc_inner(center, radius, angle_range, thickness, color, None);
     }
 
+    /// Like [`draw_arc`][`Self::draw_arc`] but with an exact number of `segments`, instead of one
+    /// based on the size of the arc on screen.
+    pub fn draw_arc_segments(
+        &mut self,
+        center: Vec2,
+        radius: f32,
+        angle_range: Range<f32>,
+        segments: usize,
+        thickness: f32,
+        color: Vec4,
+    ) {
+        self.draw_arc_inner(
+            center,
+            radius,
+            angle_range,
+            thickness,
+            color,
+            Some(segments),
+        );
+    }
+
     fn draw_arc_inner(
         &mut self,
         center: Vec2,
@@ -221,77 +330,16 @@ impl<I: Index> GraphicLayer<I> {
             return;
         }
 
-        let segments = segments.unwrap_or_else(|| {
-            // Number of segments to approximate an arc.
-            // The radius.sqrt() helps even out the quality surprisingly well.
-            let segments = ((radius / self.zoom).sqrt() * angle_span * (200.0 / (PI * 2.0))) as i32;
-
-            // Set maximum to prevent indices from overflowing.
-            segments.clamp(6, 100) as usize
-        });
-
-        // Algorithm: Build a circle outline segment by segment, going counterclockwise. Vertices
-        // are reused for maximum efficiency (except the original A and B which are duplicated at the
-        // end).
-
-        /*
-           D -> +.   <- angle & mat
-              -    .
-            -        .
-          -            .
-        +   <-- C        .
-         \                .
-          +---------------+
-          ^               ^
-          |               |
-          A (index)       B
-         */
-
-        let inner = radius - thickness * 0.5;
-        let outer = radius + thickness * 0.5;
-
-        let initial_a = Vec2::new(inner, 0.0);
-        let initial_b = Vec2::new(outer, 0.0);
-        let mat = Mat2::from_angle(angle_range.start);
-        let a = center + mat * initial_a;
-        let b = center + mat * initial_b;
-
-        let vertices = &mut self.mesh.vertices;
-
-        // Use extend instead of loop to allow pre-allocation.
-        // Calculate index before extending vertices.
-        let starting_index = vertices.len();
-        self.mesh
-            .indices
-            .extend((0..segments).into_iter().flat_map(|i| {
-                let index = starting_index + i * 2;
-                // Triangles are [A, D, B] and [A, C, D].
-                IntoIterator::into_iter([
-                    I::from_usize(index),
-                    I::from_usize(index + 3),
-                    I::from_usize(index + 1),
-                    I::from_usize(index),
-                    I::from_usize(index + 2),
-                    I::from_usize(index + 3),
-                ])
-            }));
-
-        // Use extend instead of loop to allow pre-allocation.
-        let angle_per_segment = angle_span / segments as f32;
-        vertices.extend(
-            IntoIterator::into_iter([PosColor { pos: a, color }, PosColor { pos: b, color }])
-                .chain((1..=segments).into_iter().flat_map(|i| {
-                    let angle = i as f32 * angle_per_segment + angle_range.start;
-                    let mat = Mat2::from_angle(angle);
-
-                    let c = center + mat * initial_a;
-                    let d = center + mat * initial_b;
-
-                    IntoIterator::into_iter([
-                        PosColor { pos: c, color },
-                        PosColor { pos: d, color },
-                    ])
-                })),
+        let segments =
+            segments.unwrap_or_else(|| default_arc_segments(radius / self.zoom, angle_span));
+        build_arc(
+            &mut self.mesh,
+            center,
+            radius,
+            angle_range,
+            thickness,
+            color,
+            segments,
         );
     }
 
@@ -302,8 +350,36 @@ impl<I: Index> GraphicLayer<I> {
 
     /// Like [`draw_circle`][`Self::draw_circle`] but filled instead of outlined.
     pub fn draw_filled_circle(&mut self, center: Vec2, radius: f32, color: Vec4) {
-        // TODO: Not the most efficient way to make a filled circle.
-        self.draw_circle(center, radius * 0.5, radius, color)
+        self.draw_circle_filled(center, radius, None, color)
+    }
+
+    /// Like [`draw_filled_circle`][`Self::draw_filled_circle`] but with a custom number of
+    /// `segments`.
+    pub fn draw_circle_filled(
+        &mut self,
+        center: Vec2,
+        radius: f32,
+        segments: Option<usize>,
+        color: Vec4,
+    ) {
+        if radius <= 0.0 {
+            return;
+        }
+        let segments = segments
+            .unwrap_or_else(|| default_arc_segments(radius / self.zoom, PI * 2.0))
+            .max(3);
+        let points: Vec<Vec2> = (0..segments)
+            .map(|i| center + unit(i, segments) * radius)
+            .collect();
+        self.draw_polygon_filled(&points, color);
+    }
+
+    /// Fills a polygon with corners at `points`, which must be convex and consistently wound
+    /// (either clockwise or counterclockwise). Concave polygons will be drawn incorrectly, and cause
+    /// a panic in debug builds.
+    pub fn draw_polygon_filled(&mut self, points: &[Vec2], color: Vec4) {
+        debug_assert!(is_convex(points), "polygon isn't convex: {:?}", points);
+        build_fan(&mut self.mesh, points, color);
     }
 
     /// Draws an outlined regular polygon.
@@ -346,12 +422,19 @@ impl<I: Index> Layer<Camera2d> for GraphicLayer<I> {
     }
 
     fn render(&mut self, renderer: &Renderer2d) {
+        if !renderer.is_layer_enabled::<Self>() {
+            // Don't accumulate draws while disabled.
+            self.mesh.clear();
+            return;
+        }
+
         if self.mesh.is_empty() {
             return;
         }
 
         if let Some(shader) = self.shader.bind(renderer) {
             renderer.camera.uniform_matrix(&shader);
+            shader.uniform1f("uAlpha", self.alpha);
 
             self.buffer.buffer_mesh(renderer, &self.mesh);
             self.buffer.bind(renderer).draw();
@@ -361,3 +444,347 @@ impl<I: Index> Layer<Camera2d> for GraphicLayer<I> {
         self.mesh.clear();
     }
 }
+
+/// Gets the unit vector `i / n` of the way around a circle.
+pub(crate) fn unit(i: usize, n: usize) -> Vec2 {
+    let angle = i as f32 * (PI * 2.0 / n as f32);
+    Vec2::new(angle.cos(), angle.sin())
+}
+
+/// Builds a triangle fan from the first of `points`. Does nothing if there are less than 3.
+pub(crate) fn build_fan<I: Index>(
+    mesh: &mut MeshBuilder<PosColor, I>,
+    points: &[Vec2],
+    color: Vec4,
+) {
+    if points.len() < 3 {
+        return;
+    }
+
+    // Calculate index before extending vertices.
+    let index = mesh.vertices.len();
+    mesh.vertices
+        .extend(points.iter().map(|&pos| PosColor { pos, color }));
+    mesh.indices.extend((1..points.len() - 1).flat_map(|i| {
+        [
+            I::from_usize(index),
+            I::from_usize(index + i),
+            I::from_usize(index + i + 1),
+        ]
+    }));
+}
+
+/// Returns true if the polygon with corners at `points` is convex and consistently wound.
+fn is_convex(points: &[Vec2]) -> bool {
+    let n = points.len();
+    let mut sign = 0.0;
+    for i in 0..n {
+        let a = points[i];
+        let b = points[(i + 1) % n];
+        let c = points[(i + 2) % n];
+        let cross = (b - a).perp_dot(c - b);
+        // Allow colinear points.
+        if cross.abs() <= f32::EPSILON * (b - a).length() * (c - b).length() * 4.0 {
+            continue;
+        }
+        if sign == 0.0 {
+            sign = cross.signum();
+        } else if cross.signum() != sign {
+            return false;
+        }
+    }
+    true
+}
+
+/// Gets the distances along a line of `length` spanned by dashes (see
+/// [`GraphicLayer::draw_dashed_line`]). Always returns at least one (possibly empty) dash.
+fn dashes(length: f32, dash_len: f32, gap_len: f32) -> impl Iterator<Item = Range<f32>> {
+    const MAX_DASHES: f32 = 1000.0;
+
+    let (mut dash_len, mut gap_len) = (dash_len.max(0.0), gap_len.max(0.0));
+    let mut period = dash_len + gap_len;
+    // Lengthen the pattern instead of drawing an unreasonable number of dashes.
+    if gap_len > 0.0 && length > period * MAX_DASHES {
+        let scale = (length / (period * MAX_DASHES)).ceil();
+        dash_len *= scale;
+        gap_len *= scale;
+        period = dash_len + gap_len;
+    }
+    let solid = gap_len == 0.0;
+    let count = if solid {
+        1
+    } else {
+        ((length / period).ceil() as usize).max(1)
+    };
+    (0..count).map(move |i| {
+        if solid {
+            0.0..length
+        } else {
+            let start = i as f32 * period;
+            start..(start + dash_len).min(length)
+        }
+    })
+}
+
+/// Number of segments to approximate an arc spanning `angle_span` radians, given its
+/// `relative_radius` (radius divided by zoom).
+fn default_arc_segments(relative_radius: f32, angle_span: f32) -> usize {
+    // The radius.sqrt() helps even out the quality surprisingly well.
+    let segments = (relative_radius.sqrt() * angle_span * (200.0 / (PI * 2.0))) as i32;
+
+    // Set maximum to prevent indices from overflowing.
+    segments.clamp(6, 100) as usize
+}
+
+/// Number of lines to approximate a Bezier curve, given the `relative_length` (length divided by
+/// zoom) of its control polygon.
+fn default_bezier_segments(relative_length: f32) -> usize {
+    ((relative_length.sqrt() * 32.0) as usize).clamp(4, 64)
+}
+
+/// Evaluates the Bezier curve with (at most 4) `control` points at `t` (0 to 1).
+fn bezier(control: &[Vec2], t: f32) -> Vec2 {
+    let mut points = [Vec2::ZERO; 4];
+    points[..control.len()].copy_from_slice(control);
+    // De Casteljau's algorithm.
+    for n in (1..control.len()).rev() {
+        for i in 0..n {
+            points[i] = points[i].lerp(points[i + 1], t);
+        }
+    }
+    points[0]
+}
+
+/// Adds an arc outline, made of `segments` quads, to `mesh`.
+fn build_arc<I: Index>(
+    mesh: &mut MeshBuilder<PosColor, I>,
+    center: Vec2,
+    radius: f32,
+    angle_range: Range<f32>,
+    thickness: f32,
+    color: Vec4,
+    segments: usize,
+) {
+    let angle_span = angle_range.end - angle_range.start;
+
+    // Algorithm: Build a circle outline segment by segment, going counterclockwise. Vertices
+    // are reused for maximum efficiency (except the original A and B which are duplicated at the
+    // end).
+
+    /*
+       D -> +.   <- angle & mat
+          -    .
+        -        .
+      -            .
+    +   <-- C        .
+     \                .
+      +---------------+
+      ^               ^
+      |               |
+      A (index)       B
+     */
+
+    let inner = radius - thickness * 0.5;
+    let outer = radius + thickness * 0.5;
+
+    let initial_a = Vec2::new(inner, 0.0);
+    let initial_b = Vec2::new(outer, 0.0);
+    let mat = Mat2::from_angle(angle_range.start);
+    let a = center + mat * initial_a;
+    let b = center + mat * initial_b;
+
+    let vertices = &mut mesh.vertices;
+
+    // Use extend instead of loop to allow pre-allocation.
+    // Calculate index before extending vertices.
+    let starting_index = vertices.len();
+    mesh.indices.extend((0..segments).into_iter().flat_map(|i| {
+        let index = starting_index + i * 2;
+        // Triangles are [A, D, B] and [A, C, D].
+        IntoIterator::into_iter([
+            I::from_usize(index),
+            I::from_usize(index + 3),
+            I::from_usize(index + 1),
+            I::from_usize(index),
+            I::from_usize(index + 2),
+            I::from_usize(index + 3),
+        ])
+    }));
+
+    // Use extend instead of loop to allow pre-allocation.
+    let angle_per_segment = angle_span / segments as f32;
+    vertices.extend(
+        IntoIterator::into_iter([PosColor { pos: a, color }, PosColor { pos: b, color }]).chain(
+            (1..=segments).into_iter().flat_map(|i| {
+                let angle = i as f32 * angle_per_segment + angle_range.start;
+                let mat = Mat2::from_angle(angle);
+
+                let c = center + mat * initial_a;
+                let d = center + mat * initial_b;
+
+                IntoIterator::into_iter([PosColor { pos: c, color }, PosColor { pos: d, color }])
+            }),
+        ),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graphic::{
+        bezier, build_arc, build_fan, dashes, default_arc_segments, default_bezier_segments,
+        is_convex, PosColor,
+    };
+    use glam::{Vec2, Vec4};
+    use renderer::MeshBuilder;
+    use std::f32::consts::PI;
+
+    #[test]
+    fn full_circle_segments() {
+        for segments in [3, 6, 32, 100] {
+            let mut mesh = MeshBuilder::<PosColor>::new();
+            build_arc(
+                &mut mesh,
+                Vec2::ZERO,
+                1.0,
+                0.0..PI * 2.0,
+                0.1,
+                Vec4::ONE,
+                segments,
+            );
+
+            // Two triangles per segment.
+            assert_eq!(mesh.indices.len(), segments * 6);
+            assert_eq!(mesh.vertices.len(), (segments + 1) * 2);
+
+            // Closes the circle.
+            let first = mesh.vertices.first().unwrap().pos;
+            let last = mesh.vertices[mesh.vertices.len() - 2].pos;
+            assert!(first.distance(last) < 0.001, "{} {}", first, last);
+        }
+    }
+
+    #[test]
+    fn arc_angles() {
+        let center = Vec2::new(5.0, -3.0);
+        let radius = 2.0;
+        let mut mesh = MeshBuilder::<PosColor>::new();
+        build_arc(&mut mesh, center, radius, PI * 0.5..PI, 0.0, Vec4::ONE, 8);
+
+        let start = mesh.vertices.first().unwrap().pos;
+        let end = mesh.vertices.last().unwrap().pos;
+        assert!(start.distance(center + Vec2::new(0.0, radius)) < 0.001);
+        assert!(end.distance(center + Vec2::new(-radius, 0.0)) < 0.001);
+
+        // No vertex strays outside the quadrant between the start and end angles.
+        for PosColor { pos, .. } in mesh.vertices {
+            let relative = pos - center;
+            assert!(relative.x <= 0.001 && relative.y >= -0.001, "{}", relative);
+        }
+    }
+
+    #[test]
+    fn default_segments() {
+        let small = default_arc_segments(0.01, PI * 2.0);
+        let large = default_arc_segments(1.0, PI * 2.0);
+        assert!(small <= large);
+        assert!((6..=100).contains(&small));
+        assert!((6..=100).contains(&large));
+        assert!(default_arc_segments(1.0, PI) <= large);
+    }
+
+    #[test]
+    fn bezier_curves() {
+        let (p0, p1, p2, p3) = (
+            Vec2::ZERO,
+            Vec2::new(0.0, 2.0),
+            Vec2::new(2.0, 2.0),
+            Vec2::new(2.0, 0.0),
+        );
+        assert_eq!(bezier(&[p0, p1, p2], 0.0), p0);
+        assert_eq!(bezier(&[p0, p1, p2], 1.0), p2);
+        assert_eq!(bezier(&[p0, p1, p2], 0.5), Vec2::new(0.5, 1.5));
+        assert_eq!(bezier(&[p0, p1, p2, p3], 0.0), p0);
+        assert_eq!(bezier(&[p0, p1, p2, p3], 1.0), p3);
+        assert_eq!(bezier(&[p0, p1, p2, p3], 0.5), Vec2::new(1.0, 1.5));
+
+        let short = default_bezier_segments(0.0);
+        let long = default_bezier_segments(1.0);
+        assert!(short <= long);
+        assert!((4..=64).contains(&short));
+        assert_eq!(default_bezier_segments(f32::INFINITY), 64);
+    }
+
+    #[test]
+    fn dash_pattern() {
+        let collect =
+            |length, dash_len, gap_len| dashes(length, dash_len, gap_len).collect::<Vec<_>>();
+
+        assert_eq!(
+            collect(10.0, 2.0, 1.0),
+            [0.0..2.0, 3.0..5.0, 6.0..8.0, 9.0..10.0]
+        );
+        // Pattern doesn't stretch to fit.
+        assert_eq!(collect(7.0, 2.0, 1.0)[..2], collect(10.0, 2.0, 1.0)[..2]);
+        // Shorter than a dash.
+        assert_eq!(collect(0.5, 2.0, 1.0), [0.0..0.5]);
+        assert_eq!(collect(0.0, 2.0, 1.0), [0.0..0.0]);
+        // Dotted.
+        assert_eq!(collect(2.0, 0.0, 1.0), [0.0..0.0, 1.0..1.0]);
+        // Solid.
+        assert_eq!(collect(5.0, 2.0, 0.0), [0.0..5.0]);
+        // Very long lines lengthen the pattern instead.
+        let long = collect(1e9, 2.0, 1.0);
+        assert!(long.len() <= 1000 && long.len() > 500, "{}", long.len());
+        let (first, second) = (&long[0], &long[1]);
+        assert_eq!(second.start, first.end * 1.5);
+    }
+
+    #[test]
+    fn fan() {
+        let square = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(0.0, 1.0),
+        ];
+        assert!(is_convex(&square));
+
+        let mut mesh = MeshBuilder::<PosColor>::new();
+        // Offsets indices by existing vertices.
+        build_fan(&mut mesh, &square[..3], Vec4::ONE);
+        build_fan(&mut mesh, &square, Vec4::ONE);
+        assert_eq!(mesh.vertices.len(), 7);
+        assert_eq!(mesh.indices, [0, 1, 2, 3, 4, 5, 3, 5, 6]);
+
+        // Too few points.
+        build_fan(&mut mesh, &square[..2], Vec4::ONE);
+        assert_eq!(mesh.vertices.len(), 7);
+    }
+
+    #[test]
+    fn convex() {
+        let clockwise = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(0.0, 1.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(1.0, 0.0),
+        ];
+        assert!(is_convex(&clockwise));
+
+        // Colinear.
+        assert!(is_convex(&[
+            Vec2::new(0.0, 0.0),
+            Vec2::new(0.5, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(0.0, 1.0),
+        ]));
+
+        let arrow = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.5),
+            Vec2::new(0.0, 1.0),
+            Vec2::new(0.3, 0.5),
+        ];
+        assert!(!is_convex(&arrow));
+    }
+}
@@ -19,16 +19,20 @@ use renderer::Renderer;
 mod background;
 mod camera_2d;
 mod graphic;
+mod minimap;
 mod particle;
 mod sprite;
 mod text;
+mod trail;
 
 pub use background::*;
 pub use camera_2d::*;
 pub use graphic::*;
+pub use minimap::*;
 pub use particle::*;
 pub use sprite::*;
 pub use text::*;
+pub use trail::*;
 
 /// An alias for [`Renderer<Camera2d>`].
 pub type Renderer2d = Renderer<Camera2d>;
@@ -0,0 +1,284 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::camera_2d::Camera2d;
+use crate::graphic::{build_fan, unit, PosColor};
+use crate::Renderer2d;
+use glam::{Mat2, Mat3, Vec2, Vec4};
+use renderer::{Index, Layer, MeshBuilder, Shader, TriangleBuffer};
+
+/// Corner of the screen that a [`MinimapLayer`] is drawn in.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum MinimapCorner {
+    /// Top left.
+    TopLeft,
+    /// Top right.
+    TopRight,
+    /// Bottom left.
+    BottomLeft,
+    /// Bottom right.
+    #[default]
+    BottomRight,
+}
+
+/// A contact on a [`MinimapLayer`], passed in each frame.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MinimapBlip {
+    /// Position in world space.
+    pub position: Vec2,
+    /// Radius as a fraction of the size of the minimap (e.g. `0.02`).
+    pub radius: f32,
+    /// Color (alpha is respected).
+    pub color: Vec4,
+}
+
+/// Draws a map of the whole world in a corner of the screen, ignoring the [`Camera2d`]'s zoom and
+/// pan. Shows the world bounds, the player's position and heading, and [`MinimapBlip`]s.
+pub struct MinimapLayer<I: Index = u16> {
+    shader: Shader,
+    mesh: MeshBuilder<PosColor, I>,
+    buffer: TriangleBuffer<PosColor, I>,
+    /// Corner of the screen to draw in.
+    pub corner: MinimapCorner,
+    /// Width and height, as a fraction of the smaller dimension of the screen.
+    pub size: f32,
+    /// Distance from the edges of the screen, as a fraction of its smaller dimension.
+    pub margin: f32,
+    /// Color of the area behind the map.
+    pub background_color: Vec4,
+    /// Color of the world bounds and the player.
+    pub foreground_color: Vec4,
+    /// Minimum and maximum corners of the world (see [`Self::set_world_bounds`]).
+    world_bounds: (Vec2, Vec2),
+    /// Position and direction (in radians) of the player this frame, if any.
+    player: Option<(Vec2, f32)>,
+    /// Blips this frame.
+    blips: Vec<MinimapBlip>,
+    /// Cached in pre_prepare.
+    viewport: Vec2,
+}
+
+impl<I: Index> MinimapLayer<I> {
+    /// Creates a new [`MinimapLayer`] of a world with corners at `world_min` and `world_max`.
+    pub fn new(renderer: &Renderer2d, world_min: Vec2, world_max: Vec2) -> Self {
+        // Same as GraphicLayer, but with a different view matrix.
+        let shader = renderer.create_shader(
+            include_str!("shaders/graphic.vert"),
+            include_str!("shaders/graphic.frag"),
+        );
+
+        Self {
+            shader,
+            mesh: MeshBuilder::new(),
+            buffer: TriangleBuffer::new(renderer),
+            corner: MinimapCorner::default(),
+            size: 0.25,
+            margin: 0.02,
+            background_color: Vec4::new(0.0, 0.0, 0.0, 0.4),
+            foreground_color: Vec4::ONE,
+            world_bounds: (world_min, world_max),
+            player: None,
+            blips: Vec::new(),
+            viewport: Vec2::ZERO,
+        }
+    }
+
+    /// Changes the corners of the world, e.g. if it grows.
+    pub fn set_world_bounds(&mut self, world_min: Vec2, world_max: Vec2) {
+        self.world_bounds = (world_min, world_max);
+    }
+
+    /// Draws the player at `position` (in world space) pointing in `direction` (in radians) this
+    /// frame.
+    pub fn draw_player(&mut self, position: Vec2, direction: f32) {
+        self.player = Some((position, direction));
+    }
+
+    /// Draws `blip` this frame.
+    pub fn draw_blip(&mut self, blip: MinimapBlip) {
+        self.blips.push(blip);
+    }
+
+    /// Converts a position in view space (`-1.0..1.0`, e.g. of the mouse) to the world space
+    /// position it points to on the minimap, or [`None`] if it isn't on the minimap. Useful for
+    /// recentering the view when the minimap is clicked.
+    pub fn to_world_position(&self, view_position: Vec2) -> Option<Vec2> {
+        let (min, max) = minimap_rect(self.corner, self.size, self.margin, self.viewport);
+        let pixels = (view_position + 1.0) * 0.5 * self.viewport;
+        if pixels.cmplt(min).any() || pixels.cmpgt(max).any() {
+            return None;
+        }
+        let (world_min, world_max) = self.world_bounds;
+        let (scale, offset) = world_to_minimap(world_min, world_max, min, max);
+        Some((pixels - offset) / scale)
+    }
+
+    fn build_mesh(&mut self) {
+        let (min, max) = minimap_rect(self.corner, self.size, self.margin, self.viewport);
+        let (world_min, world_max) = self.world_bounds;
+        let (scale, offset) = world_to_minimap(world_min, world_max, min, max);
+        let to_minimap = |world: Vec2| world * scale + offset;
+        let size = (max - min).x;
+
+        build_rectangle(&mut self.mesh, min, max, self.background_color);
+
+        // World bounds.
+        let (bounds_min, bounds_max) = (to_minimap(world_min), to_minimap(world_max));
+        let thickness = Vec2::splat((size * 0.01).max(1.0));
+        for (a, b) in [
+            (
+                bounds_min,
+                Vec2::new(bounds_max.x, bounds_min.y + thickness.y),
+            ),
+            (
+                Vec2::new(bounds_min.x, bounds_max.y - thickness.y),
+                bounds_max,
+            ),
+            (
+                bounds_min,
+                Vec2::new(bounds_min.x + thickness.x, bounds_max.y),
+            ),
+            (
+                Vec2::new(bounds_max.x - thickness.x, bounds_min.y),
+                bounds_max,
+            ),
+        ] {
+            build_rectangle(&mut self.mesh, a, b, self.foreground_color);
+        }
+
+        for blip in self.blips.drain(..) {
+            let center = to_minimap(blip.position).clamp(min, max);
+            let radius = blip.radius * size;
+            let points: Vec<Vec2> = (0..8).map(|i| center + unit(i, 8) * radius).collect();
+            build_fan(&mut self.mesh, &points, blip.color);
+        }
+
+        if let Some((position, direction)) = self.player.take() {
+            let center = to_minimap(position).clamp(min, max);
+            let rot = Mat2::from_angle(direction);
+            // Points in the direction of travel (+x when not rotated).
+            let points = [
+                Vec2::new(1.0, 0.0),
+                Vec2::new(-0.6, 0.6),
+                Vec2::new(-0.6, -0.6),
+            ]
+            .map(|point| center + rot * point * (size * 0.04));
+            build_fan(&mut self.mesh, &points, self.foreground_color);
+        }
+    }
+}
+
+impl<I: Index> Layer<Camera2d> for MinimapLayer<I> {
+    fn pre_prepare(&mut self, renderer: &Renderer2d) {
+        self.viewport = renderer.canvas_size().as_vec2();
+    }
+
+    fn render(&mut self, renderer: &Renderer2d) {
+        if renderer.is_layer_enabled::<Self>() && self.viewport.min_element() > 0.0 {
+            self.build_mesh();
+            if let Some(shader) = self.shader.bind(renderer) {
+                shader.uniform_matrix3f("uView", &screen_view_matrix(self.viewport));
+                shader.uniform1f("uAlpha", 1.0);
+
+                self.buffer.buffer_mesh(renderer, &self.mesh);
+                self.buffer.bind(renderer).draw();
+            }
+        }
+
+        // Always clear, even if disabled or the shader wasn't bound.
+        self.mesh.clear();
+        self.blips.clear();
+        self.player = None;
+    }
+}
+
+/// Adds an axis aligned rectangle from `min` to `max` to `mesh`.
+fn build_rectangle<I: Index>(
+    mesh: &mut MeshBuilder<PosColor, I>,
+    min: Vec2,
+    max: Vec2,
+    color: Vec4,
+) {
+    let points = [min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)];
+    build_fan(mesh, &points, color);
+}
+
+/// Gets the minimum and maximum corners, in pixels from the bottom left of a `viewport`, of a
+/// minimap in `corner`. `size` and `margin` are fractions of the smaller dimension of `viewport`.
+fn minimap_rect(corner: MinimapCorner, size: f32, margin: f32, viewport: Vec2) -> (Vec2, Vec2) {
+    let unit = viewport.min_element();
+    let (size, margin) = (size * unit, margin * unit);
+    let x = match corner {
+        MinimapCorner::TopLeft | MinimapCorner::BottomLeft => margin,
+        MinimapCorner::TopRight | MinimapCorner::BottomRight => viewport.x - margin - size,
+    };
+    let y = match corner {
+        MinimapCorner::BottomLeft | MinimapCorner::BottomRight => margin,
+        MinimapCorner::TopLeft | MinimapCorner::TopRight => viewport.y - margin - size,
+    };
+    let min = Vec2::new(x, y);
+    (min, min + size)
+}
+
+/// Gets the scale and offset that fit the world from `world_min` to `world_max` into the minimap
+/// from `min` to `max` (see [`minimap_rect`]), preserving its aspect ratio and centering it.
+fn world_to_minimap(world_min: Vec2, world_max: Vec2, min: Vec2, max: Vec2) -> (f32, Vec2) {
+    let world_size = (world_max - world_min).max(Vec2::splat(f32::EPSILON));
+    let scale = ((max - min) / world_size).min_element();
+    let center = (min + max) * 0.5;
+    let world_center = (world_min + world_max) * 0.5;
+    (scale, center - world_center * scale)
+}
+
+/// Gets a view matrix that converts pixels from the bottom left of a `viewport` to clip space.
+fn screen_view_matrix(viewport: Vec2) -> Mat3 {
+    Mat3::from_scale_angle_translation(2.0 / viewport, 0.0, Vec2::splat(-1.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::minimap::{minimap_rect, screen_view_matrix, world_to_minimap, MinimapCorner};
+    use glam::Vec2;
+
+    #[test]
+    fn rect() {
+        let viewport = Vec2::new(1000.0, 500.0);
+        assert_eq!(
+            minimap_rect(MinimapCorner::BottomLeft, 0.2, 0.02, viewport),
+            (Vec2::new(10.0, 10.0), Vec2::new(110.0, 110.0))
+        );
+        assert_eq!(
+            minimap_rect(MinimapCorner::TopRight, 0.2, 0.02, viewport),
+            (Vec2::new(890.0, 390.0), Vec2::new(990.0, 490.0))
+        );
+    }
+
+    #[test]
+    fn fits_world() {
+        let (min, max) = (Vec2::new(10.0, 10.0), Vec2::new(110.0, 110.0));
+        let world_min = Vec2::new(-1000.0, -500.0);
+        let world_max = Vec2::new(1000.0, 500.0);
+        let (scale, offset) = world_to_minimap(world_min, world_max, min, max);
+        let to_minimap = |world: Vec2| world * scale + offset;
+
+        // Wider than tall, so fits horizontally and is centered vertically.
+        assert!(to_minimap(world_min).abs_diff_eq(Vec2::new(10.0, 35.0), 0.001));
+        assert!(to_minimap(world_max).abs_diff_eq(Vec2::new(110.0, 85.0), 0.001));
+        assert!(to_minimap(Vec2::ZERO).abs_diff_eq(Vec2::new(60.0, 60.0), 0.001));
+    }
+
+    #[test]
+    fn view_matrix() {
+        let viewport = Vec2::new(800.0, 600.0);
+        let matrix = screen_view_matrix(viewport);
+        assert!(matrix
+            .transform_point2(Vec2::ZERO)
+            .abs_diff_eq(Vec2::splat(-1.0), 0.0001));
+        assert!(matrix
+            .transform_point2(viewport)
+            .abs_diff_eq(Vec2::ONE, 0.0001));
+        assert!(matrix
+            .transform_point2(viewport * 0.5)
+            .abs_diff_eq(Vec2::ZERO, 0.0001));
+    }
+}
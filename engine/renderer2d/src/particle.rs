@@ -4,7 +4,7 @@
 use crate::camera_2d::Camera2d;
 use crate::Renderer2d;
 use bytemuck::{Pod, Zeroable};
-use renderer::{Camera, Layer, LayerShader, PointDeque, Shader, Vertex};
+use renderer::{BlendMode, Camera, Layer, LayerShader, PointDeque, Shader, Vertex};
 
 /// A single particle (appended with `created: f32`). Requires calling the
 /// [`derive_vertex`][`renderer::derive_vertex`] macro.
@@ -29,6 +29,7 @@ struct ParticleVertex<T: Vertex + Copy + Pod + Zeroable + 'static> {
 
 /// Draws point [`Particle`]s.
 pub struct ParticleLayer<X: ParticleContext> {
+    blend_mode: BlendMode,
     buffer: PointDeque<ParticleVertex<X::Particle>>,
     /// The [`ParticleContext`] passed to [`new`][`Self::new`].
     pub context: X,
@@ -37,10 +38,17 @@ pub struct ParticleLayer<X: ParticleContext> {
 }
 
 impl<X: ParticleContext> ParticleLayer<X> {
-    /// Crates a new [`ParticleLayer`].
+    /// Crates a new [`ParticleLayer`], which uses [`BlendMode::AlphaBlend`].
     pub fn new(renderer: &Renderer2d, context: X) -> Self {
+        Self::with_blend_mode(renderer, context, BlendMode::default())
+    }
+
+    /// Creates a new [`ParticleLayer`] with a [`BlendMode`], such as [`BlendMode::Additive`] for
+    /// fire.
+    pub fn with_blend_mode(renderer: &Renderer2d, context: X, blend_mode: BlendMode) -> Self {
         let shader = context.create(renderer);
         Self {
+            blend_mode,
             buffer: PointDeque::new(renderer),
             context,
             shader,
@@ -48,6 +56,11 @@ impl<X: ParticleContext> ParticleLayer<X> {
         }
     }
 
+    /// Changes the [`BlendMode`] the particles are drawn with.
+    pub fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+
     /// Adds a particle. The particle will stay alive for its [`LIFESPAN`][`Particle::LIFESPAN`].
     pub fn add(&mut self, p: X::Particle) {
         self.buffer.push_back(ParticleVertex {
@@ -57,7 +70,7 @@ impl<X: ParticleContext> ParticleLayer<X> {
     }
 }
 
-impl<X: ParticleContext> Layer<Camera2d> for ParticleLayer<X> {
+impl<X: ParticleContext + 'static> Layer<Camera2d> for ParticleLayer<X> {
     fn pre_prepare(&mut self, r: &Renderer2d) {
         self.time = r.time;
 
@@ -68,18 +81,23 @@ impl<X: ParticleContext> Layer<Camera2d> for ParticleLayer<X> {
         }
     }
 
+    fn warmup(&mut self, renderer: &Renderer2d) {
+        self.shader.warmup(renderer);
+    }
+
     fn render(&mut self, renderer: &Renderer2d) {
         // Ensure ParticleVertex safely implements Pod.
         assert_safe::<X::Particle>();
 
-        if self.buffer.is_empty() {
+        // Particles still expire in pre_prepare while disabled.
+        if self.buffer.is_empty() || !renderer.is_layer_enabled::<Self>() {
             return;
         }
 
         if let Some(shader) = self.shader.bind(renderer) {
             renderer.camera.uniform_matrix(&shader);
             self.context.prepare(renderer, &shader);
-            self.buffer.bind(renderer).draw();
+            renderer.with_blend_mode(self.blend_mode, || self.buffer.bind(renderer).draw());
         }
     }
 }
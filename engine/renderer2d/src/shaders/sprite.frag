@@ -1,8 +1,9 @@
 precision mediump float;
 varying highp vec2 vUv;
-varying float vAlpha;
+varying vec4 vColor;
 uniform sampler2D uSampler;
+uniform float uAlpha;
 
 void main() {
-    gl_FragColor = texture2D(uSampler, vUv) * vAlpha;
+    gl_FragColor = texture2D(uSampler, vUv) * vColor * uAlpha;
 }
\ No newline at end of file
@@ -1,12 +1,15 @@
 attribute vec2 position;
 attribute vec2 uv;
-attribute float alpha;
+attribute vec4 color;
 uniform mat3 uView;
 varying vec2 vUv;
-varying float vAlpha;
+varying vec4 vColor;
 
 void main() {
     gl_Position = vec4(uView * vec3(position, 1.0), 1.0);
     vUv = uv;
-    vAlpha = alpha;
+    vColor = color;
+
+    // Premultiply alpha.
+    vColor.rgb *= color.a;
 }
\ No newline at end of file
@@ -1,7 +1,8 @@
 precision mediump float;
 varying vec2 vUv;
 uniform sampler2D uSampler;
+uniform float uAlpha;
 
 void main() {
-    gl_FragColor = texture2D(uSampler, vUv);
+    gl_FragColor = texture2D(uSampler, vUv) * uAlpha;
 }
\ No newline at end of file
@@ -1,8 +1,9 @@
 attribute vec2 position;
-uniform mat3 uModelView;
+attribute vec2 uv;
+uniform mat3 uView;
 varying vec2 vUv;
 
 void main() {
-    gl_Position = vec4(uModelView * vec3(position, 1.0), 1.0);
-    vUv = vec2(position.x + 0.5, 0.5 - position.y);
+    gl_Position = vec4(uView * vec3(position, 1.0), 1.0);
+    vUv = uv;
 }
\ No newline at end of file
@@ -3,25 +3,116 @@
 
 use crate::camera_2d::Camera2d;
 use crate::Renderer2d;
-use glam::{Mat3, Vec2};
+use glam::{const_vec2, Mat3, Vec2, Vec4};
 use renderer::{derive_vertex, Camera, Layer, MeshBuilder, Shader, Texture, TriangleBuffer};
 use sprite_sheet::UvSpriteSheet;
 
 derive_vertex!(
-    struct PosUvAlpha {
+    struct PosUvColor {
         pos: Vec2,
         uv: Vec2,
-        alpha: f32,
+        color: Vec4,
     }
 );
 
+/// An animation whose frames are laid out in a grid within a single sprite, left to right then top
+/// to bottom (see [`SpriteLayer::add_animated_sprite`]).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SpriteAnimation {
+    /// Name of the sprite containing all the frames.
+    pub sprite: &'static str,
+    /// Number of frames.
+    pub frames: usize,
+    /// How fast to play the animation, which loops.
+    pub frames_per_second: f32,
+    /// Number of frames per row. Frames are in a single horizontal strip if [`None`].
+    pub columns: Option<usize>,
+}
+
+impl SpriteAnimation {
+    /// Creates a [`SpriteAnimation`] of `frames` in a horizontal strip.
+    pub fn new(sprite: &'static str, frames: usize, frames_per_second: f32) -> Self {
+        Self {
+            sprite,
+            frames,
+            frames_per_second,
+            columns: None,
+        }
+    }
+
+    /// Lays the frames out in rows of `columns` instead of a single strip.
+    pub fn with_columns(mut self, columns: usize) -> Self {
+        self.columns = Some(columns);
+        self
+    }
+
+    /// Number of frames per row.
+    fn columns(&self) -> usize {
+        self.columns
+            .unwrap_or(self.frames)
+            .clamp(1, self.frames.max(1))
+    }
+
+    /// Number of rows of frames.
+    fn rows(&self) -> usize {
+        (self.frames.max(1) + self.columns() - 1) / self.columns()
+    }
+
+    /// Gets the frame to show at `time` seconds.
+    fn frame(&self, time: f32) -> usize {
+        let frame = (time * self.frames_per_second).floor();
+        // Handles negative time too.
+        (frame as i64).rem_euclid(self.frames.max(1) as i64) as usize
+    }
+
+    /// Gets the uvs of `frame` given the `uvs` of the whole sprite.
+    fn frame_uvs(&self, uvs: [Vec2; 4], frame: usize) -> [Vec2; 4] {
+        let (columns, rows) = (self.columns(), self.rows());
+        let (column, row) = (frame % columns, frame / columns);
+        // uvs are top left, top right, bottom left, bottom right.
+        let [top_left, top_right, bottom_left, _] = uvs;
+        let u = (top_right - top_left) / columns as f32;
+        let v = (bottom_left - top_left) / rows as f32;
+        let origin = top_left + u * column as f32 + v * row as f32;
+        [origin, origin + u, origin + v, origin + u + v]
+    }
+
+    /// Gets the aspect ratio of a single frame given the `aspect` of the whole sprite.
+    fn frame_aspect(&self, aspect: f32) -> f32 {
+        aspect * self.columns() as f32 / self.rows() as f32
+    }
+}
+
+/// Gets the offset, in units of the sprite's dimensions, that moves a normalized `anchor` (like
+/// uvs, with y down) to the origin of a sprite centered on it.
+fn anchor_offset(anchor: Vec2) -> Vec2 {
+    Vec2::new(0.5 - anchor.x, anchor.y - 0.5)
+}
+
+/// Gets the order to draw quads with depths `zs` back to front, or [`None`] if they already are.
+/// Quads with equal z keep their order.
+fn z_order(zs: &[f32]) -> Option<Vec<usize>> {
+    if zs.windows(2).all(|w| w[0] <= w[1]) {
+        return None;
+    }
+    let mut order: Vec<usize> = (0..zs.len()).collect();
+    order.sort_by(|&a, &b| zs[a].total_cmp(&zs[b]));
+    Some(order)
+}
+
 /// Draws sprites from a [`UvSpriteSheet`].
 pub struct SpriteLayer {
     atlas: Texture,
-    buffer: TriangleBuffer<PosUvAlpha>,
-    mesh: MeshBuilder<PosUvAlpha>,
+    buffer: TriangleBuffer<PosUvColor>,
+    mesh: MeshBuilder<PosUvColor>,
     shader: Shader,
     sheet: UvSpriteSheet,
+    /// Depth of sprites added from now on (see [`Self::set_z`]).
+    z: f32,
+    /// Depth of each quad in `mesh`.
+    zs: Vec<f32>,
+    /// Opacity of the whole layer (see [`SpriteLayer::set_alpha`]).
+    alpha: f32,
 }
 
 impl SpriteLayer {
@@ -39,9 +130,35 @@ impl SpriteLayer {
             mesh: MeshBuilder::new(),
             shader,
             sheet,
+            z: 0.0,
+            zs: Vec::new(),
+            alpha: 1.0,
         }
     }
 
+    /// Sets the opacity of everything in the layer, which multiplies the alpha of each sprite
+    /// (e.g. for fading it in and out). Defaults to `1.0`.
+    pub fn set_alpha(&mut self, alpha: f32) {
+        self.alpha = alpha;
+    }
+
+    /// Sets the depth of sprites added until the next call or render, after which it resets to 0.
+    /// Sprites with higher z are drawn on top, e.g. a boat over its shadow. Sprites with equal z
+    /// are drawn in the order they were added.
+    pub fn set_z(&mut self, z: f32) {
+        self.z = z;
+    }
+
+    /// Discards all sprites added since the last render.
+    fn clear(&mut self) {
+        self.mesh.clear();
+        self.zs.clear();
+        self.z = 0.0;
+    }
+
+    /// Default anchor of sprites, which rotate and scale around their center.
+    pub const CENTER: Vec2 = const_vec2!([0.5, 0.5]);
+
     /// Gets length of named animation in frames.
     ///
     /// # Panics
@@ -60,6 +177,52 @@ impl SpriteLayer {
         dimensions: Vec2,
         angle: f32,
         alpha: f32,
+    ) {
+        self.add_sprite_tinted(
+            sprite,
+            animation_frame,
+            center,
+            dimensions,
+            angle,
+            Vec4::new(1.0, 1.0, 1.0, alpha),
+        );
+    }
+
+    /// Like [`draw`][`Self::draw`] but multiplies the sprite's colors by `color` (e.g. a team
+    /// color or a red flash), whose alpha is used instead of `alpha`. White leaves it unchanged.
+    pub fn add_sprite_tinted(
+        &mut self,
+        sprite: &str,
+        animation_frame: Option<usize>,
+        center: Vec2,
+        dimensions: Vec2,
+        angle: f32,
+        color: Vec4,
+    ) {
+        self.add_sprite_anchored(
+            sprite,
+            animation_frame,
+            center,
+            dimensions,
+            angle,
+            color,
+            Self::CENTER,
+        );
+    }
+
+    /// Like [`add_sprite_tinted`][`Self::add_sprite_tinted`] but rotates and scales the sprite
+    /// around `anchor` instead of its center, which is drawn at `position`. `anchor` is normalized
+    /// like uvs, so `(0.5, 1.0)` is the middle of the bottom edge (e.g. the base of a turret).
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_sprite_anchored(
+        &mut self,
+        sprite: &str,
+        animation_frame: Option<usize>,
+        position: Vec2,
+        dimensions: Vec2,
+        angle: f32,
+        color: Vec4,
+        anchor: Vec2,
     ) {
         let sprite = if let Some(frame) = animation_frame {
             let animation = &self.sheet.animations.get(sprite).unwrap();
@@ -67,12 +230,56 @@ impl SpriteLayer {
         } else {
             &self.sheet.sprites.get(sprite).expect(sprite)
         };
+        let (uvs, aspect) = (sprite.uvs, sprite.aspect);
+        self.push_quad(uvs, aspect, position, dimensions, angle, color, anchor);
+    }
 
+    /// Draws the frame of a [`SpriteAnimation`] at `time` seconds (e.g. [`Renderer::time`][`renderer::Renderer::time`],
+    /// optionally offset so multiple instances aren't in sync). `angle` is in radians.
+    ///
+    /// # Panics
+    ///
+    /// If the animation's sprite doesn't exist.
+    pub fn add_animated_sprite(
+        &mut self,
+        animation: &SpriteAnimation,
+        time: f32,
+        center: Vec2,
+        dimensions: Vec2,
+        angle: f32,
+        alpha: f32,
+    ) {
+        let sprite = self
+            .sheet
+            .sprites
+            .get(animation.sprite)
+            .expect(animation.sprite);
+        let uvs = animation.frame_uvs(sprite.uvs, animation.frame(time));
+        let aspect = animation.frame_aspect(sprite.aspect);
+        let color = Vec4::new(1.0, 1.0, 1.0, alpha);
+        self.push_quad(uvs, aspect, center, dimensions, angle, color, Self::CENTER);
+    }
+
+    /// Draws a quad with `uvs`, maintaining `aspect` ratio, tinted by `color`, with `anchor` at
+    /// `position`.
+    #[allow(clippy::too_many_arguments)]
+    fn push_quad(
+        &mut self,
+        uvs: [Vec2; 4],
+        aspect: f32,
+        position: Vec2,
+        dimensions: Vec2,
+        angle: f32,
+        color: Vec4,
+        anchor: Vec2,
+    ) {
         let matrix = Mat3::from_scale_angle_translation(
-            Vec2::new(dimensions.x, dimensions.x * sprite.aspect),
+            Vec2::new(dimensions.x, dimensions.x * aspect),
             angle,
-            center,
+            position,
         );
+        let offset = anchor_offset(anchor);
+        self.zs.push(self.z);
 
         let positions = [
             Vec2::new(-0.5, 0.5),
@@ -83,11 +290,11 @@ impl SpriteLayer {
 
         self.mesh.vertices.extend(
             IntoIterator::into_iter(positions)
-                .zip(sprite.uvs.iter())
-                .map(|(pos, &uv)| PosUvAlpha {
-                    pos: matrix.transform_point2(pos),
+                .zip(uvs)
+                .map(|(pos, uv)| PosUvColor {
+                    pos: matrix.transform_point2(pos + offset),
                     uv,
-                    alpha,
+                    color,
                 }),
         );
     }
@@ -95,13 +302,29 @@ impl SpriteLayer {
 
 impl Layer<Camera2d> for SpriteLayer {
     fn render(&mut self, renderer: &Renderer2d) {
+        if !renderer.is_layer_enabled::<Self>() {
+            // Don't accumulate draws while disabled.
+            self.clear();
+            return;
+        }
+
         if self.mesh.is_empty() {
+            self.z = 0.0;
             return;
         }
 
+        if let Some(order) = z_order(&self.zs) {
+            let vertices = std::mem::take(&mut self.mesh.vertices);
+            self.mesh.vertices = order
+                .into_iter()
+                .flat_map(|i| vertices[i * 4..i * 4 + 4].iter().copied())
+                .collect();
+        }
+
         if let Some(shader) = self.shader.bind(renderer) {
             renderer.camera.uniform_matrix(&shader);
             shader.uniform_texture("uSampler", &self.atlas, 0);
+            shader.uniform1f("uAlpha", self.alpha);
 
             self.mesh.push_default_quads();
             self.buffer.buffer_mesh(renderer, &self.mesh);
@@ -110,6 +333,91 @@ impl Layer<Camera2d> for SpriteLayer {
         }
 
         // Always clear mesh even if shader wasn't bound.
-        self.mesh.clear();
+        self.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sprite::{anchor_offset, z_order, SpriteAnimation, SpriteLayer};
+    use glam::Vec2;
+
+    /// Sprite in the top left of the sheet.
+    fn uvs() -> [Vec2; 4] {
+        [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(0.8, 0.0),
+            Vec2::new(0.0, 0.2),
+            Vec2::new(0.8, 0.2),
+        ]
+    }
+
+    #[test]
+    fn animation_frame() {
+        let animation = SpriteAnimation::new("radar", 4, 10.0);
+        assert_eq!(animation.frame(0.0), 0);
+        assert_eq!(animation.frame(0.15), 1);
+        assert_eq!(animation.frame(0.39), 3);
+        // Loops.
+        assert_eq!(animation.frame(0.4), 0);
+        assert_eq!(animation.frame(1.25), 0);
+        assert_eq!(animation.frame(-0.05), 3);
+    }
+
+    #[test]
+    fn horizontal_strip() {
+        let animation = SpriteAnimation::new("radar", 4, 10.0);
+        assert_eq!(
+            animation.frame_uvs(uvs(), 1),
+            [
+                Vec2::new(0.2, 0.0),
+                Vec2::new(0.4, 0.0),
+                Vec2::new(0.2, 0.2),
+                Vec2::new(0.4, 0.2),
+            ]
+        );
+        // A quarter as wide.
+        assert_eq!(animation.frame_aspect(0.25), 1.0);
+    }
+
+    #[test]
+    fn grid() {
+        let animation = SpriteAnimation::new("propeller", 4, 10.0).with_columns(2);
+        let [top_left, _, _, bottom_right] = animation.frame_uvs(uvs(), 3);
+        assert!(
+            top_left.distance(Vec2::new(0.4, 0.1)) < 0.0001,
+            "{}",
+            top_left
+        );
+        assert!(
+            bottom_right.distance(Vec2::new(0.8, 0.2)) < 0.0001,
+            "{}",
+            bottom_right
+        );
+        // Half as wide and half as tall.
+        assert_eq!(animation.frame_aspect(0.25), 0.25);
+    }
+
+    #[test]
+    fn anchor() {
+        assert_eq!(anchor_offset(SpriteLayer::CENTER), Vec2::ZERO);
+        // Top left corner of the quad moves to the origin.
+        assert_eq!(Vec2::new(-0.5, 0.5) + anchor_offset(Vec2::ZERO), Vec2::ZERO);
+        // Middle of the bottom edge.
+        assert_eq!(
+            Vec2::new(0.0, -0.5) + anchor_offset(Vec2::new(0.5, 1.0)),
+            Vec2::ZERO
+        );
+    }
+
+    #[test]
+    fn z() {
+        assert_eq!(z_order(&[]), None);
+        assert_eq!(z_order(&[0.0, 0.0, 1.0]), None);
+        // Stable.
+        assert_eq!(
+            z_order(&[1.0, 0.0, 1.0, -1.0, 0.0]),
+            Some(vec![3, 1, 4, 0, 2])
+        );
     }
 }
@@ -3,16 +3,28 @@
 
 use crate::camera_2d::Camera2d;
 use crate::Renderer2d;
-use glam::{vec2, Mat3, Vec2, Vec4};
-use renderer::{Layer, Shader, Texture, TriangleBuffer};
+use glam::{const_uvec2, uvec2, vec2, UVec2, Vec2, Vec4};
+use js_hooks::console_warn;
+use renderer::{
+    derive_vertex, text_dimensions, text_width, Camera, Layer, MeshBuilder, Shader, TextOutline,
+    Texture, TextureFormat, TriangleBuffer, TEXT_HEIGHT,
+};
 use std::collections::HashMap;
 use std::hash::BuildHasher;
 
+derive_vertex!(
+    struct PosUv {
+        pos: Vec2,
+        uv: Vec2,
+    }
+);
+
 #[derive(Default)]
-struct Buffers {
+struct Entry {
     counter: u8,
     draws: Vec<Draw>,
-    texture: Option<Texture>,
+    /// Where the text is in the atlas, once written.
+    region: Option<Region>,
 }
 
 struct Draw {
@@ -20,16 +32,99 @@ struct Draw {
     scale: f32,
 }
 
-/// Draws single lines of text.
+/// Part of the atlas, in pixels.
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Region {
+    offset: UVec2,
+    dimensions: UVec2,
+}
+
+/// Allocates [`Region`]s of the atlas in rows (shelves), which suits text since most of it is the
+/// same height.
+#[derive(Debug)]
+struct ShelfPacker {
+    dimensions: UVec2,
+    shelves: Vec<Shelf>,
+}
+
+#[derive(Debug)]
+struct Shelf {
+    y: u32,
+    height: u32,
+    /// Width used so far.
+    width: u32,
+}
+
+impl ShelfPacker {
+    fn new(dimensions: UVec2) -> Self {
+        Self {
+            dimensions,
+            shelves: Vec::new(),
+        }
+    }
+
+    /// Allocates a [`Region`] of `dimensions`, or returns [`None`] if there isn't room.
+    fn allocate(&mut self, dimensions: UVec2) -> Option<Region> {
+        let offset = if let Some(shelf) = self.shelves.iter_mut().find(|shelf| {
+            shelf.height >= dimensions.y && shelf.width + dimensions.x <= self.dimensions.x
+        }) {
+            let offset = uvec2(shelf.width, shelf.y);
+            shelf.width += dimensions.x;
+            offset
+        } else {
+            let y = self
+                .shelves
+                .last()
+                .map_or(0, |shelf| shelf.y + shelf.height);
+            if dimensions.x > self.dimensions.x || y + dimensions.y > self.dimensions.y {
+                return None;
+            }
+            self.shelves.push(Shelf {
+                y,
+                height: dimensions.y,
+                width: dimensions.x,
+            });
+            uvec2(0, y)
+        };
+        Some(Region { offset, dimensions })
+    }
+
+    /// Frees all [`Region`]s.
+    fn clear(&mut self) {
+        self.shelves.clear();
+    }
+}
+
+/// Dimensions of the texture all text is drawn to.
+const ATLAS_DIMENSIONS: UVec2 = const_uvec2!([2048, 1024]);
+
+/// Text textures are this many pixels tall per unit of `scale` (see [`TextLayer::draw`]).
+const PIXELS_PER_SCALE: f32 = 32.0;
+/// Texture padding, in pixels, on either side of the text (see [`Texture::from_text`]).
+const PADDING: f32 = 2.0;
+/// Distance between lines of [`TextLayer::draw_text_wrapped`], relative to `scale`. Lines are as tall
+/// as their textures (excluding any outline, which may overlap).
+const LINE_HEIGHT: f32 = TEXT_HEIGHT as f32 / PIXELS_PER_SCALE;
+
+/// Draws single lines of text, which can be wrapped (see [`TextLayer::draw_text_wrapped`]).
 pub struct TextLayer {
-    /// Too expensive to create text textures every frame, so cache them.
+    /// Too expensive to draw text to the atlas every frame, so cache where it is.
     /// Index on text and color to allow CanvasRenderingContext to apply correct coloring to emojis.
     /// Uses 8 bit rbga color (compatible with JS).
     /// TODO could use additive blend mode to prevent unstable ordering if it matters.
-    buffers: HashMap<(String, [u8; 4]), Buffers>,
-    /// Same for all text.
-    geometry: TriangleBuffer<Vec2>,
+    entries: HashMap<(String, [u8; 4], Option<TextOutline>), Entry>,
+    /// All text is drawn to this, so it can be rendered in a single draw call.
+    atlas: Texture,
+    packer: ShelfPacker,
+    /// Whether part of the atlas is taken by text that was removed from `entries`.
+    atlas_wasted: bool,
+    buffer: TriangleBuffer<PosUv>,
+    mesh: MeshBuilder<PosUv>,
+    /// Applies to subsequent draws (see [`TextLayer::set_outline`]).
+    outline: Option<TextOutline>,
     shader: Shader,
+    /// Opacity of the whole layer (see [`TextLayer::set_alpha`]).
+    alpha: f32,
 }
 
 impl TextLayer {
@@ -40,25 +135,35 @@ impl TextLayer {
             include_str!("shaders/text.frag"),
         );
 
-        let mut text_geometry = TriangleBuffer::new(renderer);
-        text_geometry.buffer(
-            renderer,
-            &[
-                vec2(-0.5, 0.5),
-                vec2(0.5, 0.5),
-                vec2(-0.5, -0.5),
-                vec2(0.5, -0.5),
-            ],
-            &[2, 0, 1, 2, 1, 3],
-        );
+        let mut atlas = Texture::new_empty(renderer, TextureFormat::Rgba, true);
+        atlas.realloc_with_opt_bytes(renderer, ATLAS_DIMENSIONS, None);
 
         Self {
-            buffers: HashMap::new(),
-            geometry: text_geometry,
+            entries: HashMap::new(),
+            atlas,
+            packer: ShelfPacker::new(ATLAS_DIMENSIONS),
+            atlas_wasted: false,
+            buffer: TriangleBuffer::new(renderer),
+            mesh: MeshBuilder::new(),
+            outline: None,
             shader,
+            alpha: 1.0,
         }
     }
 
+    /// Sets the opacity of everything in the layer, which multiplies the alpha of each text's
+    /// color (e.g. for fading it in and out). Defaults to `1.0`.
+    pub fn set_alpha(&mut self, alpha: f32) {
+        self.alpha = alpha;
+    }
+
+    /// Outlines text drawn after this call with `color`, `width` pixels (of which there are 32 per
+    /// unit of `scale`) wide, for contrast against busy backgrounds (e.g. names over water). A
+    /// `width` of 0 disables outlines, at no cost.
+    pub fn set_outline(&mut self, color: Vec4, width: u32) {
+        self.outline = outline(color, width);
+    }
+
     /// Draws `text` centered at `center` with a `scale` and a `color`. TODO `scale`'s units need
     /// to be more precisely defined.
     pub fn draw(&mut self, text: &str, center: Vec2, scale: f32, color: Vec4) {
@@ -72,39 +177,193 @@ impl TextLayer {
             return;
         }
 
-        // Compensate for resizing text texture to 36 pixels to fit "😊".
+        // Compensate for resizing text texture to 36 pixels to fit "😊", and for the outline.
         // TODO find better solution.
-        let scale = scale * (36.0 / 32.0);
+        let outline = self.outline;
+        let scale = scale * (texture_height(outline) / PIXELS_PER_SCALE);
 
         // Save String allocation most of the time.
         // Can't use .from_key because can't implement the [`std::borrow::Borrow`] trait.
-        let hash = self.buffers.hasher().hash_one((text, color));
+        let hash = self.entries.hasher().hash_one((text, color, outline));
         let (_, entry) = self
-            .buffers
+            .entries
             .raw_entry_mut()
             .from_hash(hash, |existing| {
-                existing.0.as_str() == text && existing.1 == color
+                existing.0.as_str() == text && existing.1 == color && existing.2 == outline
             })
-            .or_insert_with(|| ((text.to_owned(), color), Default::default()));
+            .or_insert_with(|| ((text.to_owned(), color, outline), Default::default()));
 
         entry.draws.push(Draw { center, scale });
     }
+
+    /// Draws `text` like [`draw`][`Self::draw`], wrapping it onto multiple lines no wider than
+    /// `max_width` (in the same units as `center`). Breaks lines on whitespace, explicit newlines,
+    /// and within words that don't fit on a line of their own. The first line is centered at `center`
+    /// and the rest stack downward. Returns the total height of the lines.
+    pub fn draw_text_wrapped(
+        &mut self,
+        text: &str,
+        center: Vec2,
+        scale: f32,
+        color: Vec4,
+        max_width: f32,
+    ) -> f32 {
+        let max_pixels = max_width / scale * PIXELS_PER_SCALE;
+        let outline_pixels = self.outline.map_or(0.0, |o| o.width as f32 * 2.0);
+        let lines = wrap_text(text, max_pixels, |line| {
+            text_width(line) + PADDING + outline_pixels
+        });
+        let line_height = scale * LINE_HEIGHT;
+        for (i, line) in lines.iter().enumerate() {
+            self.draw(
+                line,
+                center - vec2(0.0, i as f32 * line_height),
+                scale,
+                color,
+            );
+        }
+        lines.len() as f32 * line_height
+    }
+}
+
+/// Gets the [`TextOutline`] for [`TextLayer::set_outline`], if it would be visible.
+fn outline(color: Vec4, width: u32) -> Option<TextOutline> {
+    let color = color.to_array().map(|c| (c * 255.0) as u8);
+    (width > 0 && color[3] > 0).then_some(TextOutline { color, width })
+}
+
+/// Height of a text texture in pixels, including any `outline` on both sides.
+fn texture_height(outline: Option<TextOutline>) -> f32 {
+    (TEXT_HEIGHT + outline.map_or(0, |o| o.width * 2)) as f32
+}
+
+/// Splits `text` into lines no wider than `max_width`, according to `width`. See
+/// [`TextLayer::draw_text_wrapped`].
+fn wrap_text(text: &str, max_width: f32, width: impl Fn(&str) -> f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            if !line.is_empty() {
+                let candidate = format!("{} {}", line, word);
+                if width(&candidate) <= max_width {
+                    line = candidate;
+                    continue;
+                }
+                lines.push(std::mem::take(&mut line));
+            }
+
+            if width(word) <= max_width {
+                line.push_str(word);
+            } else {
+                // Hard-break words that are too long, keeping at least one char per line.
+                for c in word.chars() {
+                    line.push(c);
+                    if width(&line) > max_width && line.chars().nth(1).is_some() {
+                        line.pop();
+                        lines.push(std::mem::replace(&mut line, String::from(c)));
+                    }
+                }
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Shortens `text`, ending it with an ellipsis, so it is no wider than `max_width` according to
+/// `width`. Keeps at least the ellipsis.
+fn truncate_text(text: &str, max_width: f32, width: impl Fn(&str) -> f32) -> String {
+    if width(text) <= max_width {
+        return text.to_owned();
+    }
+
+    // Binary search for how many chars to keep, since measuring text is slow.
+    let ends: Vec<usize> = text
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(text.len()))
+        .collect();
+    let truncated = |chars: usize| format!("{}…", &text[..ends[chars]]);
+    let (mut fits, mut too_wide) = (0, ends.len() - 1);
+    while too_wide - fits > 1 {
+        let mid = (fits + too_wide) / 2;
+        if width(&truncated(mid)) <= max_width {
+            fits = mid;
+        } else {
+            too_wide = mid;
+        }
+    }
+    truncated(fits)
+}
+
+impl TextLayer {
+    /// Writes text that isn't in the atlas yet. Returns false if some didn't fit.
+    fn write_entries(&mut self, renderer: &Renderer2d) -> bool {
+        let mut fit = true;
+        for ((text, color, outline), entry) in &mut self.entries {
+            if entry.region.is_some() {
+                continue;
+            }
+            let mut dimensions = text_dimensions(text, *outline);
+            let truncated;
+            let text = if dimensions.x > ATLAS_DIMENSIONS.x {
+                // Would never fit, so draw as much as possible.
+                console_warn!("truncating text wider than atlas: {}", text);
+                truncated = truncate_text(text, ATLAS_DIMENSIONS.x as f32, |text| {
+                    text_dimensions(text, *outline).x as f32
+                });
+                dimensions = text_dimensions(&truncated, *outline);
+                &truncated
+            } else {
+                text
+            };
+            if let Some(region) = self.packer.allocate(dimensions) {
+                self.atlas
+                    .write_text(renderer, text, *color, *outline, region.offset);
+                entry.region = Some(region);
+            } else if dimensions.cmple(ATLAS_DIMENSIONS).all() {
+                fit = false;
+            }
+        }
+        fit
+    }
+}
+
+/// Gets the vertices of a quad, in the order of [`MeshBuilder::push_default_quads`], that draws
+/// the `region` of the atlas centered at `center` and `scale` tall.
+fn text_quad(region: Region, center: Vec2, scale: f32) -> [PosUv; 4] {
+    let dimensions = region.dimensions.as_vec2();
+    let half = vec2(scale * dimensions.x / dimensions.y, scale) * 0.5;
+
+    // Inset by half a pixel so linear filtering doesn't sample neighboring text.
+    let atlas = ATLAS_DIMENSIONS.as_vec2();
+    let top_left = (region.offset.as_vec2() + 0.5) / atlas;
+    let bottom_right = (region.offset.as_vec2() + dimensions - 0.5) / atlas;
+
+    [
+        (vec2(-half.x, half.y), top_left),
+        (half, vec2(bottom_right.x, top_left.y)),
+        (-half, vec2(top_left.x, bottom_right.y)),
+        (vec2(half.x, -half.y), bottom_right),
+    ]
+    .map(|(offset, uv)| PosUv {
+        pos: center + offset,
+        uv,
+    })
 }
 
 impl Layer<Camera2d> for TextLayer {
     fn pre_render(&mut self, renderer: &Renderer2d) {
-        self.buffers.retain(|id, entry| {
-            entry.texture.get_or_insert_with(|| {
-                // Generate textures here to avoid pipeline stall if done during rendering.
-                Texture::from_text(renderer, &id.0, id.1)
-            });
-
-            // Remove textures that haven't been used in 255 (u8::MAX) frames.
+        let atlas_wasted = &mut self.atlas_wasted;
+        self.entries.retain(|_, entry| {
+            // Remove text that hasn't been used in 255 (u8::MAX) frames.
             if entry.draws.is_empty() {
                 if let Some(next) = entry.counter.checked_add(1) {
                     entry.counter = next;
                     true // Keep alive (was used recently).
                 } else {
+                    *atlas_wasted |= entry.region.is_some();
                     false // Destroy (wasn't used in a few seconds).
                 }
             } else {
@@ -112,39 +371,190 @@ impl Layer<Camera2d> for TextLayer {
                 true // Keep alive (was used this frame).
             }
         });
+
+        // Write text here to avoid pipeline stall if done during rendering.
+        if !self.write_entries(renderer) {
+            let unused = self
+                .entries
+                .values()
+                .any(|entry| entry.region.is_some() && entry.draws.is_empty());
+            if self.atlas_wasted || unused {
+                // Start over with only the text used this frame.
+                self.entries.retain(|_, entry| !entry.draws.is_empty());
+                for entry in self.entries.values_mut() {
+                    entry.region = None;
+                }
+                self.packer.clear();
+                self.atlas_wasted = false;
+                // Text that still doesn't fit isn't drawn.
+                self.write_entries(renderer);
+            }
+        }
     }
 
     fn render(&mut self, renderer: &Renderer2d) {
         // Haven't rendered text in a while.
-        if self.buffers.is_empty() {
+        if self.entries.is_empty() {
+            return;
+        }
+
+        if !renderer.is_layer_enabled::<Self>() {
+            // Don't accumulate draws while disabled.
+            for entry in self.entries.values_mut() {
+                entry.draws.clear();
+            }
+            return;
+        }
+
+        // Batch all text into a single draw call.
+        for entry in self.entries.values_mut() {
+            let region = if let Some(region) = entry.region {
+                region
+            } else {
+                entry.draws.clear();
+                continue;
+            };
+            for Draw { center, scale } in entry.draws.drain(..) {
+                self.mesh.vertices.extend(text_quad(region, center, scale));
+            }
+        }
+
+        if self.mesh.is_empty() {
             return;
         }
 
         if let Some(shader) = self.shader.bind(renderer) {
-            let binding = self.geometry.bind(renderer);
+            renderer.camera.uniform_matrix(&shader);
+            shader.uniform_texture("uSampler", &self.atlas, 0);
+            shader.uniform1f("uAlpha", self.alpha);
 
-            for buffers in self.buffers.values_mut() {
-                if buffers.draws.is_empty() {
-                    continue; // Nothing to draw.
-                }
+            self.mesh.push_default_quads();
+            self.buffer.buffer_mesh(renderer, &self.mesh);
+            self.buffer.bind(renderer).draw();
+        }
 
-                // Shouldn't panic because texture was initialized in pre_render.
-                let texture = buffers.texture.as_mut().unwrap();
-                let texture_aspect = texture.aspect();
-                shader.uniform_texture("uSampler", texture, 0);
-
-                // TODO could draw multiple in a single draw call.
-                for Draw { center, scale } in buffers.draws.drain(..) {
-                    let model = Mat3::from_scale_angle_translation(
-                        vec2(scale * texture_aspect, scale),
-                        0.0,
-                        center,
-                    );
-                    // Only drawing 1 at a time so we can premultiply the model and view matrix.
-                    shader.uniform_matrix3f("uModelView", &(renderer.camera.view_matrix * model));
-                    binding.draw();
-                }
-            }
+        // Always clear mesh even if shader wasn't bound.
+        self.mesh.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::text::{
+        outline, text_quad, texture_height, truncate_text, wrap_text, PosUv, Region, ShelfPacker,
+        ATLAS_DIMENSIONS,
+    };
+    use glam::{uvec2, vec2, Vec2, Vec4};
+    use renderer::MeshBuilder;
+
+    /// Each char is 1 wide.
+    fn wrap(text: &str, max_width: f32) -> Vec<String> {
+        wrap_text(text, max_width, |line| line.chars().count() as f32)
+    }
+
+    #[test]
+    fn outlines() {
+        let black = Vec4::new(0.0, 0.0, 0.0, 1.0);
+        assert_eq!(outline(black, 0), None);
+        assert_eq!(outline(Vec4::ZERO, 3), None);
+        let o = outline(black, 3).unwrap();
+        assert_eq!(o.color, [0, 0, 0, 255]);
+
+        // Texture grows on both sides.
+        assert_eq!(texture_height(None), 36.0);
+        assert_eq!(texture_height(Some(o)), 42.0);
+    }
+
+    #[test]
+    fn wrap_on_whitespace() {
+        assert_eq!(wrap("", 10.0), [""]);
+        assert_eq!(wrap("fits on one", 11.0), ["fits on one"]);
+        assert_eq!(
+            wrap("the quick brown fox jumps", 10.0),
+            ["the quick", "brown fox", "jumps"]
+        );
+        // Collapses whitespace.
+        assert_eq!(wrap("  spaced   out  ", 20.0), ["spaced out"]);
+    }
+
+    #[test]
+    fn wrap_long_words() {
+        assert_eq!(wrap("abcdefghij", 4.0), ["abcd", "efgh", "ij"]);
+        assert_eq!(wrap("hi abcdefg yo", 4.0), ["hi", "abcd", "efg", "yo"]);
+        // Always makes progress, even if nothing fits.
+        assert_eq!(wrap("大π", 0.5), ["大", "π"]);
+    }
+
+    #[test]
+    fn wrap_newlines() {
+        assert_eq!(wrap("one\ntwo three", 20.0), ["one", "two three"]);
+        assert_eq!(wrap("a\n\nb", 20.0), ["a", "", "b"]);
+    }
+
+    #[test]
+    fn truncate_wide_text() {
+        let width = |text: &str| text.chars().count() as f32;
+        assert_eq!(truncate_text("short", 10.0, width), "short");
+        assert_eq!(truncate_text("exactly10!", 10.0, width), "exactly10!");
+        assert_eq!(truncate_text("much too long", 10.0, width), "much too …");
+        assert_eq!(truncate_text("大大大大", 3.0, width), "大大…");
+        // Always keeps the ellipsis.
+        assert_eq!(truncate_text("abc", 0.0, width), "…");
+    }
+
+    #[test]
+    fn shelf_packing() {
+        let mut packer = ShelfPacker::new(uvec2(100, 50));
+        let region = |x, y, w, h| {
+            Some(Region {
+                offset: uvec2(x, y),
+                dimensions: uvec2(w, h),
+            })
+        };
+
+        assert_eq!(packer.allocate(uvec2(60, 20)), region(0, 0, 60, 20));
+        assert_eq!(packer.allocate(uvec2(40, 20)), region(60, 0, 40, 20));
+        // Next shelf.
+        assert_eq!(packer.allocate(uvec2(50, 20)), region(0, 20, 50, 20));
+        // Shorter text fits in a taller shelf.
+        assert_eq!(packer.allocate(uvec2(50, 10)), region(50, 20, 50, 10));
+        // Out of room.
+        assert_eq!(packer.allocate(uvec2(10, 20)), None);
+        assert_eq!(packer.allocate(uvec2(101, 1)), None);
+        assert_eq!(packer.allocate(uvec2(10, 10)), region(0, 40, 10, 10));
+
+        packer.clear();
+        assert_eq!(packer.allocate(uvec2(10, 20)), region(0, 0, 10, 20));
+    }
+
+    #[test]
+    fn batched_quads() {
+        let region = Region {
+            offset: uvec2(0, 0),
+            dimensions: uvec2(72, 36),
+        };
+        let [top_left, top_right, bottom_left, bottom_right] =
+            text_quad(region, vec2(10.0, 5.0), 2.0);
+        // Twice as wide as tall.
+        assert_eq!(top_left.pos, vec2(8.0, 6.0));
+        assert_eq!(bottom_right.pos, vec2(12.0, 4.0));
+        assert_eq!(top_right.pos, vec2(12.0, 6.0));
+        assert_eq!(bottom_left.pos, vec2(8.0, 4.0));
+        // Inset by half a pixel.
+        assert_eq!(top_left.uv, Vec2::splat(0.5) / ATLAS_DIMENSIONS.as_vec2());
+        assert_eq!(
+            bottom_right.uv,
+            vec2(71.5, 35.5) / ATLAS_DIMENSIONS.as_vec2()
+        );
+
+        // Many draws of different text share a single mesh, and so a single draw call.
+        let mut mesh = MeshBuilder::<PosUv>::new();
+        for i in 0..50 {
+            mesh.vertices
+                .extend(text_quad(region, vec2(i as f32, 0.0), 1.0));
         }
+        mesh.push_default_quads();
+        assert_eq!(mesh.vertices.len(), 200);
+        assert_eq!(mesh.indices.len(), 300);
     }
 }
@@ -0,0 +1,227 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::camera_2d::Camera2d;
+use crate::graphic::PosColor;
+use crate::Renderer2d;
+use glam::{Vec2, Vec4};
+use renderer::{Camera, Index, Layer, MeshBuilder, Shader, TriangleBuffer};
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+/// Draws trails (e.g. of projectiles or damaged ships) through the recent positions of each entity,
+/// identified by a `K`. Each trail is thickest and most opaque at its newest point (the head),
+/// tapering and fading to nothing at [`max_age`][`Self::max_age`].
+pub struct TrailLayer<K, I: Index = u16> {
+    shader: Shader,
+    mesh: MeshBuilder<PosColor, I>,
+    buffer: TriangleBuffer<PosColor, I>,
+    /// Seconds after which points are dropped. Defaults to `1.0`.
+    pub max_age: f32,
+    trails: HashMap<K, Trail>,
+    /// Copied from the [`Renderer2d`] in pre_prepare.
+    time: f32,
+}
+
+struct Trail {
+    /// Positions and times, oldest first.
+    points: VecDeque<(Vec2, f32)>,
+    /// Width of the head.
+    width: f32,
+    /// Color of the head.
+    color: Vec4,
+}
+
+impl Trail {
+    /// Drops points that are at least `max_age` old at `time`.
+    fn expire(&mut self, time: f32, max_age: f32) {
+        while let Some(&(_, t)) = self.points.front() {
+            if time - t < max_age {
+                break;
+            }
+            self.points.pop_front();
+        }
+    }
+
+    /// Adds a triangle strip from the oldest point to the newest to `mesh`.
+    fn build<I: Index>(&self, mesh: &mut MeshBuilder<PosColor, I>, time: f32, max_age: f32) {
+        let n = self.points.len();
+        if n < 2 {
+            return;
+        }
+
+        // Calculate index before extending vertices.
+        let index = mesh.vertices.len();
+        for i in 0..n {
+            let (pos, t) = self.points[i];
+
+            // Perpendicular to the average direction of the segments on either side.
+            let prev = self.points[i.saturating_sub(1)].0;
+            let next = self.points[(i + 1).min(n - 1)].0;
+            let normal = (next - prev).normalize_or_zero().perp();
+
+            let remaining = (1.0 - (time - t) / max_age).clamp(0.0, 1.0);
+            let offset = normal * (self.width * 0.5 * remaining);
+            let color = self.color * Vec4::new(1.0, 1.0, 1.0, remaining);
+            mesh.vertices.extend([
+                PosColor {
+                    pos: pos + offset,
+                    color,
+                },
+                PosColor {
+                    pos: pos - offset,
+                    color,
+                },
+            ]);
+        }
+
+        for i in 0..n - 1 {
+            let a = index + i * 2;
+            mesh.push_quad([
+                I::from_usize(a),
+                I::from_usize(a + 2),
+                I::from_usize(a + 1),
+                I::from_usize(a + 3),
+            ]);
+        }
+    }
+}
+
+impl<K: Hash + Eq, I: Index> TrailLayer<K, I> {
+    /// Creates a new [`TrailLayer`].
+    pub fn new(renderer: &Renderer2d) -> Self {
+        // Same as GraphicLayer, but retains points between frames.
+        let shader = renderer.create_shader(
+            include_str!("shaders/graphic.vert"),
+            include_str!("shaders/graphic.frag"),
+        );
+
+        Self {
+            shader,
+            mesh: MeshBuilder::new(),
+            buffer: TriangleBuffer::new(renderer),
+            max_age: 1.0,
+            trails: HashMap::new(),
+            time: 0.0,
+        }
+    }
+
+    /// Adds a point at `position` and `time` (in the same seconds as
+    /// [`Renderer::time`][`renderer::Renderer::time`]) to the trail of `key`, which is `width` wide
+    /// and `color` at its head. Points older than the newest one in the trail are ignored.
+    pub fn add_point(&mut self, key: K, position: Vec2, time: f32, width: f32, color: Vec4) {
+        let trail = self.trails.entry(key).or_insert_with(|| Trail {
+            points: VecDeque::new(),
+            width,
+            color,
+        });
+        if trail.points.back().map_or(false, |&(_, t)| time < t) {
+            return;
+        }
+        trail.points.push_back((position, time));
+        trail.width = width;
+        trail.color = color;
+    }
+
+    /// Removes the trail of `key` immediately, instead of letting it fade.
+    pub fn remove(&mut self, key: &K) {
+        self.trails.remove(key);
+    }
+
+    /// Removes all trails, e.g. when the view changes to a different place.
+    pub fn clear(&mut self) {
+        self.trails.clear();
+    }
+}
+
+impl<K: Hash + Eq + 'static, I: Index> Layer<Camera2d> for TrailLayer<K, I> {
+    fn pre_prepare(&mut self, renderer: &Renderer2d) {
+        self.time = renderer.time;
+
+        let (time, max_age) = (self.time, self.max_age);
+        self.trails.retain(|_, trail| {
+            trail.expire(time, max_age);
+            !trail.points.is_empty()
+        });
+    }
+
+    fn render(&mut self, renderer: &Renderer2d) {
+        if !renderer.is_layer_enabled::<Self>() {
+            return;
+        }
+
+        for trail in self.trails.values() {
+            trail.build(&mut self.mesh, self.time, self.max_age);
+        }
+
+        if self.mesh.is_empty() {
+            return;
+        }
+
+        if let Some(shader) = self.shader.bind(renderer) {
+            renderer.camera.uniform_matrix(&shader);
+            shader.uniform1f("uAlpha", 1.0);
+
+            self.buffer.buffer_mesh(renderer, &self.mesh);
+            self.buffer.bind(renderer).draw();
+        }
+
+        // Always clear mesh even if shader wasn't bound.
+        self.mesh.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graphic::PosColor;
+    use crate::trail::Trail;
+    use glam::{Vec2, Vec4};
+    use renderer::MeshBuilder;
+
+    fn trail() -> Trail {
+        Trail {
+            points: (0..5)
+                .map(|i| (Vec2::new(i as f32, 0.0), i as f32 * 0.25))
+                .collect(),
+            width: 2.0,
+            color: Vec4::ONE,
+        }
+    }
+
+    #[test]
+    fn expire() {
+        let mut trail = trail();
+        trail.expire(1.0, 1.0);
+        assert_eq!(trail.points.len(), 4);
+        trail.expire(1.9, 1.0);
+        assert_eq!(trail.points.len(), 1);
+        trail.expire(2.0, 1.0);
+        assert!(trail.points.is_empty());
+    }
+
+    #[test]
+    fn taper_and_fade() {
+        let mut mesh = MeshBuilder::<PosColor, u16>::new();
+        let trail = trail();
+        trail.build(&mut mesh, 1.0, 1.0);
+        assert_eq!(mesh.vertices.len(), 10);
+
+        let width = |i: usize| {
+            mesh.vertices[i * 2]
+                .pos
+                .distance(mesh.vertices[i * 2 + 1].pos)
+        };
+        let alpha = |i: usize| mesh.vertices[i * 2].color.w;
+
+        // Oldest point is at the max age.
+        assert_eq!(width(0), 0.0);
+        assert_eq!(alpha(0), 0.0);
+        for i in 1..5 {
+            assert!(width(i) > width(i - 1));
+            assert!(alpha(i) > alpha(i - 1));
+        }
+        // Newest point is brand new.
+        assert!((width(4) - 2.0).abs() < 0.0001);
+        assert_eq!(alpha(4), 1.0);
+    }
+}
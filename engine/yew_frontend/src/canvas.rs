@@ -58,26 +58,14 @@ impl Component for Canvas {
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
-        let w = window();
-
-        let device_pixel_ratio = w.device_pixel_ratio();
-        let window_width = dimension(
-            w.inner_width(),
-            device_pixel_ratio,
-            ctx.props().resolution_divisor,
-        );
-        let window_height = dimension(
-            w.inner_height(),
-            device_pixel_ratio,
-            ctx.props().resolution_divisor,
-        );
+        let (window_width, window_height) = resolution(ctx.props().resolution_divisor);
 
         html! {
             <canvas
                 id="canvas"
                 style="position: absolute; width: 100%; height: 100%; z-index: -1000;"
-                width={window_width}
-                height={window_height}
+                width={window_width.to_string()}
+                height={window_height.to_string()}
                 onmouseenter={ctx.props().mouse_callback.clone()}
                 onmousemove={ctx.props().mouse_callback.clone()}
                 onmousedown={ctx.props().mouse_callback.clone()}
@@ -86,6 +74,7 @@ impl Component for Canvas {
                 ontouchstart={ctx.props().touch_callback.clone()}
                 ontouchmove={ctx.props().touch_callback.clone()}
                 ontouchend={ctx.props().touch_callback.clone()}
+                ontouchcancel={ctx.props().touch_callback.clone()}
                 onwheel={ctx.props().wheel_callback.clone()}
                 onblur={ctx.props().focus_callback.clone()}
                 onfocus={ctx.props().focus_callback.clone()}
@@ -95,12 +84,43 @@ impl Component for Canvas {
     }
 }
 
-fn dimension(
-    resolution: Result<JsValue, JsValue>,
+/// Gets the width and height of the drawing buffer, in real pixels, at the current window size.
+pub fn resolution(resolution_divisor: NonZeroU8) -> (u32, u32) {
+    let w = window();
+    let device_pixel_ratio = w.device_pixel_ratio();
+    let scale = |css_pixels: Result<JsValue, JsValue>| {
+        dimension(
+            css_pixels.unwrap().as_f64().unwrap(),
+            device_pixel_ratio,
+            resolution_divisor,
+        )
+    };
+    (scale(w.inner_width()), scale(w.inner_height()))
+}
+
+/// Gets the size of the drawing buffer, in real pixels, along a window dimension of `css_pixels`.
+///
+/// Changing `resolution_divisor` only changes the drawing buffer (the canvas element is resized in
+/// place, without being recreated), whereas the element itself always fills the window.
+pub(crate) fn dimension(
+    css_pixels: f64,
     device_pixel_ratio: f64,
     resolution_divisor: NonZeroU8,
-) -> String {
-    (resolution.unwrap().as_f64().unwrap() * device_pixel_ratio / resolution_divisor.get() as f64)
-        .round()
-        .to_string()
+) -> u32 {
+    (css_pixels * device_pixel_ratio / resolution_divisor.get() as f64).round() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::canvas::dimension;
+    use std::num::NonZeroU8;
+
+    #[test]
+    fn resolution_divisor() {
+        let divisor = |n| NonZeroU8::new(n).unwrap();
+        assert_eq!(dimension(1280.0, 1.0, divisor(1)), 1280);
+        assert_eq!(dimension(1280.0, 2.0, divisor(1)), 2560);
+        assert_eq!(dimension(1280.0, 2.0, divisor(2)), 1280);
+        assert_eq!(dimension(1281.0, 1.0, divisor(3)), 427);
+    }
 }
@@ -4,8 +4,15 @@
 use crate::{Ctw, WindowEventListener};
 use gloo::timers::callback::Timeout;
 use stylist::yew::styled_component;
-use web_sys::MouseEvent;
-use yew::{function_component, html, use_effect_with_deps, Callback, Children, Properties};
+use wasm_bindgen::JsCast;
+use web_sys::{Element, HtmlElement, KeyboardEvent, MouseEvent, Node};
+use yew::virtual_dom::AttrValue;
+use yew::{
+    classes, function_component, html, use_effect_with_deps, use_mut_ref, use_node_ref, use_state,
+    Callback, Children, Properties,
+};
+use yew_router::history::Location;
+use yew_router::hooks::use_location;
 
 #[derive(Clone, PartialEq, Properties)]
 pub struct ContextMenuProps {
@@ -13,42 +20,236 @@ pub struct ContextMenuProps {
     pub children: Children,
 }
 
+/// A menu of [`ContextMenuButton`]s and [`ContextSubmenu`]s. Can be navigated with the arrow keys,
+/// and closes when clicking elsewhere or pressing escape.
 #[function_component(ContextMenu)]
 pub fn context_menu(props: &ContextMenuProps) -> Html {
     let style = format!("background-color: #444444aa; min-width: 100px; position: absolute; display: flex; flex-direction: column; left: {}px; top: {}px;", props.event.x(), props.event.y());
+    let menu_ref = use_node_ref();
 
-    // Provide for closing the menu by rightclicking elsewhere.
+    // Provide for closing the menu by rightclicking or clicking elsewhere.
     let set_context_menu_callback = Ctw::use_set_context_menu_callback();
-    let set_context_menu_callback_clone = set_context_menu_callback.clone();
-    use_effect_with_deps(
-        |_| {
-            let listener = WindowEventListener::new_body(
-                "contextmenu",
-                move |e: &MouseEvent| {
-                    e.prevent_default();
-                    e.stop_propagation();
-                    set_context_menu_callback.emit(None)
-                },
-                true,
-            );
-            let timeout = Timeout::new(5000, move || {
-                set_context_menu_callback_clone.emit(None);
-            });
-            || drop((listener, timeout))
-        },
-        props.event.clone(),
-    );
+    let timeout = use_mut_ref(|| Option::<Timeout>::None);
+    {
+        let set_context_menu_callback = set_context_menu_callback.clone();
+        let menu_ref = menu_ref.clone();
+        let timeout = timeout.clone();
+        use_effect_with_deps(
+            move |_| {
+                // Focus the first item, for keyboard navigation.
+                if let Some(menu) = menu_ref.cast::<Element>() {
+                    navigate(&menu, None, Navigation::First);
+                }
+
+                let set_context_menu_callback_clone = set_context_menu_callback.clone();
+                let listener = WindowEventListener::new_body(
+                    "contextmenu",
+                    move |e: &MouseEvent| {
+                        e.prevent_default();
+                        e.stop_propagation();
+                        set_context_menu_callback_clone.emit(None)
+                    },
+                    true,
+                );
+                let set_context_menu_callback_clone = set_context_menu_callback.clone();
+                let click_listener = WindowEventListener::new_body(
+                    "click",
+                    move |e: &MouseEvent| {
+                        let target = e.target().and_then(|t| t.dyn_into::<Node>().ok());
+                        let menu = menu_ref.cast::<Node>();
+                        if menu.map_or(true, |menu| !menu.contains(target.as_ref())) {
+                            set_context_menu_callback_clone.emit(None)
+                        }
+                    },
+                    false,
+                );
+                *timeout.borrow_mut() = Some(Timeout::new(5000, move || {
+                    set_context_menu_callback.emit(None);
+                }));
+                move || {
+                    timeout.borrow_mut().take();
+                    drop((listener, click_listener))
+                }
+            },
+            props.event.clone(),
+        );
+    }
+
+    // Navigating shouldn't dismiss the menu out from under the user.
+    {
+        let path = use_location().map(|location| location.path());
+        let last_path = use_mut_ref(|| path.clone());
+        use_effect_with_deps(
+            move |path| {
+                if *last_path.borrow() != *path {
+                    *last_path.borrow_mut() = path.clone();
+                    timeout.borrow_mut().take();
+                }
+                || ()
+            },
+            path,
+        );
+    }
+
+    let onkeydown = {
+        let menu_ref = menu_ref.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            match Navigation::from_key(&e.key()) {
+                Some(Navigation::Dismiss) => set_context_menu_callback.emit(None),
+                Some(Navigation::Open | Navigation::Close) | None => return,
+                Some(navigation) => {
+                    if let Some(menu) = menu_ref.cast::<Element>() {
+                        navigate(&menu, event_target(&e), navigation);
+                    }
+                }
+            }
+            e.prevent_default();
+            e.stop_propagation();
+        })
+    };
 
     html! {
-        <div {style}>
+        <div {style} ref={menu_ref} {onkeydown}>
             {props.children.clone()}
         </div>
     }
 }
 
+#[derive(Clone, PartialEq, Properties)]
+pub struct ContextSubmenuProps {
+    /// Label of the item that opens the submenu.
+    pub label: AttrValue,
+    /// [`ContextMenuButton`]s and [`ContextSubmenu`]s of the submenu.
+    pub children: Children,
+}
+
+/// An item of a [`ContextMenu`] that opens a submenu beside it, upon hovering, clicking, or
+/// pressing the right arrow key. Can be nested.
+#[function_component(ContextSubmenu)]
+pub fn context_submenu(props: &ContextSubmenuProps) -> Html {
+    let open = use_state(|| false);
+    // Whether to focus the first item of the submenu once it opens.
+    let focus_first = use_mut_ref(|| false);
+    let item_ref = use_node_ref();
+    let submenu_ref = use_node_ref();
+
+    {
+        let submenu_ref = submenu_ref.clone();
+        let focus_first = focus_first.clone();
+        use_effect_with_deps(
+            move |&open| {
+                if open && std::mem::take(&mut *focus_first.borrow_mut()) {
+                    if let Some(submenu) = submenu_ref.cast::<Element>() {
+                        navigate(&submenu, None, Navigation::First);
+                    }
+                }
+                || ()
+            },
+            *open,
+        );
+    }
+
+    let onmouseenter = {
+        let open = open.clone();
+        Callback::from(move |_: MouseEvent| open.set(true))
+    };
+    let onmouseleave = {
+        let open = open.clone();
+        Callback::from(move |_: MouseEvent| open.set(false))
+    };
+
+    // Toggle when the label (as opposed to the submenu) is clicked.
+    let onclick = {
+        let open = open.clone();
+        let item_ref = item_ref.clone();
+        Callback::from(move |e: MouseEvent| {
+            if is_label(
+                &item_ref,
+                e.target().and_then(|t| t.dyn_into::<Node>().ok()),
+            ) {
+                e.stop_propagation();
+                open.set(!*open);
+            }
+        })
+    };
+
+    // Open when the label is focused.
+    let onkeydown = {
+        let open = open.clone();
+        let item_ref = item_ref.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            if Navigation::from_key(&e.key()) == Some(Navigation::Open)
+                && is_label(
+                    &item_ref,
+                    e.target().and_then(|t| t.dyn_into::<Node>().ok()),
+                )
+            {
+                *focus_first.borrow_mut() = true;
+                open.set(true);
+                e.prevent_default();
+                e.stop_propagation();
+            }
+        })
+    };
+
+    // Navigate within, or close, the submenu.
+    let onkeydown_submenu = {
+        let open = open.clone();
+        let item_ref = item_ref.clone();
+        let submenu_ref = submenu_ref.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            match Navigation::from_key(&e.key()) {
+                Some(Navigation::Close) => {
+                    open.set(false);
+                    if let Some(button) = item_ref.cast::<Element>().and_then(item_button) {
+                        let _ = button.focus();
+                    }
+                }
+                Some(
+                    navigation @ (Navigation::Next
+                    | Navigation::Previous
+                    | Navigation::First
+                    | Navigation::Last),
+                ) => {
+                    if let Some(submenu) = submenu_ref.cast::<Element>() {
+                        navigate(&submenu, event_target(&e), navigation);
+                    }
+                }
+                // Let the parent menu handle it.
+                _ => return,
+            }
+            e.prevent_default();
+            e.stop_propagation();
+        })
+    };
+
+    html! {
+        <div
+            ref={item_ref}
+            style="position: relative; display: flex; flex-direction: column;"
+            {onmouseenter}
+            {onmouseleave}
+            {onclick}
+            {onkeydown}
+        >
+            <ContextMenuButton>{props.label.clone()}{" ▸"}</ContextMenuButton>
+            if *open {
+                <div
+                    ref={submenu_ref}
+                    style="background-color: #444444aa; min-width: 100px; position: absolute; left: 100%; top: 0; display: flex; flex-direction: column;"
+                    onkeydown={onkeydown_submenu}
+                >
+                    {props.children.clone()}
+                </div>
+            }
+        </div>
+    }
+}
+
 #[derive(Clone, PartialEq, Properties)]
 pub struct ContextMenuButtonProps {
     pub children: Children,
+    #[prop_or_default]
     pub onclick: Option<Callback<MouseEvent>>,
 }
 
@@ -83,6 +284,8 @@ pub fn context_menu_button(props: &ContextMenuButtonProps) -> Html {
         })
     });
 
+    let class = classes!(class, Ctw::use_theme().overlay_class());
+
     html! {
         <button {onclick} {class}>
             {props.children.clone()}
@@ -90,6 +293,88 @@ pub fn context_menu_button(props: &ContextMenuButtonProps) -> Html {
     }
 }
 
+/// How a key navigates a [`ContextMenu`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Navigation {
+    Next,
+    Previous,
+    First,
+    Last,
+    /// Opens a [`ContextSubmenu`].
+    Open,
+    /// Closes a [`ContextSubmenu`].
+    Close,
+    /// Closes the whole [`ContextMenu`].
+    Dismiss,
+}
+
+impl Navigation {
+    /// Parses a [`KeyboardEvent::key`].
+    fn from_key(key: &str) -> Option<Self> {
+        Some(match key {
+            "ArrowDown" => Self::Next,
+            "ArrowUp" => Self::Previous,
+            "Home" => Self::First,
+            "End" => Self::Last,
+            "ArrowRight" => Self::Open,
+            "ArrowLeft" => Self::Close,
+            "Escape" => Self::Dismiss,
+            _ => return None,
+        })
+    }
+}
+
+/// Gets the [`Element`] that `event` was dispatched to.
+fn event_target(event: &KeyboardEvent) -> Option<Element> {
+    event.target().and_then(|t| t.dyn_into().ok())
+}
+
+/// Returns true if `target` is (within) the label of the [`ContextSubmenu`] `item`.
+fn is_label(item: &yew::NodeRef, target: Option<Node>) -> bool {
+    item.cast::<Element>()
+        .and_then(item_button)
+        .map_or(false, |button| button.contains(target.as_ref()))
+}
+
+/// Gets the focusable button of a menu `item`, which is either a [`ContextMenuButton`] or a
+/// [`ContextSubmenu`].
+fn item_button(item: Element) -> Option<HtmlElement> {
+    let button = if item.tag_name() == "BUTTON" {
+        Some(item)
+    } else {
+        item.first_element_child()
+    };
+    button.and_then(|button| button.dyn_into().ok())
+}
+
+/// Focuses an item of `menu`, whose children are the items, relative to the item containing
+/// `target` (if any).
+fn navigate(menu: &Element, target: Option<Element>, navigation: Navigation) {
+    // Find the item, which might be an ancestor of the target.
+    let mut item = target;
+    while let Some(element) = &item {
+        if element.parent_element().as_ref() == Some(menu) {
+            break;
+        }
+        item = element.parent_element();
+    }
+
+    let next = match navigation {
+        Navigation::Next => item
+            .and_then(|item| item.next_element_sibling())
+            .or_else(|| menu.first_element_child()),
+        Navigation::Previous => item
+            .and_then(|item| item.previous_element_sibling())
+            .or_else(|| menu.last_element_child()),
+        Navigation::First => menu.first_element_child(),
+        Navigation::Last => menu.last_element_child(),
+        _ => return,
+    };
+    if let Some(button) = next.and_then(item_button) {
+        let _ = button.focus();
+    }
+}
+
 /// Returns oncontextmenu callback that dismisses existing context menu.
 pub fn dismiss_context_menu() -> Callback<MouseEvent> {
     let set_context_menu_callback = Ctw::use_set_context_menu_callback();
@@ -99,3 +384,18 @@ pub fn dismiss_context_menu() -> Callback<MouseEvent> {
         set_context_menu_callback.emit(None)
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::component::context_menu::Navigation;
+
+    #[test]
+    fn navigation_keys() {
+        assert_eq!(Navigation::from_key("ArrowDown"), Some(Navigation::Next));
+        assert_eq!(Navigation::from_key("ArrowUp"), Some(Navigation::Previous));
+        assert_eq!(Navigation::from_key("ArrowRight"), Some(Navigation::Open));
+        assert_eq!(Navigation::from_key("ArrowLeft"), Some(Navigation::Close));
+        assert_eq!(Navigation::from_key("Escape"), Some(Navigation::Dismiss));
+        assert_eq!(Navigation::from_key("a"), None);
+    }
+}
@@ -0,0 +1,88 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use stylist::yew::styled_component;
+use yew::prelude::*;
+
+#[derive(PartialEq, Properties)]
+pub struct FadeProps {
+    pub children: Children,
+    /// Whether to (fade in and) show the children, as opposed to fading them out and hiding them.
+    pub visible: bool,
+    /// How long fading in or out takes.
+    #[prop_or(300)]
+    pub duration_millis: u32,
+    /// Show and hide instantly, without fading. Fading is also skipped if the browser prefers
+    /// reduced motion.
+    #[prop_or(false)]
+    pub reduce_motion: bool,
+}
+
+/// Fades its children in and out, to avoid transient overlays popping in and out of existence.
+/// Keep rendering it while not `visible`, so it has a chance to fade out.
+#[styled_component(Fade)]
+pub fn fade(props: &FadeProps) -> Html {
+    let class = css!(
+        r#"
+        @keyframes fade_in {
+            from { opacity: 0; }
+            to   { opacity: 1; }
+        }
+
+        @media (prefers-reduced-motion) {
+            animation: none !important;
+            transition: none !important;
+        }
+        "#
+    );
+
+    html! {
+        <div {class} style={fade_style(props.visible, props.duration_millis, props.reduce_motion)}>
+            {props.children.clone()}
+        </div>
+    }
+}
+
+/// Gets the inline style of a [`Fade`]. Fades in with an animation (which also plays when first
+/// rendered), and out with a transition (which doesn't).
+pub(crate) fn fade_style(visible: bool, duration_millis: u32, reduce_motion: bool) -> String {
+    let animated = !reduce_motion && duration_millis > 0;
+    match (visible, animated) {
+        (true, true) => format!("animation: fade_in {}ms;", duration_millis),
+        (true, false) => String::new(),
+        (false, true) => format!(
+            "opacity: 0; visibility: hidden; transition: opacity {0}ms, visibility {0}ms;",
+            duration_millis
+        ),
+        (false, false) => String::from("opacity: 0; visibility: hidden;"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::component::fade::fade_style;
+
+    #[test]
+    fn fade_duration() {
+        let fade_in = fade_style(true, 250, false);
+        assert!(fade_in.contains("animation: fade_in 250ms"), "{}", fade_in);
+
+        let fade_out = fade_style(false, 400, false);
+        assert!(
+            fade_out.contains("transition: opacity 400ms"),
+            "{}",
+            fade_out
+        );
+        assert!(fade_out.contains("visibility: hidden"), "{}", fade_out);
+    }
+
+    #[test]
+    fn fade_reduce_motion() {
+        assert_eq!(fade_style(true, 250, true), "");
+        for (duration_millis, reduce_motion) in [(250, true), (0, false)] {
+            let hidden = fade_style(false, duration_millis, reduce_motion);
+            assert!(!hidden.contains("transition"), "{}", hidden);
+            assert!(hidden.contains("visibility: hidden"), "{}", hidden);
+        }
+    }
+}
@@ -2,11 +2,16 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
 use crate::translation::{t, Translation};
-use crate::Ctw;
+use crate::{Ctw, Route};
+use core_protocol::id::InvitationId;
+use core_protocol::rpc::InvitationRequest;
 use gloo::timers::callback::Timeout;
 use stylist::yew::styled_component;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_futures::{future_to_promise, JsFuture};
 use web_sys::{window, MouseEvent};
-use yew::{html, use_state, Callback, Properties};
+use yew::{html, use_effect_with_deps, use_state, Callback, Properties};
+use yew_router::Routable;
 
 #[derive(PartialEq, Properties)]
 pub struct InvitationLinkProps;
@@ -38,36 +43,96 @@ pub fn invitation_link(_props: &InvitationLinkProps) -> Html {
     }
 }
 
-/// [`None`] indicates the button was pressed recently.
+/// Gets the absolute URL of an invitation, at the current `origin` (e.g. `https://mk48.io`). The
+/// path is [`Route::Invitation`]'s, so the invitation is accepted when the URL is visited.
+pub fn invitation_url(origin: &str, invitation_id: InvitationId) -> String {
+    format!(
+        "{}{}",
+        origin,
+        Route::Invitation { invitation_id }.to_path()
+    )
+}
+
+/// Copies the [`invitation_url`] to the clipboard, and calls `on_copied` once it is written. Must be
+/// called from an event handler, because browsers only permit writing the clipboard in response to
+/// user input.
+fn copy_invitation_url(invitation_id: InvitationId, on_copied: impl FnOnce() + 'static) {
+    let window = window().unwrap();
+    if let Some((origin, clipboard)) = window
+        .location()
+        .origin()
+        .ok()
+        .zip(window.navigator().clipboard())
+    {
+        let promise = clipboard.write_text(&invitation_url(&origin, invitation_id));
+        let _ = future_to_promise(async move {
+            if JsFuture::from(promise).await.is_ok() {
+                on_copied();
+            }
+            Ok(JsValue::NULL)
+        });
+    }
+}
+
+/// [`None`] indicates the invitation was copied recently. An invitation is requested ahead of time,
+/// so it can be copied as soon as the button is pressed.
 pub fn use_copy_invitation_link() -> Option<Callback<MouseEvent>> {
     let timeout = use_state::<Option<Timeout>, _>(|| None);
     let created_invitation_id = Ctw::use_core_state().created_invitation_id;
+    let invitation_request_callback = Ctw::use_invitation_request_callback();
+
+    {
+        let invitation_request_callback = invitation_request_callback.clone();
+        use_effect_with_deps(
+            move |&created_invitation_id| {
+                if created_invitation_id.is_none() {
+                    invitation_request_callback.emit(InvitationRequest::CreateInvitation);
+                }
+                || ()
+            },
+            created_invitation_id,
+        );
+    }
 
     timeout.is_none().then(|| {
         Callback::from(move |e: MouseEvent| {
             e.prevent_default();
             e.stop_propagation();
 
-            let window = window().unwrap();
-
-            if let Some((invitation_id, (origin, clipboard))) = created_invitation_id.zip(
-                window
-                    .location()
-                    .origin()
-                    .ok()
-                    .zip(window.navigator().clipboard()),
-            ) {
-                let invitation_link = format!("{}/invite/{}", origin, invitation_id.0);
-
-                // TODO: await this.
-                let _ = clipboard.write_text(&invitation_link);
-
-                let timeout_clone = timeout.clone();
-
-                timeout.set(Some(Timeout::new(5000, move || {
-                    timeout_clone.set(None);
-                })));
+            if let Some(invitation_id) = created_invitation_id {
+                let timeout = timeout.clone();
+                copy_invitation_url(invitation_id, move || {
+                    // Disable copying for a while, to acknowledge the copy.
+                    let timeout_clone = timeout.clone();
+                    timeout.set(Some(Timeout::new(5000, move || {
+                        timeout_clone.set(None);
+                    })));
+                });
+            } else {
+                // Still waiting for the server, or the earlier request failed.
+                invitation_request_callback.emit(InvitationRequest::CreateInvitation);
             }
         })
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::component::invitation_link::invitation_url;
+    use crate::Route;
+    use core_protocol::id::InvitationId;
+    use std::num::NonZeroU32;
+    use yew_router::Routable;
+
+    #[test]
+    fn round_trip() {
+        let invitation_id = InvitationId(NonZeroU32::new(1234567).unwrap());
+        let origin = "https://mk48.io";
+        let url = invitation_url(origin, invitation_id);
+        assert_eq!(url, "https://mk48.io/invite/1234567/");
+        assert!(
+            Route::recognize(url.strip_prefix(origin).unwrap())
+                == Some(Route::Invitation { invitation_id })
+        );
+    }
+}
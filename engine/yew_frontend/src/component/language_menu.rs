@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
 use crate::event::event_target;
-use crate::translation::{t, Translation};
+use crate::translation::{t, use_set_language, Translation};
 use crate::Ctw;
 use core_protocol::id::LanguageId;
 use gloo::timers::callback::Timeout;
@@ -62,7 +62,7 @@ pub fn language_menu() -> Html {
     };
 
     let handle_change = {
-        let change_common_settings_callback = Ctw::use_change_common_settings_callback();
+        let set_language = use_set_language();
         let menu_open = menu_open.clone();
 
         move |event: Event| {
@@ -71,11 +71,7 @@ pub fn language_menu() -> Html {
             // TODO: Very bad code. Probably should use settings object + serde.
             let parsed = LanguageId::iter().find(|l| format!("{:?}", l) == value);
             if let Some(parsed) = parsed {
-                change_common_settings_callback.emit(Box::new(
-                    move |common_settings, browser_storage| {
-                        common_settings.set_language(parsed, browser_storage);
-                    },
-                ));
+                set_language.emit(parsed);
             }
             menu_open.set(None);
         }
@@ -26,7 +26,7 @@ pub fn link_icon(props: &LinkIconProps) -> Html {
 
             if let Err(e) = window().unwrap().open_with_url_and_target(&link, "_blank") {
                 if cfg!(debug_assertions) {
-                    js_hooks::console_log!("could not open link: {:?}", e);
+                    js_hooks::console_warn!("could not open link: {:?}", e);
                 }
             }
         })
@@ -11,6 +11,12 @@ pub struct MeterProps {
     pub color: u32,
     /// 0 to 1.
     pub value: f32,
+    /// Fill smoothly when `value` changes, instead of instantly.
+    #[prop_or(true)]
+    pub animate: bool,
+    /// Duration of the fill animation, if `animate`.
+    #[prop_or(300)]
+    pub animation_millis: u32,
 }
 
 #[styled_component(Meter)]
@@ -28,7 +34,6 @@ pub fn meter(props: &MeterProps) -> Html {
         overflow: hidden;
         padding: 0.2em;
         text-align: center;
-        transition: background-size 0.5s;
         user-select: none;
         width: 100%;
     "#
@@ -38,7 +43,14 @@ pub fn meter(props: &MeterProps) -> Html {
     let percentage = (props.value.clamp(0.0, 1.0) * 100.0).round();
     let background_size = (percentage.max(1.0) * 100.0).round();
 
-    let style = format!("background: linear-gradient(90deg, #{:06x} 0%, #{:06x} 1%, #{:06x} 1%, #{:06x} 100%); background-origin: border-box; background-size: {}%;", props.color, props.color, background_color, background_color, background_size);
+    let mut style = format!("background: linear-gradient(90deg, #{:06x} 0%, #{:06x} 1%, #{:06x} 1%, #{:06x} 100%); background-origin: border-box; background-size: {}%;", props.color, props.color, background_color, background_color, background_size);
+
+    if props.animate {
+        style += &format!(
+            "transition: background-size {}ms ease-out;",
+            props.animation_millis
+        );
+    }
 
     html! {
         <div class={div_css_class} style={style}>
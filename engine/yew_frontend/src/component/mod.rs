@@ -4,6 +4,7 @@
 pub mod context_menu;
 pub mod curtain;
 pub mod discord_icon;
+pub mod fade;
 pub mod github_icon;
 pub mod invitation_icon;
 pub mod invitation_link;
@@ -10,6 +10,8 @@ pub struct PositionerProps {
     pub id: Option<AttrValue>,
     pub children: Children,
     pub position: Position,
+    /// Nudge away from `position`.
+    pub offset: Option<Offset>,
     /// Override default alignment (horizontal position).
     pub align: Option<Align>,
     /// Use flex layout.
@@ -22,25 +24,53 @@ pub struct PositionerProps {
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum Position {
-    BottomLeft { margin: &'static str },
-    BottomMiddle { margin: &'static str },
-    BottomRight { margin: &'static str },
-    CenterLeft { margin: &'static str },
+    BottomLeft {
+        margin: &'static str,
+    },
+    BottomMiddle {
+        margin: &'static str,
+    },
+    BottomRight {
+        margin: &'static str,
+    },
+    CenterLeft {
+        margin: &'static str,
+    },
     Center,
-    CenterRight { margin: &'static str },
-    TopLeft { margin: &'static str },
-    TopMiddle { margin: &'static str },
-    TopRight { margin: &'static str },
+    CenterRight {
+        margin: &'static str,
+    },
+    TopLeft {
+        margin: &'static str,
+    },
+    TopMiddle {
+        margin: &'static str,
+    },
+    TopRight {
+        margin: &'static str,
+    },
+    /// Top left corner at the given CSS `left` and `top` (e.g. `"25%"`).
+    Custom {
+        left: &'static str,
+        top: &'static str,
+    },
+}
+
+/// Translation of a [`Position`], in CSS units (e.g. `"2rem"` or `"5vw"`). Positive values
+/// are right and down, respectively.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Offset {
+    pub x: &'static str,
+    pub y: &'static str,
 }
 
 impl Position {
     pub fn default_text_align(&self) -> Align {
         self.horizontal().default_text_align()
     }
-}
 
-impl ToString for Position {
-    fn to_string(&self) -> String {
+    /// Absolute positioning rules, optionally translated by `offset`.
+    pub fn to_css(self, offset: Option<Offset>) -> String {
         let mut style = String::with_capacity(64);
 
         style += "position: absolute;";
@@ -53,6 +83,7 @@ impl ToString for Position {
             HorizontalPosition::Right { margin, .. } => {
                 ("right: 0;", format!("margin-right: {};", margin), 0)
             }
+            HorizontalPosition::Custom { left } => ("", format!("left: {};", left), 0),
         };
 
         style += h_position;
@@ -64,22 +95,33 @@ impl ToString for Position {
             }
             VerticalPosition::Center => ("top: 50%;", String::new(), -50),
             VerticalPosition::Top { margin } => ("top: 0;", format!("margin-top: {};", margin), 0),
+            VerticalPosition::Custom { top } => ("", format!("top: {};", top), 0),
         };
 
         style += v_position;
         style += &v_margin;
 
+        let mut transform = Vec::with_capacity(2);
         if h_translation != 0 || v_translation != 0 {
-            style += &format!(
-                "transform: translate({}%, {}%);",
-                h_translation, v_translation
-            );
+            transform.push(format!("translate({}%, {}%)", h_translation, v_translation));
+        }
+        if let Some(Offset { x, y }) = offset {
+            transform.push(format!("translate({}, {})", x, y));
+        }
+        if !transform.is_empty() {
+            style += &format!("transform: {};", transform.join(" "));
         }
 
         style
     }
 }
 
+impl ToString for Position {
+    fn to_string(&self) -> String {
+        self.to_css(None)
+    }
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum Align {
     Left,
@@ -117,12 +159,13 @@ enum HorizontalPosition {
     Left { margin: &'static str },
     Middle,
     Right { margin: &'static str },
+    Custom { left: &'static str },
 }
 
 impl HorizontalPosition {
     fn default_text_align(self) -> Align {
         match self {
-            Self::Left { .. } => Align::Left,
+            Self::Left { .. } | Self::Custom { .. } => Align::Left,
             Self::Middle => Align::Center,
             Self::Right { .. } => Align::Right,
         }
@@ -134,6 +177,7 @@ enum VerticalPosition {
     Bottom { margin: &'static str },
     Center,
     Top { margin: &'static str },
+    Custom { top: &'static str },
 }
 
 impl Position {
@@ -148,6 +192,7 @@ impl Position {
             Self::BottomRight { margin }
             | Self::CenterRight { margin }
             | Self::TopRight { margin } => HorizontalPosition::Right { margin },
+            Self::Custom { left, .. } => HorizontalPosition::Custom { left },
         }
     }
 
@@ -162,13 +207,14 @@ impl Position {
             Self::TopLeft { margin }
             | Self::TopMiddle { margin }
             | Self::TopRight { margin, .. } => VerticalPosition::Top { margin },
+            Self::Custom { top, .. } => VerticalPosition::Custom { top },
         }
     }
 }
 
 #[styled_component(Positioner)]
 pub fn positioner(props: &PositionerProps) -> Html {
-    let mut style = props.position.to_string();
+    let mut style = props.position.to_css(props.offset);
 
     if let Some(min_width) = props.min_width.as_ref() {
         style += &format!("min-width: {};", min_width);
@@ -193,3 +239,28 @@ pub fn positioner(props: &PositionerProps) -> Html {
         </div>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::component::positioner::{Offset, Position};
+
+    #[test]
+    fn to_css() {
+        assert_eq!(
+            Position::TopLeft { margin: "1rem" }.to_string(),
+            "position: absolute;left: 0;margin-left: 1rem;top: 0;margin-top: 1rem;"
+        );
+        assert_eq!(
+            Position::BottomMiddle { margin: "0" }.to_css(Some(Offset { x: "0", y: "-2rem" })),
+            "position: absolute;left: 50%;bottom: 0;margin-bottom: 0;transform: translate(-50%, 0%) translate(0, -2rem);"
+        );
+        assert_eq!(
+            Position::Custom {
+                left: "25%",
+                top: "10vh"
+            }
+            .to_string(),
+            "position: absolute;left: 25%;top: 10vh;"
+        );
+    }
+}
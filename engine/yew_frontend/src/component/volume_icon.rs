@@ -16,8 +16,9 @@ pub struct VolumeIconProps {
 
 #[function_component(VolumeIcon)]
 pub fn volume_icon(props: &VolumeIconProps) -> Html {
-    let volume = Ctw::use_ctw().setting_cache.volume;
-    let current = ((volume * 2.0).round() as u8).clamp(0, 2);
+    let setting_cache = Ctw::use_ctw().setting_cache;
+    let muted = setting_cache.muted;
+    let current = ((setting_cache.volume * 2.0).round() as u8).clamp(0, 2);
 
     let onclick = {
         let change_common_settings_callback = Ctw::use_change_common_settings_callback();
@@ -28,6 +29,11 @@ pub fn volume_icon(props: &VolumeIconProps) -> Html {
 
             change_common_settings_callback.emit(Box::new(
                 move |common_settings, browser_storages| {
+                    if muted {
+                        // Unmute, without changing the volume.
+                        common_settings.set_muted(false, browser_storages);
+                        return;
+                    }
                     let next = (current + 1) % 3;
                     common_settings.set_volume(next as f32 / 2.0, browser_storages);
                 },
@@ -52,6 +58,7 @@ pub fn volume_icon(props: &VolumeIconProps) -> Html {
     };
 
     let (icon_id, style) = match current {
+        _ if muted => (IconId::BootstrapVolumeMuteFill, "opacity: 0.6;"),
         0 => (IconId::BootstrapVolumeMute, "opacity: 0.6;"),
         1 => (IconId::BootstrapVolumeDownFill, "opacity: 1;"),
         2 => (IconId::BootstrapVolumeUpFill, "opacity: 1;"),
@@ -0,0 +1,334 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::dialog::dialog::Dialog;
+use crate::frontend::Ctw;
+use crate::translation::{t, Translation};
+use core_protocol::dto::ServerDto;
+use core_protocol::id::{PeriodId, ServerId};
+use std::str::FromStr;
+use stylist::yew::styled_component;
+use web_sys::{HtmlSelectElement, InputEvent};
+use yew::virtual_dom::AttrValue;
+use yew::{classes, html, html_nested, use_state, Callback, Html, TargetCast};
+
+/// Which scores to show.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+enum Board {
+    /// Players currently in the game.
+    #[default]
+    Live,
+    Period(PeriodId),
+}
+
+impl Board {
+    const ALL: [Self; 4] = [
+        Self::Live,
+        Self::Period(PeriodId::Daily),
+        Self::Period(PeriodId::Weekly),
+        Self::Period(PeriodId::AllTime),
+    ];
+}
+
+/// Column to sort by.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+enum SortBy {
+    #[default]
+    Score,
+    Kills,
+    Name,
+    Team,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Row {
+    /// 1-based rank by score, regardless of sorting.
+    rank: usize,
+    name: String,
+    team: Option<String>,
+    kills: u32,
+    score: u32,
+}
+
+/// Ranks `rows` (of name, team, kills, and score) by score, then sorts them by `sort_by`. Ties
+/// are broken by score.
+fn sort_rows(
+    rows: impl Iterator<Item = (String, Option<String>, u32, u32)>,
+    sort_by: SortBy,
+) -> Vec<Row> {
+    let mut rows: Vec<(String, Option<String>, u32, u32)> = rows.collect();
+    rows.sort_by(|a, b| b.3.cmp(&a.3));
+    let mut rows: Vec<Row> = rows
+        .into_iter()
+        .enumerate()
+        .map(|(i, (name, team, kills, score))| Row {
+            rank: i + 1,
+            name,
+            team,
+            kills,
+            score,
+        })
+        .collect();
+
+    // Stable, so ties stay sorted by score.
+    match sort_by {
+        SortBy::Score => {}
+        SortBy::Kills => rows.sort_by(|a, b| b.kills.cmp(&a.kills)),
+        SortBy::Name => rows.sort_by_key(|row| row.name.to_lowercase()),
+        // Players without a team last.
+        SortBy::Team => rows.sort_by_key(|row| {
+            (
+                row.team.is_none(),
+                row.team.as_ref().map(|team| team.to_lowercase()),
+            )
+        }),
+    }
+    rows
+}
+
+const ROWS_PER_PAGE: usize = 20;
+
+/// Number of pages needed for `rows`, which is at least 1 (even if empty).
+fn page_count(rows: usize) -> usize {
+    ((rows + ROWS_PER_PAGE - 1) / ROWS_PER_PAGE).max(1)
+}
+
+/// Rankings of the selected server, at a shareable URL.
+#[styled_component(LeaderboardDialog)]
+pub fn leaderboard_dialog() -> Html {
+    let select_style = css!(
+        r#"
+        border-radius: 0.25em;
+        border: 0;
+        color: white;
+        cursor: pointer;
+        font-size: 1em;
+        margin: 0.25em;
+        padding: 0.5em;
+        background-color: #0075ff;
+        "#
+    );
+
+    let table_style = css!(
+        r#"
+        margin: auto;
+        min-width: 50%;
+
+        th.sortable {
+            cursor: pointer;
+            text-decoration: underline;
+        }
+
+        th.sorted {
+            text-decoration: none;
+        }
+
+        td.name {
+            font-weight: bold;
+            text-align: left;
+        }
+
+        td.kills, td.score {
+            text-align: right;
+        }
+        "#
+    );
+
+    let t = t();
+    let ctw = Ctw::use_ctw();
+    let core_state = Ctw::use_core_state();
+    let board = use_state(Board::default);
+    let sort_by = use_state(SortBy::default);
+    let page = use_state(|| 0usize);
+
+    let rows = match *board {
+        Board::Live => sort_rows(
+            core_state.liveboard.iter().filter_map(|dto| {
+                let player = core_state.player_or_bot(dto.player_id)?;
+                let team = dto
+                    .team_id
+                    .and_then(|team_id| core_state.teams.get(&team_id))
+                    .map(|team| team.name.to_string());
+                Some((player.alias.to_string(), team, dto.kills, dto.score))
+            }),
+            *sort_by,
+        ),
+        Board::Period(period_id) => sort_rows(
+            core_state
+                .leaderboard(period_id)
+                .iter()
+                .map(|dto| (dto.alias.to_string(), None, 0, dto.score)),
+            *sort_by,
+        ),
+    };
+    let pages = page_count(rows.len());
+    let current_page = (*page).min(pages - 1);
+
+    let board_label = |board: Board| match board {
+        Board::Live => t.liveboard_label(),
+        Board::Period(period_id) => t.leaderboard_label(period_id),
+    };
+
+    let on_select_board = {
+        let board = board.clone();
+        let page = page.clone();
+        Callback::from(move |event: InputEvent| {
+            let index = event
+                .target_unchecked_into::<HtmlSelectElement>()
+                .selected_index();
+            if let Some(&selected) = usize::try_from(index).ok().and_then(|i| Board::ALL.get(i)) {
+                board.set(selected);
+                page.set(0);
+            }
+        })
+    };
+
+    let sort_header = |column: SortBy, label: &'static str| {
+        let sorted = *sort_by == column;
+        let onclick = {
+            let sort_by = sort_by.clone();
+            let page = page.clone();
+            Callback::from(move |_| {
+                sort_by.set(column);
+                page.set(0);
+            })
+        };
+        html! {
+            <th class={classes!("sortable", sorted.then_some("sorted"))} {onclick}>
+                {label}{if sorted { " ▾" } else { "" }}
+            </th>
+        }
+    };
+
+    let go_to_page = |target: usize| {
+        let page = page.clone();
+        Callback::from(move |_| page.set(target))
+    };
+
+    let selected_server_id = ctw.setting_cache.server_id;
+    let on_select_server_id = ctw.set_server_id_callback.reform(move |event: InputEvent| {
+        let value = event.target_unchecked_into::<HtmlSelectElement>().value();
+        ServerId::from_str(&value).ok()
+    });
+
+    html! {
+        <Dialog title={t.leaderboard_title()}>
+            <select
+                value={selected_server_id.map(|s| AttrValue::Owned(s.to_string())).unwrap_or(AttrValue::Static("unknown"))}
+                oninput={on_select_server_id}
+                class={select_style.clone()}
+            >
+                if selected_server_id.is_none() || core_state.servers.is_empty() {
+                    <option value="unknown">{"Unknown server"}</option>
+                }
+                {core_state.servers.values().map(|&ServerDto{server_id, region_id, player_count}| {
+                    let region_str = region_id.as_human_readable_str();
+                    html_nested!{
+                        <option value={server_id.0.to_string()}>
+                            {format!("Server {server_id} - {region_str} ({player_count} players)")}
+                        </option>
+                    }
+                }).collect::<Html>()}
+            </select>
+
+            <select oninput={on_select_board} class={select_style.clone()}>
+                {Board::ALL.into_iter().map(|b| html_nested!{
+                    <option selected={b == *board}>{board_label(b)}</option>
+                }).collect::<Html>()}
+            </select>
+
+            <table class={table_style}>
+                <thead>
+                    <tr>
+                        <th>{"#"}</th>
+                        {sort_header(SortBy::Name, "Name")}
+                        if *board == Board::Live {
+                            {sort_header(SortBy::Team, "Team")}
+                            {sort_header(SortBy::Kills, "Kills")}
+                        }
+                        {sort_header(SortBy::Score, "Score")}
+                    </tr>
+                </thead>
+                <tbody>
+                    {rows.iter().skip(current_page * ROWS_PER_PAGE).take(ROWS_PER_PAGE).map(|row| html_nested!{
+                        <tr>
+                            <td>{row.rank}</td>
+                            <td class="name">{row.name.clone()}</td>
+                            if *board == Board::Live {
+                                <td>{row.team.clone().unwrap_or_default()}</td>
+                                <td class="kills">{row.kills}</td>
+                            }
+                            <td class="score">{row.score}</td>
+                        </tr>
+                    }).collect::<Html>()}
+                </tbody>
+            </table>
+
+            if pages > 1 {
+                <p>
+                    <button onclick={go_to_page(current_page.saturating_sub(1))} disabled={current_page == 0} class={select_style.clone()}>{"Previous"}</button>
+                    {format!("Page {} of {}", current_page + 1, pages)}
+                    <button onclick={go_to_page((current_page + 1).min(pages - 1))} disabled={current_page + 1 == pages} class={select_style}>{"Next"}</button>
+                </p>
+            }
+        </Dialog>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dialog::leaderboard_dialog::{page_count, sort_rows, SortBy};
+
+    fn rows() -> impl Iterator<Item = (String, Option<String>, u32, u32)> {
+        [
+            ("bob", Some("Zeta"), 2, 10),
+            ("Alice", None, 1, 30),
+            ("carol", Some("alpha"), 2, 20),
+            ("dave", Some("Zeta"), 0, 40),
+        ]
+        .into_iter()
+        .map(|(name, team, kills, score)| (name.to_owned(), team.map(str::to_owned), kills, score))
+    }
+
+    fn names(sort_by: SortBy) -> Vec<(usize, String)> {
+        sort_rows(rows(), sort_by)
+            .into_iter()
+            .map(|row| (row.rank, row.name))
+            .collect()
+    }
+
+    #[test]
+    fn sort() {
+        let expected = |names: &[(usize, &str)]| -> Vec<(usize, String)> {
+            names.iter().map(|&(r, n)| (r, n.to_owned())).collect()
+        };
+
+        assert_eq!(
+            names(SortBy::Score),
+            expected(&[(1, "dave"), (2, "Alice"), (3, "carol"), (4, "bob")])
+        );
+        // Most kills first, then score.
+        assert_eq!(
+            names(SortBy::Kills),
+            expected(&[(3, "carol"), (4, "bob"), (2, "Alice"), (1, "dave")])
+        );
+        // Case insensitive, keeping ranks by score.
+        assert_eq!(
+            names(SortBy::Name),
+            expected(&[(2, "Alice"), (4, "bob"), (3, "carol"), (1, "dave")])
+        );
+        // By team name, then score, with no team last.
+        assert_eq!(
+            names(SortBy::Team),
+            expected(&[(3, "carol"), (1, "dave"), (4, "bob"), (2, "Alice")])
+        );
+    }
+
+    #[test]
+    fn pages() {
+        assert_eq!(page_count(0), 1);
+        assert_eq!(page_count(1), 1);
+        assert_eq!(page_count(20), 1);
+        assert_eq!(page_count(21), 2);
+    }
+}
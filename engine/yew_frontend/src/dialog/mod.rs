@@ -2,5 +2,6 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
 pub mod dialog;
+pub(crate) mod leaderboard_dialog;
 pub(crate) mod privacy_dialog;
 pub(crate) mod terms_dialog;
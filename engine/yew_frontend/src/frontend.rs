@@ -1,6 +1,7 @@
 // SPDX-FileCopyrightText: 2021 Softbear, Inc.
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use crate::theme::Theme;
 use crate::Route;
 use client_util::browser_storage::BrowserStorages;
 use client_util::context::{StrongCoreState, WeakCoreState};
@@ -10,8 +11,12 @@ use client_util::js_util::referrer;
 use client_util::setting::CommonSettings;
 use core_protocol::id::{GameId, ServerId};
 use core_protocol::name::Referrer;
-use core_protocol::rpc::{ChatRequest, PlayerRequest, SystemQuery, SystemResponse, TeamRequest};
-use js_hooks::console_log;
+use core_protocol::rpc::{
+    ChatRequest, InvitationRequest, PlayerRequest, SystemQuery, SystemResponse, TeamRequest,
+};
+use js_hooks::{console_error, console_info, console_warn};
+use serde::Deserialize;
+use std::num::NonZeroU8;
 use std::ops::Deref;
 use wasm_bindgen::{JsCast, JsValue};
 use wasm_bindgen_futures::JsFuture;
@@ -36,17 +41,26 @@ impl<P: PartialEq> Deref for PropertiesWrapper<P> {
 #[derive(Clone, PartialEq)]
 pub struct Ctw {
     pub game_id: GameId,
+    /// See [`GameClient::VERSION`].
+    pub game_version: &'static str,
+    /// False if the player hid the HUD (see
+    /// [`GameAction::ToggleHud`][`client_util::key_bindings::GameAction::ToggleHud`]), in which
+    /// case in-game overlays should hide too. Menus, such as the spawn screen, stay visible.
+    pub hud_visible: bool,
     /// Outbound links.
     pub outbound_enabled: bool,
     pub setting_cache: CommonSettings,
     pub change_common_settings_callback:
         Callback<Box<dyn FnOnce(&mut CommonSettings, &mut BrowserStorages)>>,
     pub chat_request_callback: Callback<ChatRequest>,
+    pub invitation_request_callback: Callback<InvitationRequest>,
     pub player_request_callback: Callback<PlayerRequest>,
     pub raw_zoom_callback: Callback<f32>,
     pub recreate_renderer_callback: Callback<()>,
     pub set_server_id_callback: Callback<Option<ServerId>>,
     pub set_context_menu_callback: Callback<Option<Html>>,
+    /// Changes the canvas resolution divisor live (without recreating the canvas or renderer).
+    pub set_resolution_divisor_callback: Callback<NonZeroU8>,
     pub(crate) routes: Vec<&'static str>,
     /// A copy of the core state.
     pub state: WeakCoreState,
@@ -58,6 +72,10 @@ impl Ctw {
         Self::use_ctw().chat_request_callback.clone()
     }
 
+    pub fn use_invitation_request_callback() -> Callback<InvitationRequest> {
+        Self::use_ctw().invitation_request_callback.clone()
+    }
+
     pub fn use_player_request_callback() -> Callback<PlayerRequest> {
         Self::use_ctw().player_request_callback.clone()
     }
@@ -71,6 +89,10 @@ impl Ctw {
         Self::use_ctw().set_context_menu_callback.clone()
     }
 
+    pub fn use_set_resolution_divisor_callback() -> Callback<NonZeroU8> {
+        Self::use_ctw().set_resolution_divisor_callback.clone()
+    }
+
     pub fn use_core_state() -> StrongCoreState<'static> {
         Self::use_ctw().state.into_strong()
     }
@@ -83,10 +105,18 @@ impl Ctw {
         Self::use_ctw().game_id
     }
 
+    pub fn use_game_version() -> &'static str {
+        Self::use_ctw().game_version
+    }
+
     pub fn use_raw_zoom_callback() -> Callback<f32> {
         Self::use_ctw().raw_zoom_callback.clone()
     }
 
+    pub fn use_theme() -> Theme {
+        Theme::new(&Self::use_ctw().setting_cache)
+    }
+
     pub fn use_team_request_callback() -> Callback<TeamRequest> {
         Self::use_ctw().team_request_callback.clone()
     }
@@ -94,6 +124,10 @@ impl Ctw {
     pub fn use_outbound_enabled() -> bool {
         Self::use_ctw().outbound_enabled
     }
+
+    pub fn use_hud_visible() -> bool {
+        Self::use_ctw().hud_visible
+    }
 }
 
 /// Game-specific context wrapper.
@@ -101,6 +135,8 @@ pub struct Gctw<G: GameClient> {
     pub send_ui_event_callback: Callback<G::UiEvent>,
     pub change_settings_callback:
         Callback<Box<dyn FnOnce(&mut G::GameSettings, &mut BrowserStorages)>>,
+    /// See [`Gctw::recreate_renderer`].
+    pub(crate) recreate_renderer_callback: Callback<()>,
     pub settings_cache: G::GameSettings,
 }
 
@@ -109,6 +145,7 @@ impl<G: GameClient> Clone for Gctw<G> {
         Self {
             send_ui_event_callback: self.send_ui_event_callback.clone(),
             change_settings_callback: self.change_settings_callback.clone(),
+            recreate_renderer_callback: self.recreate_renderer_callback.clone(),
             settings_cache: self.settings_cache.clone(),
         }
     }
@@ -121,6 +158,9 @@ impl<G: GameClient> PartialEq for Gctw<G> {
             && self
                 .change_settings_callback
                 .eq(&other.change_settings_callback)
+            && self
+                .recreate_renderer_callback
+                .eq(&other.recreate_renderer_callback)
             && self.settings_cache == other.settings_cache
     }
 }
@@ -139,6 +179,13 @@ impl<G: GameClient> Gctw<G> {
     pub fn use_gctw() -> Self {
         use_context::<Self>().unwrap()
     }
+
+    /// Forces the canvas and renderer to be rebuilt, e.g. after loading new assets. The game's
+    /// render layer is recreated with [`GameClient::init_layer`]. Takes effect over the next few
+    /// frames.
+    pub fn recreate_renderer(&self) {
+        self.recreate_renderer_callback.emit(());
+    }
 }
 
 pub struct Yew<P> {
@@ -161,7 +208,7 @@ impl<P: PartialEq> Yew<P> {
             referrer: get_real_referrer(),
             system_info: SystemInfo::new()
                 .await
-                .inspect_err(|e| console_log!("system error: {}", e))
+                .inspect_err(|e| console_error!("system error: {}", e))
                 .ok(),
         }
     }
@@ -182,9 +229,13 @@ impl SystemInfo {
             }
         });
 
+        // TODO: Hack.
+        let browser_storages = BrowserStorages::new();
         let query = SystemQuery {
-            // TODO: Hack.
-            server_id: BrowserStorages::new().session.get("serverId"),
+            server_id: CommonSettings::startup_server_id(
+                browser_storages.session.get("serverId"),
+                browser_storages.local.get("lastServerId"),
+            ),
             region_id: None,
             invitation_id,
         };
@@ -253,7 +304,7 @@ fn get_real_referrer() -> Option<Referrer> {
         .and_then(|pathname| Route::recognize(&pathname))
         .and_then(|route| {
             if let Route::Referrer { referrer } = route {
-                console_log!("overriding referrer to: {}", referrer);
+                console_info!("overriding referrer to: {}", referrer);
                 Some(referrer)
             } else {
                 None
@@ -269,6 +320,137 @@ pub(crate) fn post_message(message: &str) {
         .post_message(&JsValue::from_str(message), "*")
         .is_err()
     {
-        console_log!("error posting message");
+        console_warn!("error posting message");
+    }
+}
+
+/// Longest chat message, in characters, that the parent window may send.
+pub(crate) const PARENT_CHAT_MAX_CHARS: usize = 150;
+
+/// Longest message, in bytes, accepted from the parent window.
+pub(crate) const PARENT_MESSAGE_MAX_BYTES: usize = 4096;
+
+/// Structured (JSON) message from the parent window, e.g. `{"type":"chat","message":"..."}`.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub(crate) enum ParentMessage {
+    /// Send a chat message on behalf of the player (still subject to server rate limiting).
+    Chat { message: String },
+    /// Change the minimum [`LogLevel`][`js_hooks::LogLevel`] of console messages, e.g. `"debug"`
+    /// or `"warn"`.
+    LogLevel { level: String },
+    /// Mute or unmute audio, like the bare `"mute"` and `"unmute"` messages.
+    Mute { muted: bool },
+    /// Switch to a server, or automatically choose one if `null`.
+    SetServer {
+        #[serde(default, rename = "serverId")]
+        server_id: Option<ServerId>,
+    },
+    /// Change [`CommonSettings::volume`], from 0 to 1.
+    SetVolume { volume: f32 },
+    /// Any other type, e.g. from a newer parent.
+    #[serde(other)]
+    Unknown,
+}
+
+/// Parses a structured `message` from the parent window. Returns `None` for bare string
+/// messages and malformed ones.
+pub(crate) fn parent_message(message: &str) -> Option<ParentMessage> {
+    serde_json::from_str(message).ok()
+}
+
+/// Builds the chat request for a [`ParentMessage::Chat`] `message`. Messages that are empty, too
+/// long, or sent while not `connected` are rejected, with the reason.
+pub(crate) fn parent_chat_request(
+    message: String,
+    connected: bool,
+) -> Result<ChatRequest, &'static str> {
+    if !connected {
+        Err("disconnected")
+    } else if message.trim().is_empty() || message.chars().count() > PARENT_CHAT_MAX_CHARS {
+        Err("invalid")
+    } else {
+        Ok(ChatRequest::Send {
+            message,
+            whisper: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::frontend::{
+        parent_chat_request, parent_message, ParentMessage, PARENT_CHAT_MAX_CHARS,
+    };
+    use core_protocol::id::ServerId;
+    use core_protocol::rpc::ChatRequest;
+    use std::num::NonZeroU8;
+
+    #[test]
+    fn parent_chat() {
+        let valid = r#"{"type":"chat","message":"Event starting!"}"#;
+        let message = match parent_message(valid) {
+            Some(ParentMessage::Chat { message }) => message,
+            other => panic!("{:?}", other),
+        };
+        assert!(matches!(
+            parent_chat_request(message.clone(), true),
+            Ok(ChatRequest::Send { message, whisper: false }) if message == "Event starting!"
+        ));
+
+        // Disconnected.
+        assert_eq!(
+            parent_chat_request(message, false).unwrap_err(),
+            "disconnected"
+        );
+
+        // Oversized or empty.
+        let oversized = "a".repeat(PARENT_CHAT_MAX_CHARS + 1);
+        assert_eq!(parent_chat_request(oversized, true).unwrap_err(), "invalid");
+        assert!(parent_chat_request("  ".to_owned(), true).is_err());
+
+        // Not chat.
+        assert!(parent_message("snippetLoaded").is_none());
+    }
+
+    #[test]
+    fn parent_log_level_message() {
+        assert_eq!(
+            parent_message(r#"{"type":"logLevel","level":"warn"}"#),
+            Some(ParentMessage::LogLevel {
+                level: "warn".to_owned()
+            })
+        );
+        assert!(parent_message(r#"{"type":"logLevel"}"#).is_none());
+    }
+
+    #[test]
+    fn parent_commands() {
+        assert_eq!(
+            parent_message(r#"{"type":"mute","muted":true}"#),
+            Some(ParentMessage::Mute { muted: true })
+        );
+        assert_eq!(
+            parent_message(r#"{"type":"setServer","serverId":3}"#),
+            Some(ParentMessage::SetServer {
+                server_id: Some(ServerId(NonZeroU8::new(3).unwrap()))
+            })
+        );
+        assert_eq!(
+            parent_message(r#"{"type":"setServer"}"#),
+            Some(ParentMessage::SetServer { server_id: None })
+        );
+        assert_eq!(
+            parent_message(r#"{"type":"setVolume","volume":0.5}"#),
+            Some(ParentMessage::SetVolume { volume: 0.5 })
+        );
+
+        // Bare strings are handled elsewhere.
+        assert!(parent_message("mute").is_none());
+        assert_eq!(
+            parent_message(r#"{"type":"launchMissiles"}"#),
+            Some(ParentMessage::Unknown)
+        );
+        assert!(parent_message(r#"{"type":"setVolume","volume":"loud"}"#).is_none());
     }
 }
@@ -8,7 +8,7 @@
 #![feature(pattern)]
 #![feature(array_try_map)]
 
-mod canvas;
+pub mod canvas;
 pub mod component;
 pub mod dialog;
 mod error_tracer;
@@ -16,32 +16,42 @@ pub mod event;
 pub mod frontend;
 mod keyboard;
 pub mod overlay;
+pub mod settings_json;
+pub mod theme;
 pub mod translation;
 pub mod window;
 
 use crate::canvas::Canvas;
+use crate::dialog::leaderboard_dialog::LeaderboardDialog;
 use crate::dialog::privacy_dialog::PrivacyDialog;
 use crate::dialog::terms_dialog::TermsDialog;
 use crate::error_tracer::ErrorTracer;
-use crate::frontend::post_message;
+use crate::frontend::{
+    parent_chat_request, parent_message, post_message, ParentMessage, PARENT_MESSAGE_MAX_BYTES,
+};
 use crate::overlay::fatal_error::FatalError;
+use crate::overlay::motd::MotdOverlay;
 use crate::overlay::reconnecting::Reconnecting;
 use crate::window::event_listener::WindowEventListener;
 use client_util::browser_storage::BrowserStorages;
 use client_util::context::WeakCoreState;
 use client_util::game_client::GameClient;
 use client_util::infrastructure::Infrastructure;
+use client_util::rate_limiter::RateLimiter;
+use client_util::reconn_web_socket::ReconnectStatus;
 use client_util::setting::CommonSettings;
 use core_protocol::id::{InvitationId, ServerId};
 use core_protocol::name::Referrer;
-use core_protocol::rpc::{ChatRequest, PlayerRequest, Request, TeamRequest};
+use core_protocol::rpc::{ChatRequest, InvitationRequest, PlayerRequest, Request, TeamRequest};
 use engine_macros::SmolRoutable;
 use frontend::{Ctw, Gctw, PropertiesWrapper, Yew};
+use gloo::timers::callback::Interval;
 use gloo_render::{request_animation_frame, AnimationFrame};
-use js_hooks::console_log;
+use js_hooks::{console_debug, console_error, console_warn, set_log_level, window, LogLevel};
 use keyboard::KeyboardEventsListener;
 use std::marker::PhantomData;
 use std::num::NonZeroU8;
+use std::str::FromStr;
 use stylist::{global_style, GlobalStyle};
 use wasm_bindgen::JsValue;
 use wasm_bindgen_futures::future_to_promise;
@@ -63,7 +73,20 @@ where
     recreating_canvas: RecreatingCanvas,
     /// Whether outbound links are enabled.
     outbound_enabled: bool,
-    _animation_frame: AnimationFrame,
+    /// Shown while the game websocket is reconnecting.
+    reconnect_status: Option<ReconnectStatus>,
+    /// Whether in-game overlays are shown, as opposed to hidden by
+    /// [`GameAction::ToggleHud`][`client_util::key_bindings::GameAction::ToggleHud`] to leave
+    /// only the game (see [`Ctw::hud_visible`]).
+    hud_visible: bool,
+    /// Throttles frames to [`CommonSettings::fps_limit`].
+    fps_limiter: RateLimiter,
+    /// Time of the last [`AppMsg::Frame`], in seconds.
+    last_frame_seconds: Option<f32>,
+    /// `None` while the page is hidden, to save CPU.
+    animation_frame: Option<AnimationFrame>,
+    /// Processes network messages while the page is hidden, in lieu of frames.
+    hidden_interval: Option<Interval>,
     _keyboard_events_listener: KeyboardEventsListener,
     _visibility_listener: WindowEventListener<Event>,
     /// Message from parent window.
@@ -71,6 +94,10 @@ where
     _context_menu_inhibitor: WindowEventListener<MouseEvent>,
     _error_tracer: ErrorTracer,
     _global_style: GlobalStyle,
+    /// The [`CommonSettings::ui_scale`] that [`Self::ui_scale_style`] applies.
+    ui_scale: f32,
+    /// Sets the base font size, which everything in the UI is sized relative to.
+    ui_scale_style: GlobalStyle,
     _spooky: PhantomData<(UI, R)>,
 }
 
@@ -85,6 +112,16 @@ enum RecreatingCanvas {
     Finished,
 }
 
+impl RecreatingCanvas {
+    /// Gets the state after the current step of recreation is done.
+    fn next(self) -> Self {
+        match self {
+            Self::Started => Self::Finished,
+            Self::None | Self::Finished => Self::None,
+        }
+    }
+}
+
 #[derive(Default, PartialEq, Properties)]
 struct AppProps {}
 
@@ -99,11 +136,18 @@ enum AppMsg<G: GameClient> {
     RecreateCanvasPart2,
     /// Signals just the renderer should be recreated.
     RecreateRenderer,
+    /// Reconnects the game websocket immediately, instead of waiting.
+    RetryReconnect,
     SetServerId(Option<ServerId>),
+    /// Changes the canvas resolution divisor, resizing its drawing buffer in place (without
+    /// recreating the canvas or renderer).
+    SetResolutionDivisor(NonZeroU8),
     FatalError(String),
     Frame {
         time: f64,
     },
+    /// Periodic substitute for [`AppMsg::Frame`] while the page is hidden.
+    HiddenTick,
     KeyboardFocus(FocusEvent),
     Keyboard(KeyboardEvent),
     MouseFocus(FocusEvent),
@@ -111,6 +155,7 @@ enum AppMsg<G: GameClient> {
     RawZoom(f32),
     SendChatRequest(ChatRequest),
     SendPlayerRequest(PlayerRequest),
+    SendInvitationRequest(InvitationRequest),
     SendTeamRequest(TeamRequest),
     SendUiEvent(G::UiEvent),
     SetContextMenuProps(Option<Html>),
@@ -136,6 +181,51 @@ where
         let link = ctx.link().clone();
         request_animation_frame(move |time| link.send_message(AppMsg::Frame { time }))
     }
+
+    /// How often to process network messages while the page is hidden.
+    const HIDDEN_INTERVAL_MILLIS: u32 = 1000;
+
+    pub fn create_hidden_interval(ctx: &Context<Self>) -> Interval {
+        let link = ctx.link().clone();
+        Interval::new(Self::HIDDEN_INTERVAL_MILLIS, move || {
+            link.send_message(AppMsg::HiddenTick)
+        })
+    }
+
+    /// Creates a style that scales the base font size by `ui_scale`.
+    fn create_ui_scale_style(ui_scale: f32) -> GlobalStyle {
+        let fallback = format!("{}vmin", 1.5 * ui_scale);
+        let font_size = format!("calc((7px + 0.8vmin) * {})", ui_scale);
+        global_style!(
+            r#"
+            html {
+                font-size: ${fallback};
+                font-size: ${font_size};
+            }
+        "#
+        )
+        .expect("failed to mount ui scale style")
+    }
+
+    /// Replaces [`Self::ui_scale_style`] if [`CommonSettings::ui_scale`] changed.
+    fn update_ui_scale(&mut self) {
+        if let Some(infrastructure) = self.infrastructure.as_ref() {
+            let ui_scale = infrastructure.context.common_settings.ui_scale;
+            if ui_scale != self.ui_scale {
+                self.ui_scale_style.unregister();
+                self.ui_scale_style = Self::create_ui_scale_style(ui_scale);
+                self.ui_scale = ui_scale;
+            }
+        }
+    }
+
+    /// Mutes or unmutes audio at the request of the parent window, e.g. during an ad.
+    fn set_muted_by_parent(&mut self, _muted: bool) {
+        #[cfg(feature = "audio")]
+        if let Some(infrastructure) = self.infrastructure.as_mut() {
+            infrastructure.context.audio.set_muted_by_ad(_muted);
+        }
+    }
 }
 
 impl<
@@ -163,7 +253,12 @@ where
             recreating_canvas: RecreatingCanvas::default(),
             fatal_error: None,
             outbound_enabled: true,
-            _animation_frame: Self::create_animation_frame(ctx),
+            reconnect_status: None,
+            hud_visible: true,
+            fps_limiter: RateLimiter::new(1.0 / 60.0),
+            last_frame_seconds: None,
+            animation_frame: Some(Self::create_animation_frame(ctx)),
+            hidden_interval: None,
             _keyboard_events_listener: KeyboardEventsListener::new(
                 keyboard_callback,
                 keyboard_focus_callback,
@@ -179,11 +274,22 @@ where
                 "message",
                 move |event: &MessageEvent| {
                     let data = event.data();
-                    if let Some(string) = data.as_string() {
-                        message_callback.emit(string);
+                    // Structured commands may be posted as objects instead of JSON strings.
+                    let string = data.as_string().or_else(|| {
+                        data.is_object()
+                            .then(|| js_sys::JSON::stringify(&data).ok())
+                            .flatten()
+                            .and_then(|string| string.as_string())
+                    });
+                    if let Some(string) = string {
+                        if string.len() > PARENT_MESSAGE_MAX_BYTES {
+                            console_debug!("dropping oversized message: {} bytes", string.len());
+                        } else {
+                            message_callback.emit(string);
+                        }
                     } else {
                         #[cfg(debug_assertions)]
-                        console_log!(
+                        console_debug!(
                             "invalid message type: {:?} {:?}",
                             data.js_typeof().as_string(),
                             js_sys::JSON::stringify(&data)
@@ -202,8 +308,6 @@ where
                 r#"
                 html {
                     font-family: sans-serif;
-                    font-size: 1.5vmin;
-                    font-size: calc(7px + 0.8vmin);
                 }
 
                 body {
@@ -218,9 +322,18 @@ where
                 a {
                     color: white;
                 }
+
+                .high-contrast {
+                    background-color: #000000 !important;
+                    color: white !important;
+                    font-weight: bold;
+                    text-shadow: none !important;
+                }
             "#
             )
             .expect("failed to mount global style"),
+            ui_scale: 1.0,
+            ui_scale_style: Self::create_ui_scale_style(1.0),
             _spooky: PhantomData,
         }
     }
@@ -233,6 +346,7 @@ where
                         &mut infrastructure.context.common_settings,
                         &mut infrastructure.context.browser_storages,
                     );
+                    self.update_ui_scale();
                     // Just in case.
                     return true;
                 }
@@ -250,48 +364,121 @@ where
             AppMsg::CreateInfrastructure(infrastructure) => {
                 assert!(self.infrastructure.is_none());
                 self.infrastructure = Some(*infrastructure);
+                // Settings, such as the resolution divisor, are now available.
+                self.update_ui_scale();
+                return true;
             }
             AppMsg::RecreateCanvas => {
                 self.recreating_canvas = RecreatingCanvas::Started;
-                console_log!("started recreating canvas");
+                console_debug!("started recreating canvas");
                 return true;
             }
             AppMsg::RecreateCanvasPart2 => {
-                self.recreating_canvas = RecreatingCanvas::Finished;
-                console_log!("finished recreating canvas");
+                self.recreating_canvas = self.recreating_canvas.next();
+                console_debug!("finished recreating canvas");
                 return true;
             }
             AppMsg::RecreateRenderer => {
-                self.recreating_canvas = RecreatingCanvas::None;
+                self.recreating_canvas = self.recreating_canvas.next();
                 if let Some(infrastructure) = self.infrastructure.as_mut() {
                     if let Err(e) = infrastructure.recreate_renderer() {
-                        console_log!("could not recreate renderer: {}", e);
+                        console_error!("could not recreate renderer: {}", e);
                     } else {
-                        console_log!("finished recreating renderer");
+                        console_debug!("finished recreating renderer");
                     }
                 }
                 return true;
             }
+            AppMsg::RetryReconnect => {
+                if let Some(infrastructure) = self.infrastructure.as_mut() {
+                    infrastructure.context.socket.retry_now();
+                }
+            }
             AppMsg::SetServerId(server_id) => {
                 if let Some(infrastructure) = self.infrastructure.as_mut() {
                     infrastructure.choose_server_id(server_id);
                 }
             }
+            AppMsg::SetResolutionDivisor(resolution_divisor) => {
+                if let Some(infrastructure) = self.infrastructure.as_mut() {
+                    let common_settings = &mut infrastructure.context.common_settings;
+                    if resolution_divisor != common_settings.canvas_resolution_divisor() {
+                        common_settings.set_resolution_divisor(
+                            resolution_divisor.get(),
+                            &mut infrastructure.context.browser_storages,
+                        );
+                        return true;
+                    }
+                }
+            }
             AppMsg::FatalError(e) => {
                 self.fatal_error = Some(e);
                 return true;
             }
             AppMsg::Frame { time } => {
+                let time_seconds = (time * 0.001) as f32;
+                let elapsed_seconds = self
+                    .last_frame_seconds
+                    .map_or(0.0, |last| (time_seconds - last).max(0.0));
+                self.last_frame_seconds = Some(time_seconds);
+
                 if self.recreating_canvas != RecreatingCanvas::Started {
                     if let Some(infrastructure) = self.infrastructure.as_mut() {
-                        infrastructure.frame((time * 0.001) as f32);
+                        // Input events are handled as they arrive, regardless of the limit.
+                        let ready = match infrastructure.context.common_settings.fps_limit {
+                            Some(fps_limit) if fps_limit > 0 => {
+                                self.fps_limiter.set_period(1.0 / fps_limit as f32);
+                                self.fps_limiter
+                                    .update_ready_with_tolerance(elapsed_seconds, 0.1)
+                            }
+                            _ => true,
+                        };
+                        if ready {
+                            infrastructure.frame(time_seconds);
+                        }
                     }
                 }
-                self._animation_frame = Self::create_animation_frame(ctx);
+                if self.hidden_interval.is_none() {
+                    self.animation_frame = Some(Self::create_animation_frame(ctx));
+                }
+
+                // Only re-render when the countdown changes.
+                let reconnect_status = self
+                    .infrastructure
+                    .as_ref()
+                    .and_then(|i| i.context.socket.reconnect_status());
+                if reconnect_status != self.reconnect_status {
+                    self.reconnect_status = reconnect_status;
+                    return true;
+                }
+            }
+            AppMsg::HiddenTick => {
+                if let Some(infrastructure) = self.infrastructure.as_mut() {
+                    let time_seconds = window().performance().map_or_else(
+                        // Unavailable in some embedded browsers, so estimate from the interval.
+                        || {
+                            self.last_frame_seconds.unwrap_or(0.0)
+                                + Self::HIDDEN_INTERVAL_MILLIS as f32 * 0.001
+                        },
+                        |performance| (performance.now() * 0.001) as f32,
+                    );
+                    // Same clock as frames, so the next frame's elapsed time is still valid.
+                    self.last_frame_seconds = Some(time_seconds);
+                    infrastructure.update_network(time_seconds);
+                }
             }
             AppMsg::Keyboard(event) => {
                 if let Some(infrastructure) = self.infrastructure.as_mut() {
-                    infrastructure.keyboard(event);
+                    let muted = infrastructure.context.common_settings.muted;
+                    if infrastructure.keyboard(event) {
+                        self.hud_visible = !self.hud_visible;
+                        if !self.hud_visible {
+                            self.context_menu = None;
+                        }
+                        return true;
+                    }
+                    // The mute key changes the volume icon.
+                    return infrastructure.context.common_settings.muted != muted;
                 }
             }
             AppMsg::KeyboardFocus(event) => {
@@ -328,6 +515,11 @@ where
                     infrastructure.send_request(Request::Player(request));
                 }
             }
+            AppMsg::SendInvitationRequest(request) => {
+                if let Some(infrastructure) = self.infrastructure.as_mut() {
+                    infrastructure.send_request(Request::Invitation(request));
+                }
+            }
             AppMsg::SendTeamRequest(request) => {
                 if let Some(infrastructure) = self.infrastructure.as_mut() {
                     infrastructure.send_request(Request::Team(request));
@@ -355,10 +547,17 @@ where
             AppMsg::VisibilityChange(event) => {
                 if let Some(infrastructure) = self.infrastructure.as_mut() {
                     infrastructure.visibility_change(event);
+                    if infrastructure.context.visibility.is_hidden() {
+                        // Cancels the pending frame.
+                        self.animation_frame = None;
+                        self.hidden_interval = Some(Self::create_hidden_interval(ctx));
+                    } else if self.hidden_interval.take().is_some() {
+                        self.animation_frame = Some(Self::create_animation_frame(ctx));
+                    }
                 }
             }
             AppMsg::Message(message) => {
-                console_log!("received message: {}", message);
+                console_debug!("received message: {}", message);
                 match message.as_str() {
                     "snippetLoaded" => {
                         post_message("gameLoaded");
@@ -371,19 +570,50 @@ where
                         self.outbound_enabled = false;
                         return true;
                     }
-                    #[cfg(feature = "audio")]
-                    "mute" => {
-                        if let Some(infrastructure) = self.infrastructure.as_mut() {
-                            infrastructure.context.audio.set_muted_by_ad(true);
+                    "mute" => self.set_muted_by_parent(true),
+                    "unmute" => self.set_muted_by_parent(false),
+                    _ => match parent_message(&message) {
+                        Some(ParentMessage::Chat { message }) => {
+                            let connected = self
+                                .infrastructure
+                                .as_ref()
+                                .map(|infrastructure| infrastructure.context.socket.is_open())
+                                .unwrap_or(false);
+                            match parent_chat_request(message, connected) {
+                                Ok(request) => {
+                                    ctx.link().send_message(AppMsg::SendChatRequest(request));
+                                }
+                                Err(reason) => {
+                                    console_warn!("dropping chat from parent: {}", reason);
+                                }
+                            }
                         }
-                    }
-                    #[cfg(feature = "audio")]
-                    "unmute" => {
-                        if let Some(infrastructure) = self.infrastructure.as_mut() {
-                            infrastructure.context.audio.set_muted_by_ad(false);
+                        Some(ParentMessage::LogLevel { level }) => {
+                            if let Ok(level) = LogLevel::from_str(&level) {
+                                set_log_level(level);
+                            }
                         }
-                    }
-                    _ => {}
+                        Some(ParentMessage::Mute { muted }) => {
+                            self.set_muted_by_parent(muted);
+                        }
+                        Some(ParentMessage::SetServer { server_id }) => {
+                            ctx.link().send_message(AppMsg::SetServerId(server_id));
+                        }
+                        Some(ParentMessage::SetVolume { volume }) => {
+                            ctx.link()
+                                    .send_message(AppMsg::ChangeCommonSettings(Box::new(
+                                        move |common_settings: &mut CommonSettings,
+                                              browser_storages: &mut BrowserStorages| {
+                                            common_settings.set_volume(volume, browser_storages);
+                                        },
+                                    )));
+                        }
+                        Some(ParentMessage::Unknown) => {
+                            #[cfg(debug_assertions)]
+                            console_debug!("unknown message type: {}", message);
+                        }
+                        None => {}
+                    },
                 }
             }
             AppMsg::Wheel(event) => {
@@ -399,9 +629,11 @@ where
         let change_common_settings_callback = ctx.link().callback(AppMsg::ChangeCommonSettings);
         let change_settings_callback = ctx.link().callback(AppMsg::ChangeSettings);
         let chat_request_callback = ctx.link().callback(AppMsg::SendChatRequest);
+        let invitation_request_callback = ctx.link().callback(AppMsg::SendInvitationRequest);
         let player_request_callback = ctx.link().callback(AppMsg::SendPlayerRequest);
         let raw_zoom_callback = ctx.link().callback(AppMsg::RawZoom);
         let recreate_renderer_callback = ctx.link().callback(|_| AppMsg::RecreateCanvas);
+        let set_resolution_divisor_callback = ctx.link().callback(AppMsg::SetResolutionDivisor);
         let set_server_id_callback = ctx.link().callback(AppMsg::SetServerId);
         let send_ui_event_callback = ctx.link().callback(AppMsg::SendUiEvent);
         let set_context_menu_callback = ctx.link().callback(AppMsg::SetContextMenuProps);
@@ -418,12 +650,16 @@ where
             chat_request_callback,
             change_common_settings_callback,
             game_id: G::GAME_ID,
+            game_version: G::VERSION,
+            hud_visible: self.hud_visible,
+            invitation_request_callback,
             outbound_enabled: self.outbound_enabled,
             player_request_callback,
             raw_zoom_callback,
-            recreate_renderer_callback,
+            recreate_renderer_callback: recreate_renderer_callback.clone(),
             set_server_id_callback,
             set_context_menu_callback,
+            set_resolution_divisor_callback,
             routes,
             setting_cache: self
                 .infrastructure
@@ -446,6 +682,7 @@ where
                 .map(|i| i.context.settings.clone())
                 .unwrap_or_default(),
             change_settings_callback,
+            recreate_renderer_callback,
         };
 
         html! {
@@ -454,7 +691,7 @@ where
                     <ContextProvider<Gctw<G>> context={game_context}>
                         if self.recreating_canvas != RecreatingCanvas::Started {
                             <Canvas
-                                resolution_divisor={NonZeroU8::new(1).unwrap()}
+                                resolution_divisor={self.infrastructure.as_ref().map(|i| i.context.common_settings.canvas_resolution_divisor()).unwrap_or(NonZeroU8::new(1).unwrap())}
                                 mouse_callback={ctx.link().callback(AppMsg::Mouse)}
                                 touch_callback={ctx.link().callback(AppMsg::Touch)}
                                 focus_callback={ctx.link().callback(AppMsg::MouseFocus)}
@@ -467,13 +704,17 @@ where
                             <FatalError message={message.to_owned()}/>
                         } else {
                             <>
+                                // Hides its own in-game overlays, but not menus (see `Ctw::hud_visible`).
                                 <UI props={self.ui_props.clone()}/>
-                                <Switch<Route> render={Switch::render(switch)}/>
-                                if let Some(context_menu) = self.context_menu.as_ref() {
+                                if self.hud_visible {
+                                    <MotdOverlay/>
+                                }
+                                <Switch<Route> render={Switch::render(switch::<G>)}/>
+                                if let Some(context_menu) = self.context_menu.as_ref().filter(|_| self.hud_visible) {
                                     {context_menu.clone()}
                                 }
-                                if self.infrastructure.as_ref().map(|i| i.context.socket.is_reconnecting()).unwrap_or_default() {
-                                    <Reconnecting/>
+                                if let Some(status) = self.reconnect_status {
+                                    <Reconnecting {status} on_retry={ctx.link().callback(|_| AppMsg::RetryReconnect)}/>
                                 }
                             </>
                         }
@@ -521,10 +762,14 @@ where
 pub enum Route {
     #[at("/invite/:invitation_id/")]
     Invitation { invitation_id: InvitationId },
+    #[at("/leaderboard/")]
+    Leaderboard,
     #[at("/referrer/:referrer/")]
     Referrer { referrer: Referrer },
     #[at("/privacy/")]
     Privacy,
+    #[at("/spectate/")]
+    Spectate,
     #[at("/terms/")]
     Terms,
     #[not_found]
@@ -532,9 +777,26 @@ pub enum Route {
     Home,
 }
 
-fn switch(routes: &Route) -> Html {
+/// Consults [`GameClient::switch_route`] before falling back to the engine's default for `route`.
+fn switch<G: GameClient>(route: &Route) -> Html {
+    let path = web_sys::window()
+        .and_then(|w| w.location().pathname().ok())
+        .unwrap_or_default();
+    or_default_switch(G::switch_route(&path), route)
+}
+
+fn or_default_switch(custom: Option<Html>, route: &Route) -> Html {
+    custom.unwrap_or_else(|| default_switch(route))
+}
+
+fn default_switch(routes: &Route) -> Html {
     match routes {
-        Route::Home | Route::Invitation { .. } | Route::Referrer { .. } => html! {},
+        Route::Home | Route::Invitation { .. } | Route::Referrer { .. } | Route::Spectate => {
+            html! {}
+        }
+        Route::Leaderboard => html! {
+            <LeaderboardDialog/>
+        },
         Route::Privacy => html! {
             <PrivacyDialog/>
         },
@@ -543,3 +805,40 @@ fn switch(routes: &Route) -> Html {
         },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{default_switch, or_default_switch, RecreatingCanvas, Route};
+    use yew::html;
+
+    #[test]
+    fn switch_route() {
+        let custom = or_default_switch(
+            Some(html! {
+                <p>{"custom"}</p>
+            }),
+            &Route::Home,
+        );
+        assert_eq!(
+            custom,
+            html! {
+                <p>{"custom"}</p>
+            }
+        );
+        assert_ne!(custom, default_switch(&Route::Home));
+
+        // Falls back to the engine default.
+        assert_eq!(
+            or_default_switch(None, &Route::Terms),
+            default_switch(&Route::Terms)
+        );
+    }
+
+    #[test]
+    fn recreating_canvas_next() {
+        let started = RecreatingCanvas::Started;
+        assert!(started.next() == RecreatingCanvas::Finished);
+        assert!(started.next().next() == RecreatingCanvas::None);
+        assert!(RecreatingCanvas::None.next() == RecreatingCanvas::None);
+    }
+}
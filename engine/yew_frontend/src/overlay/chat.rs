@@ -14,10 +14,10 @@ use core_protocol::rpc::{ChatRequest, PlayerRequest};
 use std::ops::Deref;
 use std::str::pattern::Pattern;
 use stylist::yew::styled_component;
-use web_sys::{window, HtmlInputElement, InputEvent, KeyboardEvent, MouseEvent};
+use web_sys::{window, Element, Event, HtmlInputElement, InputEvent, KeyboardEvent, MouseEvent};
 use yew::{
-    classes, html, html_nested, use_effect_with_deps, use_node_ref, use_state, Callback, Html,
-    Properties,
+    classes, html, html_nested, use_effect_with_deps, use_node_ref, use_state, use_state_eq,
+    Callback, Html, Properties,
 };
 
 #[derive(PartialEq, Properties)]
@@ -78,6 +78,27 @@ pub fn chat_overlay(props: &ChatProps) -> Html {
         "#
     );
 
+    let messages_css_class = css!(
+        r#"
+        max-height: 40vh;
+        overflow-y: auto;
+        pointer-events: all;
+        "#
+    );
+
+    let jump_css_class = css!(
+        r#"
+        background-color: #00000040;
+        border: 0;
+        border-radius: 0.25em;
+        color: white;
+        cursor: pointer;
+        font-size: 0.8rem;
+        pointer-events: all;
+        width: 100%;
+        "#
+    );
+
     let input_css_class = css!(
         r#"
         border-radius: 0.25em;
@@ -98,6 +119,7 @@ pub fn chat_overlay(props: &ChatProps) -> Html {
     );
 
     let ctw = Ctw::use_ctw();
+    let overlay_class = Ctw::use_theme().overlay_class();
 
     let on_open_changed = ctw.change_common_settings_callback.reform(|open| {
         Box::new(
@@ -176,12 +198,89 @@ pub fn chat_overlay(props: &ChatProps) -> Html {
     }
 
     let core_state = Ctw::use_core_state();
+    let messages_ref = use_node_ref();
+    // Whether to follow new messages, i.e. the player hasn't scrolled up.
+    let at_bottom = use_state_eq(|| true);
+    // Messages arrived while scrolled up.
+    let unseen = use_state_eq(|| false);
+
+    let onscroll = {
+        let at_bottom = at_bottom.clone();
+        let unseen = unseen.clone();
+
+        move |event: Event| {
+            let element: Element = event_target(&event);
+            let bottom = is_scrolled_to_bottom(&element);
+            if bottom {
+                unseen.set(false);
+            }
+            at_bottom.set(bottom);
+        }
+    };
+
+    let onclick_jump = {
+        let messages_ref = messages_ref.clone();
+
+        move |_: MouseEvent| {
+            if let Some(element) = messages_ref.cast::<Element>() {
+                scroll_to_bottom(&element);
+            }
+        }
+    };
+
+    // Scrollback starts at the latest messages when the chat is opened.
+    {
+        let messages_ref = messages_ref.clone();
+        let at_bottom = at_bottom.clone();
+        let unseen = unseen.clone();
+
+        use_effect_with_deps(
+            move |&open| {
+                if open {
+                    if let Some(element) = messages_ref.cast::<Element>() {
+                        scroll_to_bottom(&element);
+                    }
+                    at_bottom.set(true);
+                    unseen.set(false);
+                }
+                || ()
+            },
+            ctw.setting_cache.chat_dialog_shown,
+        );
+    }
+
+    // Follow new messages, unless scrolled up.
+    {
+        let messages_ref = messages_ref.clone();
+        let at_bottom = *at_bottom;
+        let unseen = unseen.clone();
+        let latest = core_state
+            .messages
+            .recent()
+            .map(|m| (m.date_sent, m.player_id));
+
+        use_effect_with_deps(
+            move |latest| {
+                if let Some(element) = messages_ref.cast::<Element>() {
+                    if at_bottom {
+                        scroll_to_bottom(&element);
+                    } else if latest.is_some() {
+                        unseen.set(true);
+                    }
+                }
+                || ()
+            },
+            latest,
+        );
+    }
+
     let (mention_string, moderator) = core_state
         .player()
         .map(|p| (format!("@{}", p.alias), p.moderator))
         .unwrap_or((String::from("PLACEHOLDER"), false));
 
-    let items = core_state.messages.oldest_ordered().map(|dto| {
+    let streamer_mode = ctw.setting_cache.streamer();
+    let items = core_state.messages.oldest_ordered().filter(|dto| !streamer_mode.hides_message(dto)).map(|dto| {
         let onclick_reply = {
             let at_alias = format!("@{} ", dto.alias).to_string();
             let message = message.clone();
@@ -265,7 +364,7 @@ pub fn chat_overlay(props: &ChatProps) -> Html {
                     {dto.team_name.map(|team_name| format!("[{}] {}", team_name, dto.alias)).unwrap_or(dto.alias.to_string())}
                 </span>
                 <span class={no_select_style.clone()}>{" "}</span>
-                {segments(&dto.text, &mention_string).map(|Segment{contents, mention}| html_nested!{
+                {segments(&streamer_mode.mask_invitation_links(&dto.text), &mention_string).map(|Segment{contents, mention}| html_nested!{
                     <span class={classes!(mention.then(|| mention_style.clone()))}>{contents.to_owned()}</span>
                 }).collect::<Html>()}
             </p>
@@ -282,7 +381,12 @@ pub fn chat_overlay(props: &ChatProps) -> Html {
 
     html! {
         <Section name={(props.label)(t)} open={ctw.setting_cache.chat_dialog_shown} {on_open_changed}>
-            {items}
+            <div class={messages_css_class} ref={messages_ref} {onscroll}>
+                {items}
+            </div>
+            if *unseen && !*at_bottom {
+                <button class={classes!(jump_css_class, overlay_class)} onclick={onclick_jump}>{"Jump to latest ↓"}</button>
+            }
             if let Some(help_hint) = help_hint {
                 <p><b>{"Automated help: "}{help_hint}</b></p>
             }
@@ -297,13 +401,22 @@ pub fn chat_overlay(props: &ChatProps) -> Html {
                 maxLength="128"
                 value={message.deref().clone()}
                 placeholder={t.chat_send_message_placeholder()}
-                class={input_css_class.clone()}
+                class={classes!(input_css_class.clone(), overlay_class)}
                 ref={input_ref}
             />
         </Section>
     }
 }
 
+/// Whether `element` is scrolled to the bottom, give or take rounding.
+fn is_scrolled_to_bottom(element: &Element) -> bool {
+    element.scroll_top() + element.client_height() >= element.scroll_height() - 2
+}
+
+fn scroll_to_bottom(element: &Element) {
+    element.set_scroll_top(element.scroll_height());
+}
+
 fn help_hint_of(props: &ChatProps, text: &str) -> Option<&'static str> {
     let text = text.to_ascii_lowercase();
     if text.find("/invite").is_some() {
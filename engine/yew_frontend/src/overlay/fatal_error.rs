@@ -2,13 +2,19 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
 use crate::component::positioner::{Position, Positioner};
+use crate::component::spinner::Spinner;
+use crate::frontend::Ctw;
 use crate::translation::{t, Translation};
+use gloo::timers::callback::Timeout;
+use std::rc::Rc;
 use stylist::yew::styled_component;
 use wasm_bindgen::{JsCast, JsValue};
 use wasm_bindgen_futures::{future_to_promise, JsFuture};
 use web_sys::{window, Request, RequestInit, RequestMode, Response};
 use yew::virtual_dom::AttrValue;
-use yew::{classes, html, Properties};
+use yew::{
+    classes, html, use_node_ref, use_reducer, use_state, Callback, NodeRef, Properties, Reducible,
+};
 
 #[derive(Properties, PartialEq)]
 pub struct FatalErrorProps {
@@ -65,40 +71,219 @@ pub fn fatal_error(props: &FatalErrorProps) -> Html {
         "#
     };
 
+    let overlay_class = Ctw::use_theme().overlay_class();
+    let refresh_state = use_reducer(RefreshState::default);
+
     // Refresh the page, which serves two purposes:
     // - The server may have restarted, so might need to download new client
     // - The refreshed client will attempt to regain connection
-    let refresh = |_| {
-        let _ = future_to_promise(async {
-            // Do a pre-flight request to make sure we aren't refreshing ourselves into a browser error.
-            let mut opts = RequestInit::new();
-            opts.method("GET");
-            opts.mode(RequestMode::Cors);
-
-            let request = match Request::new_with_str_and_init("/", &opts) {
-                Ok(request) => request,
-                Err(_) => return Err(JsValue::NULL),
-            };
-            let window = window().unwrap();
-            let response_value = match JsFuture::from(window.fetch_with_request(&request)).await {
-                Ok(response_value) => response_value,
-                Err(_) => return Err(JsValue::NULL),
-            };
-            let response: Response = match response_value.dyn_into() {
-                Ok(response) => response,
-                Err(_) => return Err(JsValue::NULL),
+    let refresh = {
+        let dispatcher = refresh_state.dispatcher();
+        Callback::from(move |_| {
+            dispatcher.dispatch(RefreshAction::Click);
+
+            let cooldown_dispatcher = dispatcher.clone();
+            Timeout::new(RefreshState::COOLDOWN_MILLIS, move || {
+                cooldown_dispatcher.dispatch(RefreshAction::CooldownElapsed)
+            })
+            .forget();
+
+            let failure_dispatcher = dispatcher.clone();
+            let _ = future_to_promise(async move {
+                if !preflight().await {
+                    failure_dispatcher.dispatch(RefreshAction::Failed);
+                }
+                Ok(JsValue::NULL)
+            });
+        })
+    };
+
+    let message = props
+        .message
+        .clone()
+        .unwrap_or(t().connection_lost_message().into());
+    let message_ref = use_node_ref();
+    let copied = use_state(|| false);
+
+    // Copy a report including the full message, which may be cut off on screen.
+    let copy = {
+        let report = error_report(
+            &message,
+            &format!("{:?} {}", Ctw::use_game_id(), Ctw::use_game_version()),
+            &window()
+                .unwrap()
+                .navigator()
+                .user_agent()
+                .unwrap_or_default(),
+        );
+        let message_ref = message_ref.clone();
+        let copied = copied.clone();
+        Callback::from(move |_| {
+            let clipboard = match window().unwrap().navigator().clipboard() {
+                Some(clipboard) => clipboard,
+                None => {
+                    select_text(&message_ref);
+                    return;
+                }
             };
-            if response.ok() {
-                let _ = window.location().reload();
-            }
-            Ok(JsValue::NULL)
-        });
+            let promise = clipboard.write_text(&report);
+            let message_ref = message_ref.clone();
+            let copied = copied.clone();
+            let _ = future_to_promise(async move {
+                if JsFuture::from(promise).await.is_ok() {
+                    copied.set(true);
+                    Timeout::new(COPIED_MILLIS, move || copied.set(false)).forget();
+                } else {
+                    select_text(&message_ref);
+                }
+                Ok(JsValue::NULL)
+            });
+        })
     };
 
     html! {
-        <Positioner id="fatal_error" position={Position::Center} class={classes!(container_style)}>
-            <p class={p_css}>{props.message.clone().unwrap_or(t().connection_lost_message().into())}</p>
-            <button onclick={refresh} class={button_css}>{"Refresh"}</button>
+        <Positioner id="fatal_error" position={Position::Center} class={classes!(container_style, overlay_class)}>
+            <p ref={message_ref} class={p_css}>{message}</p>
+            if refresh_state.in_flight {
+                <Spinner/>
+            }
+            <button onclick={refresh} disabled={refresh_state.disabled()} class={button_css.clone()}>{"Refresh"}</button>
+            <button onclick={copy} class={button_css}>{if *copied { "Copied!" } else { "Copy Error" }}</button>
         </Positioner>
     }
 }
+
+/// How long the copy button says it copied.
+const COPIED_MILLIS: u32 = 2000;
+
+/// Formats an error `message` for a bug report.
+fn error_report(message: &str, version: &str, user_agent: &str) -> String {
+    format!(
+        "Error: {}\nVersion: {}\nUser agent: {}",
+        message, version, user_agent
+    )
+}
+
+/// Selects the text of an element, so the player can copy it manually (if the clipboard is
+/// unavailable).
+fn select_text(node_ref: &NodeRef) {
+    if let Some((selection, node)) = window()
+        .unwrap()
+        .get_selection()
+        .ok()
+        .flatten()
+        .zip(node_ref.get())
+    {
+        let _ = selection.select_all_children(&node);
+    }
+}
+
+/// Does a pre-flight request to make sure we aren't refreshing ourselves into a browser error,
+/// and if it succeeds, refreshes. Returns whether it succeeded.
+async fn preflight() -> bool {
+    let mut opts = RequestInit::new();
+    opts.method("GET");
+    opts.mode(RequestMode::Cors);
+
+    let request = match Request::new_with_str_and_init("/", &opts) {
+        Ok(request) => request,
+        Err(_) => return false,
+    };
+    let window = window().unwrap();
+    let response_value = match JsFuture::from(window.fetch_with_request(&request)).await {
+        Ok(response_value) => response_value,
+        Err(_) => return false,
+    };
+    let response: Response = match response_value.dyn_into() {
+        Ok(response) => response,
+        Err(_) => return false,
+    };
+    if response.ok() {
+        let _ = window.location().reload();
+    }
+    response.ok()
+}
+
+/// State of the refresh button, which is rate limited to prevent a storm of pre-flight requests
+/// while the server is down.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+struct RefreshState {
+    /// A pre-flight request is in progress.
+    in_flight: bool,
+    /// The button was clicked recently.
+    cooling_down: bool,
+}
+
+enum RefreshAction {
+    Click,
+    CooldownElapsed,
+    /// The pre-flight request failed.
+    Failed,
+}
+
+impl RefreshState {
+    /// How long the button is disabled after being clicked.
+    const COOLDOWN_MILLIS: u32 = 2000;
+
+    fn disabled(&self) -> bool {
+        self.in_flight || self.cooling_down
+    }
+}
+
+impl Reducible for RefreshState {
+    type Action = RefreshAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut state = *self;
+        match action {
+            RefreshAction::Click => {
+                state.in_flight = true;
+                state.cooling_down = true;
+            }
+            RefreshAction::CooldownElapsed => state.cooling_down = false,
+            RefreshAction::Failed => state.in_flight = false,
+        }
+        Rc::new(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::overlay::fatal_error::{error_report, RefreshAction, RefreshState};
+    use std::rc::Rc;
+    use yew::Reducible;
+
+    fn reduce(state: RefreshState, action: RefreshAction) -> RefreshState {
+        *Rc::new(state).reduce(action)
+    }
+
+    #[test]
+    fn refresh_cooldown() {
+        let ready = RefreshState::default();
+        assert!(!ready.disabled());
+
+        let clicked = reduce(ready, RefreshAction::Click);
+        assert!(clicked.disabled());
+        assert!(clicked.in_flight);
+
+        // Failing early doesn't re-enable the button until the cooldown elapses.
+        let failed = reduce(clicked, RefreshAction::Failed);
+        assert!(failed.disabled());
+        assert!(!failed.in_flight);
+        assert!(!reduce(failed, RefreshAction::CooldownElapsed).disabled());
+
+        // Still disabled while the request is in flight, even after the cooldown.
+        let slow = reduce(clicked, RefreshAction::CooldownElapsed);
+        assert!(slow.disabled());
+        assert!(!reduce(slow, RefreshAction::Failed).disabled());
+    }
+
+    #[test]
+    fn report() {
+        let report = error_report("Out of memory", "Mk48 0.1.0", "Mozilla/5.0");
+        assert_eq!(
+            report,
+            "Error: Out of memory\nVersion: Mk48 0.1.0\nUser agent: Mozilla/5.0"
+        );
+    }
+}
@@ -131,6 +131,7 @@ pub fn leaderboard_overlay(props: &LeaderboardProps) -> Html {
                         LiveboardDto {
                             player_id: player.player_id,
                             score,
+                            kills: 0,
                             team_captain: player.team_captain,
                             team_id: player.team_id,
                         },
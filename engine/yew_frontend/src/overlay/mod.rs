@@ -4,6 +4,7 @@
 pub mod chat;
 pub mod fatal_error;
 pub mod leaderboard;
+pub(crate) mod motd;
 pub(crate) mod reconnecting;
 pub mod spawn;
 pub mod team;
@@ -0,0 +1,81 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::component::fade::Fade;
+use crate::component::positioner::{Position, Positioner};
+use crate::component::x_button::XButton;
+use crate::theme::Theme;
+use crate::Ctw;
+use stylist::yew::styled_component;
+use web_sys::MouseEvent;
+use yew::{classes, html, Callback, Html};
+
+/// Returns if the message of the day (`version`, `text`) should be shown, given the version that
+/// was last dismissed, if any. Each version is only shown until dismissed, and empty ones never are.
+pub(crate) fn is_motd_shown(version: u32, text: &str, dismissed_version: Option<u32>) -> bool {
+    !text.trim().is_empty() && dismissed_version != Some(version)
+}
+
+/// Dismissible banner with the server's message of the day, if any.
+#[styled_component(MotdOverlay)]
+pub fn motd_overlay() -> Html {
+    let ctw = Ctw::use_ctw();
+    let motd = Ctw::use_core_state().motd.clone();
+    let change_common_settings_callback = ctw.change_common_settings_callback.clone();
+
+    let (version, text) = match motd {
+        Some(motd) => motd,
+        None => return html! {},
+    };
+    // Keep rendering once dismissed, to fade out.
+    let visible = is_motd_shown(version, &text, ctw.setting_cache.dismissed_motd_version);
+
+    let on_dismiss = Callback::from(move |_: MouseEvent| {
+        change_common_settings_callback.emit(Box::new(move |common_settings, browser_storages| {
+            common_settings.set_dismissed_motd_version(Some(version), browser_storages);
+        }));
+    });
+
+    let class = css!(
+        r#"
+        align-items: center;
+        background-color: #00000040;
+        border-radius: 0.5em;
+        color: white;
+        display: flex;
+        gap: 1em;
+        padding: 0.5em 0.5em 0.5em 1em;
+        "#
+    );
+
+    let class = classes!(class, Theme::new(&ctw.setting_cache).overlay_class());
+
+    html! {
+        <Positioner position={Position::TopMiddle{margin: "6rem"}} max_width="50%">
+            <Fade {visible}>
+                <div {class}>
+                    <p style="margin: 0; white-space: pre-line;">{text.to_string()}</p>
+                    <XButton onclick={on_dismiss}/>
+                </div>
+            </Fade>
+        </Positioner>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::overlay::motd::is_motd_shown;
+
+    #[test]
+    fn motd_shown_once_per_version() {
+        assert!(is_motd_shown(1, "Welcome!", None));
+        assert!(!is_motd_shown(1, "Welcome!", Some(1)));
+
+        // A new version is shown again, even if the text is the same.
+        assert!(is_motd_shown(2, "Welcome!", Some(1)));
+
+        // Empty messages are never shown.
+        assert!(!is_motd_shown(3, "", None));
+        assert!(!is_motd_shown(3, "  \n", Some(2)));
+    }
+}
@@ -4,16 +4,59 @@
 use crate::component::curtain::Curtain;
 use crate::component::positioner::{Position, Positioner};
 use crate::component::spinner::Spinner;
+use client_util::reconn_web_socket::ReconnectStatus;
 use stylist::yew::styled_component;
-use yew::html;
+use web_sys::MouseEvent;
+use yew::{html, Callback, Properties};
+
+#[derive(Properties, PartialEq)]
+pub struct ReconnectingProps {
+    pub status: ReconnectStatus,
+    /// Attempts to reconnect immediately.
+    pub on_retry: Callback<MouseEvent>,
+}
 
 #[styled_component(Reconnecting)]
-pub fn reconnecting() -> Html {
+pub fn reconnecting(props: &ReconnectingProps) -> Html {
+    let button_style = css! {
+        r#"
+        background-color: #549f57;
+        border-radius: 0.5rem;
+        border: 1px solid #61b365;
+        color: white;
+        cursor: pointer;
+        font-size: 1.25rem;
+        padding: 0.5rem 1rem;
+        pointer-events: all;
+
+        :hover {
+            filter: brightness(0.95);
+        }
+        "#
+    };
+
+    let ReconnectStatus {
+        tries,
+        seconds_until_retry,
+    } = props.status;
+
     html! {
         <Curtain>
             <Positioner position={Position::Center}>
                 <Spinner/>
                 <p>{"Connection lost, attempting to reconnect..."}</p>
+                <p>
+                    {format!("Attempt {}", tries)}
+                    if let Some(seconds) = seconds_until_retry {
+                        {format!(", retrying in {} second{}", seconds, if seconds == 1 { "" } else { "s" })}
+                    }
+                </p>
+                if seconds_until_retry.is_some() {
+                    <button onclick={props.on_retry.clone()} class={button_style}>{"Retry Now"}</button>
+                }
+                if props.status.suggests_refresh() {
+                    <p>{"Still having trouble? Try refreshing the page."}</p>
+                }
             </Positioner>
         </Curtain>
     }
@@ -98,6 +98,7 @@ pub fn spawn_overlay(props: &DialogProps) -> Html {
     let (paused, transitioning, onanimationend) = use_splash_screen();
 
     let alias_setting = Ctw::use_ctw().setting_cache.alias;
+    let overlay_class = Ctw::use_theme().overlay_class();
     let alias = use_state(|| alias_setting.unwrap_or(PlayerAlias::new_unsanitized("")));
 
     let oninput = {
@@ -133,7 +134,7 @@ pub fn spawn_overlay(props: &DialogProps) -> Html {
     html! {
         <form id="spawn_overlay" class={form_style} style={props.position.to_string()} {onsubmit} {onanimationend}>
             {props.children.clone()}
-            <input id="alias_input" class={input_style} disabled={*transitioning} type="text" name="name" placeholder={t().splash_screen_alias_placeholder()} autocomplete="off" value={alias.to_string()} {oninput}/>
+            <input id="alias_input" class={classes!(input_style, overlay_class)} disabled={*transitioning} type="text" name="name" placeholder={t().splash_screen_alias_placeholder()} autocomplete="off" value={alias.to_string()} {oninput}/>
             <button id="play_button" class={button_style} disabled={*paused || *transitioning} {onclick}>{t().splash_screen_play_label()}</button>
             <div id="banner_bottom" style="margin: auto;"></div>
         </form>
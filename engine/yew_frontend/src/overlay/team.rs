@@ -1,6 +1,7 @@
 // SPDX-FileCopyrightText: 2021 Softbear, Inc.
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use crate::component::invitation_link::use_copy_invitation_link;
 use crate::component::section::Section;
 use crate::event::event_target;
 use crate::translation::{t, Translation};
@@ -124,6 +125,7 @@ pub fn team_overlay(props: &TeamOverlayProps) -> Html {
 
     let t = t();
     let ctw = Ctw::use_ctw();
+    let overlay_class = Ctw::use_theme().overlay_class();
     let core_state = Ctw::use_core_state();
     let team_id = core_state.team_id();
     let team = team_id.and_then(|team_id| core_state.teams.get(&team_id));
@@ -210,6 +212,13 @@ pub fn team_overlay(props: &TeamOverlayProps) -> Html {
                 })
         };
 
+    let on_copy_invitation = use_copy_invitation_link();
+    let invitation_label = if on_copy_invitation.is_some() {
+        t.invitation_label()
+    } else {
+        t.invitation_copied_label()
+    };
+
     const CHECK_MARK: &'static str = "✔";
     const X_MARK: &'static str = "✘";
 
@@ -243,6 +252,7 @@ pub fn team_overlay(props: &TeamOverlayProps) -> Html {
                         }
                     }).collect::<Html>()}
                 </table>
+                <button disabled={on_copy_invitation.is_none()} onclick={on_copy_invitation} class={button_css_class.clone()}>{invitation_label}</button>
                 <button onclick={move |_| on_leave_team()} class={button_css_class}>{t.team_leave_hint()}</button>
             } else {
                 <form onsubmit={move |e: FocusEvent| {e.prevent_default(); on_create_team();}}>
@@ -262,7 +272,7 @@ pub fn team_overlay(props: &TeamOverlayProps) -> Html {
                         }).collect::<Html>()}
                         <tr>
                             <td>
-                                <input type="text" placeholder={(props.name_placeholder)(t)} oninput={on_new_team_name_change} value={new_team_name.to_string()} class={input_css_class}/>
+                                <input type="text" placeholder={(props.name_placeholder)(t)} oninput={on_new_team_name_change} value={new_team_name.to_string()} class={classes!(input_css_class, overlay_class)}/>
                             </td>
                             <td>
                                 <button disabled={new_team_name.is_empty()} class={button_css_class}>{t.team_create_hint()}</button>
@@ -0,0 +1,95 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::frontend::{Ctw, Gctw};
+use client_util::browser_storage::BrowserStorages;
+use client_util::game_client::GameClient;
+use client_util::setting::{CommonSettings, Settings};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Exported [`CommonSettings`] and game settings, keyed by their local storage keys.
+#[derive(Default, Deserialize, Serialize)]
+struct ExportedSettings {
+    #[serde(default)]
+    common: HashMap<String, Value>,
+    #[serde(default)]
+    game: HashMap<String, Value>,
+}
+
+/// Serializes all exportable settings (see [`Settings::export`]) to a JSON string, which players
+/// can copy to another device and [`import_settings`] there.
+pub fn export_settings<S: Settings>(common: &CommonSettings, game: &S) -> String {
+    let to_map = |pairs: Vec<(&'static str, String)>| {
+        pairs
+            .into_iter()
+            .map(|(key, value)| (key.to_owned(), Value::String(value)))
+            .collect()
+    };
+    let exported = ExportedSettings {
+        common: to_map(common.export()),
+        game: to_map(game.export()),
+    };
+    serde_json::to_string(&exported).unwrap()
+}
+
+/// Parses the output of [`export_settings`] into common and game settings. Values that aren't
+/// strings are converted to them, and validation is left to [`Settings::import`].
+fn parse_settings(
+    json: &str,
+) -> Result<(HashMap<String, String>, HashMap<String, String>), String> {
+    let exported: ExportedSettings = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    let to_strings = |map: HashMap<String, Value>| {
+        map.into_iter()
+            .filter_map(|(key, value)| match value {
+                Value::String(s) => Some((key, s)),
+                Value::Null | Value::Array(_) | Value::Object(_) => None,
+                value => Some((key, value.to_string())),
+            })
+            .collect()
+    };
+    Ok((to_strings(exported.common), to_strings(exported.game)))
+}
+
+/// Applies settings exported by [`export_settings`] via the settings callbacks, ignoring unknown
+/// settings (e.g. from a different version). Returns an error if `json` is malformed.
+pub fn import_settings<G: GameClient>(json: &str, ctw: &Ctw, gctw: &Gctw<G>) -> Result<(), String> {
+    let (common, game) = parse_settings(json)?;
+    ctw.change_common_settings_callback.emit(Box::new(
+        move |settings: &mut CommonSettings, browser_storages: &mut BrowserStorages| {
+            settings.import(&common, browser_storages);
+        },
+    ));
+    gctw.change_settings_callback.emit(Box::new(
+        move |settings: &mut G::GameSettings, browser_storages: &mut BrowserStorages| {
+            settings.import(&game, browser_storages);
+        },
+    ));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::settings_json::parse_settings;
+
+    #[test]
+    fn parse() {
+        let (common, game) = parse_settings(
+            r#"{"common":{"volume":"0.25","muted":true,"future":[1]},"game":{"cinematic":"true"},"extra":1}"#,
+        )
+        .unwrap();
+        assert_eq!(common.len(), 2);
+        assert_eq!(common["volume"], "0.25");
+        // Converted to a string.
+        assert_eq!(common["muted"], "true");
+        assert_eq!(game["cinematic"], "true");
+
+        // Sections are optional.
+        let (common, game) = parse_settings("{}").unwrap();
+        assert!(common.is_empty() && game.is_empty());
+
+        assert!(parse_settings("not json").is_err());
+        assert!(parse_settings(r#"{"common":"volume"}"#).is_err());
+    }
+}
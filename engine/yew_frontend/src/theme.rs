@@ -0,0 +1,48 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use client_util::setting::CommonSettings;
+
+/// How the UI looks, according to the player's settings (see
+/// [`Ctw::use_theme`][`crate::frontend::Ctw::use_theme`]).
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct Theme {
+    /// See [`CommonSettings::high_contrast`].
+    pub high_contrast: bool,
+}
+
+impl Theme {
+    /// Class of overlays in high contrast mode, which is styled globally (see `App`) so it can
+    /// override their own styles.
+    pub(crate) const HIGH_CONTRAST_CLASS: &'static str = "high-contrast";
+
+    pub fn new(settings: &CommonSettings) -> Self {
+        Self {
+            high_contrast: settings.high_contrast,
+        }
+    }
+
+    /// Gets the class to add to an overlay with a translucent background, if any, which makes the
+    /// background opaque and the text bolder.
+    pub fn overlay_class(self) -> Option<&'static str> {
+        self.high_contrast.then_some(Self::HIGH_CONTRAST_CLASS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::theme::Theme;
+    use client_util::setting::CommonSettings;
+
+    #[test]
+    fn overlay_class() {
+        let mut settings = CommonSettings::default();
+        assert_eq!(Theme::new(&settings).overlay_class(), None);
+
+        settings.high_contrast = true;
+        assert_eq!(
+            Theme::new(&settings).overlay_class(),
+            Some(Theme::HIGH_CONTRAST_CLASS)
+        );
+    }
+}
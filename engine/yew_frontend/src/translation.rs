@@ -2,10 +2,15 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
 use crate::frontend::Ctw;
+use client_util::browser_storage::BrowserStorages;
+use client_util::setting::CommonSettings;
 use core_protocol::id::LanguageId::*;
 use core_protocol::id::{GameId, LanguageId, PeriodId};
+use yew::Callback;
 
-/// Only works in function component.
+/// Only works in function component. Components using it re-render when the language changes.
+///
+/// Outside of components, use [`CommonSettings::language`][`client_util::setting::CommonSettings::language`].
 pub fn use_translation() -> LanguageId {
     yew::use_context::<Ctw>().unwrap().setting_cache.language
 }
@@ -15,6 +20,18 @@ pub fn t() -> LanguageId {
     use_translation()
 }
 
+/// Only works in function component. Returns a callback that switches to a language immediately
+/// (without reloading), and remembers it for next time.
+pub fn use_set_language() -> Callback<LanguageId> {
+    Ctw::use_change_common_settings_callback().reform(|language: LanguageId| {
+        Box::new(
+            move |common_settings: &mut CommonSettings, browser_storages: &mut BrowserStorages| {
+                common_settings.set_language(language, browser_storages);
+            },
+        )
+    })
+}
+
 /// Declare static translations.
 #[macro_export]
 macro_rules! s {
@@ -72,6 +89,7 @@ pub trait Translation: Sized {
     s!(leaderboard_all_time_label);
     s!(leaderboard_daily_label);
     s!(leaderboard_weekly_label);
+    s!(leaderboard_title);
 
     // Teams.
     s!(team_label);
@@ -357,6 +375,23 @@ impl Translation for LanguageId {
         }
     }
 
+    fn leaderboard_title(self) -> &'static str {
+        match self {
+            Bork => "Leaderbork",
+            German => "Bestenliste",
+            English => "Leaderboard",
+            Spanish => "Tabla",
+            French => "Classement",
+            Italian => "Classifica",
+            Arabic => "المتصدرين",
+            Japanese => "リーダーボード",
+            Russian => "Таблица лидеров",
+            Vietnamese => "Bảng xếp hạng",
+            SimplifiedChinese => "排行榜",
+            Hindi => "लीडरबोर्ड",
+        }
+    }
+
     fn team_label(self) -> &'static str {
         match self {
             Bork => "Borks",
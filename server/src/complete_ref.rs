@@ -112,6 +112,7 @@ impl<'a, I: Iterator<Item = ContactRef<'a>>> CompleteRef<'a, I> {
                 .collect(),
             death_reason,
             score: self.player.score,
+            kills: self.player.kills,
             world_radius: self.world.radius,
             terrain,
         }
@@ -159,6 +159,7 @@ impl Entity {
         // Set status to alive.
         assert!(!player.data.status.is_alive());
         player.data.status = Status::new_alive(i);
+        player.data.spectating = None;
 
         // Clear flags when player's boat is spawned.
         player.data.flags = Flags::default();
@@ -2,10 +2,13 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
 use crate::entities::*;
+use crate::server::Server;
 use common::death_reason::DeathReason;
 use common::protocol::Hint;
+use game_server::player::PlayerTuple;
 use glam::Vec2;
 use std::fmt::Debug;
+use std::sync::Weak;
 use std::time::Instant;
 
 /// A player's view into the world.
@@ -99,6 +102,22 @@ pub struct Player {
     pub hint: Hint,
     /// Current status e.g. Alive, Dead, or Spawning.
     pub status: Status,
+    /// Whose boat to watch while not alive. Weak, in case players spectate each other.
+    pub spectating: Option<Weak<PlayerTuple<Server>>>,
+}
+
+impl Player {
+    /// Returns the index of the boat being spectated, if any.
+    pub fn spectated_entity_index(&self) -> Option<EntityIndex> {
+        let target = self.spectating.as_ref()?.upgrade()?;
+        let target = target.borrow_player();
+        match target.data.status {
+            Status::Alive { entity_index, .. } if !target.data.flags.left_game => {
+                Some(entity_index)
+            }
+            _ => None,
+        }
+    }
 }
 
 impl Default for Player {
@@ -108,6 +127,7 @@ impl Default for Player {
             flags: Flags::default(),
             hint: Hint::default(),
             status: Status::Spawning,
+            spectating: None,
         }
     }
 }
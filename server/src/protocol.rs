@@ -25,6 +25,7 @@ impl AsCommandTrait for Command {
         match *self {
             Command::Control(ref v) => v as &dyn CommandTrait,
             Command::Spawn(ref v) => v as &dyn CommandTrait,
+            Command::Spectate(ref v) => v as &dyn CommandTrait,
             Command::Upgrade(ref v) => v as &dyn CommandTrait,
         }
     }
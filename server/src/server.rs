@@ -141,6 +141,7 @@ impl GameArenaService for Server {
 
         // Clear player's score.
         player.score = 0;
+        player.kills = 0;
 
         // Delete all player's entities (efficiently, in the next update cycle).
         player.data.flags.left_game = true;
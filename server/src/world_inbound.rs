@@ -470,6 +470,36 @@ impl CommandTrait for Upgrade {
     }
 }
 
+impl CommandTrait for Spectate {
+    fn apply(
+        &self,
+        world: &mut World,
+        player_tuple: &Arc<PlayerTuple<Server>>,
+    ) -> Result<(), &'static str> {
+        if player_tuple.borrow_player().data.status.is_alive() {
+            return Err("cannot spectate while alive");
+        }
+
+        let target = if let Some(player_id) = self.player_id {
+            // TODO: Inefficient to scan all entities; only need to scan all players.
+            let (_, boat) = world
+                .entities
+                .par_iter()
+                .into_maybe_parallel_iter()
+                .find_any(|(_, entity)| {
+                    entity.is_boat() && entity.borrow_player().player_id == player_id
+                })
+                .ok_or("cannot spectate player without a boat")?;
+            Some(Arc::downgrade(boat.player.as_ref().unwrap()))
+        } else {
+            None
+        };
+
+        player_tuple.borrow_player_mut().data.spectating = target;
+        Ok(())
+    }
+}
+
 /// Returns an error if the float isn't finite. Otherwise, clamps it to the provided range.
 fn sanitize_float(float: f32, valid: Range<f32>) -> Result<f32, &'static str> {
     if float.is_finite() {
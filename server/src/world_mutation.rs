@@ -126,6 +126,7 @@ impl Mutation {
                     let killer_alias = {
                         let mut other_player = other_player.borrow_player_mut();
                         other_player.score += kill_score(e.borrow_player().score);
+                        other_player.kills += 1;
                         let alias = other_player.alias();
                         drop(other_player);
                         alias
@@ -146,6 +147,7 @@ impl Mutation {
                     let killer_alias = {
                         let mut other_player = other_player.borrow_player_mut();
                         other_player.score += ram_score(entity.borrow_player().score);
+                        other_player.kills += 1;
                         let alias = other_player.alias();
                         drop(other_player);
                         alias
@@ -334,6 +336,7 @@ impl Mutation {
         } else {
             respawn_score(player.score)
         };
+        player.kills = 0;
         drop(player);
 
         let data = entity.data();
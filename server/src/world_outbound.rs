@@ -29,6 +29,11 @@ impl World {
             }
             _ => None,
         };
+        // Spectators see what the boat they follow sees.
+        let spectated_entity = player
+            .data
+            .spectated_entity_index()
+            .map(|entity_index| &self.entities[entity_index]);
 
         struct Camera {
             active: bool,
@@ -42,7 +47,7 @@ impl World {
         }
 
         // Players, whether alive or dead, can see other entities based on these parameters.
-        let camera = if let Some(entity) = player_entity {
+        let camera = if let Some(entity) = player_entity.or(spectated_entity) {
             let data = entity.data();
             let sensors = &data.sensors;
 
@@ -62,19 +67,15 @@ impl World {
                 sensors.sonar.range
             };
 
-            if player.data.status.is_alive() {
-                Camera {
-                    active: entity.extension().is_active(),
-                    inner: data.radii().start,
-                    position: entity.transform.position,
-                    radar,
-                    sonar,
-                    speed: entity.transform.velocity.abs().to_mps(),
-                    view: data.camera_range(),
-                    visual,
-                }
-            } else {
-                panic!("player not alive in outbound");
+            Camera {
+                active: entity.extension().is_active(),
+                inner: data.radii().start,
+                position: entity.transform.position,
+                radar,
+                sonar,
+                speed: entity.transform.velocity.abs().to_mps(),
+                view: data.camera_range(),
+                visual,
             }
         } else if let Status::Dead {
             position,